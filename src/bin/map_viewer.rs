@@ -1,38 +1,53 @@
 use bevy::prelude::*;
 use task_masker::*;
 
-use std::path::PathBuf;
-
-use map::plugins::TiledCameraPlugin;
+use map::inspector::InspectedMap;
+use map::plugins::{MapInspectorPlugin, TiledCameraPlugin};
 use map::tilemap::*;
+use visual::tile_animations::animate_tile_sprites;
+
+/// The Tiled map being viewed.
+const MAP_PATH: &str = "assets/TM_map.tmx";
 
-/// Renders all Tiles from some Tiled map.
+/// Renders all Tiles from some Tiled map, then inspects it.
 pub fn spawn_tiled_tiles(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     texture_atlas_assets: ResMut<Assets<TextureAtlasLayout>>,
 ) {
-    let mut tilemap = Tilemap::new();
-    tilemap.load_tiles_from_tiled_map(&PathBuf::from("assets/TM_map.tmx"));
+    let tiled_map = tiled::Loader::new()
+        .load_tmx_map(MAP_PATH)
+        .expect("spawn_tiled_tiles: Could not load Tiled map.");
+
+    let mut tilemap = Tilemap::from_tiled_map(&tiled_map);
     tilemap.to_isometric_coordinates();
     tilemap.y_sort_tiles();
     tilemap.flip_y_axis();
     let render_tiles = convert_tilemap_to_bevy_render_tiles(
         &tilemap,
+        &[],
         &asset_server.into_inner(),
         texture_atlas_assets.into_inner(),
     );
 
     for render_tile in render_tiles {
-        commands.spawn(render_tile);
+        let tile_animation = render_tile.get_animation().cloned();
+        let mut render_tile_commands = commands.spawn(render_tile);
+        if let Some(tile_animation) = tile_animation {
+            render_tile_commands.insert(tile_animation);
+        }
     }
+
+    commands.insert_resource(InspectedMap::new(tiled_map));
 }
 
 fn main() {
     let mut map_viewer = App::new();
     map_viewer.add_plugins(DefaultPlugins.set(ImagePlugin::default_nearest()));
     map_viewer.add_plugins(TiledCameraPlugin);
+    map_viewer.add_plugins(MapInspectorPlugin);
     map_viewer.add_systems(Startup, spawn_tiled_tiles);
+    map_viewer.add_systems(Update, animate_tile_sprites);
 
     map_viewer.init_state::<GameState>();
     map_viewer.insert_state::<GameState>(GameState::InGame);
@@ -3,10 +3,13 @@ use task_masker::*;
 
 use audio::plugins::BackgroundMusicPlugin;
 use bevy::window::WindowResolution;
-use bevy_ecs_tilemap::prelude::*;
 use chat_interactions::plugins::TwitchChatPlugin;
-use entities::plugins::{ChatterPlugin, CropPlugin, FruitPlugin, StreamerPlugin, SubscriberPlugin};
-use map::plugins::{PathFindingPlugin, TiledCameraPlugin, TiledMapPlugin};
+use entities::plugins::{
+    ChatterPlugin, CritterPlugin, CropPlugin, EntityDefinitionsPlugin, FruitPlugin, RaiderPlugin,
+    StreamerPlugin, SubscriberPlugin, WanderPlugin,
+};
+use map::plugins::{NavOverlayPlugin, PathFindingPlugin, TiledCameraPlugin, TiledMapPlugin};
+use session::plugins::SessionPlugin;
 use ui::plugins::{ChattingPlugin, StartupScreenPlugin};
 use visual::plugins::{AnimationPlugin, EnvironmentAnimationsPlugin};
 
@@ -29,7 +32,7 @@ fn main() {
                 }),
         )
         .init_state::<GameState>()
-        .add_plugins(TilemapPlugin)
+        .add_plugins(EntityDefinitionsPlugin)
         .add_plugins(TiledMapPlugin)
         .add_plugins(PathFindingPlugin)
         .add_plugins(StartupScreenPlugin)
@@ -43,6 +46,13 @@ fn main() {
         .add_plugins(CropPlugin)
         .add_plugins(ChatterPlugin)
         .add_plugins(SubscriberPlugin)
+        .add_plugins(RaiderPlugin)
+        .add_plugins(CritterPlugin)
+        .add_plugins(WanderPlugin)
+        .add_plugins(SessionPlugin {
+            restore_on_launch: std::env::args().any(|arg| arg == "--restore-session"),
+        })
         .add_plugins(TiledCameraPlugin)
+        .add_plugins(NavOverlayPlugin)
         .run();
 }
@@ -0,0 +1,88 @@
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use task_masker::*;
+
+use entities::definitions::EntityDefinitions;
+use map::scenes::MapScenes;
+use map::validation::MapValidation;
+
+/// Checks some Tiled map the same way the game lays it out, printing
+/// whatever is wrong with it, and returns whether it can go live.
+fn validate(
+    map_path: &Path,
+    map_scenes: &MapScenes,
+    entity_definitions: &EntityDefinitions,
+) -> bool {
+    let map_name = map_path.display();
+    let tiled_map = match tiled::Loader::new().load_tmx_map(map_path) {
+        Ok(tiled_map) => tiled_map,
+        Err(load_error) => {
+            eprintln!("{map_name}: error: Could not load map: {load_error}");
+            return false;
+        }
+    };
+
+    // NOTE: The Streamer spawn is set by whichever Map Scene uses the map.
+    let streamer_spawn = map_scenes
+        .scenes()
+        .iter()
+        .find(|scene| map_path.ends_with(&scene.map_path))
+        .map(|scene| scene.streamer_spawn);
+    if streamer_spawn.is_none() {
+        eprintln!("{map_name}: warning: No Map Scene uses this map, so nothing is checked from its Streamer spawn.");
+    }
+
+    let map_errors = match MapValidation::from_map(&tiled_map) {
+        Ok(map_validation) => map_validation.errors(streamer_spawn, entity_definitions),
+        Err(map_error) => vec![map_error],
+    };
+    for map_error in &map_errors {
+        eprintln!("{map_name}: error: {map_error}");
+    }
+
+    if map_errors.is_empty() {
+        println!("{map_name}: ok");
+    }
+
+    map_errors.is_empty()
+}
+
+fn main() -> ExitCode {
+    // NOTE: Panics while building the graphs are reported as errors instead.
+    std::panic::set_hook(Box::new(|_| {}));
+    let map_scenes = MapScenes::default();
+
+    // NOTE: Entities are checked on the layers their shipped definitions place them on.
+    let entity_definitions = match EntityDefinitions::from_assets_folder(Path::new("assets")) {
+        Ok(entity_definitions) => entity_definitions,
+        Err(definition_error) => {
+            eprintln!("assets/definitions: error: {definition_error}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    // NOTE: Without any maps to check, every map a Map Scene uses is checked.
+    let mut map_paths = std::env::args()
+        .skip(1)
+        .map(PathBuf::from)
+        .collect::<Vec<PathBuf>>();
+    if map_paths.is_empty() {
+        map_paths = map_scenes
+            .scenes()
+            .iter()
+            .map(|scene| Path::new("assets").join(&scene.map_path))
+            .collect();
+    }
+
+    let mut all_valid = true;
+    for map_path in &map_paths {
+        all_valid &= validate(map_path, &map_scenes, &entity_definitions);
+    }
+
+    if all_valid {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
@@ -1,15 +1,22 @@
 use bevy::prelude::*;
 
 use crate::chat_interactions::twitch_chat_reader::*;
+use crate::entities::critter::FollowMsg;
+use crate::entities::raider::RaidMsg;
 
 pub const CHANNEL_NAME: &str = "divarktech";
 
+/// Chat bots announcing new followers in the channel's chat.
+pub const FOLLOW_ALERT_BOTS: [&str; 3] = ["streamelements", "streamlabs", "nightbot"];
+
 #[derive(Default)]
 pub struct TwitchChatPlugin;
 
 impl Plugin for TwitchChatPlugin {
     fn build(&self, app: &mut App) {
         app.add_event::<Notification>();
+        app.add_event::<RaidMsg>();
+        app.add_event::<FollowMsg>();
 
         let twitch_msg_reader = TwitchMsgReader::connect_anonymously(String::from(CHANNEL_NAME));
 
@@ -20,6 +27,9 @@ impl Plugin for TwitchChatPlugin {
                 notify_all_about_twitch_msg,
                 convert_notification_to_msg,
                 convert_notification_to_event,
+                convert_notification_to_raid,
+                convert_notification_to_follow,
+                convert_notification_to_scene_switch,
             ),
         );
     }
@@ -14,12 +14,15 @@ use twitch_irc::message::{
 };
 use twitch_irc::{ClientConfig, SecureTCPTransport, TwitchIRCClient};
 
-use crate::chat_interactions::plugins::CHANNEL_NAME;
+use crate::chat_interactions::plugins::{CHANNEL_NAME, FOLLOW_ALERT_BOTS};
 use crate::entities::chatter::ChatMsg;
+use crate::entities::critter::FollowMsg;
 use crate::entities::crop::CropState;
 use crate::entities::fruit::FruitState;
+use crate::entities::raider::RaidMsg;
 use crate::entities::subscriber::SubscriberMsg;
 use crate::entities::{GameEntityType, TriggerQueue};
+use crate::map::scenes::SwitchSceneEvent;
 use crate::ui::chatting::Msg;
 
 /// Represents a chatter's role sending
@@ -106,6 +109,15 @@ impl Notification {
         None
     }
 
+    /// Converts the contents of the Notification into a
+    /// SwitchSceneEvent if the Streamer sent a scene command,
+    /// or returns None otherwise.
+    pub fn as_switch_scene_event(&self) -> Option<SwitchSceneEvent> {
+        let streamer_msg = self.as_streamer_msg()?;
+
+        SwitchSceneEvent::from_command(&streamer_msg.msg)
+    }
+
     /// Returns whether the message has a donation attached to it
     /// or not.
     pub fn has_bits(&self) -> bool {
@@ -134,6 +146,49 @@ impl Notification {
         }
     }
 
+    /// Converts the contents of the Notification into a
+    /// RaidMsg if another channel has raided, or returns None otherwise.
+    pub fn as_raid_msg(&self) -> Option<RaidMsg> {
+        if let UserNotice(current_msg) = &self.msg {
+            if let UserNoticeEvent::Raid { viewer_count, .. } = &current_msg.event {
+                return Some(RaidMsg {
+                    channel_name: current_msg.sender.name.clone(),
+                    viewer_count: *viewer_count,
+                });
+            }
+        }
+
+        None
+    }
+
+    /// Converts the contents of the Notification into a
+    /// FollowMsg if an alert bot announced a new follower in chat,
+    /// or returns None otherwise.
+    ///
+    /// NOTE: Twitch does not send follows over chat, so this relies on
+    /// the alert bot mentioning the follower, e.g. "Thanks for the follow @name!".
+    pub fn as_follow_msg(&self) -> Option<FollowMsg> {
+        let Privmsg(current_msg) = &self.msg else {
+            return None;
+        };
+
+        let is_alert_bot = FOLLOW_ALERT_BOTS.contains(&current_msg.sender.login.as_str());
+        let alert_text = current_msg.message_text.to_lowercase();
+        if !is_alert_bot || !alert_text.contains("follow") {
+            return None;
+        }
+
+        current_msg
+            .message_text
+            .split_whitespace()
+            .find_map(|word| word.strip_prefix('@'))
+            .map(|mention| mention.trim_end_matches(|c: char| !c.is_alphanumeric() && c != '_'))
+            .filter(|follower_name| !follower_name.is_empty())
+            .map(|follower_name| FollowMsg {
+                name: follower_name.to_string(),
+            })
+    }
+
     /// Returns the type of chat message that was captured
     /// from Twitch.
     pub fn msg_type(&self) -> Option<NotificationType> {
@@ -249,6 +304,9 @@ pub fn convert_notification_to_msg(
             NotificationType::Msg(TwitchRole::Subscriber) => {
                 subscriber_msg_writer.send(notification.as_subscriber_msg().unwrap());
             }
+            // NOTE: Scene commands are for the game, not for everyone to read.
+            NotificationType::Msg(TwitchRole::Streamer)
+                if notification.as_switch_scene_event().is_some() => {}
             NotificationType::Msg(TwitchRole::Streamer) => {
                 streamer_msg_writer.send(notification.as_streamer_msg().unwrap());
             }
@@ -290,3 +348,42 @@ pub fn convert_notification_to_event(
         }
     }
 }
+
+/// Converts Notifications from Twitch messages into a Raid to be
+/// paraded around the Streamer if found.
+pub fn convert_notification_to_raid(
+    mut notification_reader: EventReader<Notification>,
+    mut raid_msg_writer: EventWriter<RaidMsg>,
+) {
+    for notification in notification_reader.read() {
+        if let Some(raid_msg) = notification.as_raid_msg() {
+            raid_msg_writer.send(raid_msg);
+        }
+    }
+}
+
+/// Converts Notifications from Twitch messages into a Follow to be
+/// settled on the map as a Critter if found.
+pub fn convert_notification_to_follow(
+    mut notification_reader: EventReader<Notification>,
+    mut follow_msg_writer: EventWriter<FollowMsg>,
+) {
+    for notification in notification_reader.read() {
+        if let Some(follow_msg) = notification.as_follow_msg() {
+            follow_msg_writer.send(follow_msg);
+        }
+    }
+}
+
+/// Converts Notifications from the Streamer's scene commands into
+/// a switch over to another Map Scene if found.
+pub fn convert_notification_to_scene_switch(
+    mut notification_reader: EventReader<Notification>,
+    mut switch_scene_writer: EventWriter<SwitchSceneEvent>,
+) {
+    for notification in notification_reader.read() {
+        if let Some(switch_scene_event) = notification.as_switch_scene_event() {
+            switch_scene_writer.send(switch_scene_event);
+        }
+    }
+}
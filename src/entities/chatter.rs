@@ -2,26 +2,26 @@ use bevy::prelude::*;
 use bevy_ecs_tilemap::prelude::*;
 use std::collections::VecDeque;
 
-use crate::entities::streamer::{StreamerLabel, StreamerState};
-use crate::entities::WaitToLeaveTimer;
+use crate::entities::definitions::{EntityDefinitions, EntityKind, SpriteDefinition};
+use crate::entities::streamer::StreamerState;
+use crate::entities::visitor::*;
+use crate::map::layer_roles::LayerRoles;
 use crate::map::path_finding::*;
-use crate::map::tiled::{to_bevy_transform, LayerNumber, TiledMapInformation};
-use crate::ui::chatting::{Msg, TypingMsg};
+use crate::map::tiled::{find_layer, tile_transform_on, LayerNumber, MapLayer};
 
 use super::GameEntityType;
 
-pub const CHATTER_LAYER_NUM: usize = 19;
 pub const DIST_AWAY_FROM_STREAMER: usize = 2;
 
 #[derive(Component)]
 pub struct ChatterLabel;
 
-#[derive(Component, Debug, PartialEq)]
-pub enum ChatterStatus {
-    Idle,
-    Approaching,
-    Speaking,
-    Leaving,
+impl VisitorConfig for ChatterLabel {
+    type Msg = ChatMsg;
+
+    const MOVEMENT_TYPE: GameEntityType = GameEntityType::Fly;
+    const GRAPH_TYPE: GraphType = GraphType::Air;
+    const DIST_AWAY_FROM_STREAMER: usize = DIST_AWAY_FROM_STREAMER;
 }
 
 #[derive(Event, Clone)]
@@ -30,8 +30,17 @@ pub struct ChatMsg {
     pub msg: String,
 }
 
-#[derive(Component, Deref, DerefMut)]
-pub struct ChatMessageQueue(VecDeque<ChatMsg>);
+impl VisitorMsg for ChatMsg {
+    fn speaker_name(&self) -> &str {
+        &self.name
+    }
+
+    fn contents(&self) -> &str {
+        &self.msg
+    }
+}
+
+pub type ChatMessageQueue = VisitorMsgQueue<ChatMsg>;
 
 #[derive(Bundle)]
 pub struct ChatterBundle {
@@ -39,7 +48,7 @@ pub struct ChatterBundle {
     sprite: SpriteBundle,
     texture_atlas: TextureAtlas,
     movement_type: GameEntityType,
-    status: ChatterStatus,
+    status: VisitorStatus,
 }
 
 pub fn replace_chatter_sprite(
@@ -47,11 +56,12 @@ pub fn replace_chatter_sprite(
     mut texture_atlases: ResMut<Assets<TextureAtlasLayout>>,
     mut commands: Commands,
     asset_server: Res<AssetServer>,
+    entity_definitions: Res<EntityDefinitions>,
 ) {
+    let chatter_definition = entity_definitions.get(EntityKind::Chatter);
     for (chatter_entity, chatter_transform, tile_texture_index) in &chatter {
-        let texture_handle = asset_server.load("chatter/BirdSprite (16x16).png");
-        let chatter_texture_atlas =
-            TextureAtlasLayout::from_grid(UVec2::new(16, 16), 8, 3, None, None);
+        let texture_handle = asset_server.load(chatter_definition.sprite.clone());
+        let chatter_texture_atlas = chatter_definition.texture_atlas_layout();
         let chatter_texture_atlas_handle = texture_atlases.add(chatter_texture_atlas);
 
         let chatter_texture_atlas = TextureAtlas {
@@ -67,51 +77,68 @@ pub fn replace_chatter_sprite(
         };
 
         commands.entity(chatter_entity).remove::<Transform>();
-        commands
-            .entity(chatter_entity)
-            .insert((chatter_sprite, chatter_texture_atlas));
+        commands.entity(chatter_entity).insert((
+            chatter_sprite,
+            chatter_texture_atlas,
+            SpriteDefinition(EntityKind::Chatter),
+        ));
     }
 }
 
 /// Respawns Chatter without rendering components
 pub fn replace_chatter_tile(
     tiles_query: Query<(Entity, &LayerNumber, &TilePos, &TileTextureIndex)>,
-    map_info_query: Query<
-        (&Transform, &TilemapGridSize, &TilemapSize, &TilemapType),
-        Added<TilemapGridSize>,
-    >,
+    map_layers: Query<MapLayer, Added<TilemapGridSize>>,
+    chatters: Query<Entity, With<ChatterLabel>>,
     mut commands: Commands,
+    entity_definitions: Res<EntityDefinitions>,
+    layer_roles: Res<LayerRoles>,
 ) {
-    let map_information = map_info_query
-        .iter()
-        .find(|map_info| map_info.0.translation.z == CHATTER_LAYER_NUM as f32);
-
-    if map_information.is_none() {
+    let chatter_definition = entity_definitions.get(EntityKind::Chatter);
+    let (Some(chatter_layer), Some(chatter_spawn_layer)) = (
+        chatter_definition.drawn_layer(&layer_roles),
+        chatter_definition.spawn_layer(&layer_roles),
+    ) else {
         return;
-    }
+    };
 
-    let (map_transform, grid_size, map_size, map_type) =
-        map_information.expect("mock_replace_chatter: Map information should exist by now.");
+    let Some(spawn_map_layer) = find_layer(&map_layers, chatter_spawn_layer) else {
+        return;
+    };
 
+    let mut spawn_tiles = Vec::new();
     for (chatter_entity, layer_number, tile_pos, tile_texture_index) in &tiles_query {
-        if layer_number.0 != CHATTER_LAYER_NUM {
+        if layer_number.0 != chatter_layer {
             continue;
         }
 
-        let map_info = TiledMapInformation::new(grid_size, map_size, map_type, map_transform);
-        let tile_transform = to_bevy_transform(tile_pos, map_info);
+        let tile_transform = tile_transform_on(tile_pos, spawn_map_layer);
 
         commands.entity(chatter_entity).despawn_recursive();
+        spawn_tiles.push((tile_transform, *tile_pos, *tile_texture_index));
+    }
+
+    // Chatters from the map switched away from take the
+    // place of any that would have spawned on this one.
+    let spawn_points = spawn_tiles
+        .iter()
+        .map(|(tile_transform, tile_pos, _)| (*tile_transform, *tile_pos))
+        .collect::<Vec<(Transform, TilePos)>>();
+    if migrate_visitors(&chatters, &spawn_points, &mut commands) {
+        return;
+    }
+
+    for (tile_transform, tile_pos, tile_texture_index) in spawn_tiles {
         commands.spawn((
             (
                 ChatterLabel,
                 tile_transform,
                 GameEntityType::Fly,
-                ChatterStatus::Idle,
-                ChatMessageQueue(VecDeque::new()),
-                *tile_texture_index,
+                VisitorStatus::Idle,
+                ChatMessageQueue::default(),
+                tile_texture_index,
             ),
-            *tile_pos,
+            tile_pos,
         ));
     }
 }
@@ -132,201 +159,11 @@ pub fn trigger_flying_to_streamer(
     chatter_msg.send(chat_msg);
 }
 
-/// Adds a recently broadcasted Chat Message into a Chatter's
-/// Chat Message Queue.
-pub fn add_chat_msg_to_queue(
-    mut received_chat_msgs: EventReader<ChatMsg>,
-    mut chatter_queues: Query<&mut ChatMessageQueue>,
-) {
-    if chatter_queues.is_empty() {
-        return;
-    }
-
-    let mut chatter_queue = chatter_queues.single_mut();
-    for received_chat_msg in received_chat_msgs.read() {
-        chatter_queue.push_back(received_chat_msg.clone());
-    }
-}
-
-pub fn fly_to_streamer_to_speak(
-    mut chatter: Query<
-        (&TilePos, &mut Path, &mut ChatterStatus, &ChatMessageQueue),
-        With<ChatterLabel>,
-    >,
-    air_graph: Query<&UndirectedGraph>,
-    streamer: Query<&TilePos, With<StreamerLabel>>,
-) {
-    if air_graph.is_empty() || streamer.is_empty() {
-        return;
-    }
-
-    let air_graph = air_graph
-        .iter()
-        .find(|graph| *graph.get_node_type() == GraphType::Air)
-        .expect("fly_to_streamer_to_speak: There should only be one air graph.");
-    let streamer_tilepos = streamer
-        .get_single()
-        .expect("fly_to_streamer_to_speak: There should only be one streamer.");
-    for (chatter_tilepos, mut chatter_path, mut chatter_status, chatter_message_queue) in
-        &mut chatter
-    {
-        if chatter_message_queue.is_empty() || *chatter_status != ChatterStatus::Idle {
-            continue;
-        }
-
-        if let Some(mut path) = air_graph.shortest_path(*chatter_tilepos, *streamer_tilepos) {
-            // The chatter should not be directly on top of the
-            // streamer, so we provide some distance by adjusting
-            // the path to not go straight to the streamer.
-            for _i in 0..DIST_AWAY_FROM_STREAMER {
-                path.pop_back();
-            }
-
-            *chatter_path = path;
-            *chatter_status = ChatterStatus::Approaching;
-        }
-    }
-}
-
-pub fn speak_to_streamer_from_chatter(
-    mut chatter_query: Query<(
-        &mut ChatMessageQueue,
-        &Path,
-        &Target,
-        &mut ChatterStatus,
-        &GameEntityType,
-    )>,
-    mut chat_msg_requester: EventWriter<Msg>,
-) {
-    for (
-        mut chatter_message_queue,
-        chatter_path,
-        chatter_target,
-        mut chatter_status,
-        &chatter_type,
-    ) in &mut chatter_query
-    {
-        if !chatter_path.0.is_empty()
-            || chatter_target.is_some()
-            || chatter_message_queue.is_empty()
-            || *chatter_status != ChatterStatus::Approaching
-        {
-            continue;
-        }
-
-        let recent_chat_msg = chatter_message_queue.pop_front().unwrap();
-        *chatter_status = ChatterStatus::Speaking;
-        chat_msg_requester.send(Msg::new(
-            recent_chat_msg.name,
-            recent_chat_msg.msg,
-            chatter_type,
-        ));
-    }
-}
-
-/// Starts to wait to leave when the Chatter is finished speaking.
-pub fn chatter_waits_to_leave_from_streamer(
-    typed_messages: Query<&TypingMsg>,
-    mut chatters: Query<(Entity, &mut ChatterStatus, &ChatMessageQueue), Without<WaitToLeaveTimer>>,
-    mut commands: Commands,
-) {
-    if typed_messages.is_empty() || chatters.is_empty() {
-        return;
-    }
-
-    let (chatter_entity, mut chatter_status, chatter_message_queue) = chatters.single_mut();
-    if *chatter_status != ChatterStatus::Speaking {
-        return;
-    }
-
-    let typing_msg = typed_messages.single();
-    if !typing_msg.at_end() {
-        return;
-    }
-
-    let next_message = chatter_message_queue.front();
-    if next_message.is_none() || next_message.unwrap().name != typing_msg.speaker_name() {
-        commands
-            .entity(chatter_entity)
-            .insert(WaitToLeaveTimer(Timer::from_seconds(10.0, TimerMode::Once)));
-        return;
-    }
-
-    *chatter_status = ChatterStatus::Idle;
-}
-
-pub fn leave_from_streamer_from_chatter(
-    time: Res<Time>,
-    mut chatter: Query<(
-        Entity,
-        &mut WaitToLeaveTimer,
-        &mut Path,
-        &StartingPoint,
-        &SpawnPoint,
-        &mut ChatterStatus,
-    )>,
-    air_graph_info: Query<&UndirectedGraph>,
-    mut commands: Commands,
-) {
-    if chatter.is_empty() || air_graph_info.is_empty() {
-        return;
-    }
-
-    let air_graph = air_graph_info
-        .iter()
-        .filter(|graph| *graph.get_node_type() == GraphType::Air)
-        .next()
-        .expect("leave_from_streamer: Exactly one air graph should exist by now.");
-
-    for (
-        chatter_entity,
-        mut chatter_wait_time,
-        mut chatter_path,
-        chatter_start_pos,
-        chatter_spawn_pos,
-        mut chatter_status,
-    ) in &mut chatter
-    {
-        chatter_wait_time.0.tick(time.delta());
-        if !chatter_wait_time.0.finished() {
-            continue;
-        }
-
-        if let Some(path) = air_graph.shortest_path(chatter_start_pos.1, chatter_spawn_pos.0) {
-            *chatter_path = path;
-            commands.entity(chatter_entity).remove::<WaitToLeaveTimer>();
-
-            *chatter_status = ChatterStatus::Leaving;
-        }
-    }
-}
-
-/// Sets the Chatter's Status back to Idle
-/// when reaching its starting position once
-/// again after leaving.
-pub fn return_chatter_to_idle(
-    mut chatter: Query<(&Path, &Target, &mut ChatterStatus), With<ChatterLabel>>,
-) {
-    for (chatter_path, chatter_target, mut chatter_status) in &mut chatter {
-        if *chatter_status != ChatterStatus::Leaving {
-            continue;
-        }
-
-        if !chatter_path.0.is_empty() {
-            continue;
-        }
-
-        if chatter_target.is_some() {
-            continue;
-        }
-
-        *chatter_status = ChatterStatus::Idle;
-    }
-}
-
+/// Keeps a speaking Chatter near the Streamer whenever
+/// the Streamer moves somewhere else.
 pub fn follow_streamer_while_speaking(
     streamer_info: Query<(&StreamerState, &Path), Changed<StreamerState>>,
-    mut chatter_info: Query<(&ChatterStatus, &mut Path), Without<StreamerState>>,
+    mut chatter_info: Query<(&VisitorStatus, &mut Path), VisitorsOf<ChatterLabel>>,
     map_info: Query<&TilemapSize>,
 ) {
     if streamer_info.is_empty() || chatter_info.is_empty() || map_info.is_empty() {
@@ -347,7 +184,7 @@ pub fn follow_streamer_while_speaking(
     }
 
     for (chatter_status, mut chatter_path) in &mut chatter_info {
-        if *chatter_status != ChatterStatus::Speaking || !chatter_path.is_empty() {
+        if *chatter_status != VisitorStatus::Speaking || !chatter_path.is_empty() {
             continue;
         }
 
@@ -367,74 +204,3 @@ pub fn follow_streamer_while_speaking(
             .collect::<VecDeque<usize>>();
     }
 }
-
-pub fn follow_streamer_while_approaching_for_chatter(
-    streamer_info: Query<(&StreamerState, &Path), Without<ChatterStatus>>,
-    mut chatter_info: Query<(&ChatterStatus, &TilePos, &mut Path), Without<StreamerState>>,
-    air_graph_info: Query<&UndirectedGraph>,
-    map_info: Query<&TilemapSize>,
-) {
-    if streamer_info.is_empty() || chatter_info.is_empty() || map_info.is_empty() {
-        return;
-    }
-
-    let map_size = map_info
-        .iter()
-        .last()
-        .expect("follow_streamer_while_approaching: Map should be spawned by now.");
-
-    let (streamer_status, streamer_path) = streamer_info
-        .get_single()
-        .expect("follow_streamer_while_approaching: Streamer should exist by now.");
-
-    if *streamer_status != StreamerState::Moving {
-        return;
-    }
-
-    if streamer_path.0.is_empty() {
-        return;
-    }
-
-    let air_graph = air_graph_info
-        .iter()
-        .filter(|graph| *graph.get_node_type() == GraphType::Air)
-        .next()
-        .expect("follow_streamer_while_approaching: Exactly one air graph should exist by now.");
-
-    for (chatter_status, chatter_pos, mut chatter_path) in &mut chatter_info {
-        if *chatter_status != ChatterStatus::Approaching || chatter_path.0.is_empty() {
-            continue;
-        }
-
-        let streamer_destination = streamer_path
-            .0
-            .iter()
-            .last()
-            .expect("follow_streamer_while_approaching: Streamer Path should be populated.");
-        let streamer_destination_tilepos = idx_to_tilepos(*streamer_destination, map_size.y);
-        let chatter_destination_distanced = TilePos::new(
-            streamer_destination_tilepos.x + DIST_AWAY_FROM_STREAMER as u32,
-            streamer_destination_tilepos.y + DIST_AWAY_FROM_STREAMER as u32,
-        );
-
-        let current_chatter_destination =
-            idx_to_tilepos(*chatter_path.0.iter().last().unwrap(), map_size.y);
-
-        // We do not want to re-populate the path if the Chatter is already
-        // going to the desired destination.
-        if current_chatter_destination == chatter_destination_distanced {
-            continue;
-        }
-
-        // This accounts for the situation when the Chatter
-        // arrives before the Streamer does, and the Chatter
-        // is just waiting.
-        if *chatter_pos == chatter_destination_distanced {
-            continue;
-        }
-
-        if let Some(path) = air_graph.shortest_path(*chatter_pos, chatter_destination_distanced) {
-            *chatter_path = path;
-        }
-    }
-}
@@ -0,0 +1,455 @@
+use bevy::prelude::*;
+use bevy_ecs_tilemap::prelude::*;
+use rand::seq::IteratorRandom;
+use sqlite::{Connection, ConnectionThreadSafe};
+
+use crate::entities::definitions::{EntityDefinitions, EntityKind, SpriteDefinition};
+use crate::map::path_finding::*;
+use crate::map::scenes::SceneSwitched;
+
+use super::GameEntityType;
+
+pub const MAX_CRITTERS_ON_SCREEN: usize = 20;
+pub const CRITTER_NAME_HEIGHT: f32 = 14.0;
+
+#[derive(Component)]
+pub struct CritterLabel;
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CritterKind {
+    Rabbit,
+    Hedgehog,
+    Frog,
+    Turtle,
+}
+
+impl CritterKind {
+    const ALL: [CritterKind; 4] = [
+        CritterKind::Rabbit,
+        CritterKind::Hedgehog,
+        CritterKind::Frog,
+        CritterKind::Turtle,
+    ];
+
+    /// Returns the kind of Critter some follower is represented as,
+    /// always picking the same kind for the same name.
+    pub fn from_name(name: &str) -> Self {
+        let name_sum = name.bytes().map(|byte| byte as usize).sum::<usize>();
+
+        Self::ALL[name_sum % Self::ALL.len()]
+    }
+
+    /// Returns how the Critter gets around the map.
+    pub fn movement_type(&self) -> GameEntityType {
+        match self {
+            CritterKind::Rabbit | CritterKind::Hedgehog => GameEntityType::Walk,
+            CritterKind::Frog | CritterKind::Turtle => GameEntityType::Swim,
+        }
+    }
+
+    /// Returns the type of Graph the Critter settles on.
+    pub fn graph_type(&self) -> GraphType {
+        match self.movement_type() {
+            GameEntityType::Swim => GraphType::Water,
+            _ => GraphType::Ground,
+        }
+    }
+
+    /// Returns the row of the Critter spritesheet holding
+    /// this kind of Critter.
+    pub fn sprite_row(&self) -> usize {
+        match self {
+            CritterKind::Rabbit => 0,
+            CritterKind::Hedgehog => 1,
+            CritterKind::Frog => 2,
+            CritterKind::Turtle => 3,
+        }
+    }
+
+    fn to_id(self) -> i64 {
+        self.sprite_row() as i64
+    }
+
+    fn from_id(id: i64) -> Self {
+        Self::ALL[id as usize % Self::ALL.len()]
+    }
+}
+
+/// The name of the follower some Critter represents.
+#[derive(Component, Debug, Clone, PartialEq, Eq)]
+pub struct CritterName(pub String);
+
+/// The order in which the Critter's follower followed,
+/// where higher means more recent.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct FollowOrder(pub i64);
+
+/// Someone new following the channel.
+///
+/// NOTE: Follows are not announced over Twitch chat, so this is
+/// sent when an alert bot thanks the follower in chat instead.
+#[derive(Event, Clone)]
+pub struct FollowMsg {
+    pub name: String,
+}
+
+/// A follower recorded along with where their Critter
+/// settled on the map.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FollowerRecord {
+    pub name: String,
+    pub kind: CritterKind,
+    pub tile_pos: TilePos,
+    pub order: i64,
+}
+
+#[derive(Resource)]
+pub struct FollowerRecords {
+    db_connection: ConnectionThreadSafe,
+}
+
+impl FollowerRecords {
+    pub fn new(db_name: String) -> Self {
+        let sqlite_connection = Connection::open_thread_safe(db_name).unwrap();
+
+        let create_table_query = "
+            CREATE TABLE IF NOT EXISTS twitch_followers (name VARCHAR(25) NOT NULL PRIMARY KEY, kind INTEGER, x INTEGER, y INTEGER);
+        ";
+
+        sqlite_connection
+            .execute(create_table_query)
+            .expect("FollowerRecords::new: Could not create Twitch Followers table.");
+
+        Self {
+            db_connection: sqlite_connection,
+        }
+    }
+
+    /// Returns whether some user has already been recorded
+    /// as a follower.
+    pub fn contains(&self, user: &str) -> bool {
+        let find_follower_query = "SELECT name FROM twitch_followers WHERE name = ?";
+        let mut find_follower_statement = self
+            .db_connection
+            .prepare(find_follower_query)
+            .expect("FollowerRecords contains: Could not make Select statement from query.");
+
+        find_follower_statement
+            .bind((1, user))
+            .expect("FollowerRecords contains: Could not bind user string.");
+
+        find_follower_statement
+            .into_iter()
+            .map(|row| row.unwrap())
+            .next()
+            .is_some()
+    }
+
+    /// Records a new follower along with where their Critter settled,
+    /// returning the order they followed in.
+    pub fn add(&mut self, user: &str, kind: CritterKind, tile_pos: TilePos) -> i64 {
+        let insert_query = "
+            INSERT OR IGNORE INTO twitch_followers(name, kind, x, y)
+            VALUES (?, ?, ?, ?);
+        ";
+        let mut insert_statement = self
+            .db_connection
+            .prepare(insert_query)
+            .expect("FollowerRecords add: Could not make Insert Statement from query.");
+
+        insert_statement
+            .bind((1, user))
+            .expect("FollowerRecords add: Could not bind user string.");
+        insert_statement
+            .bind((2, kind.to_id()))
+            .expect("FollowerRecords add: Could not bind critter kind.");
+        insert_statement
+            .bind((3, tile_pos.x as i64))
+            .expect("FollowerRecords add: Could not bind x position.");
+        insert_statement
+            .bind((4, tile_pos.y as i64))
+            .expect("FollowerRecords add: Could not bind y position.");
+
+        insert_statement
+            .next()
+            .expect("FollowerRecords add: Could not process insert_statement.");
+
+        let find_order_query = "SELECT rowid FROM twitch_followers WHERE name = ?";
+        let mut find_order_statement = self
+            .db_connection
+            .prepare(find_order_query)
+            .expect("FollowerRecords add: Could not make Select statement from query.");
+
+        find_order_statement
+            .bind((1, user))
+            .expect("FollowerRecords add: Could not bind user string.");
+
+        find_order_statement
+            .into_iter()
+            .map(|row| row.unwrap())
+            .next()
+            .map(|row| row.read::<i64, _>("rowid"))
+            .expect("FollowerRecords add: Follower should have just been recorded.")
+    }
+
+    /// Returns up to the specified number of the most recent followers,
+    /// from oldest to newest.
+    pub fn most_recent(&self, limit: usize) -> Vec<FollowerRecord> {
+        let recent_followers_query = "
+            SELECT rowid, name, kind, x, y FROM twitch_followers
+            ORDER BY rowid DESC LIMIT ?;
+        ";
+        let mut recent_followers_statement = self
+            .db_connection
+            .prepare(recent_followers_query)
+            .expect("FollowerRecords most_recent: Could not make Select statement from query.");
+
+        recent_followers_statement
+            .bind((1, limit as i64))
+            .expect("FollowerRecords most_recent: Could not bind limit.");
+
+        let mut follower_records = recent_followers_statement
+            .into_iter()
+            .map(|row| row.unwrap())
+            .map(|row| FollowerRecord {
+                name: row.read::<&str, _>("name").to_string(),
+                kind: CritterKind::from_id(row.read::<i64, _>("kind")),
+                tile_pos: TilePos::new(
+                    row.read::<i64, _>("x") as u32,
+                    row.read::<i64, _>("y") as u32,
+                ),
+                order: row.read::<i64, _>("rowid"),
+            })
+            .collect::<Vec<FollowerRecord>>();
+
+        follower_records.reverse();
+        follower_records
+    }
+}
+
+pub fn trigger_follow_on_o_key(
+    mut follow_msg: EventWriter<FollowMsg>,
+    pressed_key: Res<ButtonInput<KeyCode>>,
+) {
+    if !pressed_key.just_pressed(KeyCode::KeyO) {
+        return;
+    }
+
+    follow_msg.send(FollowMsg {
+        name: format!("Followu{}", rand::random::<u16>()),
+    });
+}
+
+/// Spawns a Critter without rendering components for
+/// some recorded follower.
+fn spawn_critter(follower: &FollowerRecord, graph: &UndirectedGraph, commands: &mut Commands) {
+    let critter_idx = tilepos_to_idx(follower.tile_pos.x, follower.tile_pos.y, graph.length());
+    let Some(critter_translation) = graph.get_node(critter_idx) else {
+        return;
+    };
+
+    commands.spawn((
+        (
+            CritterLabel,
+            CritterName(follower.name.clone()),
+            follower.kind,
+            FollowOrder(follower.order),
+            follower.kind.movement_type(),
+            Transform::from_translation(*critter_translation),
+        ),
+        follower.tile_pos,
+    ));
+}
+
+/// Returns a random Tile Position found in the Graph that
+/// no other entity is standing on, or None if there are none.
+fn free_tile_from(graph: &UndirectedGraph, occupied_tiles: &[TilePos]) -> Option<TilePos> {
+    graph
+        .edges()
+        .0
+        .iter()
+        .enumerate()
+        .filter(|(_idx, node_edges)| !node_edges.is_empty())
+        .map(|(idx, _node_edges)| idx_to_tilepos(idx, graph.length()))
+        .filter(|tile_pos| !occupied_tiles.contains(tile_pos))
+        .choose(&mut rand::thread_rng())
+}
+
+/// Checks whether some Tile is walkable on a Graph.
+fn is_walkable_on(graph: &UndirectedGraph, tile_pos: &TilePos) -> bool {
+    tile_pos.x < graph.length()
+        && tile_pos.y < graph.length()
+        && graph
+            .edges()
+            .0
+            .get(tilepos_to_idx(tile_pos.x, tile_pos.y, graph.length()))
+            .is_some_and(|node_edges| !node_edges.is_empty())
+}
+
+/// Spawns Critters for the most recent followers recorded
+/// from previous streams, and again after each Map Scene switch.
+pub fn restore_critters_from_records(
+    mut has_restored: Local<bool>,
+    mut scene_switched_reader: EventReader<SceneSwitched>,
+    follower_records: Res<FollowerRecords>,
+    graphs: Query<&UndirectedGraph>,
+    mut commands: Commands,
+) {
+    // NOTE: Critters are left behind with the old map on a switch,
+    // so they wait for the Graphs of the new map to come back.
+    if scene_switched_reader.read().last().is_some() {
+        *has_restored = false;
+        return;
+    }
+    if *has_restored {
+        return;
+    }
+
+    if graphs.is_empty() {
+        return;
+    }
+
+    let mut occupied_tiles = Vec::new();
+    for mut follower in follower_records.most_recent(MAX_CRITTERS_ON_SCREEN) {
+        // NOTE: Maps without some Graph, like one without water, only
+        // bring back the Critters that can get around on them.
+        let Some(critter_graph) = graphs
+            .iter()
+            .find(|graph| *graph.get_node_type() == follower.kind.graph_type())
+        else {
+            continue;
+        };
+
+        // Maps of other Map Scenes may not have the recorded Tile.
+        if !is_walkable_on(critter_graph, &follower.tile_pos) {
+            let Some(free_tilepos) = free_tile_from(critter_graph, &occupied_tiles) else {
+                continue;
+            };
+            follower.tile_pos = free_tilepos;
+        }
+
+        spawn_critter(&follower, critter_graph, &mut commands);
+        occupied_tiles.push(follower.tile_pos);
+    }
+
+    *has_restored = true;
+}
+
+/// Settles a Critter on a free Tile for each new follower,
+/// recording them to be restored next time.
+pub fn settle_critter_on_follow(
+    mut follow_msgs: EventReader<FollowMsg>,
+    mut follower_records: ResMut<FollowerRecords>,
+    graphs: Query<&UndirectedGraph>,
+    occupied_tiles: Query<&TilePos, With<GameEntityType>>,
+    mut commands: Commands,
+) {
+    if graphs.is_empty() {
+        return;
+    }
+
+    let mut occupied_tiles = occupied_tiles.iter().copied().collect::<Vec<TilePos>>();
+    for follow_msg in follow_msgs.read() {
+        if follower_records.contains(&follow_msg.name) {
+            continue;
+        }
+
+        let critter_kind = CritterKind::from_name(&follow_msg.name);
+        let Some(critter_graph) = graphs
+            .iter()
+            .find(|graph| *graph.get_node_type() == critter_kind.graph_type())
+        else {
+            continue;
+        };
+
+        let Some(critter_tilepos) = free_tile_from(critter_graph, &occupied_tiles) else {
+            continue;
+        };
+
+        let follow_order = follower_records.add(&follow_msg.name, critter_kind, critter_tilepos);
+        let follower = FollowerRecord {
+            name: follow_msg.name.clone(),
+            kind: critter_kind,
+            tile_pos: critter_tilepos,
+            order: follow_order,
+        };
+
+        spawn_critter(&follower, critter_graph, &mut commands);
+        occupied_tiles.push(critter_tilepos);
+    }
+}
+
+/// Makes way for newer followers by removing the Critters
+/// of the oldest followers once there are too many on screen.
+pub fn rotate_critters_over_cap(
+    critters: Query<(Entity, &FollowOrder), With<CritterLabel>>,
+    mut commands: Commands,
+) {
+    if critters.iter().len() <= MAX_CRITTERS_ON_SCREEN {
+        return;
+    }
+
+    let mut critters_by_order = critters.iter().collect::<Vec<(Entity, &FollowOrder)>>();
+    critters_by_order.sort_by_key(|(_entity, follow_order)| **follow_order);
+
+    let num_over_cap = critters_by_order.len() - MAX_CRITTERS_ON_SCREEN;
+    for (critter_entity, _follow_order) in critters_by_order.into_iter().take(num_over_cap) {
+        commands.entity(critter_entity).despawn_recursive();
+    }
+}
+
+pub fn replace_critter_sprite(
+    critters: Query<(Entity, &Transform, &CritterKind, &CritterName), Added<CritterLabel>>,
+    mut texture_atlases: ResMut<Assets<TextureAtlasLayout>>,
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    entity_definitions: Res<EntityDefinitions>,
+) {
+    let critter_definition = entity_definitions.get(EntityKind::Critter);
+    for (critter_entity, critter_transform, critter_kind, critter_name) in &critters {
+        let texture_handle = asset_server.load(critter_definition.sprite.clone());
+        let critter_texture_atlas = critter_definition.texture_atlas_layout();
+        let critter_texture_atlas_handle = texture_atlases.add(critter_texture_atlas);
+
+        let critter_texture_atlas = TextureAtlas {
+            layout: critter_texture_atlas_handle,
+            index: tilepos_to_idx(
+                critter_kind.sprite_row() as u32,
+                0,
+                critter_definition.columns,
+            ),
+        };
+
+        let critter_sprite = SpriteBundle {
+            sprite: Sprite::default(),
+            texture: texture_handle,
+            transform: *critter_transform,
+            ..default()
+        };
+
+        let critter_name_text = Text2dBundle {
+            text: Text::from_section(
+                critter_name.0.clone(),
+                TextStyle {
+                    font_size: 8.0,
+                    color: Color::WHITE,
+                    ..default()
+                },
+            ),
+            transform: Transform::from_xyz(0.0, CRITTER_NAME_HEIGHT, 1.0),
+            ..default()
+        };
+
+        commands.entity(critter_entity).remove::<Transform>();
+        commands
+            .entity(critter_entity)
+            .insert((
+                critter_sprite,
+                critter_texture_atlas,
+                SpriteDefinition(EntityKind::Critter),
+            ))
+            .with_children(|critter| {
+                critter.spawn(critter_name_text);
+            });
+    }
+}
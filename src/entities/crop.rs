@@ -6,13 +6,17 @@ use bevy_ecs_tilemap::prelude::*;
 use bevy_ecs_tilemap::tiles::{TilePos, TileTextureIndex};
 use rand::seq::IteratorRandom;
 
+use crate::map::destinations::{DestinationRequest, DestinationSource};
+use crate::map::hot_reload::MapPlaced;
+use crate::map::layer_roles::LayerRoles;
 use crate::map::plugins::TilePosEvent;
-use crate::map::tiled::{to_bevy_transform, LayerNumber, TiledMapInformation};
+use crate::map::tiled::{find_layer, tile_transform_on, LayerNumber, MapLayer};
 
 use super::streamer::StreamerLabel;
+use crate::entities::definitions::{EntityDefinitions, EntityKind, SpriteDefinition};
 use crate::entities::TriggerQueue;
 
-#[derive(Component, Debug, PartialEq, PartialOrd)]
+#[derive(Component, Debug, Clone, PartialEq, PartialOrd)]
 pub enum CropState {
     Spawned,
     Planted,
@@ -27,37 +31,44 @@ pub struct CropEndIdx(usize);
 pub struct NewSubscriber;
 
 pub const CROP_NUM_STAGES: usize = 7;
-const CROP_LAYER_NUM: usize = 13;
-const IDEAL_CROP_LAYER_NUM: usize = 3;
 
 pub fn replace_crop_tiles(
     tiles_query: Query<(Entity, &LayerNumber, &TilePos, &TileTextureIndex)>,
-    map_info_query: Query<
-        (&Transform, &TilemapGridSize, &TilemapSize, &TilemapType),
-        Added<TilemapGridSize>,
-    >,
+    map_layers: Query<MapLayer, Added<TilemapGridSize>>,
+    placed_crops: Query<(Entity, &TilePos), With<CropState>>,
     mut commands: Commands,
+    entity_definitions: Res<EntityDefinitions>,
+    layer_roles: Res<LayerRoles>,
 ) {
-    let map_information = map_info_query
-        .iter()
-        .find(|map_info| map_info.0.translation.z == IDEAL_CROP_LAYER_NUM as f32);
-
-    if map_information.is_none() {
+    let crop_definition = entity_definitions.get(EntityKind::Crop);
+    let (Some(crop_layer), Some(crop_spawn_layer)) = (
+        crop_definition.drawn_layer(&layer_roles),
+        crop_definition.spawn_layer(&layer_roles),
+    ) else {
         return;
-    }
+    };
 
-    let (map_transform, grid_size, world_size, map_type) =
-        map_information.expect("replace_crop_tiles: Map information should exist by now.");
+    let Some(spawn_map_layer) = find_layer(&map_layers, crop_spawn_layer) else {
+        return;
+    };
 
+    let mut placed_crops = MapPlaced::new(
+        placed_crops
+            .iter()
+            .map(|(crop_entity, tile_pos)| (crop_entity, *tile_pos)),
+    );
     for (_entity, layer_number, tile_pos, tile_texture_index) in &tiles_query {
-        if layer_number.0 != CROP_LAYER_NUM {
+        if layer_number.0 != crop_layer {
             continue;
         }
 
-        let map_info = TiledMapInformation::new(grid_size, world_size, map_type, map_transform);
-        let tile_transform = to_bevy_transform(tile_pos, map_info);
+        let tile_transform = tile_transform_on(tile_pos, spawn_map_layer);
 
         commands.entity(_entity).despawn_recursive();
+        if placed_crops.keep(tile_pos).is_some() {
+            continue;
+        }
+
         commands.spawn((
             tile_transform,
             *tile_texture_index,
@@ -67,6 +78,7 @@ pub fn replace_crop_tiles(
             TriggerQueue(VecDeque::new()),
         ));
     }
+    placed_crops.despawn_rest(&mut commands);
 }
 
 pub fn replace_crop_sprites(
@@ -74,11 +86,12 @@ pub fn replace_crop_sprites(
     mut texture_atlases: ResMut<Assets<TextureAtlasLayout>>,
     mut commands: Commands,
     asset_server: Res<AssetServer>,
+    entity_definitions: Res<EntityDefinitions>,
 ) {
+    let crop_definition = entity_definitions.get(EntityKind::Crop);
     for (crop_entity, crop_transform, tile_texture_index) in &crops {
-        let texture_handle = asset_server.load("environment/farming crops 1(16x16).png");
-        let crop_texture_atlas =
-            TextureAtlasLayout::from_grid(UVec2::new(16, 16), 16, 16, None, None);
+        let texture_handle = asset_server.load(crop_definition.sprite.clone());
+        let crop_texture_atlas = crop_definition.texture_atlas_layout();
         let crop_texture_atlas_handle = texture_atlases.add(crop_texture_atlas);
 
         let crop_texture_atlas = TextureAtlas {
@@ -94,9 +107,11 @@ pub fn replace_crop_sprites(
         };
 
         commands.entity(crop_entity).remove::<Transform>();
-        commands
-            .entity(crop_entity)
-            .insert((crop_sprite, crop_texture_atlas));
+        commands.entity(crop_entity).insert((
+            crop_sprite,
+            crop_texture_atlas,
+            SpriteDefinition(EntityKind::Crop),
+        ));
     }
 }
 
@@ -145,7 +160,9 @@ pub fn pathfind_streamer_to_crops(
             continue;
         }
 
-        streamer_destination_broadcast.send(TilePosEvent::new(*crop_tile_pos));
+        streamer_destination_broadcast.send(TilePosEvent::from_request(
+            DestinationRequest::new(*crop_tile_pos).from_source(DestinationSource::Crop),
+        ));
     }
 }
 
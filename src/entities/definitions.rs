@@ -0,0 +1,442 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use bevy::{
+    asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext},
+    prelude::*,
+    reflect::TypePath,
+    utils::HashMap,
+};
+use serde::Deserialize;
+use thiserror::Error;
+
+use super::GameEntityType;
+use crate::map::{
+    layer_roles::{LayerRole, LayerRoles},
+    links::LinkTransition,
+    path_finding::Direction,
+};
+
+/// Every kind of entity whose look and placement is
+/// read from a definition file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EntityKind {
+    Streamer,
+    Raider,
+    Chatter,
+    Subscriber,
+    Crop,
+    Fruit,
+    Campfire,
+    Critter,
+}
+
+impl EntityKind {
+    pub const ALL: [EntityKind; 8] = [
+        EntityKind::Streamer,
+        EntityKind::Raider,
+        EntityKind::Chatter,
+        EntityKind::Subscriber,
+        EntityKind::Crop,
+        EntityKind::Fruit,
+        EntityKind::Campfire,
+        EntityKind::Critter,
+    ];
+
+    /// Returns where the definition file for this kind
+    /// of entity lives within the assets folder.
+    pub fn asset_path(&self) -> &'static str {
+        match self {
+            EntityKind::Streamer => "definitions/streamer.entity.ron",
+            EntityKind::Raider => "definitions/raider.entity.ron",
+            EntityKind::Chatter => "definitions/chatter.entity.ron",
+            EntityKind::Subscriber => "definitions/subscriber.entity.ron",
+            EntityKind::Crop => "definitions/crop.entity.ron",
+            EntityKind::Fruit => "definitions/fruit.entity.ron",
+            EntityKind::Campfire => "definitions/campfire.entity.ron",
+            EntityKind::Critter => "definitions/critter.entity.ron",
+        }
+    }
+
+    /// Returns the kind of entity whose animations are
+    /// shared by everything getting around in some way.
+    pub fn animated_like(movement_type: &GameEntityType) -> Self {
+        match movement_type {
+            GameEntityType::Walk => EntityKind::Streamer,
+            GameEntityType::Fly => EntityKind::Chatter,
+            GameEntityType::Swim => EntityKind::Subscriber,
+            // "Environment" is Campfire for the time being.
+            GameEntityType::Environment => EntityKind::Campfire,
+        }
+    }
+
+    /// Returns the definition used until the definition
+    /// file for this kind of entity has been loaded, being
+    /// the one shipped in the assets folder.
+    pub fn builtin_definition(&self) -> EntityDefinition {
+        let definition_bytes: &[u8] = match self {
+            EntityKind::Streamer => include_bytes!("../../assets/definitions/streamer.entity.ron"),
+            EntityKind::Raider => include_bytes!("../../assets/definitions/raider.entity.ron"),
+            EntityKind::Chatter => include_bytes!("../../assets/definitions/chatter.entity.ron"),
+            EntityKind::Subscriber => {
+                include_bytes!("../../assets/definitions/subscriber.entity.ron")
+            }
+            EntityKind::Crop => include_bytes!("../../assets/definitions/crop.entity.ron"),
+            EntityKind::Fruit => include_bytes!("../../assets/definitions/fruit.entity.ron"),
+            EntityKind::Campfire => include_bytes!("../../assets/definitions/campfire.entity.ron"),
+            EntityKind::Critter => include_bytes!("../../assets/definitions/critter.entity.ron"),
+        };
+
+        EntityDefinition::from_bytes(definition_bytes)
+            .expect("builtin_definition: Shipped definition files should be valid.")
+    }
+}
+
+/// The kind of entity whose definition some sprite is drawn from.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpriteDefinition(pub EntityKind);
+
+/// How some kind of entity looks, and where it is found
+/// within the Tiled Map.
+#[derive(Asset, TypePath, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct EntityDefinition {
+    /// The spritesheet, relative to the assets folder.
+    pub sprite: String,
+    /// The width and height of each frame in the spritesheet.
+    pub frame_size: (u32, u32),
+    pub columns: u32,
+    pub rows: u32,
+    /// The role of the Tiled layer the entity is drawn on within
+    /// the map, left out for entities that are not placed by the map.
+    #[serde(default)]
+    pub layer: Option<LayerRole>,
+    /// The role of the Tiled layer whose placement the entity
+    /// takes on, when it differs from the layer it is drawn on.
+    #[serde(default)]
+    pub placed_on_layer: Option<LayerRole>,
+    /// How many frames make up each animation, being a whole
+    /// row of the spritesheet when left out.
+    #[serde(default)]
+    pub animation_len: Option<usize>,
+    /// The row of the spritesheet animating each heading. Headings
+    /// left out borrow the row of their nearest diagonal heading.
+    #[serde(default)]
+    pub heading_rows: BTreeMap<Direction, usize>,
+    /// The row of the spritesheet animating each way of crossing a
+    /// Link, such as climbing a ladder. Transitions left out keep
+    /// animating the entity's heading instead.
+    #[serde(default)]
+    pub transition_rows: BTreeMap<LinkTransition, usize>,
+}
+
+impl EntityDefinition {
+    /// Returns the EntityDefinition read from the contents
+    /// of a RON file, as long as every field holds a
+    /// usable value.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, EntityDefinitionError> {
+        let entity_definition = ron::de::from_bytes::<EntityDefinition>(bytes)?;
+        entity_definition.validate()?;
+
+        Ok(entity_definition)
+    }
+
+    /// Returns an error naming the first field holding
+    /// a value that cannot be used.
+    pub fn validate(&self) -> Result<(), EntityDefinitionError> {
+        if self.sprite.trim().is_empty() {
+            return Err(EntityDefinitionError::InvalidField {
+                field: "sprite",
+                reason: "must name a spritesheet",
+            });
+        }
+
+        if self.frame_size.0 == 0 || self.frame_size.1 == 0 {
+            return Err(EntityDefinitionError::InvalidField {
+                field: "frame_size",
+                reason: "must be wider and taller than zero",
+            });
+        }
+
+        if self.columns == 0 {
+            return Err(EntityDefinitionError::InvalidField {
+                field: "columns",
+                reason: "must be greater than zero",
+            });
+        }
+
+        if self.rows == 0 {
+            return Err(EntityDefinitionError::InvalidField {
+                field: "rows",
+                reason: "must be greater than zero",
+            });
+        }
+
+        if self.animation_len.is_some_and(|animation_len| {
+            animation_len == 0 || animation_len > self.columns as usize
+        }) {
+            return Err(EntityDefinitionError::InvalidField {
+                field: "animation_len",
+                reason: "must be between one and the number of columns",
+            });
+        }
+
+        if self
+            .heading_rows
+            .values()
+            .any(|heading_row| *heading_row >= self.rows as usize)
+        {
+            return Err(EntityDefinitionError::InvalidField {
+                field: "heading_rows",
+                reason: "must be rows found within the spritesheet",
+            });
+        }
+
+        if self
+            .transition_rows
+            .values()
+            .any(|transition_row| *transition_row >= self.rows as usize)
+        {
+            return Err(EntityDefinitionError::InvalidField {
+                field: "transition_rows",
+                reason: "must be rows found within the spritesheet",
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Returns the row of the spritesheet animating some heading.
+    pub fn heading_row(&self, direction: &Direction) -> Option<usize> {
+        self.heading_rows
+            .get(direction)
+            .or_else(|| self.heading_rows.get(&direction.nearest_diagonal()))
+            .copied()
+    }
+
+    /// Returns the row of the spritesheet animating some way of
+    /// crossing a Link.
+    pub fn transition_row(&self, transition: &LinkTransition) -> Option<usize> {
+        self.transition_rows.get(transition).copied()
+    }
+
+    /// Returns the Tiled layer the entity is drawn on,
+    /// found by its role within the map.
+    pub fn drawn_layer(&self, layer_roles: &LayerRoles) -> Option<usize> {
+        self.layer
+            .and_then(|layer_role| layer_roles.index_of(layer_role))
+    }
+
+    /// Returns the Tiled layer whose placement the entity takes on,
+    /// found by its role within the map.
+    pub fn spawn_layer(&self, layer_roles: &LayerRoles) -> Option<usize> {
+        match self.placed_on_layer {
+            Some(layer_role) => layer_roles.index_of(layer_role),
+            None => self.drawn_layer(layer_roles),
+        }
+    }
+
+    /// Returns how many frames make up each animation.
+    pub fn animation_len(&self) -> usize {
+        self.animation_len.unwrap_or(self.columns as usize)
+    }
+
+    /// Returns the layout of the spritesheet's frames.
+    pub fn texture_atlas_layout(&self) -> TextureAtlasLayout {
+        TextureAtlasLayout::from_grid(
+            UVec2::new(self.frame_size.0, self.frame_size.1),
+            self.columns,
+            self.rows,
+            None,
+            None,
+        )
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum EntityDefinitionError {
+    #[error("Could not load entity definition: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Could not parse entity definition: {0}")]
+    Parse(#[from] ron::error::SpannedError),
+    #[error("Invalid `{field}` in entity definition: {reason}")]
+    InvalidField {
+        field: &'static str,
+        reason: &'static str,
+    },
+}
+
+#[derive(Default)]
+pub struct EntityDefinitionLoader;
+
+impl AssetLoader for EntityDefinitionLoader {
+    type Asset = EntityDefinition;
+    type Settings = ();
+    type Error = EntityDefinitionError;
+
+    /// Returns an EntityDefinition loaded from a RON file
+    /// passed into the AssetLoader.
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a Self::Settings,
+        _load_context: &'a mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+
+        EntityDefinition::from_bytes(&bytes)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["entity.ron"]
+    }
+}
+
+/// The definition in use for every kind of entity.
+#[derive(Resource, Clone)]
+pub struct EntityDefinitions(HashMap<EntityKind, EntityDefinition>);
+
+impl Default for EntityDefinitions {
+    fn default() -> Self {
+        Self(
+            EntityKind::ALL
+                .iter()
+                .map(|entity_kind| (*entity_kind, entity_kind.builtin_definition()))
+                .collect(),
+        )
+    }
+}
+
+impl EntityDefinitions {
+    /// Returns the definitions read straight from the definition
+    /// files within some assets folder, for use outside of the game.
+    pub fn from_assets_folder(assets_folder: &Path) -> Result<Self, EntityDefinitionError> {
+        EntityKind::ALL
+            .iter()
+            .map(|entity_kind| {
+                let definition_bytes = std::fs::read(assets_folder.join(entity_kind.asset_path()))?;
+
+                Ok((
+                    *entity_kind,
+                    EntityDefinition::from_bytes(&definition_bytes)?,
+                ))
+            })
+            .collect::<Result<HashMap<EntityKind, EntityDefinition>, EntityDefinitionError>>()
+            .map(Self)
+    }
+
+    pub fn get(&self, entity_kind: EntityKind) -> &EntityDefinition {
+        self.0
+            .get(&entity_kind)
+            .expect("EntityDefinitions::get: Every kind of entity should have a definition.")
+    }
+
+    pub fn insert(&mut self, entity_kind: EntityKind, entity_definition: EntityDefinition) {
+        self.0.insert(entity_kind, entity_definition);
+    }
+
+    /// Returns how many frames make up each animation for
+    /// anything getting around in some way.
+    pub fn animation_len_for(&self, movement_type: &GameEntityType) -> usize {
+        self.get(EntityKind::animated_like(movement_type))
+            .animation_len()
+    }
+
+    /// Returns the row of the spritesheet animating some heading
+    /// for anything getting around in some way.
+    pub fn heading_row_for(
+        &self,
+        movement_type: &GameEntityType,
+        direction: &Direction,
+    ) -> Option<usize> {
+        self.get(EntityKind::animated_like(movement_type))
+            .heading_row(direction)
+    }
+
+    /// Returns the row of the spritesheet animating some way of
+    /// crossing a Link for anything getting around in some way.
+    pub fn transition_row_for(
+        &self,
+        movement_type: &GameEntityType,
+        transition: &LinkTransition,
+    ) -> Option<usize> {
+        self.get(EntityKind::animated_like(movement_type))
+            .transition_row(transition)
+    }
+}
+
+/// The definition files being loaded for each kind of entity.
+#[derive(Resource, Default)]
+pub struct EntityDefinitionHandles(HashMap<EntityKind, Handle<EntityDefinition>>);
+
+pub fn load_entity_definitions(
+    asset_server: Res<AssetServer>,
+    mut definition_handles: ResMut<EntityDefinitionHandles>,
+) {
+    for entity_kind in EntityKind::ALL {
+        definition_handles
+            .0
+            .insert(entity_kind, asset_server.load(entity_kind.asset_path()));
+    }
+}
+
+/// Swaps in each definition file once it has loaded,
+/// or whenever it changes.
+pub fn apply_loaded_entity_definitions(
+    mut definition_events: EventReader<AssetEvent<EntityDefinition>>,
+    definition_assets: Res<Assets<EntityDefinition>>,
+    definition_handles: Res<EntityDefinitionHandles>,
+    mut entity_definitions: ResMut<EntityDefinitions>,
+) {
+    for definition_event in definition_events.read() {
+        let (AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id }) =
+            definition_event
+        else {
+            continue;
+        };
+
+        let Some(entity_kind) = definition_handles
+            .0
+            .iter()
+            .find(|(_entity_kind, handle)| handle.id() == *id)
+            .map(|(entity_kind, _handle)| *entity_kind)
+        else {
+            continue;
+        };
+
+        if let Some(entity_definition) = definition_assets.get(*id) {
+            entity_definitions.insert(entity_kind, entity_definition.clone());
+        }
+    }
+}
+
+/// Redraws every sprite from the definition of its kind whenever
+/// the definitions change, so sprites drawn before a definition
+/// file loaded, or was edited, do not keep their old look.
+pub fn redraw_sprites_from_definitions(
+    mut sprites: Query<(&SpriteDefinition, &mut Handle<Image>, &mut TextureAtlas)>,
+    entity_definitions: Res<EntityDefinitions>,
+    asset_server: Res<AssetServer>,
+    mut texture_atlases: ResMut<Assets<TextureAtlasLayout>>,
+) {
+    if !entity_definitions.is_changed() {
+        return;
+    }
+
+    let mut redrawn_kinds = HashMap::new();
+    for (sprite_definition, mut texture_handle, mut texture_atlas) in &mut sprites {
+        let (redrawn_texture, redrawn_layout) =
+            redrawn_kinds.entry(sprite_definition.0).or_insert_with(|| {
+                let entity_definition = entity_definitions.get(sprite_definition.0);
+
+                (
+                    asset_server.load(entity_definition.sprite.clone()),
+                    texture_atlases.add(entity_definition.texture_atlas_layout()),
+                )
+            });
+
+        *texture_handle = redrawn_texture.clone();
+        texture_atlas.layout = redrawn_layout.clone();
+    }
+}
@@ -1,10 +1,13 @@
 use std::collections::VecDeque;
 
+use crate::entities::definitions::{EntityDefinitions, EntityKind, SpriteDefinition};
 use crate::entities::TriggerQueue;
 use crate::map::{
-    path_finding::{
-        tilepos_to_idx, GraphType, MovementTimer, StartingPoint, Target, UndirectedGraph,
-    },
+    destinations::{DestinationRequest, DestinationSource},
+    hot_reload::MapPlaced,
+    layer_roles::LayerRoles,
+    movement::{MovementSpeed, StepProgress},
+    path_finding::{tilepos_to_idx, GraphType, StartingPoint, Target, UndirectedGraph},
     plugins::TilePosEvent,
     tiled::*,
 };
@@ -14,7 +17,7 @@ use rand::seq::IteratorRandom;
 
 use super::streamer::StreamerLabel;
 
-#[derive(Component, Debug, PartialEq, Eq)]
+#[derive(Component, Debug, Clone, PartialEq, Eq)]
 pub enum FruitState {
     Spawned,
     Hanging,
@@ -25,37 +28,62 @@ pub enum FruitState {
 #[derive(Component)]
 pub struct RespawnPoint(pub StartingPoint);
 
-const FRUIT_LAYER_NUM: usize = 17;
-const FALLEN_FRUIT_LAYER_NUM: usize = FRUIT_LAYER_NUM - 4;
+/// How many layers below its tree some Fruit lands on.
+const FALLEN_FRUIT_LAYER_DROP: usize = 4;
+
+/// How many Tiles some Fruit falls each second.
+const FRUIT_FALL_SPEED: f32 = 2.5;
+
+/// How many Tiles along each axis some Fruit lands away from its tree.
+const FRUIT_FALL_DISTANCE: u32 = 3;
+
+/// Returns the Tile some Fruit lands on once it falls from
+/// its tree, or None when it would fall off the map.
+pub fn fall_target(fruit_tile_pos: TilePos) -> Option<TilePos> {
+    Some(TilePos::new(
+        fruit_tile_pos.x + FRUIT_FALL_DISTANCE,
+        fruit_tile_pos.y.checked_sub(FRUIT_FALL_DISTANCE)?,
+    ))
+}
 
 pub fn replace_fruit_tiles(
     mut tiles_query: Query<(Entity, &LayerNumber, &TilePos, &TileTextureIndex)>,
-    map_info_query: Query<
-        (&Transform, &TilemapGridSize, &TilemapSize, &TilemapType),
-        Added<TilemapGridSize>,
-    >,
+    map_layers: Query<MapLayer, Added<TilemapGridSize>>,
+    placed_fruit: Query<(Entity, &RespawnPoint)>,
     mut commands: Commands,
+    entity_definitions: Res<EntityDefinitions>,
+    layer_roles: Res<LayerRoles>,
 ) {
-    let map_information = map_info_query
-        .iter()
-        .find(|map_info| map_info.0.translation.z == FRUIT_LAYER_NUM as f32);
-
-    if map_information.is_none() {
+    let fruit_definition = entity_definitions.get(EntityKind::Fruit);
+    let (Some(fruit_layer), Some(fruit_spawn_layer)) = (
+        fruit_definition.drawn_layer(&layer_roles),
+        fruit_definition.spawn_layer(&layer_roles),
+    ) else {
         return;
-    }
-
-    let (map_transform, grid_size, map_size, map_type) =
-        map_information.expect("replace_fruit_tiles: Map information should exist by now.");
+    };
 
+    let Some(spawn_map_layer) = find_layer(&map_layers, fruit_spawn_layer) else {
+        return;
+    };
+
+    // NOTE: Fruit is known by the tree it grows on, wherever it has fallen to.
+    let mut placed_fruit = MapPlaced::new(
+        placed_fruit
+            .iter()
+            .map(|(fruit_entity, respawn_point)| (fruit_entity, respawn_point.0 .1)),
+    );
     for (_entity, layer_number, tile_pos, tile_texture_index) in &mut tiles_query {
-        if layer_number.0 != FRUIT_LAYER_NUM {
+        if layer_number.0 != fruit_layer {
             continue;
         }
 
-        let map_info = TiledMapInformation::new(grid_size, map_size, map_type, map_transform);
-        let tile_transform = to_bevy_transform(tile_pos, map_info);
+        let tile_transform = tile_transform_on(tile_pos, spawn_map_layer);
 
         commands.entity(_entity).despawn_recursive();
+        if placed_fruit.keep(tile_pos).is_some() {
+            continue;
+        }
+
         commands.spawn((
             *tile_pos,
             *tile_texture_index,
@@ -64,10 +92,12 @@ pub fn replace_fruit_tiles(
             StartingPoint(tile_transform.translation, *tile_pos),
             RespawnPoint(StartingPoint(tile_transform.translation, *tile_pos)),
             Target(None),
-            MovementTimer(Timer::from_seconds(0.05, TimerMode::Repeating)),
+            MovementSpeed(FRUIT_FALL_SPEED),
+            StepProgress::default(),
             TriggerQueue(VecDeque::new()),
         ));
     }
+    placed_fruit.despawn_rest(&mut commands);
 }
 
 pub fn replace_fruit_sprites(
@@ -75,11 +105,12 @@ pub fn replace_fruit_sprites(
     mut texture_atlases: ResMut<Assets<TextureAtlasLayout>>,
     mut commands: Commands,
     asset_server: Res<AssetServer>,
+    entity_definitions: Res<EntityDefinitions>,
 ) {
+    let fruit_definition = entity_definitions.get(EntityKind::Fruit);
     for (fruit_entity, fruit_transform, tile_texture_index) in &fruit {
-        let texture_handle = asset_server.load("environment/Fruit(16x16).png");
-        let fruit_texture_atlas =
-            TextureAtlasLayout::from_grid(UVec2::new(16, 16), 38, 6, None, None);
+        let texture_handle = asset_server.load(fruit_definition.sprite.clone());
+        let fruit_texture_atlas = fruit_definition.texture_atlas_layout();
         let fruit_texture_atlas_handle = texture_atlases.add(fruit_texture_atlas);
 
         let fruit_texture_atlas = TextureAtlas {
@@ -95,36 +126,41 @@ pub fn replace_fruit_sprites(
         };
 
         commands.entity(fruit_entity).remove::<Transform>();
-        commands
-            .entity(fruit_entity)
-            .insert((fruit_sprite, fruit_texture_atlas));
+        commands.entity(fruit_entity).insert((
+            fruit_sprite,
+            fruit_texture_atlas,
+            SpriteDefinition(EntityKind::Fruit),
+        ));
     }
 }
 
 pub fn make_fruit_fall(
     mut fruit_query: Query<(&TilePos, &mut FruitState, &mut Target, &TriggerQueue)>,
     ground_graph_query: Query<&UndirectedGraph>,
-    map_info_query: Query<(&Transform, &TilemapSize)>,
+    map_layers: Query<MapLayer>,
+    entity_definitions: Res<EntityDefinitions>,
+    layer_roles: Res<LayerRoles>,
 ) {
     if ground_graph_query.is_empty() {
         return;
     }
 
+    let Some(fallen_fruit_layer) = entity_definitions
+        .get(EntityKind::Fruit)
+        .drawn_layer(&layer_roles)
+        .and_then(|fruit_layer| fruit_layer.checked_sub(FALLEN_FRUIT_LAYER_DROP))
+    else {
+        return;
+    };
+
     let ground_graph = ground_graph_query
         .iter()
         .find(|graph| *graph.get_node_type() == GraphType::Ground)
         .expect("make_fruit_fall: Could not find Undirected Graph representing Ground tiles.");
 
-    let map_information = map_info_query
-        .iter()
-        .find(|map_info| map_info.0.translation.z == FALLEN_FRUIT_LAYER_NUM as f32);
-
-    if map_information.is_none() {
+    let Some((_, _, world_size, _)) = find_layer(&map_layers, fallen_fruit_layer) else {
         return;
-    }
-
-    let (_map_transform, world_size) =
-        map_information.expect("make_fruit_fall: Map information should exist by now.");
+    };
 
     for (fruit_tile_pos, mut fruit_state, mut fruit_pathing_target, fruit_trigger_queue) in
         fruit_query.iter_mut()
@@ -137,7 +173,9 @@ pub fn make_fruit_fall(
             continue;
         }
 
-        let tile_target_pos = TilePos::new(fruit_tile_pos.x + 3, fruit_tile_pos.y - 3);
+        let Some(tile_target_pos) = fall_target(*fruit_tile_pos) else {
+            continue;
+        };
         let tile_translation = ground_graph
             .get_node(tilepos_to_idx(
                 tile_target_pos.x,
@@ -175,7 +213,9 @@ pub fn pathfind_streamer_to_fruit(
             continue;
         }
 
-        streamer_destination_request_writer.send(TilePosEvent::new(*fruit_tile_pos));
+        streamer_destination_request_writer.send(TilePosEvent::from_request(
+            DestinationRequest::new(*fruit_tile_pos).from_source(DestinationSource::Fruit),
+        ));
     }
 }
 
@@ -3,13 +3,18 @@ use std::collections::VecDeque;
 use bevy::prelude::*;
 
 pub mod chatter;
+pub mod critter;
 pub mod crop;
+pub mod definitions;
 pub mod fruit;
 pub mod plugins;
+pub mod raider;
 pub mod streamer;
 pub mod subscriber;
+pub mod visitor;
+pub mod wander;
 
-#[derive(Component, Debug, Default, PartialEq, Eq, Clone, Copy)]
+#[derive(Component, Debug, Default, PartialEq, Eq, Hash, Clone, Copy)]
 pub enum GameEntityType {
     Walk,
     #[default]
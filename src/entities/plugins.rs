@@ -1,30 +1,87 @@
 use crate::entities::chatter::*;
+use crate::entities::critter::*;
 use crate::entities::crop::*;
+use crate::entities::definitions::*;
 use crate::entities::fruit::*;
+use crate::entities::raider::*;
 use crate::entities::streamer::*;
 use crate::entities::subscriber::*;
+use crate::entities::visitor::*;
+use crate::entities::wander::*;
+use crate::map::layer_roles::LayerRoles;
 use crate::GameState;
 use bevy::prelude::*;
+use std::marker::PhantomData;
+
+/// Loads what every kind of entity looks like, and where
+/// each is found within the map, from its definition file.
+#[derive(Default)]
+pub struct EntityDefinitionsPlugin;
+
+impl Plugin for EntityDefinitionsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<EntityDefinition>()
+            .register_asset_loader(EntityDefinitionLoader)
+            .init_resource::<EntityDefinitions>()
+            .init_resource::<LayerRoles>()
+            .init_resource::<EntityDefinitionHandles>()
+            .add_systems(Startup, load_entity_definitions)
+            .add_systems(
+                Update,
+                (
+                    apply_loaded_entity_definitions,
+                    redraw_sprites_from_definitions,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// Lets some kind of NPC visit the Streamer to speak,
+/// then head back home once done.
+pub struct VisitorPlugin<V: VisitorConfig>(PhantomData<V>);
+
+impl<V: VisitorConfig> Default for VisitorPlugin<V> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<V: VisitorConfig> Plugin for VisitorPlugin<V> {
+    fn build(&self, app: &mut App) {
+        app.add_event::<V::Msg>();
+        app.init_resource::<StreamerFlowFields>();
+        app.add_systems(
+            Update,
+            (
+                add_msg_to_queue::<V>,
+                update_flow_field_towards_streamer::<V>
+                    .before(approach_streamer_to_speak::<V>)
+                    .before(follow_streamer_while_approaching::<V>),
+                approach_streamer_to_speak::<V>,
+                speak_to_streamer::<V>,
+                wait_to_leave_from_streamer::<V>,
+                leave_from_streamer::<V>,
+                return_visitor_to_idle::<V>,
+                follow_streamer_while_approaching::<V>,
+            ),
+        );
+    }
+}
 
 #[derive(Default)]
 pub struct ChatterPlugin;
 
 impl Plugin for ChatterPlugin {
     fn build(&self, app: &mut App) {
-        app.add_event::<ChatMsg>();
+        app.add_plugins(VisitorPlugin::<ChatterLabel>::default());
         app.add_systems(
             Update,
             (
                 replace_chatter_tile,
                 replace_chatter_sprite,
                 trigger_flying_to_streamer,
-                add_chat_msg_to_queue,
-                fly_to_streamer_to_speak,
-                chatter_waits_to_leave_from_streamer,
-                leave_from_streamer_from_chatter,
-                return_chatter_to_idle,
                 follow_streamer_while_speaking,
-                follow_streamer_while_approaching_for_chatter,
             ),
         );
     }
@@ -35,18 +92,13 @@ pub struct SubscriberPlugin;
 
 impl Plugin for SubscriberPlugin {
     fn build(&self, app: &mut App) {
-        app.add_event::<SubscriberMsg>();
+        app.add_plugins(VisitorPlugin::<SubscriberLabel>::default());
         app.add_systems(
             Update,
             (
                 replace_subscriber_sprite,
                 replace_subscriber_tile,
                 trigger_swimming_to_streamer,
-                swim_to_streamer_to_speak,
-                subscriber_waits_to_leave_from_streamer,
-                leave_from_streamer_from_subscriber,
-                return_subscriber_to_idle,
-                follow_streamer_while_approaching_for_subscriber,
             ),
         );
     }
@@ -58,12 +110,14 @@ pub struct StreamerPlugin;
 impl Plugin for StreamerPlugin {
     fn build(&self, app: &mut App) {
         app.add_event::<OnlineStatus>();
+        app.init_resource::<RouteSettings>();
         app.add_systems(
             Update,
             (
                 spawn_player_sprite,
                 spawn_player_tile,
                 move_streamer,
+                follow_path_found_for_streamer,
                 make_streamer_idle_when_not_moving,
                 change_status_for_streamer,
                 move_streamer_on_status_change,
@@ -117,3 +171,64 @@ impl Plugin for CropPlugin {
         );
     }
 }
+
+#[derive(Default)]
+pub struct RaiderPlugin;
+
+impl Plugin for RaiderPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<RaidMsg>();
+        app.add_systems(
+            Update,
+            (
+                trigger_raid_on_r_key,
+                spawn_raiders_on_raid,
+                replace_raider_sprite,
+                replace_raid_banner_text,
+                walk_raiders_to_streamer,
+                disperse_raiders,
+                despawn_dispersed_raiders,
+                move_raid_banner_with_leader,
+            ),
+        );
+    }
+}
+
+#[derive(Default)]
+pub struct CritterPlugin;
+
+impl Plugin for CritterPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<FollowMsg>();
+        app.insert_resource(FollowerRecords::new(String::from("twitch_followers.db")));
+        app.add_systems(
+            Update,
+            (
+                trigger_follow_on_o_key,
+                restore_critters_from_records,
+                settle_critter_on_follow,
+                rotate_critters_over_cap,
+                replace_critter_sprite,
+            )
+                .chain(),
+        );
+    }
+}
+
+#[derive(Default)]
+pub struct WanderPlugin;
+
+impl Plugin for WanderPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<WanderSettings>();
+        app.add_systems(
+            Update,
+            (
+                insert_wander_area::<VisitorStatus>,
+                insert_wander_area::<CritterLabel>,
+                wander_while_idle::<VisitorStatus>,
+                wander_while_idle::<CritterLabel>,
+            ),
+        );
+    }
+}
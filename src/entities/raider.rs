@@ -0,0 +1,372 @@
+use bevy::prelude::*;
+use bevy_ecs_tilemap::prelude::*;
+
+use crate::entities::definitions::{EntityDefinitions, EntityKind, SpriteDefinition};
+use crate::entities::streamer::StreamerLabel;
+use crate::map::path_finding::*;
+
+use super::GameEntityType;
+
+pub const MAX_RAID_VISITORS: usize = 12;
+pub const MIN_DIST_AWAY_FROM_STREAMER: usize = 2;
+pub const MAX_DIST_AWAY_FROM_STREAMER: usize = 5;
+pub const RAID_BANNER_HEIGHT: f32 = 24.0;
+
+#[derive(Component)]
+pub struct RaiderLabel;
+
+#[derive(Component, Debug, PartialEq)]
+pub enum RaiderStatus {
+    Arriving,
+    Gathering,
+    Dispersing,
+}
+
+#[derive(Event, Clone)]
+pub struct RaidMsg {
+    pub channel_name: String,
+    pub viewer_count: u64,
+}
+
+/// The Tile a Raider stands on while gathered around
+/// the Streamer.
+#[derive(Component)]
+pub struct GatheringPoint(pub TilePos);
+
+/// How long Raiders stick around the Streamer before
+/// heading back to where they came from.
+#[derive(Component, Deref, DerefMut)]
+pub struct DisperseTimer(pub Timer);
+
+/// Shows the name of the raiding channel above the
+/// Raider leading the parade.
+#[derive(Component)]
+pub struct RaidBanner {
+    pub channel_name: String,
+    pub leader: Entity,
+}
+
+pub fn trigger_raid_on_r_key(
+    mut raid_msg: EventWriter<RaidMsg>,
+    pressed_key: Res<ButtonInput<KeyCode>>,
+) {
+    if !pressed_key.just_pressed(KeyCode::KeyR) {
+        return;
+    }
+
+    raid_msg.send(RaidMsg {
+        channel_name: String::from("Raidu"),
+        viewer_count: 5,
+    });
+}
+
+/// Returns how many Tiles away some Tile Position is from
+/// the closest edge of the map.
+fn distance_to_map_edge(tile_pos: TilePos, map_size: &TilemapSize) -> u32 {
+    let from_left = tile_pos.x;
+    let from_right = map_size.x.saturating_sub(1 + tile_pos.x);
+    let from_bottom = tile_pos.y;
+    let from_top = map_size.y.saturating_sub(1 + tile_pos.y);
+
+    from_left.min(from_right).min(from_bottom).min(from_top)
+}
+
+/// Returns the Node indices closest to the edge of the map
+/// that can still reach the Streamer.
+fn edge_tiles_from(
+    distances_to_streamer: &[Option<usize>],
+    map_size: &TilemapSize,
+    graph_length: u32,
+) -> Vec<usize> {
+    let mut reachable_tiles = distances_to_streamer
+        .iter()
+        .enumerate()
+        .filter(|(_idx, distance)| distance.is_some())
+        .map(|(idx, _distance)| idx)
+        .collect::<Vec<usize>>();
+
+    reachable_tiles.sort_by_key(|idx| {
+        let tile_pos = idx_to_tilepos(*idx, graph_length);
+        (distance_to_map_edge(tile_pos, map_size), *idx)
+    });
+
+    reachable_tiles
+}
+
+/// Returns the Node indices of Tiles surrounding the Streamer,
+/// sorted from closest to furthest.
+fn gathering_tiles_from(distances_to_streamer: &[Option<usize>]) -> Vec<usize> {
+    let mut gathering_tiles = distances_to_streamer
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, distance)| distance.map(|distance| (idx, distance)))
+        .filter(|(_idx, distance)| {
+            (MIN_DIST_AWAY_FROM_STREAMER..=MAX_DIST_AWAY_FROM_STREAMER).contains(distance)
+        })
+        .collect::<Vec<(usize, usize)>>();
+
+    gathering_tiles.sort_by_key(|(idx, distance)| (*distance, *idx));
+
+    gathering_tiles
+        .into_iter()
+        .map(|(idx, _distance)| idx)
+        .collect()
+}
+
+/// Spawns a crowd of Raiders walking in from the edge of
+/// the map, scaled to the size of the Raid.
+pub fn spawn_raiders_on_raid(
+    mut raid_msgs: EventReader<RaidMsg>,
+    streamer: Query<&TilePos, With<StreamerLabel>>,
+    graphs: Query<&UndirectedGraph>,
+    map_info: Query<&TilemapSize>,
+    mut commands: Commands,
+) {
+    if streamer.is_empty() || graphs.is_empty() || map_info.is_empty() {
+        return;
+    }
+
+    let ground_graph = graphs
+        .iter()
+        .find(|graph| *graph.get_node_type() == GraphType::Ground)
+        .expect("spawn_raiders_on_raid: There should only be one ground graph.");
+    let streamer_tilepos = streamer
+        .get_single()
+        .expect("spawn_raiders_on_raid: There should only be one streamer.");
+    let map_size = map_info
+        .iter()
+        .last()
+        .expect("spawn_raiders_on_raid: Map should be spawned by now.");
+
+    for raid_msg in raid_msgs.read() {
+        let distances_to_streamer = ground_graph.distances_from(*streamer_tilepos);
+        let spawn_tiles = edge_tiles_from(&distances_to_streamer, map_size, ground_graph.length());
+        let gathering_tiles = gathering_tiles_from(&distances_to_streamer);
+
+        let num_raiders = (raid_msg.viewer_count as usize)
+            .min(MAX_RAID_VISITORS)
+            .min(spawn_tiles.len())
+            .min(gathering_tiles.len());
+
+        let mut raid_leader = None;
+        for (spawn_idx, gathering_idx) in spawn_tiles
+            .iter()
+            .zip(gathering_tiles.iter())
+            .take(num_raiders)
+        {
+            let spawn_tilepos = idx_to_tilepos(*spawn_idx, ground_graph.length());
+            let spawn_translation = ground_graph
+                .get_node(*spawn_idx)
+                .expect("spawn_raiders_on_raid: Spawn Tile should exist in the ground graph.");
+
+            let raider_entity = commands
+                .spawn((
+                    (
+                        RaiderLabel,
+                        GameEntityType::Walk,
+                        Transform::from_translation(*spawn_translation),
+                        RaiderStatus::Arriving,
+                        GatheringPoint(idx_to_tilepos(*gathering_idx, ground_graph.length())),
+                    ),
+                    spawn_tilepos,
+                ))
+                .id();
+
+            raid_leader.get_or_insert(raider_entity);
+        }
+
+        if let Some(leader) = raid_leader {
+            commands.spawn(RaidBanner {
+                channel_name: raid_msg.channel_name.clone(),
+                leader,
+            });
+        }
+    }
+}
+
+pub fn replace_raider_sprite(
+    raiders: Query<(Entity, &Transform), Added<RaiderLabel>>,
+    mut texture_atlases: ResMut<Assets<TextureAtlasLayout>>,
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    entity_definitions: Res<EntityDefinitions>,
+) {
+    let raider_definition = entity_definitions.get(EntityKind::Raider);
+    for (raider_entity, raider_transform) in &raiders {
+        let texture_handle = asset_server.load(raider_definition.sprite.clone());
+        let raider_texture_atlas = raider_definition.texture_atlas_layout();
+        let raider_texture_atlas_handle = texture_atlases.add(raider_texture_atlas);
+
+        let raider_texture_atlas = TextureAtlas {
+            layout: raider_texture_atlas_handle,
+            index: 0,
+        };
+
+        let raider_sprite = SpriteBundle {
+            sprite: Sprite::default(),
+            texture: texture_handle,
+            transform: *raider_transform,
+            ..default()
+        };
+
+        commands.entity(raider_entity).remove::<Transform>();
+        commands.entity(raider_entity).insert((
+            raider_sprite,
+            raider_texture_atlas,
+            SpriteDefinition(EntityKind::Raider),
+        ));
+    }
+}
+
+/// Attaches the text for the Raid Banner when first raised.
+pub fn replace_raid_banner_text(
+    raid_banners: Query<(Entity, &RaidBanner), Added<RaidBanner>>,
+    mut commands: Commands,
+) {
+    for (banner_entity, raid_banner) in &raid_banners {
+        let banner_text = Text2dBundle {
+            text: Text::from_section(
+                raid_banner.channel_name.clone(),
+                TextStyle {
+                    font_size: 12.0,
+                    color: Color::WHITE,
+                    ..default()
+                },
+            ),
+            ..default()
+        };
+
+        commands.entity(banner_entity).insert(banner_text);
+    }
+}
+
+/// Sends each arriving Raider towards their spot
+/// around the Streamer.
+pub fn walk_raiders_to_streamer(
+    mut raiders: Query<(
+        Entity,
+        &TilePos,
+        &GatheringPoint,
+        &mut Path,
+        &Target,
+        &mut RaiderStatus,
+    )>,
+    graphs: Query<&UndirectedGraph>,
+    mut commands: Commands,
+) {
+    if raiders.is_empty() || graphs.is_empty() {
+        return;
+    }
+
+    let ground_graph = graphs
+        .iter()
+        .find(|graph| *graph.get_node_type() == GraphType::Ground)
+        .expect("walk_raiders_to_streamer: There should only be one ground graph.");
+
+    for (
+        raider_entity,
+        raider_tilepos,
+        gathering_point,
+        mut raider_path,
+        raider_target,
+        mut raider_status,
+    ) in &mut raiders
+    {
+        if *raider_status != RaiderStatus::Arriving
+            || !raider_path.is_empty()
+            || raider_target.is_some()
+        {
+            continue;
+        }
+
+        if *raider_tilepos == gathering_point.0 {
+            *raider_status = RaiderStatus::Gathering;
+            commands
+                .entity(raider_entity)
+                .insert(DisperseTimer(Timer::from_seconds(30.0, TimerMode::Once)));
+            continue;
+        }
+
+        if let Some(path) = ground_graph.shortest_path(*raider_tilepos, gathering_point.0) {
+            *raider_path = path;
+        }
+    }
+}
+
+/// Sends Raiders back to where they came from once they
+/// have gathered long enough.
+pub fn disperse_raiders(
+    time: Res<Time>,
+    mut raiders: Query<(
+        &TilePos,
+        &SpawnPoint,
+        &mut DisperseTimer,
+        &mut Path,
+        &mut RaiderStatus,
+    )>,
+    graphs: Query<&UndirectedGraph>,
+) {
+    if raiders.is_empty() || graphs.is_empty() {
+        return;
+    }
+
+    let ground_graph = graphs
+        .iter()
+        .find(|graph| *graph.get_node_type() == GraphType::Ground)
+        .expect("disperse_raiders: There should only be one ground graph.");
+
+    for (raider_tilepos, raider_spawn, mut disperse_timer, mut raider_path, mut raider_status) in
+        &mut raiders
+    {
+        if *raider_status != RaiderStatus::Gathering {
+            continue;
+        }
+
+        disperse_timer.tick(time.delta());
+        if !disperse_timer.finished() {
+            continue;
+        }
+
+        if let Some(path) = ground_graph.shortest_path(*raider_tilepos, raider_spawn.0) {
+            *raider_path = path;
+            *raider_status = RaiderStatus::Dispersing;
+        }
+    }
+}
+
+/// Removes Raiders who have made it back to the edge of the
+/// map after dispersing.
+pub fn despawn_dispersed_raiders(
+    raiders: Query<(Entity, &Path, &Target, &RaiderStatus)>,
+    mut commands: Commands,
+) {
+    for (raider_entity, raider_path, raider_target, raider_status) in &raiders {
+        if *raider_status != RaiderStatus::Dispersing {
+            continue;
+        }
+
+        if !raider_path.is_empty() || raider_target.is_some() {
+            continue;
+        }
+
+        commands.entity(raider_entity).despawn_recursive();
+    }
+}
+
+/// Keeps the Raid Banner above the Raider leading the parade,
+/// taking it down once the leader has left.
+pub fn move_raid_banner_with_leader(
+    mut raid_banners: Query<(Entity, &RaidBanner, &mut Transform), Without<RaiderLabel>>,
+    raiders: Query<&Transform, With<RaiderLabel>>,
+    mut commands: Commands,
+) {
+    for (banner_entity, raid_banner, mut banner_transform) in &mut raid_banners {
+        let Ok(leader_transform) = raiders.get(raid_banner.leader) else {
+            commands.entity(banner_entity).despawn_recursive();
+            continue;
+        };
+
+        banner_transform.translation =
+            leader_transform.translation + Vec3::new(0.0, RAID_BANNER_HEIGHT, 1.0);
+    }
+}
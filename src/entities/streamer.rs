@@ -1,12 +1,15 @@
 use bevy::prelude::*;
-use bevy_ecs_tilemap::{
-    prelude::{TilemapGridSize, TilemapSize, TilemapType},
-    tiles::TilePos,
-};
+use bevy_ecs_tilemap::prelude::TilemapType;
 
+use crate::entities::definitions::{EntityDefinitions, EntityKind, SpriteDefinition};
+use crate::map::destinations::*;
+use crate::map::layer_roles::LayerRoles;
+use crate::map::movement::{HopHeight, MovementEasing};
 use crate::map::path_finding::*;
-use crate::map::plugins::TilePosEvent;
-use crate::map::tiled::{to_bevy_transform, TiledMapInformation};
+use crate::map::path_requests::{AwaitingPath, PathError, PathRequest, PathResult};
+use crate::map::plugins::{CancelDestinationEvent, TilePosEvent};
+use crate::map::scenes::{MapScenes, SceneArrival};
+use crate::map::tiled::{find_layer, tile_transform_on, MapLayer};
 use crate::ui::chatting::ChattingStatus;
 
 use super::GameEntityType;
@@ -32,6 +35,24 @@ pub enum OnlineStatus {
     Away,
 }
 
+/// How the Streamer picks between destinations
+/// that are equally urgent.
+#[derive(Resource, Default)]
+pub struct RouteSettings {
+    /// Heads to whichever destination is the cheapest walk
+    /// away, rather than whichever was requested first.
+    pub shorten_walk: bool,
+}
+
+/// The destination the Streamer is waiting on a Path towards,
+/// kept to be queued back up if that Path comes back stale.
+#[derive(Component, Default, Deref, DerefMut)]
+pub struct RequestedDestination(pub Option<DestinationRequest>);
+
+/// How high the Streamer hops, in pixels, when
+/// climbing up or dropping down onto other Tiles.
+const STREAMER_HOP_HEIGHT: f32 = 4.0;
+
 #[derive(Bundle)]
 pub struct Streamer {
     label: StreamerLabel,
@@ -40,13 +61,12 @@ pub struct Streamer {
     status: StreamerState,
 }
 
-pub const STREAMER_LAYER_NUM: usize = 6;
-
 pub fn spawn_player_sprite(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     mut texture_atlases: ResMut<Assets<TextureAtlasLayout>>,
     streamer_query: Query<(Entity, &Transform), (With<StreamerLabel>, Without<TextureAtlas>)>,
+    entity_definitions: Res<EntityDefinitions>,
 ) {
     if streamer_query.is_empty() {
         return;
@@ -56,8 +76,9 @@ pub fn spawn_player_sprite(
         .get_single()
         .expect("spawn_player: Could not find Streamer.");
 
-    let texture_handle = asset_server.load("caveman/caveman-sheet.png");
-    let texture_atlas = TextureAtlasLayout::from_grid(UVec2::new(16, 16), 4, 9, None, None);
+    let streamer_definition = entity_definitions.get(EntityKind::Streamer);
+    let texture_handle = asset_server.load(streamer_definition.sprite.clone());
+    let texture_atlas = streamer_definition.texture_atlas_layout();
     let texture_atlas_handle = texture_atlases.add(texture_atlas);
 
     let streamer_texture_atlas = TextureAtlas {
@@ -73,36 +94,48 @@ pub fn spawn_player_sprite(
     };
 
     commands.entity(streamer_entity).remove::<Transform>();
-    commands
-        .entity(streamer_entity)
-        .insert((streamer_sprite, streamer_texture_atlas));
+    commands.entity(streamer_entity).insert((
+        streamer_sprite,
+        streamer_texture_atlas,
+        SpriteDefinition(EntityKind::Streamer),
+    ));
 }
 
-/// Spawns Player without any component related to rendering
+/// Spawns Player without any component related to rendering, or
+/// moves them over onto a newly switched map when already around.
 pub fn spawn_player_tile(
     mut commands: Commands,
-    map_information: Query<
-        (&Transform, &TilemapType, &TilemapGridSize, &TilemapSize),
-        Added<TilemapType>,
-    >,
-    streamer_query: Query<(), With<StreamerLabel>>,
+    map_layers: Query<MapLayer, Added<TilemapType>>,
+    streamer_query: Query<Entity, With<StreamerLabel>>,
+    entity_definitions: Res<EntityDefinitions>,
+    map_scenes: Res<MapScenes>,
+    layer_roles: Res<LayerRoles>,
 ) {
-    if !streamer_query.is_empty() {
+    if map_layers.is_empty() {
         return;
     }
 
-    if map_information.is_empty() {
+    let Some(streamer_layer) = entity_definitions
+        .get(EntityKind::Streamer)
+        .spawn_layer(&layer_roles)
+    else {
         return;
-    }
+    };
+
+    let streamer_map_layer = find_layer(&map_layers, streamer_layer)
+        .expect("spawn_player_tile: The Streamer layer should be laid out by now.");
 
-    let (map_transform, map_type, grid_size, map_size) = map_information
-        .iter()
-        .nth(STREAMER_LAYER_NUM)
-        .expect("Could not load map information. Is world loaded?");
-    let map_info = TiledMapInformation::new(grid_size, map_size, map_type, map_transform);
+    let streamer_bevy_tilepos = map_scenes.active().streamer_spawn;
+    let streamer_transform = tile_transform_on(&streamer_bevy_tilepos, streamer_map_layer);
 
-    let streamer_bevy_tilepos = TilePos::new(39, 40);
-    let streamer_transform = to_bevy_transform(&streamer_bevy_tilepos, map_info);
+    if let Ok(streamer_entity) = streamer_query.get_single() {
+        // NOTE: Any destination still being waited on belongs to the old map.
+        commands.entity(streamer_entity).insert((
+            SceneArrival::at(streamer_transform, streamer_bevy_tilepos),
+            RequestedDestination::default(),
+        ));
+        return;
+    }
 
     commands.spawn((
         (
@@ -112,42 +145,51 @@ pub fn spawn_player_tile(
             streamer_transform,
         ),
         streamer_bevy_tilepos,
+        MovementEasing::Smooth,
+        HopHeight(STREAMER_HOP_HEIGHT),
+        RequestedDestination::default(),
     ));
 }
 
+/// Everything needed to send the Streamer off
+/// towards its next destination.
+type StreamerTraveler<'a> = (
+    Entity,
+    &'a Path,
+    &'a StartingPoint,
+    &'a Target,
+    &'a mut DestinationQueue,
+    &'a mut AwaitingPath,
+    &'a mut RequestedDestination,
+    &'a mut StreamerState,
+);
+
+/// Asks for a Path towards the next destination queued
+/// up for the Streamer, once done with the last one.
 pub fn move_streamer(
-    mut streamer_entity: Query<
-        (
-            &mut Path,
-            &StartingPoint,
-            &Target,
-            &mut DestinationQueue,
-            &mut StreamerState,
-        ),
-        With<StreamerLabel>,
-    >,
+    mut streamer_entity: Query<StreamerTraveler, With<StreamerLabel>>,
     ground_graph_query: Query<&UndirectedGraph>,
+    route_settings: Res<RouteSettings>,
+    mut path_requests: EventWriter<PathRequest>,
 ) {
     if streamer_entity.is_empty() || ground_graph_query.is_empty() {
         return;
     }
 
-    let ground_graph = ground_graph_query
-        .iter()
-        .find(|graph| *graph.get_node_type() == GraphType::Ground)
-        .expect("move_streamer: Could not find Ground-based UndirectedGraph.");
-
     let (
-        mut streamer_path,
+        streamer,
+        streamer_path,
         streamer_tile_pos,
         streamer_target,
         mut streamer_destination_queue,
+        mut streamer_awaiting_path,
+        mut streamer_requested_destination,
         mut streamer_status,
     ) = streamer_entity
         .get_single_mut()
         .expect("The streamer should be loaded.");
 
-    if !streamer_path.is_empty() || streamer_target.is_some() {
+    if !streamer_path.is_empty() || streamer_target.is_some() || **streamer_awaiting_path {
         return;
     }
 
@@ -155,33 +197,122 @@ pub fn move_streamer(
         return;
     }
 
-    let streamer_target = streamer_destination_queue.pop_front().expect(
+    let next_request = match graph_of(&ground_graph_query, GraphType::Ground) {
+        Ok(ground_graph) if route_settings.shorten_walk => streamer_destination_queue
+            .pop_nearest(|destination| ground_graph.walk_cost(streamer_tile_pos.1, destination)),
+        _ => streamer_destination_queue.pop_next(),
+    };
+    let next_request = next_request.expect(
         "move_streamer: Destination queue for streamer should have been filled with something.",
     );
-    if let Some(found_path) = ground_graph.shortest_path(streamer_tile_pos.1, streamer_target) {
-        *streamer_path = found_path;
-        *streamer_status = StreamerState::Moving;
+    let streamer_target = next_request.destination;
+
+    path_requests.send(PathRequest::new(
+        streamer,
+        GraphType::Ground,
+        streamer_tile_pos.1,
+        streamer_target,
+    ));
+    **streamer_awaiting_path = true;
+    **streamer_requested_destination = Some(next_request);
+    *streamer_status = StreamerState::Moving;
+}
+
+/// Everything needed to set the Streamer off along
+/// the Path found for it.
+type StreamerPathFollower<'a> = (
+    Entity,
+    &'a StartingPoint,
+    &'a mut Path,
+    &'a mut AwaitingPath,
+    &'a mut RequestedDestination,
+    &'a mut DestinationQueue,
+);
+
+/// Sets the Streamer off along the Path found for it, walking as
+/// close as it can get to any destination no Path leads to.
+pub fn follow_path_found_for_streamer(
+    mut path_results: EventReader<PathResult>,
+    mut streamer_entity: Query<StreamerPathFollower, With<StreamerLabel>>,
+    ground_graph_query: Query<&UndirectedGraph>,
+) {
+    if streamer_entity.is_empty() {
+        return;
+    }
+
+    let (
+        streamer,
+        streamer_tile_pos,
+        mut streamer_path,
+        mut streamer_awaiting_path,
+        mut streamer_requested_destination,
+        mut streamer_destination_queue,
+    ) = streamer_entity
+        .get_single_mut()
+        .expect("follow_path_found_for_streamer: The streamer should be loaded.");
+
+    for path_result in path_results.read() {
+        if path_result.request.requester != streamer {
+            continue;
+        }
+
+        **streamer_awaiting_path = false;
+        let requested_destination = streamer_requested_destination.take();
+
+        // The Streamer was moved elsewhere while the Path was being found,
+        // so it no longer leads from where it stands. Its destination is
+        // queued back up to be headed to from wherever that is instead.
+        if path_result.request.start != streamer_tile_pos.1 {
+            if let Some(requested_destination) = requested_destination {
+                streamer_destination_queue.push(requested_destination);
+            }
+            continue;
+        }
+
+        let found_path = match &path_result.result {
+            Ok(found_path) => Some(found_path.clone()),
+            Err(PathError::Unreachable { start, end }) => {
+                graph_of(&ground_graph_query, GraphType::Ground)
+                    .ok()
+                    .and_then(|ground_graph| {
+                        let nearest_tile_pos = ground_graph.nearest_reachable_to(*start, *end)?;
+                        ground_graph.shortest_path(*start, nearest_tile_pos)
+                    })
+            }
+            Err(path_error) => {
+                warn!("follow_path_found_for_streamer: {path_error}.");
+                None
+            }
+        };
+
+        if let Some(found_path) = found_path {
+            *streamer_path = found_path;
+        }
     }
 }
 
 /// Updates the Status of the Streamer to Idle when the Streamer
 /// is no longer following some path.
 pub fn make_streamer_idle_when_not_moving(
-    mut streamer: Query<(&mut StreamerState, &Path, &Target)>,
+    mut streamer: Query<(&mut StreamerState, &Path, &Target, &AwaitingPath)>,
 ) {
     if streamer.is_empty() {
         return;
     }
 
-    let (mut streamer_status, streamer_path, streamer_target) = streamer.single_mut();
+    let (mut streamer_status, streamer_path, streamer_target, streamer_awaiting_path) =
+        streamer.single_mut();
 
-    if streamer_path.len() == 0 && streamer_target.is_none() {
+    if streamer_path.len() == 0 && streamer_target.is_none() && !**streamer_awaiting_path {
         *streamer_status = StreamerState::Idle;
     }
 }
 
+/// Takes back any cancelled destinations, before queueing
+/// up every newly requested destination.
 pub fn queue_destination_for_streamer(
     mut destination_request_listener: EventReader<TilePosEvent>,
+    mut destination_cancel_listener: EventReader<CancelDestinationEvent>,
     mut streamer_entity: Query<&mut DestinationQueue, With<StreamerLabel>>,
 ) {
     if streamer_entity.is_empty() {
@@ -189,8 +320,24 @@ pub fn queue_destination_for_streamer(
     }
 
     let mut streamer_destination_queue = streamer_entity.single_mut();
+    for cancel_info in &mut destination_cancel_listener.read() {
+        match cancel_info {
+            CancelDestinationEvent::Matching(dedupe_key) => {
+                streamer_destination_queue.cancel(dedupe_key);
+            }
+            CancelDestinationEvent::From(source) => {
+                streamer_destination_queue.cancel_from(*source);
+            }
+        }
+    }
+
     for destination_info in &mut destination_request_listener.read() {
-        streamer_destination_queue.push_back(destination_info.destination);
+        let request = destination_info.request.clone();
+        if destination_info.replaces_source {
+            streamer_destination_queue.replace(request);
+        } else {
+            streamer_destination_queue.push(request);
+        }
     }
 }
 
@@ -215,14 +362,21 @@ pub fn change_status_for_streamer(
 pub fn move_streamer_on_status_change(
     mut online_status_listener: EventReader<OnlineStatus>,
     mut destination_request_writer: EventWriter<TilePosEvent>,
+    map_scenes: Res<MapScenes>,
 ) {
     for new_online_status in &mut online_status_listener.read() {
         let new_destination = match new_online_status {
-            OnlineStatus::Online => TilePos::new(41, 49),
-            OnlineStatus::Away => TilePos::new(39, 40),
+            OnlineStatus::Online => map_scenes.active().online_spot,
+            OnlineStatus::Away => map_scenes.active().streamer_spawn,
         };
 
-        destination_request_writer.send(TilePosEvent::new(new_destination));
+        // Only the latest Online Status matters, so it takes the
+        // place of wherever the previous one was headed to.
+        destination_request_writer.send(TilePosEvent::replacing(
+            DestinationRequest::new(new_destination)
+                .from_source(DestinationSource::OnlineStatus)
+                .with_priority(DestinationPriority::High),
+        ));
     }
 }
 
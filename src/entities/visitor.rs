@@ -0,0 +1,452 @@
+use bevy::prelude::*;
+use bevy_ecs_tilemap::prelude::*;
+use std::collections::{HashMap, VecDeque};
+
+use crate::entities::streamer::{StreamerLabel, StreamerState};
+use crate::entities::WaitToLeaveTimer;
+use crate::map::flow_fields::FlowField;
+use crate::map::path_finding::*;
+use crate::map::scenes::SceneArrival;
+use crate::ui::chatting::{Msg, TypingMsg};
+
+use super::GameEntityType;
+
+/// Where some NPC is in visiting the Streamer to speak.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VisitorStatus {
+    Idle,
+    Approaching,
+    Speaking,
+    Leaving,
+}
+
+/// A message some NPC visits the Streamer to say.
+pub trait VisitorMsg: Event + Clone {
+    fn speaker_name(&self) -> &str;
+    fn contents(&self) -> &str;
+}
+
+/// The messages an NPC has yet to say to the Streamer.
+#[derive(Component, Deref, DerefMut)]
+pub struct VisitorMsgQueue<M: VisitorMsg>(VecDeque<M>);
+
+impl<M: VisitorMsg> Default for VisitorMsgQueue<M> {
+    fn default() -> Self {
+        Self(VecDeque::new())
+    }
+}
+
+/// Describes some kind of NPC that visits the Streamer, where
+/// the NPC is labeled by the Component implementing it.
+pub trait VisitorConfig: Component {
+    /// The message sent for this kind of NPC to say.
+    type Msg: VisitorMsg;
+
+    /// How the NPC gets around, which is also who the
+    /// Streamer sees speaking.
+    const MOVEMENT_TYPE: GameEntityType;
+
+    /// The Graph the NPC is able to travel on.
+    const GRAPH_TYPE: GraphType;
+
+    /// How many Tiles short of the Streamer the NPC stops at.
+    const DIST_AWAY_FROM_STREAMER: usize;
+}
+
+/// A Flow Field for each Graph, leading towards wherever the
+/// Streamer is, or is about to be. Every approaching NPC reads
+/// from these rather than searching for their own Path.
+#[derive(Resource, Default)]
+pub struct StreamerFlowFields(HashMap<GraphType, FlowField>);
+
+impl StreamerFlowFields {
+    /// Returns the Flow Field for some Graph, as long as it
+    /// leads towards the given Tile.
+    pub fn towards(&self, graph_type: GraphType, target: TilePos) -> Option<&FlowField> {
+        self.0
+            .get(&graph_type)
+            .filter(|flow_field| flow_field.target() == target)
+    }
+}
+
+/// Moves each Visitor already around onto the Tiles marking where
+/// Visitors spawn on a newly switched map, taking turns between the
+/// Tiles when there are more Visitors than Tiles. Returns whether
+/// there were any Visitors around to move.
+pub fn migrate_visitors(
+    visitors: impl IntoIterator<Item = Entity>,
+    spawn_tiles: &[(Transform, TilePos)],
+    commands: &mut Commands,
+) -> bool {
+    let mut migrated = false;
+    for (visitor_entity, (tile_transform, tile_pos)) in
+        visitors.into_iter().zip(spawn_tiles.iter().cycle())
+    {
+        commands.entity(visitor_entity).insert((
+            SceneArrival::at(*tile_transform, *tile_pos),
+            VisitorStatus::Idle,
+        ));
+        migrated = true;
+    }
+
+    migrated
+}
+
+/// Returns the Path some NPC takes to get as close to the
+/// Streamer as they are able and willing to.
+fn path_towards_streamer<V: VisitorConfig>(
+    visitor_graph: &UndirectedGraph,
+    flow_fields: &StreamerFlowFields,
+    visitor_tilepos: TilePos,
+    streamer_tilepos: TilePos,
+) -> Option<Path> {
+    let flow_path = flow_fields
+        .towards(V::GRAPH_TYPE, streamer_tilepos)
+        .and_then(|flow_field| flow_field.path_from(visitor_graph, visitor_tilepos));
+
+    let mut path = match flow_path {
+        Some(path) => path,
+        None => {
+            // Not everyone can get right up to the Streamer, such as
+            // anyone swimming, so head wherever gets closest instead.
+            let destination =
+                visitor_graph.nearest_reachable_to(visitor_tilepos, streamer_tilepos)?;
+            visitor_graph.shortest_path(visitor_tilepos, destination)?
+        }
+    };
+
+    // The NPC should not be directly on top of the
+    // streamer, so we provide some distance by adjusting
+    // the path to not go straight to the streamer.
+    while path.0.back().is_some_and(|node_idx| {
+        let node_tilepos = idx_to_tilepos(*node_idx, visitor_graph.length());
+        (tiles_between(node_tilepos, streamer_tilepos) as usize) < V::DIST_AWAY_FROM_STREAMER
+    }) {
+        path.0.pop_back();
+    }
+
+    Some(path)
+}
+
+/// The messages some kind of NPC has yet to say.
+type MsgQueueOf<V> = VisitorMsgQueue<<V as VisitorConfig>::Msg>;
+
+/// NPCs of some kind, who are never the Streamer.
+pub type VisitorsOf<V> = (With<V>, Without<StreamerState>);
+
+/// NPCs of some kind who have yet to wait to leave.
+type YetToWait<V> = (With<V>, Without<WaitToLeaveTimer>);
+
+/// Everything needed to send some NPC towards the Streamer.
+type ApproachingVisitor<'a, V> = (
+    &'a TilePos,
+    &'a mut Path,
+    &'a mut VisitorStatus,
+    &'a MsgQueueOf<V>,
+);
+
+/// Everything needed for some NPC to speak once arrived.
+type ArrivingVisitor<'a, V> = (
+    &'a mut MsgQueueOf<V>,
+    &'a Path,
+    &'a Target,
+    &'a mut VisitorStatus,
+);
+
+/// Everything needed to send some NPC back home.
+type LeavingVisitor<'a> = (
+    Entity,
+    &'a mut WaitToLeaveTimer,
+    &'a mut Path,
+    &'a StartingPoint,
+    &'a SpawnPoint,
+    &'a mut VisitorStatus,
+);
+
+/// Adds a recently broadcasted message into the
+/// NPC's Message Queue.
+pub fn add_msg_to_queue<V: VisitorConfig>(
+    mut received_msgs: EventReader<V::Msg>,
+    mut visitor_queues: Query<&mut VisitorMsgQueue<V::Msg>, With<V>>,
+) {
+    if visitor_queues.is_empty() {
+        return;
+    }
+
+    let mut visitor_queue = visitor_queues.single_mut();
+    for received_msg in received_msgs.read() {
+        visitor_queue.push_back(received_msg.clone());
+    }
+}
+
+/// Sends idle NPCs with something to say towards the Streamer.
+pub fn approach_streamer_to_speak<V: VisitorConfig>(
+    mut visitors: Query<ApproachingVisitor<V>, With<V>>,
+    graphs: Query<&UndirectedGraph>,
+    streamer: Query<&TilePos, With<StreamerLabel>>,
+    flow_fields: Res<StreamerFlowFields>,
+) {
+    if graphs.is_empty() || streamer.is_empty() {
+        return;
+    }
+
+    // NOTE: Maps without a layer for some kind of visitor have no Graph for it.
+    let Ok(visitor_graph) = graph_of(&graphs, V::GRAPH_TYPE) else {
+        return;
+    };
+    let streamer_tilepos = streamer
+        .get_single()
+        .expect("approach_streamer_to_speak: There should only be one streamer.");
+    for (visitor_tilepos, mut visitor_path, mut visitor_status, visitor_msg_queue) in &mut visitors
+    {
+        if visitor_msg_queue.is_empty() || *visitor_status != VisitorStatus::Idle {
+            continue;
+        }
+
+        if let Some(path) = path_towards_streamer::<V>(
+            visitor_graph,
+            &flow_fields,
+            *visitor_tilepos,
+            *streamer_tilepos,
+        ) {
+            *visitor_path = path;
+            *visitor_status = VisitorStatus::Approaching;
+        }
+    }
+}
+
+/// Says the next queued message once the NPC has
+/// arrived to the Streamer.
+pub fn speak_to_streamer<V: VisitorConfig>(
+    mut visitors: Query<ArrivingVisitor<V>, With<V>>,
+    mut chat_msg_requester: EventWriter<Msg>,
+) {
+    for (mut visitor_msg_queue, visitor_path, visitor_target, mut visitor_status) in &mut visitors {
+        if !visitor_path.0.is_empty()
+            || visitor_target.is_some()
+            || visitor_msg_queue.is_empty()
+            || *visitor_status != VisitorStatus::Approaching
+        {
+            continue;
+        }
+
+        let recent_msg = visitor_msg_queue
+            .pop_front()
+            .expect("speak_to_streamer: Message Queue should be populated.");
+        *visitor_status = VisitorStatus::Speaking;
+        chat_msg_requester.send(Msg::new(
+            recent_msg.speaker_name().to_string(),
+            recent_msg.contents().to_string(),
+            V::MOVEMENT_TYPE,
+        ));
+    }
+}
+
+/// Starts to wait to leave when the NPC is finished speaking,
+/// unless they have something else to say right after.
+pub fn wait_to_leave_from_streamer<V: VisitorConfig>(
+    typed_messages: Query<&TypingMsg>,
+    mut visitors: Query<(Entity, &mut VisitorStatus, &MsgQueueOf<V>), YetToWait<V>>,
+    mut commands: Commands,
+) {
+    if typed_messages.is_empty() || visitors.is_empty() {
+        return;
+    }
+
+    let typing_msg = typed_messages.single();
+    if !typing_msg.at_end() || typing_msg.speaker_role() != V::MOVEMENT_TYPE {
+        return;
+    }
+
+    for (visitor_entity, mut visitor_status, visitor_msg_queue) in &mut visitors {
+        if *visitor_status != VisitorStatus::Speaking {
+            continue;
+        }
+
+        let speaks_again = visitor_msg_queue
+            .front()
+            .is_some_and(|next_msg| next_msg.speaker_name() == typing_msg.speaker_name());
+        if speaks_again {
+            *visitor_status = VisitorStatus::Idle;
+            continue;
+        }
+
+        commands
+            .entity(visitor_entity)
+            .insert(WaitToLeaveTimer(Timer::from_seconds(10.0, TimerMode::Once)));
+    }
+}
+
+/// Sends the NPC back to where it spawned once it
+/// is done waiting.
+pub fn leave_from_streamer<V: VisitorConfig>(
+    time: Res<Time>,
+    mut visitors: Query<LeavingVisitor, With<V>>,
+    graphs: Query<&UndirectedGraph>,
+    mut commands: Commands,
+) {
+    if visitors.is_empty() || graphs.is_empty() {
+        return;
+    }
+
+    let Ok(visitor_graph) = graph_of(&graphs, V::GRAPH_TYPE) else {
+        return;
+    };
+
+    for (
+        visitor_entity,
+        mut visitor_wait_time,
+        mut visitor_path,
+        visitor_start_pos,
+        visitor_spawn_pos,
+        mut visitor_status,
+    ) in &mut visitors
+    {
+        visitor_wait_time.0.tick(time.delta());
+        if !visitor_wait_time.0.finished() {
+            continue;
+        }
+
+        if let Some(path) = visitor_graph.shortest_path(visitor_start_pos.1, visitor_spawn_pos.0) {
+            *visitor_path = path;
+            commands.entity(visitor_entity).remove::<WaitToLeaveTimer>();
+
+            *visitor_status = VisitorStatus::Leaving;
+        }
+    }
+}
+
+/// Sets the NPC's Status back to Idle
+/// when reaching its starting position once
+/// again after leaving.
+pub fn return_visitor_to_idle<V: VisitorConfig>(
+    mut visitors: Query<(&Path, &Target, &mut VisitorStatus), With<V>>,
+) {
+    for (visitor_path, visitor_target, mut visitor_status) in &mut visitors {
+        if *visitor_status != VisitorStatus::Leaving {
+            continue;
+        }
+
+        if !visitor_path.0.is_empty() {
+            continue;
+        }
+
+        if visitor_target.is_some() {
+            continue;
+        }
+
+        *visitor_status = VisitorStatus::Idle;
+    }
+}
+
+/// Rebuilds the Flow Field some kind of NPC approaches the
+/// Streamer with, only once the Streamer heads somewhere new
+/// or the Graph the NPC travels on changes.
+pub fn update_flow_field_towards_streamer<V: VisitorConfig>(
+    streamer: Query<(&TilePos, &Path), With<StreamerLabel>>,
+    graphs: Query<Ref<UndirectedGraph>>,
+    mut flow_fields: ResMut<StreamerFlowFields>,
+) {
+    let Ok((streamer_tilepos, streamer_path)) = streamer.get_single() else {
+        return;
+    };
+    let Some(visitor_graph) = graphs
+        .iter()
+        .find(|graph| *graph.get_node_type() == V::GRAPH_TYPE)
+    else {
+        return;
+    };
+
+    let streamer_destination = streamer_path
+        .0
+        .back()
+        .map(|node_idx| idx_to_tilepos(*node_idx, visitor_graph.length()))
+        .unwrap_or(*streamer_tilepos);
+
+    if !visitor_graph.is_changed()
+        && flow_fields
+            .towards(V::GRAPH_TYPE, streamer_destination)
+            .is_some()
+    {
+        return;
+    }
+
+    match FlowField::towards(&visitor_graph, streamer_destination) {
+        Some(flow_field) => flow_fields.0.insert(V::GRAPH_TYPE, flow_field),
+        None => flow_fields.0.remove(&V::GRAPH_TYPE),
+    };
+}
+
+/// Redirects approaching NPCs towards wherever the
+/// Streamer is moving to.
+pub fn follow_streamer_while_approaching<V: VisitorConfig>(
+    streamer_info: Query<(&StreamerState, &Path), Without<VisitorStatus>>,
+    mut visitor_info: Query<(&VisitorStatus, &TilePos, &mut Path), VisitorsOf<V>>,
+    graphs: Query<&UndirectedGraph>,
+    map_info: Query<&TilemapSize>,
+    flow_fields: Res<StreamerFlowFields>,
+) {
+    if streamer_info.is_empty() || visitor_info.is_empty() || map_info.is_empty() {
+        return;
+    }
+
+    let map_size = map_info
+        .iter()
+        .last()
+        .expect("follow_streamer_while_approaching: Map should be spawned by now.");
+
+    let (streamer_status, streamer_path) = streamer_info
+        .get_single()
+        .expect("follow_streamer_while_approaching: Streamer should exist by now.");
+
+    if *streamer_status != StreamerState::Moving {
+        return;
+    }
+
+    let Some(streamer_destination) = streamer_path.0.back() else {
+        return;
+    };
+    let streamer_destination_tilepos = idx_to_tilepos(*streamer_destination, map_size.y);
+
+    let Ok(visitor_graph) = graph_of(&graphs, V::GRAPH_TYPE) else {
+        return;
+    };
+
+    for (visitor_status, visitor_pos, mut visitor_path) in &mut visitor_info {
+        if *visitor_status != VisitorStatus::Approaching {
+            continue;
+        }
+
+        let Some(current_visitor_destination) = visitor_path.0.back().copied() else {
+            continue;
+        };
+
+        let Some(path) = path_towards_streamer::<V>(
+            visitor_graph,
+            &flow_fields,
+            *visitor_pos,
+            streamer_destination_tilepos,
+        ) else {
+            continue;
+        };
+
+        let Some(next_visitor_destination) = path.0.back().copied() else {
+            continue;
+        };
+
+        // We do not want to re-populate the path if the NPC is already
+        // going to the desired destination.
+        if current_visitor_destination == next_visitor_destination {
+            continue;
+        }
+
+        // This accounts for the situation when the NPC
+        // arrives before the Streamer does, and the NPC
+        // is just waiting.
+        if *visitor_pos == idx_to_tilepos(next_visitor_destination, map_size.y) {
+            continue;
+        }
+
+        *visitor_path = path;
+    }
+}
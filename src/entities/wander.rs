@@ -0,0 +1,184 @@
+use bevy::prelude::*;
+use bevy_ecs_tilemap::prelude::*;
+use rand::seq::IteratorRandom;
+
+use crate::entities::critter::CritterLabel;
+use crate::entities::visitor::VisitorStatus;
+use crate::map::path_finding::*;
+
+use super::GameEntityType;
+
+pub const DEFAULT_AIR_WANDER_RADIUS: usize = 4;
+pub const DEFAULT_WATER_WANDER_RADIUS: usize = 6;
+pub const DEFAULT_GROUND_WANDER_RADIUS: usize = 3;
+pub const DEFAULT_WANDER_PAUSE_SECS: f32 = 4.0;
+
+/// Whether some NPC has nothing better to do than
+/// wander around.
+pub trait IdleStatus: Component {
+    fn is_idle(&self) -> bool;
+}
+
+impl IdleStatus for VisitorStatus {
+    fn is_idle(&self) -> bool {
+        *self == VisitorStatus::Idle
+    }
+}
+
+/// Critters never have anything to say, so they
+/// are always free to wander.
+impl IdleStatus for CritterLabel {
+    fn is_idle(&self) -> bool {
+        true
+    }
+}
+
+/// The Tiles an NPC is allowed to wander to while idle, being
+/// every Tile within some number of steps from its home.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WanderArea {
+    pub home: TilePos,
+    pub radius: usize,
+}
+
+/// How long an NPC lingers in place before wandering
+/// somewhere else.
+#[derive(Component, Deref, DerefMut)]
+pub struct WanderTimer(pub Timer);
+
+/// The default Wander Area sizes given to NPCs who were
+/// not given one of their own.
+#[derive(Resource, Clone, Copy)]
+pub struct WanderSettings {
+    pub air_radius: usize,
+    pub water_radius: usize,
+    pub ground_radius: usize,
+    pub pause_secs: f32,
+}
+
+impl Default for WanderSettings {
+    fn default() -> Self {
+        Self {
+            air_radius: DEFAULT_AIR_WANDER_RADIUS,
+            water_radius: DEFAULT_WATER_WANDER_RADIUS,
+            ground_radius: DEFAULT_GROUND_WANDER_RADIUS,
+            pause_secs: DEFAULT_WANDER_PAUSE_SECS,
+        }
+    }
+}
+
+impl WanderSettings {
+    /// Returns the default radius for NPCs getting
+    /// around in some way.
+    pub fn radius_for(&self, movement_type: &GameEntityType) -> usize {
+        match movement_type {
+            GameEntityType::Fly => self.air_radius,
+            GameEntityType::Swim => self.water_radius,
+            _ => self.ground_radius,
+        }
+    }
+}
+
+/// Returns the type of Graph some NPC wanders on.
+fn graph_type_for(movement_type: &GameEntityType) -> GraphType {
+    match movement_type {
+        GameEntityType::Walk => GraphType::Ground,
+        GameEntityType::Fly => GraphType::Air,
+        _ => GraphType::Water,
+    }
+}
+
+/// NPCs of some kind that have not been set up to wander yet.
+type YetToWander<S> = (With<S>, Without<WanderTimer>);
+
+/// Gives idle NPCs a Wander Area around where they spawned,
+/// leaving any Wander Area they were already given alone.
+pub fn insert_wander_area<S: IdleStatus>(
+    npcs: Query<(Entity, &SpawnPoint, &GameEntityType, Option<&WanderArea>), YetToWander<S>>,
+    wander_settings: Res<WanderSettings>,
+    mut commands: Commands,
+) {
+    for (npc_entity, npc_spawn, npc_movement_type, npc_wander_area) in &npcs {
+        let wander_area = npc_wander_area.copied().unwrap_or(WanderArea {
+            home: npc_spawn.0,
+            radius: wander_settings.radius_for(npc_movement_type),
+        });
+
+        commands.entity(npc_entity).insert((
+            wander_area,
+            WanderTimer(Timer::from_seconds(
+                wander_settings.pause_secs,
+                TimerMode::Repeating,
+            )),
+        ));
+    }
+}
+
+/// Everything needed to send some NPC wandering.
+type WanderingNpc<'a, S> = (
+    &'a S,
+    &'a TilePos,
+    &'a GameEntityType,
+    &'a WanderArea,
+    &'a mut WanderTimer,
+    &'a mut Path,
+    &'a Target,
+);
+
+/// Sends idle NPCs to a random Tile in their Wander Area
+/// every so often, leaving them be once they have
+/// something else to do.
+pub fn wander_while_idle<S: IdleStatus>(
+    time: Res<Time>,
+    mut npcs: Query<WanderingNpc<S>>,
+    graphs: Query<&UndirectedGraph>,
+) {
+    if npcs.is_empty() || graphs.is_empty() {
+        return;
+    }
+
+    for (
+        npc_status,
+        npc_tilepos,
+        npc_movement_type,
+        npc_wander_area,
+        mut npc_wander_timer,
+        mut npc_path,
+        npc_target,
+    ) in &mut npcs
+    {
+        if !npc_status.is_idle() || !npc_path.is_empty() || npc_target.is_some() {
+            continue;
+        }
+
+        npc_wander_timer.tick(time.delta());
+        if !npc_wander_timer.just_finished() {
+            continue;
+        }
+
+        let Some(wander_graph) = graphs
+            .iter()
+            .find(|graph| *graph.get_node_type() == graph_type_for(npc_movement_type))
+        else {
+            continue;
+        };
+
+        let wander_destination = wander_graph
+            .distances_from(npc_wander_area.home)
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, distance)| distance.map(|distance| (idx, distance)))
+            .filter(|(_idx, distance)| *distance <= npc_wander_area.radius)
+            .map(|(idx, _distance)| idx_to_tilepos(idx, wander_graph.length()))
+            .filter(|tile_pos| tile_pos != npc_tilepos)
+            .choose(&mut rand::thread_rng());
+
+        let Some(wander_destination) = wander_destination else {
+            continue;
+        };
+
+        if let Some(path) = wander_graph.shortest_path(*npc_tilepos, wander_destination) {
+            *npc_path = path;
+        }
+    }
+}
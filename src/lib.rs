@@ -4,6 +4,7 @@ pub mod audio;
 pub mod chat_interactions;
 pub mod entities;
 pub mod map;
+pub mod session;
 pub mod ui;
 pub mod visual;
 
@@ -1,4 +1,7 @@
 use bevy::{input::ButtonInput, math::Vec3, prelude::*, render::camera::Camera};
+use bevy_ecs_tilemap::prelude::*;
+
+use super::tiled::tile_center_in_world;
 
 // A simple camera system for moving and zooming the camera.
 #[allow(dead_code)]
@@ -49,3 +52,24 @@ pub fn movement(
 pub fn spawn_camera(mut commands: Commands) {
     commands.spawn(Camera2dBundle::default());
 }
+
+/// Points the camera at the middle of a newly laid out map,
+/// which the Tilemap draws away from the origin.
+pub fn center_camera_on_map(
+    new_layers: Query<(&TilemapGridSize, &TilemapSize), Added<TilemapType>>,
+    mut cameras: Query<&mut Transform, With<Camera>>,
+) {
+    let Some((grid_size, map_size)) = new_layers.iter().next() else {
+        return;
+    };
+
+    let map_center = TilePos {
+        x: map_size.x / 2,
+        y: map_size.y / 2,
+    };
+    let map_center_in_world = tile_center_in_world(&map_center, grid_size, map_size);
+    for mut camera_transform in &mut cameras {
+        camera_transform.translation.x = map_center_in_world.x;
+        camera_transform.translation.y = map_center_in_world.y;
+    }
+}
@@ -0,0 +1,180 @@
+use std::cmp::Reverse;
+
+use bevy::prelude::*;
+use bevy_ecs_tilemap::prelude::*;
+
+/// How urgently some destination should be headed to,
+/// where more urgent destinations are headed to first.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DestinationPriority {
+    Low,
+    #[default]
+    Normal,
+    High,
+}
+
+/// Whatever asked for some destination to be headed to.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum DestinationSource {
+    #[default]
+    Manual,
+    OnlineStatus,
+    Crop,
+    Fruit,
+    Session,
+}
+
+/// What makes two requested destinations the same trip.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DedupeKey {
+    Tile(TilePos),
+    Named(String),
+}
+
+/// Some destination requested to be headed to, where the
+/// same trip is only ever queued once.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DestinationRequest {
+    pub destination: TilePos,
+    pub priority: DestinationPriority,
+    pub source: DestinationSource,
+    pub dedupe_key: DedupeKey,
+}
+
+impl DestinationRequest {
+    /// Returns a request of normal priority, where any other
+    /// request for the same Tile is the same trip.
+    pub fn new(destination: TilePos) -> Self {
+        Self {
+            destination,
+            priority: DestinationPriority::default(),
+            source: DestinationSource::default(),
+            dedupe_key: DedupeKey::Tile(destination),
+        }
+    }
+
+    pub fn with_priority(mut self, priority: DestinationPriority) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    pub fn from_source(mut self, source: DestinationSource) -> Self {
+        self.source = source;
+        self
+    }
+
+    pub fn with_dedupe_key(mut self, dedupe_key: impl Into<String>) -> Self {
+        self.dedupe_key = DedupeKey::Named(dedupe_key.into());
+        self
+    }
+}
+
+/// The destinations some entity has yet to head to,
+/// kept in the order they were requested.
+#[derive(Component, Default, Debug)]
+pub struct DestinationQueue(Vec<DestinationRequest>);
+
+impl DestinationQueue {
+    /// Queues the request, returning whether it was queued. When
+    /// the same trip is already queued, it is kept where it is
+    /// with whichever of both priorities is more urgent instead.
+    pub fn push(&mut self, request: DestinationRequest) -> bool {
+        if let Some(queued_request) = self
+            .0
+            .iter_mut()
+            .find(|queued_request| queued_request.dedupe_key == request.dedupe_key)
+        {
+            queued_request.priority = queued_request.priority.max(request.priority);
+            return false;
+        }
+
+        self.0.push(request);
+        true
+    }
+
+    /// Takes the trip with some key off of the queue.
+    pub fn cancel(&mut self, dedupe_key: &DedupeKey) -> Option<DestinationRequest> {
+        let cancelled_idx = self
+            .0
+            .iter()
+            .position(|queued_request| queued_request.dedupe_key == *dedupe_key)?;
+
+        Some(self.0.remove(cancelled_idx))
+    }
+
+    /// Takes every trip requested by some source off of the
+    /// queue, returning how many were taken off.
+    pub fn cancel_from(&mut self, source: DestinationSource) -> usize {
+        let num_queued = self.0.len();
+        self.0
+            .retain(|queued_request| queued_request.source != source);
+
+        num_queued - self.0.len()
+    }
+
+    /// Queues the request in place of every trip still
+    /// queued from the same source.
+    pub fn replace(&mut self, request: DestinationRequest) {
+        self.cancel_from(request.source);
+        self.push(request);
+    }
+
+    /// Takes the most urgent trip off of the queue, picking
+    /// whichever was requested first between equally urgent trips.
+    pub fn pop_next(&mut self) -> Option<DestinationRequest> {
+        let (next_idx, _next_request) = self
+            .0
+            .iter()
+            .enumerate()
+            .min_by_key(|(idx, request)| (Reverse(request.priority), *idx))?;
+
+        Some(self.0.remove(next_idx))
+    }
+
+    /// Takes the most urgent trip off of the queue, picking
+    /// whichever is the cheapest walk away between equally
+    /// urgent trips. Unreachable trips are picked last.
+    pub fn pop_nearest(
+        &mut self,
+        cost_to: impl Fn(TilePos) -> Option<u32>,
+    ) -> Option<DestinationRequest> {
+        let (next_idx, _next_request) =
+            self.0.iter().enumerate().min_by_key(|(idx, request)| {
+                (
+                    Reverse(request.priority),
+                    cost_to(request.destination).unwrap_or(u32::MAX),
+                    *idx,
+                )
+            })?;
+
+        Some(self.0.remove(next_idx))
+    }
+
+    /// Returns every queued destination, in the order
+    /// they would be taken off of the queue.
+    pub fn destinations(&self) -> Vec<TilePos> {
+        let mut queued_requests = self.0.iter().collect::<Vec<&DestinationRequest>>();
+        queued_requests.sort_by_key(|request| Reverse(request.priority));
+
+        queued_requests
+            .into_iter()
+            .map(|request| request.destination)
+            .collect()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &DestinationRequest> {
+        self.0.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+}
@@ -0,0 +1,80 @@
+use bevy_ecs_tilemap::prelude::*;
+use std::collections::VecDeque;
+
+use super::path_finding::{idx_to_tilepos, tiles_between, Path, UndirectedGraph};
+
+/// How much it costs to head towards some Tile from every Node on
+/// a Graph, shared by everyone going there instead of each of them
+/// searching for a Path of their own.
+#[derive(Clone, Debug)]
+pub struct FlowField {
+    /// The Tile the Flow Field was asked to lead towards.
+    target: TilePos,
+    /// The Tile on the Graph nearest the target, where
+    /// the Flow Field actually leads to.
+    root: TilePos,
+    costs_to_root: Vec<Option<u32>>,
+}
+
+impl FlowField {
+    /// Returns a Flow Field leading towards whichever Tile on the
+    /// Graph is nearest some target Tile, or nothing if the Graph
+    /// has no Tiles to stand on at all.
+    pub fn towards(graph: &UndirectedGraph, target: TilePos) -> Option<Self> {
+        let root = graph.nearest_node_to(target)?;
+
+        Some(Self {
+            target,
+            root,
+            costs_to_root: graph.costs_to(root),
+        })
+    }
+
+    /// Returns the Tile the Flow Field was asked to lead towards.
+    pub fn target(&self) -> TilePos {
+        self.target
+    }
+
+    /// Returns the Tile on the Graph the Flow Field leads to.
+    pub fn root(&self) -> TilePos {
+        self.root
+    }
+
+    /// Returns how much it costs to reach the root from some
+    /// Tile, or None when there is no way there.
+    pub fn cost_from(&self, graph: &UndirectedGraph, tile_pos: TilePos) -> Option<u32> {
+        graph
+            .node_idx(tile_pos)
+            .and_then(|node_idx| self.costs_to_root.get(node_idx).copied().flatten())
+    }
+
+    /// Returns the cheapest Path from some starting Tile to the
+    /// root, found by always stepping wherever is cheapest to
+    /// reach the root from. Ties are settled by whichever Tile
+    /// is closest to the root, keeping the Path fairly straight.
+    pub fn path_from(&self, graph: &UndirectedGraph, start: TilePos) -> Option<Path> {
+        let mut current_idx = graph.node_idx(start)?;
+        let mut current_cost = self.costs_to_root.get(current_idx).copied().flatten()?;
+
+        let mut path = VecDeque::from([current_idx]);
+        while current_cost > 0 {
+            let (next_idx, next_cost) = graph.edges()[current_idx]
+                .iter()
+                .filter_map(|node_edge| {
+                    let edge_cost = self.costs_to_root[*node_edge]?;
+
+                    (edge_cost + graph.step_cost(current_idx, *node_edge) == current_cost)
+                        .then_some((*node_edge, edge_cost))
+                })
+                .min_by_key(|(node_edge, edge_cost)| {
+                    let node_tilepos = idx_to_tilepos(*node_edge, graph.length());
+                    (*edge_cost, tiles_between(node_tilepos, self.root))
+                })?;
+
+            path.push_back(next_idx);
+            (current_idx, current_cost) = (next_idx, next_cost);
+        }
+
+        Some(Path(path))
+    }
+}
@@ -0,0 +1,55 @@
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use bevy_ecs_tilemap::prelude::*;
+
+use super::path_finding::UndirectedGraph;
+
+/// Tells everyone the map of the Map Scene the stream is set in
+/// has been edited, and is being laid out again in its place.
+#[derive(Event, Clone, Debug)]
+pub struct MapReloaded;
+
+/// The entities an earlier version of the map placed, by the Tile they
+/// were placed on. Whichever of them the reloaded map still places are
+/// kept as they are, so nothing is lost, such as how grown some Crop is.
+pub struct MapPlaced(HashMap<TilePos, Entity>);
+
+impl MapPlaced {
+    pub fn new(placed_entities: impl IntoIterator<Item = (Entity, TilePos)>) -> Self {
+        Self(
+            placed_entities
+                .into_iter()
+                .map(|(placed_entity, tile_pos)| (tile_pos, placed_entity))
+                .collect(),
+        )
+    }
+
+    /// Returns the entity already placed on some Tile, if any,
+    /// which is then kept rather than spawned again.
+    pub fn keep(&mut self, tile_pos: &TilePos) -> Option<Entity> {
+        self.0.remove(tile_pos)
+    }
+
+    /// Despawns every entity the map no longer places.
+    pub fn despawn_rest(self, commands: &mut Commands) {
+        for placed_entity in self.0.into_values() {
+            commands.entity(placed_entity).despawn_recursive();
+        }
+    }
+}
+
+/// Takes down the Undirected Graphs of a reloaded
+/// map, to be built again from its new Tiles.
+pub fn take_down_reloaded_graphs(
+    mut map_reloaded_reader: EventReader<MapReloaded>,
+    graphs: Query<Entity, With<UndirectedGraph>>,
+    mut commands: Commands,
+) {
+    if map_reloaded_reader.read().last().is_none() {
+        return;
+    }
+
+    for graph_entity in &graphs {
+        commands.entity(graph_entity).despawn_recursive();
+    }
+}
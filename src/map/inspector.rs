@@ -0,0 +1,477 @@
+use std::collections::HashSet;
+
+use bevy::color::palettes::css;
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use bevy_ecs_tilemap::prelude::*;
+
+use super::path_finding::{GraphType, Path, UndirectedGraph};
+use super::path_requests::PathError;
+use super::tiled::convert_tiled_to_bevy_pos;
+use super::tilemap::{layer_origin, Tile, TileGridCoordinates, Tilemap};
+use super::validation::{MapValidation, MapValidationError};
+
+/// The mouse button pressed to inspect whichever Tile is under the cursor.
+pub const INSPECT_BUTTON: MouseButton = MouseButton::Left;
+
+/// The keys pressed to select the layer below or above.
+pub const PREVIOUS_LAYER_KEY: KeyCode = KeyCode::BracketLeft;
+pub const NEXT_LAYER_KEY: KeyCode = KeyCode::BracketRight;
+
+/// The key pressed to show or hide the selected layer.
+pub const TOGGLE_LAYER_KEY: KeyCode = KeyCode::KeyH;
+
+/// The keys pressed to start or end the tested Path on the inspected Tile.
+pub const PATH_START_KEY: KeyCode = KeyCode::KeyB;
+pub const PATH_END_KEY: KeyCode = KeyCode::KeyE;
+
+/// The key pressed to test the Path along the next type of Graph.
+pub const GRAPH_TYPE_KEY: KeyCode = KeyCode::KeyG;
+
+/// How large each marked Tile is drawn, in pixels.
+const MARKER_RADIUS: f32 = 6.0;
+
+/// Some Tiled map being inspected, along with the
+/// Undirected Graphs the game would build from it.
+#[derive(Resource)]
+pub struct InspectedMap {
+    tiled_map: tiled::Map,
+    tilemap: Tilemap,
+    graphs: Result<MapValidation, MapValidationError>,
+}
+
+impl InspectedMap {
+    pub fn new(tiled_map: tiled::Map) -> Self {
+        Self {
+            tilemap: Tilemap::from_tiled_map(&tiled_map),
+            graphs: MapValidation::from_map(&tiled_map),
+            tiled_map,
+        }
+    }
+
+    /// Returns the Tiled map being inspected.
+    pub fn tiled_map(&self) -> &tiled::Map {
+        &self.tiled_map
+    }
+
+    /// Returns the Tiles of the map being inspected.
+    pub fn tilemap(&self) -> &Tilemap {
+        &self.tilemap
+    }
+
+    /// Returns the Undirected Graph holding some type of
+    /// Nodes, or None when the Graphs could not be built.
+    pub fn graph(&self, graph_type: GraphType) -> Option<&UndirectedGraph> {
+        self.graphs
+            .as_ref()
+            .ok()
+            .and_then(|graphs| graphs.graph(graph_type))
+    }
+
+    /// Returns the Tile Position the Undirected Graphs know some Tile by.
+    pub fn to_graph_pos(&self, grid_coordinates: &TileGridCoordinates) -> TilePos {
+        convert_tiled_to_bevy_pos(
+            TilePos::new(grid_coordinates.x() as u32, grid_coordinates.y() as u32),
+            self.tiled_map.height,
+        )
+    }
+
+    /// Returns where the center of some Tile is drawn, shifted by its layer.
+    pub fn drawn_center(&self, grid_coordinates: &TileGridCoordinates) -> Vec2 {
+        let Some(tile) = self.tilemap.get_tile(grid_coordinates) else {
+            return Vec2::ZERO;
+        };
+
+        let layer_shift = layer_origin(&self.tiled_map, grid_coordinates.z())
+            .translation
+            .truncate();
+        grid_coordinates.to_world_center(tile.get_tile_dimensions(), self.tiled_map.height as usize)
+            + layer_shift
+    }
+
+    /// Returns the highest drawn Tile whose center is nearest to some
+    /// point in the world, looking through each hidden layer.
+    pub fn tile_at(&self, world_pos: Vec2, hidden_layers: &HashSet<usize>) -> Option<&Tile> {
+        let map_depth = self.tilemap.get_dimensions().depth();
+        let map_height = self.tilemap.get_dimensions().height();
+
+        (0..map_depth)
+            .rev()
+            .filter(|layer| !hidden_layers.contains(layer))
+            .find_map(|layer| {
+                // NOTE: Each layer is drawn shifted by its offsets, so
+                // the point is shifted back before picking on the layer.
+                let layer_shift = layer_origin(&self.tiled_map, layer).translation.truncate();
+                let tile_dimensions = self.tilemap.get_tiles().first()?.get_tile_dimensions();
+                let grid_coordinates = TileGridCoordinates::from_world_center(
+                    world_pos - layer_shift,
+                    tile_dimensions,
+                    map_height,
+                )?;
+
+                self.tilemap
+                    .get_tile(&TileGridCoordinates::new_3d(
+                        grid_coordinates.x(),
+                        grid_coordinates.y(),
+                        layer,
+                    ))
+                    .filter(|tile| tile.get_tile_texture().is_some())
+            })
+    }
+
+    /// Returns each type of Graph some Tile can be stood on in.
+    pub fn graphs_containing(&self, grid_coordinates: &TileGridCoordinates) -> Vec<GraphType> {
+        let graph_pos = self.to_graph_pos(grid_coordinates);
+
+        [GraphType::Ground, GraphType::Water, GraphType::Air]
+            .into_iter()
+            .filter(|graph_type| {
+                self.graph(*graph_type)
+                    .is_some_and(|graph| graph.is_on_graph(graph_pos))
+            })
+            .collect()
+    }
+
+    /// Returns the cheapest Path along some type of Graph between
+    /// two Tiles, or why none could be found.
+    pub fn find_path(
+        &self,
+        graph_type: GraphType,
+        start: &TileGridCoordinates,
+        end: &TileGridCoordinates,
+    ) -> Result<Path, PathError> {
+        self.graph(graph_type)
+            .ok_or(PathError::GraphMissing(graph_type))?
+            .find_path(self.to_graph_pos(start), self.to_graph_pos(end))
+    }
+
+    /// Returns the description of some Tile's grid coordinates, layer,
+    /// texture, drawing offsets, and which Graphs it can be stood on in.
+    pub fn describe_tile(&self, tile: &Tile) -> String {
+        let grid_coordinates = tile.get_grid_coordinates();
+        let layer_name = self
+            .tiled_map
+            .get_layer(grid_coordinates.z())
+            .map_or(String::new(), |layer| layer.name.clone());
+        let texture_line = tile
+            .get_tile_texture()
+            .map_or(String::from("none"), |tile_texture| {
+                tile_texture.get_sprite().get_index().to_string()
+            });
+        let drawing_offsets = tile.get_drawing_offsets();
+
+        let graphs_line = match &self.graphs {
+            Err(map_error) => format!("not built ({map_error})"),
+            Ok(_) => {
+                let graph_names = self
+                    .graphs_containing(grid_coordinates)
+                    .iter()
+                    .map(|graph_type| format!("{graph_type:?}"))
+                    .collect::<Vec<String>>();
+                if graph_names.is_empty() {
+                    String::from("none")
+                } else {
+                    graph_names.join(", ")
+                }
+            }
+        };
+
+        format!(
+            "Tile {}, {} on layer {} ({layer_name})\nTexture index: {texture_line}\nDrawing offsets: {}, {}\nGraphs: {graphs_line}",
+            grid_coordinates.x(),
+            grid_coordinates.y(),
+            grid_coordinates.z(),
+            drawing_offsets.x(),
+            drawing_offsets.y()
+        )
+    }
+}
+
+/// Which Tile is inspected, which layers are hidden,
+/// and the Path being tested between two Tiles.
+#[derive(Resource, Debug)]
+pub struct MapInspector {
+    pub inspected_tile: Option<TileGridCoordinates>,
+    pub selected_layer: usize,
+    pub hidden_layers: HashSet<usize>,
+    pub path_start: Option<TileGridCoordinates>,
+    pub path_end: Option<TileGridCoordinates>,
+    pub graph_type: GraphType,
+    pub path: Option<Result<Path, PathError>>,
+}
+
+impl Default for MapInspector {
+    fn default() -> Self {
+        Self {
+            inspected_tile: None,
+            selected_layer: 0,
+            hidden_layers: HashSet::new(),
+            path_start: None,
+            path_end: None,
+            graph_type: GraphType::Ground,
+            path: None,
+        }
+    }
+}
+
+impl MapInspector {
+    /// Tests the Path between the start and end Tiles again,
+    /// once both have been picked.
+    pub fn retest_path(&mut self, inspected_map: &InspectedMap) {
+        self.path = match (&self.path_start, &self.path_end) {
+            (Some(path_start), Some(path_end)) => {
+                Some(inspected_map.find_path(self.graph_type, path_start, path_end))
+            }
+            _ => None,
+        };
+    }
+
+    /// Returns the description of the selected layer, the
+    /// inspected Tile, and the Path being tested.
+    pub fn describe(&self, inspected_map: &InspectedMap) -> String {
+        let layer_name = inspected_map
+            .tiled_map()
+            .get_layer(self.selected_layer)
+            .map_or(String::new(), |layer| layer.name.clone());
+        let layer_state = if self.hidden_layers.contains(&self.selected_layer) {
+            "hidden"
+        } else {
+            "shown"
+        };
+        let layer_line = format!(
+            "Layer {} ({layer_name}) is {layer_state} [{:?}/{:?} select, {:?} show/hide]",
+            self.selected_layer, PREVIOUS_LAYER_KEY, NEXT_LAYER_KEY, TOGGLE_LAYER_KEY
+        );
+
+        let tile_line = self
+            .inspected_tile
+            .as_ref()
+            .and_then(|inspected_tile| inspected_map.tilemap().get_tile(inspected_tile))
+            .map_or(String::from("Click a Tile to inspect it."), |tile| {
+                inspected_map.describe_tile(tile)
+            });
+
+        let tile_name = |tile: &Option<TileGridCoordinates>| {
+            tile.as_ref().map_or(String::from("unset"), |tile| {
+                format!("{}, {}", tile.x(), tile.y())
+            })
+        };
+        let path_line = format!(
+            "{:?} Path from {} to {} [{:?} start, {:?} end, {:?} graph]",
+            self.graph_type,
+            tile_name(&self.path_start),
+            tile_name(&self.path_end),
+            PATH_START_KEY,
+            PATH_END_KEY,
+            GRAPH_TYPE_KEY
+        );
+        let path_result = match &self.path {
+            None => String::from("Pick both ends of the Path to test it."),
+            Some(Ok(path)) => format!("Path found, {} Nodes long.", path.len()),
+            Some(Err(path_error)) => format!("No Path: {path_error}"),
+        };
+
+        format!("{layer_line}\n\n{tile_line}\n\n{path_line}\n{path_result}")
+    }
+}
+
+/// Returns the type of Graph tested after some other.
+fn next_graph_type(graph_type: GraphType) -> GraphType {
+    match graph_type {
+        GraphType::Ground => GraphType::Water,
+        GraphType::Water => GraphType::Air,
+        GraphType::Air => GraphType::Ground,
+    }
+}
+
+/// The panel describing the inspected Tile and tested Path.
+#[derive(Component)]
+pub struct InspectorPanel;
+
+/// Inspects whichever drawn Tile was clicked on.
+pub fn inspect_clicked_tile(
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    inspected_map: Option<Res<InspectedMap>>,
+    mut map_inspector: ResMut<MapInspector>,
+) {
+    if !mouse_input.just_pressed(INSPECT_BUTTON) {
+        return;
+    }
+
+    let Some(inspected_map) = inspected_map else {
+        return;
+    };
+
+    let clicked_tile = windows
+        .get_single()
+        .ok()
+        .and_then(|window| window.cursor_position())
+        .and_then(|cursor_position| {
+            let (camera, camera_transform) = cameras.get_single().ok()?;
+            camera.viewport_to_world_2d(camera_transform, cursor_position)
+        })
+        .and_then(|world_pos| inspected_map.tile_at(world_pos, &map_inspector.hidden_layers));
+
+    map_inspector.inspected_tile = clicked_tile.map(|tile| tile.get_grid_coordinates().clone());
+}
+
+/// Selects the layer below or above, and shows or hides it.
+pub fn toggle_layers(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    inspected_map: Option<Res<InspectedMap>>,
+    mut map_inspector: ResMut<MapInspector>,
+) {
+    let Some(inspected_map) = inspected_map else {
+        return;
+    };
+
+    let map_depth = inspected_map.tilemap().get_dimensions().depth().max(1);
+    if keyboard_input.just_pressed(PREVIOUS_LAYER_KEY) {
+        map_inspector.selected_layer = (map_inspector.selected_layer + map_depth - 1) % map_depth;
+    }
+    if keyboard_input.just_pressed(NEXT_LAYER_KEY) {
+        map_inspector.selected_layer = (map_inspector.selected_layer + 1) % map_depth;
+    }
+
+    if keyboard_input.just_pressed(TOGGLE_LAYER_KEY) {
+        let selected_layer = map_inspector.selected_layer;
+        if !map_inspector.hidden_layers.remove(&selected_layer) {
+            map_inspector.hidden_layers.insert(selected_layer);
+        }
+    }
+}
+
+/// Hides every drawn Tile on a hidden layer, showing the rest.
+pub fn hide_layers(
+    map_inspector: Res<MapInspector>,
+    mut render_tiles: Query<(&TileGridCoordinates, &mut Visibility)>,
+) {
+    if !map_inspector.is_changed() {
+        return;
+    }
+
+    for (grid_coordinates, mut visibility) in &mut render_tiles {
+        *visibility = if map_inspector.hidden_layers.contains(&grid_coordinates.z()) {
+            Visibility::Hidden
+        } else {
+            Visibility::Inherited
+        };
+    }
+}
+
+/// Starts or ends the tested Path on the inspected Tile, or tests
+/// it along another type of Graph, finding the Path again.
+pub fn test_path(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    inspected_map: Option<Res<InspectedMap>>,
+    mut map_inspector: ResMut<MapInspector>,
+) {
+    let Some(inspected_map) = inspected_map else {
+        return;
+    };
+
+    let inspected_tile = map_inspector.inspected_tile.clone();
+    if keyboard_input.just_pressed(PATH_START_KEY) {
+        map_inspector.path_start = inspected_tile.clone();
+    }
+    if keyboard_input.just_pressed(PATH_END_KEY) {
+        map_inspector.path_end = inspected_tile;
+    }
+    if keyboard_input.just_pressed(GRAPH_TYPE_KEY) {
+        map_inspector.graph_type = next_graph_type(map_inspector.graph_type);
+    }
+
+    if keyboard_input.any_just_pressed([PATH_START_KEY, PATH_END_KEY, GRAPH_TYPE_KEY]) {
+        map_inspector.retest_path(&inspected_map);
+    }
+}
+
+/// Marks the inspected Tile, both ends of the tested
+/// Path, and the Path found between them.
+pub fn draw_inspection(
+    map_inspector: Res<MapInspector>,
+    inspected_map: Option<Res<InspectedMap>>,
+    mut gizmos: Gizmos,
+) {
+    let Some(inspected_map) = inspected_map else {
+        return;
+    };
+
+    if let Some(inspected_tile) = &map_inspector.inspected_tile {
+        let center = inspected_map.drawn_center(inspected_tile);
+        gizmos.circle_2d(center, MARKER_RADIUS, Color::from(css::YELLOW));
+    }
+
+    let path_ends = [
+        (&map_inspector.path_start, Color::from(css::LIME)),
+        (&map_inspector.path_end, Color::from(css::RED)),
+    ];
+    for (path_end, color) in path_ends {
+        if let Some(path_end) = path_end {
+            let size = Vec2::splat(MARKER_RADIUS * 2.0);
+            gizmos.rect_2d(inspected_map.drawn_center(path_end), 0.0, size, color);
+        }
+    }
+
+    let (Some(Ok(path)), Some(graph)) = (
+        &map_inspector.path,
+        inspected_map.graph(map_inspector.graph_type),
+    ) else {
+        return;
+    };
+
+    // NOTE: Paths leave out the Node they start from.
+    let path_start = map_inspector
+        .path_start
+        .as_ref()
+        .and_then(|path_start| graph.node_idx(inspected_map.to_graph_pos(path_start)));
+    let path_points = path_start
+        .into_iter()
+        .chain(path.iter().copied())
+        .filter_map(|node_idx| graph.get_node(node_idx))
+        .map(|translation| translation.truncate())
+        .collect::<Vec<Vec2>>();
+    gizmos.linestrip_2d(path_points, Color::from(css::ORANGE));
+}
+
+/// Spawns the panel describing the inspected Tile and tested Path.
+pub fn spawn_inspector_panel(mut commands: Commands) {
+    let panel_text = TextBundle::from_section(
+        "",
+        TextStyle {
+            font_size: 16.0,
+            color: Color::WHITE,
+            ..default()
+        },
+    )
+    .with_style(Style {
+        position_type: PositionType::Absolute,
+        left: Val::Px(8.0),
+        top: Val::Px(8.0),
+        ..default()
+    })
+    .with_background_color(Color::BLACK.with_alpha(0.7));
+
+    commands.spawn((panel_text, InspectorPanel));
+}
+
+/// Describes the inspected Tile and tested Path whenever either changes.
+pub fn update_inspector_panel(
+    map_inspector: Res<MapInspector>,
+    inspected_map: Option<Res<InspectedMap>>,
+    mut panels: Query<&mut Text, With<InspectorPanel>>,
+) {
+    let Some(inspected_map) = inspected_map else {
+        return;
+    };
+
+    if !(map_inspector.is_changed() || inspected_map.is_changed()) {
+        return;
+    }
+
+    for mut panel_text in &mut panels {
+        panel_text.sections[0].value = map_inspector.describe(&inspected_map);
+    }
+}
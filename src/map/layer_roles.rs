@@ -0,0 +1,152 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use bevy::log;
+use bevy::prelude::*;
+use serde::Deserialize;
+use thiserror::Error;
+
+/// The name of the custom property giving a Tiled layer its role.
+pub const ROLE_PROPERTY: &str = "role";
+
+/// What some Tiled layer holds, which entities are spawned from
+/// or placed by, whatever order the layers are stacked in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize)]
+pub enum LayerRole {
+    Streamer,
+    Chatter,
+    Subscriber,
+    Fruit,
+    Crop,
+    Campfire,
+    Water,
+    Farmland,
+}
+
+impl LayerRole {
+    /// Every role a Tiled map is expected to give its layers.
+    pub const ALL: [LayerRole; 8] = [
+        LayerRole::Streamer,
+        LayerRole::Chatter,
+        LayerRole::Subscriber,
+        LayerRole::Fruit,
+        LayerRole::Crop,
+        LayerRole::Campfire,
+        LayerRole::Water,
+        LayerRole::Farmland,
+    ];
+
+    /// Returns the role named by a `role` property, or by a layer
+    /// name such as "Fruit (17)", where the number is left out.
+    pub fn from_name(role_name: &str) -> Option<Self> {
+        let role_name = match role_name.trim().rsplit_once(" (") {
+            Some((role_name, number)) if number.ends_with(')') => role_name,
+            _ => role_name.trim(),
+        };
+
+        match role_name.to_lowercase().as_str() {
+            "streamer" => Some(LayerRole::Streamer),
+            "chatter" | "chatters" | "bird" => Some(LayerRole::Chatter),
+            "subscriber" | "subscribers" | "fish" => Some(LayerRole::Subscriber),
+            "fruit" => Some(LayerRole::Fruit),
+            "crop" | "crops" => Some(LayerRole::Crop),
+            "campfire" => Some(LayerRole::Campfire),
+            "water" => Some(LayerRole::Water),
+            "farmland" => Some(LayerRole::Farmland),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for LayerRole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let role_name = match self {
+            LayerRole::Streamer => "streamer",
+            LayerRole::Chatter => "chatter",
+            LayerRole::Subscriber => "subscriber",
+            LayerRole::Fruit => "fruit",
+            LayerRole::Crop => "crop",
+            LayerRole::Campfire => "campfire",
+            LayerRole::Water => "water",
+            LayerRole::Farmland => "farmland",
+        };
+
+        write!(f, "{role_name}")
+    }
+}
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum LayerRoleError {
+    #[error("Map has no layers for the roles: {}", list_roles(.0))]
+    MissingRoles(Vec<LayerRole>),
+}
+
+/// Returns some roles listed out by name.
+fn list_roles(roles: &[LayerRole]) -> String {
+    roles
+        .iter()
+        .map(LayerRole::to_string)
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+/// Which Tiled layer holds each role, counting layers
+/// in the order they are found within the map.
+#[derive(Resource, Default, Debug, Clone, PartialEq, Eq)]
+pub struct LayerRoles(BTreeMap<LayerRole, usize>);
+
+impl LayerRoles {
+    /// Returns the role of each layer within a Tiled map, read from its
+    /// `role` property or else its name. Should several layers share a
+    /// role, the first of them keeps it.
+    pub fn from_map(map: &tiled::Map) -> Self {
+        let mut layer_roles = BTreeMap::new();
+        for (layer_index, layer) in map.layers().enumerate() {
+            let layer_role = match layer.properties.get(ROLE_PROPERTY) {
+                Some(tiled::PropertyValue::StringValue(role_name)) => {
+                    let layer_role = LayerRole::from_name(role_name);
+                    if layer_role.is_none() {
+                        log::warn!("Layer {} has an unknown role: {role_name}", layer.name);
+                    }
+
+                    layer_role
+                }
+                _ => LayerRole::from_name(&layer.name),
+            };
+
+            let Some(layer_role) = layer_role else {
+                continue;
+            };
+            if layer_roles.contains_key(&layer_role) {
+                log::warn!(
+                    "Layer {} is also given the {layer_role} role, which is already taken.",
+                    layer.name
+                );
+                continue;
+            }
+
+            layer_roles.insert(layer_role, layer_index);
+        }
+
+        Self(layer_roles)
+    }
+
+    /// Returns the layer holding some role, if any layer does.
+    pub fn index_of(&self, layer_role: LayerRole) -> Option<usize> {
+        self.0.get(&layer_role).copied()
+    }
+
+    /// Returns an error listing every role no layer holds.
+    pub fn ensure_all(&self) -> Result<(), LayerRoleError> {
+        let missing_roles = LayerRole::ALL
+            .into_iter()
+            .filter(|layer_role| !self.0.contains_key(layer_role))
+            .collect::<Vec<LayerRole>>();
+
+        if missing_roles.is_empty() {
+            Ok(())
+        } else {
+            Err(LayerRoleError::MissingRoles(missing_roles))
+        }
+    }
+}
@@ -0,0 +1,292 @@
+use bevy::log;
+use bevy::prelude::*;
+use bevy_ecs_tilemap::prelude::*;
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::entities::GameEntityType;
+
+use super::path_finding::{graph_of, GraphType, StartingPoint, Target, UndirectedGraph};
+use super::tiled::convert_tiled_to_bevy_pos;
+
+/// The class given to Tiled objects that link two Tiles together.
+pub const LINK_CLASS: &str = "link";
+
+/// How many steps along a Path crossing a Link is worth,
+/// when the Link does not say otherwise.
+pub const DEFAULT_LINK_COST: u32 = 1;
+
+/// How some entity looks while crossing a Link.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize)]
+pub enum LinkTransition {
+    Ladder,
+    Boat,
+    Portal,
+}
+
+impl LinkTransition {
+    /// Returns the Link Transition named by a Tiled object property value.
+    pub fn from_name(transition_name: &str) -> Option<Self> {
+        match transition_name.trim().to_lowercase().as_str() {
+            "ladder" => Some(LinkTransition::Ladder),
+            "boat" => Some(LinkTransition::Boat),
+            "portal" => Some(LinkTransition::Portal),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum LinkError {
+    #[error("Link {object_id} is missing the `{property}` property")]
+    MissingProperty {
+        object_id: u32,
+        property: &'static str,
+    },
+    #[error("Link {object_id} has an invalid `{property}`: {reason}")]
+    InvalidProperty {
+        object_id: u32,
+        property: &'static str,
+        reason: &'static str,
+    },
+}
+
+/// A way across between two Tiles that are not neighbours,
+/// such as a ladder up to a treehouse or a boat out to an island.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NavLink {
+    pub from: TilePos,
+    pub to: TilePos,
+    /// The Graph whose Nodes are linked.
+    pub graph_type: GraphType,
+    /// How many steps along a Path crossing the Link is worth.
+    pub cost: u32,
+    pub transition: Option<LinkTransition>,
+    /// Whether the Link can be crossed back the other way.
+    pub two_way: bool,
+}
+
+/// Returns the value of some integer property on a Tiled object.
+fn int_property(
+    object: &tiled::ObjectData,
+    property: &'static str,
+) -> Result<Option<i32>, LinkError> {
+    match object.properties.get(property) {
+        None => Ok(None),
+        Some(tiled::PropertyValue::IntValue(value)) => Ok(Some(*value)),
+        Some(_) => Err(LinkError::InvalidProperty {
+            object_id: object.id(),
+            property,
+            reason: "must be an int",
+        }),
+    }
+}
+
+/// Returns the Tile named by a pair of integer properties on a
+/// Tiled object, converted from Tiled's coordinates into Bevy's.
+fn tile_property(
+    object: &tiled::ObjectData,
+    x_property: &'static str,
+    y_property: &'static str,
+    map_size: &TilemapSize,
+) -> Result<TilePos, LinkError> {
+    let coordinate = |property: &'static str, size: u32| {
+        let value = int_property(object, property)?.ok_or(LinkError::MissingProperty {
+            object_id: object.id(),
+            property,
+        })?;
+
+        u32::try_from(value)
+            .ok()
+            .filter(|value| *value < size)
+            .ok_or(LinkError::InvalidProperty {
+                object_id: object.id(),
+                property,
+                reason: "must be a Tile within the map",
+            })
+    };
+
+    let tiled_x = coordinate(x_property, map_size.x)?;
+    let tiled_y = coordinate(y_property, map_size.y)?;
+
+    Ok(convert_tiled_to_bevy_pos(
+        TilePos::new(tiled_x, tiled_y),
+        map_size.y,
+    ))
+}
+
+impl NavLink {
+    /// Returns the Link declared by some Tiled object, read from the
+    /// `from_x`, `from_y`, `to_x` and `to_y` Tiles it joins along with
+    /// the optional `graph`, `cost`, `transition` and `two_way`.
+    pub fn from_object(
+        object: &tiled::ObjectData,
+        map_size: &TilemapSize,
+    ) -> Result<Self, LinkError> {
+        let from = tile_property(object, "from_x", "from_y", map_size)?;
+        let to = tile_property(object, "to_x", "to_y", map_size)?;
+
+        let graph_type = match object.properties.get("graph") {
+            None => GraphType::Ground,
+            Some(tiled::PropertyValue::StringValue(graph_name)) => {
+                match graph_name.trim().to_lowercase().as_str() {
+                    "ground" => GraphType::Ground,
+                    "air" => GraphType::Air,
+                    "water" => GraphType::Water,
+                    _ => {
+                        return Err(LinkError::InvalidProperty {
+                            object_id: object.id(),
+                            property: "graph",
+                            reason: "must be ground, air or water",
+                        })
+                    }
+                }
+            }
+            Some(_) => {
+                return Err(LinkError::InvalidProperty {
+                    object_id: object.id(),
+                    property: "graph",
+                    reason: "must be a string",
+                })
+            }
+        };
+
+        let cost = match int_property(object, "cost")? {
+            None => DEFAULT_LINK_COST,
+            Some(cost) => u32::try_from(cost).ok().filter(|cost| *cost > 0).ok_or(
+                LinkError::InvalidProperty {
+                    object_id: object.id(),
+                    property: "cost",
+                    reason: "must be greater than zero",
+                },
+            )?,
+        };
+
+        let transition = match object.properties.get("transition") {
+            None => None,
+            Some(tiled::PropertyValue::StringValue(transition_name)) => Some(
+                LinkTransition::from_name(transition_name).ok_or(LinkError::InvalidProperty {
+                    object_id: object.id(),
+                    property: "transition",
+                    reason: "must be ladder, boat or portal",
+                })?,
+            ),
+            Some(_) => {
+                return Err(LinkError::InvalidProperty {
+                    object_id: object.id(),
+                    property: "transition",
+                    reason: "must be a string",
+                })
+            }
+        };
+
+        let two_way = match object.properties.get("two_way") {
+            None => true,
+            Some(tiled::PropertyValue::BoolValue(two_way)) => *two_way,
+            Some(_) => {
+                return Err(LinkError::InvalidProperty {
+                    object_id: object.id(),
+                    property: "two_way",
+                    reason: "must be a bool",
+                })
+            }
+        };
+
+        Ok(Self {
+            from,
+            to,
+            graph_type,
+            cost,
+            transition,
+            two_way,
+        })
+    }
+
+    /// Returns each way the Link can be crossed, from one Tile to the other.
+    pub fn crossings(&self) -> Vec<(TilePos, TilePos)> {
+        if self.two_way {
+            vec![(self.from, self.to), (self.to, self.from)]
+        } else {
+            vec![(self.from, self.to)]
+        }
+    }
+
+    /// Returns whether the Link can be crossed from one Tile to another.
+    pub fn crosses(&self, from: TilePos, to: TilePos) -> bool {
+        self.crossings().contains(&(from, to))
+    }
+}
+
+/// Every Link declared within the Tiled Map.
+#[derive(Resource, Default, Debug, Clone, Deref)]
+pub struct NavLinks(pub Vec<NavLink>);
+
+impl NavLinks {
+    /// Returns every Link declared by objects of the link class
+    /// found on the object layers of a Tiled Map, skipping over
+    /// any declared wrongly.
+    pub fn from_map(map: &tiled::Map) -> Self {
+        let map_size = TilemapSize {
+            x: map.width,
+            y: map.height,
+        };
+
+        let links = map
+            .layers()
+            .filter_map(|layer| layer.as_object_layer())
+            .flat_map(|object_layer| object_layer.objects())
+            .filter(|object| object.user_type == LINK_CLASS)
+            .filter_map(|object| match NavLink::from_object(&object, &map_size) {
+                Ok(link) => Some(link),
+                Err(link_error) => {
+                    log::warn!("Skipping Link: {link_error}");
+                    None
+                }
+            })
+            .collect();
+
+        Self(links)
+    }
+
+    /// Returns every Link joining Nodes of some Graph.
+    pub fn on_graph(&self, graph_type: GraphType) -> Vec<NavLink> {
+        self.0
+            .iter()
+            .filter(|link| link.graph_type == graph_type)
+            .cloned()
+            .collect()
+    }
+}
+
+/// How some entity looks while crossing a Link, if it is crossing one.
+#[derive(Component, Default, Debug, Clone, Copy, PartialEq, Eq, Deref)]
+pub struct CrossingLink(pub Option<LinkTransition>);
+
+/// Everything needed to tell whether some entity is crossing a Link.
+type LinkCrosser<'a> = (
+    &'a GameEntityType,
+    &'a StartingPoint,
+    &'a Target,
+    &'a mut CrossingLink,
+);
+
+/// Records how each entity looks while crossing a Link,
+/// clearing it again once they have made it across.
+pub fn mark_link_crossings(mut crossers: Query<LinkCrosser>, graphs: Query<&UndirectedGraph>) {
+    for (movement_type, starting_point, target, mut crossing_link) in &mut crossers {
+        let Ok(graph) = graph_of(&graphs, GraphType::for_movement(movement_type)) else {
+            continue;
+        };
+
+        let transition = target
+            .0
+            .and_then(|(_target_translation, target_tilepos)| {
+                graph.link_between(starting_point.1, target_tilepos)
+            })
+            .and_then(|link| link.transition);
+
+        if crossing_link.0 != transition {
+            crossing_link.0 = transition;
+        }
+    }
+}
@@ -1,5 +1,19 @@
 pub mod camera;
+pub mod destinations;
+pub mod flow_fields;
+pub mod hot_reload;
+pub mod inspector;
+pub mod layer_roles;
+pub mod links;
+pub mod movement;
+pub mod nav_overlay;
+pub mod objects;
+pub mod occupancy;
 pub mod path_finding;
+pub mod path_requests;
 pub mod plugins;
+pub mod scenes;
+pub mod terrain;
 pub mod tiled;
 pub mod tilemap;
+pub mod validation;
@@ -0,0 +1,149 @@
+use bevy::prelude::*;
+use bevy_ecs_tilemap::prelude::*;
+
+use crate::entities::GameEntityType;
+
+use super::path_finding::{Path, StartingPoint, Target};
+
+/// How many Tiles some entity crosses each second.
+#[derive(Component, Clone, Copy, Deref, DerefMut, PartialEq, Debug)]
+pub struct MovementSpeed(pub f32);
+
+impl MovementSpeed {
+    /// Returns how quickly some kind of entity gets around,
+    /// where fish are slow swimmers and birds are quick flyers.
+    pub fn for_movement(movement_type: &GameEntityType) -> Self {
+        match movement_type {
+            GameEntityType::Walk => MovementSpeed(2.5),
+            GameEntityType::Fly => MovementSpeed(4.0),
+            GameEntityType::Swim => MovementSpeed(1.5),
+            GameEntityType::Environment => MovementSpeed(2.5),
+        }
+    }
+}
+
+/// How far along, from 0 to 1, some entity is in
+/// stepping from its Starting Point to its Target.
+#[derive(Component, Clone, Copy, Default, Debug)]
+pub struct StepProgress {
+    pub progress: f32,
+    /// Whether the step carries straight on from the last
+    /// one, rather than setting off from a standstill.
+    pub carried_on: bool,
+}
+
+/// How some entity speeds up and slows down along its Path,
+/// moving at an even pace when left out.
+#[derive(Component, Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub enum MovementEasing {
+    #[default]
+    Linear,
+    /// Sets off slowly from a standstill, and slows down again
+    /// before stopping, keeping its pace on every step between.
+    Smooth,
+}
+
+impl MovementEasing {
+    /// Returns how far between its Starting Point and Target some
+    /// entity is drawn, for how far along the step it is, whether
+    /// it set off from a standstill, and whether it stops once there.
+    pub fn ease(&self, progress: f32, sets_off: bool, stops: bool) -> f32 {
+        match (self, sets_off, stops) {
+            (MovementEasing::Linear, _, _) | (MovementEasing::Smooth, false, false) => progress,
+            // Speeds up to the pace it carries on at into the next step.
+            (MovementEasing::Smooth, true, false) => progress * progress * (2.0 - progress),
+            // Slows down from the pace it carried on at from the last step.
+            (MovementEasing::Smooth, false, true) => {
+                progress * (1.0 + progress - progress * progress)
+            }
+            (MovementEasing::Smooth, true, true) => progress * progress * (3.0 - 2.0 * progress),
+        }
+    }
+}
+
+/// How high some entity walking hops, in pixels, whenever it
+/// steps up or down onto Tiles of a different height.
+#[derive(Component, Clone, Copy, Deref, DerefMut, PartialEq, Debug)]
+pub struct HopHeight(pub f32);
+
+/// Returns how many Tiles long a step between two Tiles is, where
+/// diagonal steps, and Links between far off Tiles, are longer.
+fn step_length(from: TilePos, to: TilePos) -> f32 {
+    let x_dist = from.x.abs_diff(to.x) as f32;
+    let y_dist = from.y.abs_diff(to.y) as f32;
+
+    x_dist.hypot(y_dist).max(1.0)
+}
+
+/// Everything needed to carry some entity along its current step.
+type Mover<'a> = (
+    &'a mut Transform,
+    &'a mut Target,
+    &'a mut StartingPoint,
+    &'a mut TilePos,
+    &'a mut StepProgress,
+    &'a MovementSpeed,
+    Option<&'a MovementEasing>,
+    Option<&'a HopHeight>,
+    Option<&'a Path>,
+);
+
+/// Carries each moving entity towards its Target at its own
+/// Movement Speed, however long each frame happens to take,
+/// placing it exactly on the Target once arrived.
+pub fn move_entities(mut moving_entities: Query<Mover>, time: Res<Time>) {
+    for (
+        mut current_pos,
+        mut target,
+        mut starting_point,
+        mut tile_pos,
+        mut step_progress,
+        movement_speed,
+        movement_easing,
+        hop_height,
+        path,
+    ) in &mut moving_entities
+    {
+        let Some((target_vec, target_tile_pos)) = target.0 else {
+            // Standing still for a frame, such as when someone
+            // is in the way, means setting off again after.
+            step_progress.carried_on = false;
+            continue;
+        };
+
+        // Anyone without a Path left to walk stops once there.
+        let stops = path.is_none_or(|path| path.is_empty());
+        let step_tiles = step_length(starting_point.1, target_tile_pos);
+        step_progress.progress = (step_progress.progress
+            + movement_speed.0 * time.delta_seconds() / step_tiles)
+            .min(1.0);
+
+        if step_progress.progress >= 1.0 {
+            current_pos.translation = target_vec;
+            *starting_point = StartingPoint(target_vec, target_tile_pos);
+            *tile_pos = target_tile_pos;
+            target.0 = None;
+            *step_progress = StepProgress {
+                progress: 0.0,
+                carried_on: !stops,
+            };
+            continue;
+        }
+
+        let eased_progress = movement_easing.copied().unwrap_or_default().ease(
+            step_progress.progress,
+            !step_progress.carried_on,
+            stops,
+        );
+        current_pos.translation = starting_point.0.lerp(target_vec, eased_progress);
+
+        // Only steps between Tiles of different heights are hopped,
+        // peaking halfway through the step.
+        if let Some(hop_height) = hop_height {
+            if starting_point.0.z != target_vec.z {
+                current_pos.translation.y +=
+                    hop_height.0 * 4.0 * step_progress.progress * (1.0 - step_progress.progress);
+            }
+        }
+    }
+}
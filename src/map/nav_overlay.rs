@@ -0,0 +1,253 @@
+use bevy::color::palettes::css;
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use bevy_ecs_tilemap::prelude::*;
+
+use crate::entities::GameEntityType;
+
+use super::destinations::DestinationQueue;
+use super::path_finding::{graph_of, GraphType, Path, Target, UndirectedGraph};
+
+/// The key pressed to show or hide the navigation overlay.
+pub const NAV_OVERLAY_KEY: KeyCode = KeyCode::F3;
+
+/// How far from a Tile's centre, in pixels, the cursor
+/// can be while still pointing at that Tile.
+const HOVER_RADIUS: f32 = 8.0;
+
+/// How large each Node is drawn, in pixels.
+const NODE_RADIUS: f32 = 1.5;
+
+/// Whether every Undirected Graph, and where everyone is
+/// headed along them, is drawn over the map.
+#[derive(Resource, Default, Debug)]
+pub struct NavOverlay {
+    pub enabled: bool,
+}
+
+/// The tooltip describing whichever Tile the cursor is over.
+#[derive(Component)]
+pub struct NavTooltip;
+
+/// Returns the colour some type of Graph is drawn in.
+fn graph_color(graph_type: &GraphType) -> Color {
+    match graph_type {
+        GraphType::Ground => Color::from(css::LIME),
+        GraphType::Air => Color::from(css::SKY_BLUE),
+        GraphType::Water => Color::from(css::ROYAL_BLUE),
+    }
+}
+
+/// Returns the colour some entity's Path is drawn in, which
+/// stays the same for as long as the entity is around.
+fn entity_color(entity: Entity) -> Color {
+    Color::hsl((entity.index() * 67 % 360) as f32, 0.9, 0.6)
+}
+
+/// Returns the Tile drawn nearest some point in the world, as long
+/// as it is drawn close enough to be the one being pointed at.
+pub fn tile_at<'a>(
+    graphs: impl IntoIterator<Item = &'a UndirectedGraph>,
+    world_pos: Vec2,
+) -> Option<TilePos> {
+    graphs
+        .into_iter()
+        .flat_map(|graph| graph.drawn_nodes())
+        .map(|(tile_pos, translation)| (tile_pos, translation.truncate().distance(world_pos)))
+        .filter(|(_tile_pos, distance)| *distance <= HOVER_RADIUS)
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(tile_pos, _distance)| tile_pos)
+}
+
+/// Returns the tooltip describing some Tile's Node index,
+/// height, and which Graphs it can be stood on in.
+pub fn describe_tile<'a>(
+    graphs: impl IntoIterator<Item = &'a UndirectedGraph>,
+    tile_pos: TilePos,
+) -> String {
+    let graphs = graphs.into_iter().collect::<Vec<&UndirectedGraph>>();
+
+    let node_idx = graphs.iter().find_map(|graph| graph.node_idx(tile_pos));
+    let height = graphs.iter().find_map(|graph| graph.height_of(tile_pos));
+    let graph_names = graphs
+        .iter()
+        .filter(|graph| graph.is_on_graph(tile_pos))
+        .map(|graph| format!("{:?}", graph.get_node_type()))
+        .collect::<Vec<String>>();
+
+    let node_line = node_idx.map_or(String::from("off the map"), |node_idx| {
+        format!("Node {node_idx}")
+    });
+    let height_line = height.map_or(String::from("unknown"), |height| height.to_string());
+    let graphs_line = if graph_names.is_empty() {
+        String::from("none")
+    } else {
+        graph_names.join(", ")
+    };
+
+    format!(
+        "Tile {}, {} ({node_line})\nHeight: {height_line}\nGraphs: {graphs_line}",
+        tile_pos.x, tile_pos.y
+    )
+}
+
+/// Shows or hides the navigation overlay.
+pub fn toggle_nav_overlay(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut nav_overlay: ResMut<NavOverlay>,
+) {
+    if keyboard_input.just_pressed(NAV_OVERLAY_KEY) {
+        nav_overlay.enabled = !nav_overlay.enabled;
+    }
+}
+
+/// Draws every Node of each Undirected Graph, and every
+/// Edge joining them, in the colour of their Graph.
+pub fn draw_graphs(
+    nav_overlay: Res<NavOverlay>,
+    graphs: Query<&UndirectedGraph>,
+    mut gizmos: Gizmos,
+) {
+    if !nav_overlay.enabled {
+        return;
+    }
+
+    for graph in &graphs {
+        let color = graph_color(graph.get_node_type());
+
+        for (tile_pos, translation) in graph.drawn_nodes() {
+            gizmos.circle_2d(translation.truncate(), NODE_RADIUS, color);
+
+            let Some(node_idx) = graph.node_idx(tile_pos) else {
+                continue;
+            };
+            for node_edge in &graph.edges()[node_idx] {
+                let Some(edge_translation) = graph.get_node(*node_edge) else {
+                    continue;
+                };
+
+                gizmos.line_2d(
+                    translation.truncate(),
+                    edge_translation.truncate(),
+                    color.with_alpha(0.3),
+                );
+            }
+        }
+    }
+}
+
+/// Everything drawn about where some entity is headed.
+type NavigatingEntity<'a> = (
+    Entity,
+    &'a Transform,
+    &'a GameEntityType,
+    &'a Path,
+    &'a Target,
+    &'a DestinationQueue,
+);
+
+/// Draws each entity's Path, Target, and queued destinations
+/// along their Graph, in a colour of their own.
+pub fn draw_entity_paths(
+    nav_overlay: Res<NavOverlay>,
+    entities: Query<NavigatingEntity>,
+    graphs: Query<&UndirectedGraph>,
+    mut gizmos: Gizmos,
+) {
+    if !nav_overlay.enabled {
+        return;
+    }
+
+    for (entity, transform, movement_type, path, target, destinations) in &entities {
+        let Ok(graph) = graph_of(&graphs, GraphType::for_movement(movement_type)) else {
+            continue;
+        };
+        let color = entity_color(entity);
+
+        let path_points = std::iter::once(transform.translation.truncate())
+            .chain(
+                path.iter()
+                    .filter_map(|node_idx| graph.get_node(*node_idx))
+                    .map(|translation| translation.truncate()),
+            )
+            .collect::<Vec<Vec2>>();
+        gizmos.linestrip_2d(path_points, color);
+
+        if let Some((target_translation, _target_tilepos)) = target.0 {
+            gizmos.circle_2d(target_translation.truncate(), NODE_RADIUS * 3.0, color);
+        }
+
+        for destination in destinations.destinations() {
+            let Some(translation) = graph
+                .node_idx(destination)
+                .and_then(|node_idx| graph.get_node(node_idx))
+            else {
+                continue;
+            };
+
+            let size = Vec2::splat(NODE_RADIUS * 4.0);
+            gizmos.rect_2d(translation.truncate(), 0.0, size, color);
+        }
+    }
+}
+
+/// Spawns the tooltip describing whichever Tile the
+/// cursor is over, hidden until the overlay is shown.
+pub fn spawn_nav_tooltip(mut commands: Commands) {
+    let tooltip_text = TextBundle::from_section(
+        "",
+        TextStyle {
+            font_size: 16.0,
+            color: Color::WHITE,
+            ..default()
+        },
+    )
+    .with_style(Style {
+        position_type: PositionType::Absolute,
+        ..default()
+    })
+    .with_background_color(Color::BLACK.with_alpha(0.7));
+
+    commands.spawn((tooltip_text, Visibility::Hidden, NavTooltip));
+}
+
+/// Everything needed to place and fill in the tooltip.
+type Tooltip<'a> = (&'a mut Text, &'a mut Style, &'a mut Visibility);
+
+/// Describes whichever Tile the cursor is over, next to the cursor.
+pub fn update_nav_tooltip(
+    nav_overlay: Res<NavOverlay>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    graphs: Query<&UndirectedGraph>,
+    mut tooltips: Query<Tooltip, With<NavTooltip>>,
+) {
+    let Ok((mut tooltip_text, mut tooltip_style, mut tooltip_visibility)) =
+        tooltips.get_single_mut()
+    else {
+        return;
+    };
+
+    let cursor_position = windows
+        .get_single()
+        .ok()
+        .and_then(|window| window.cursor_position());
+    let hovered_tile = cursor_position.and_then(|cursor_position| {
+        let (camera, camera_transform) = cameras.get_single().ok()?;
+        let world_pos = camera.viewport_to_world_2d(camera_transform, cursor_position)?;
+
+        tile_at(&graphs, world_pos)
+    });
+
+    let (true, Some(cursor_position), Some(hovered_tile)) =
+        (nav_overlay.enabled, cursor_position, hovered_tile)
+    else {
+        *tooltip_visibility = Visibility::Hidden;
+        return;
+    };
+
+    tooltip_text.sections[0].value = describe_tile(&graphs, hovered_tile);
+    tooltip_style.left = Val::Px(cursor_position.x + 12.0);
+    tooltip_style.top = Val::Px(cursor_position.y + 12.0);
+    *tooltip_visibility = Visibility::Visible;
+}
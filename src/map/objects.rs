@@ -0,0 +1,498 @@
+use std::collections::HashSet;
+
+use bevy::log;
+use bevy::prelude::*;
+use bevy_ecs_tilemap::helpers::square_grid::neighbors::Neighbors;
+use bevy_ecs_tilemap::prelude::*;
+
+use crate::entities::GameEntityType;
+
+use super::links::LINK_CLASS;
+use super::tiled::{convert_tiled_to_bevy_pos, find_layer, tile_transform_on, MapLayer, TiledMap};
+use super::tilemap::to_bevy_path;
+
+/// The class given to point objects marking where
+/// something spawns, rather than a Point of Interest.
+pub const SPAWN_CLASS: &str = "spawn";
+
+/// What some object on an object layer of the Tiled Map becomes.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MapObjectKind {
+    /// A point object of the spawn class.
+    SpawnPoint(TilePos),
+    /// Any other point object.
+    PointOfInterest(TilePos),
+    /// A rectangle, ellipse or polygon, covering each
+    /// Tile whose centre lies within it.
+    TriggerZone(HashSet<TilePos>),
+    /// A tile object, drawn with a Tile from one of the map's tilesets.
+    Prop {
+        tile_pos: TilePos,
+        /// The map tileset drawing the Prop, which is left
+        /// out when drawn with the tileset of a template.
+        tileset_index: Option<usize>,
+        tile_id: u32,
+        /// The Prop's Tile along with each of its neighbours.
+        reach: HashSet<TilePos>,
+    },
+}
+
+/// Some object found on an object layer of the Tiled Map.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MapObjectData {
+    pub id: u32,
+    pub name: String,
+    pub class: String,
+    pub kind: MapObjectKind,
+    /// The tile layer the object's layer is stacked on top of.
+    pub layer: usize,
+    pub properties: tiled::Properties,
+}
+
+/// How many pixels along each axis of the Tiled Map make up a Tile, with
+/// isometric maps measuring objects in Tile heights along both axes.
+fn tile_units(map: &tiled::Map) -> Vec2 {
+    match map.orientation {
+        tiled::Orientation::Isometric => Vec2::splat(map.tile_height as f32),
+        _ => Vec2::new(map.tile_width as f32, map.tile_height as f32),
+    }
+}
+
+/// Returns the Tile found at some pixel position of
+/// the Tiled Map, in Bevy's coordinates.
+fn tile_at(map: &tiled::Map, pixel_pos: Vec2) -> Option<TilePos> {
+    let tiled_pos = (pixel_pos / tile_units(map)).floor();
+    if tiled_pos.x < 0.0
+        || tiled_pos.y < 0.0
+        || tiled_pos.x >= map.width as f32
+        || tiled_pos.y >= map.height as f32
+    {
+        return None;
+    }
+
+    Some(convert_tiled_to_bevy_pos(
+        TilePos::new(tiled_pos.x as u32, tiled_pos.y as u32),
+        map.height,
+    ))
+}
+
+/// Returns each Tile between two pixel positions of the Tiled Map
+/// whose centre lies within some shape, in Bevy's coordinates.
+fn tiles_within(
+    map: &tiled::Map,
+    min: Vec2,
+    max: Vec2,
+    contains: impl Fn(Vec2) -> bool,
+) -> HashSet<TilePos> {
+    let units = tile_units(map);
+    let min_tile = (min / units).floor().max(Vec2::ZERO);
+    let max_tile = (max / units)
+        .ceil()
+        .min(Vec2::new(map.width as f32, map.height as f32));
+
+    let mut tiles = HashSet::new();
+    for tiled_x in min_tile.x as u32..max_tile.x as u32 {
+        for tiled_y in min_tile.y as u32..max_tile.y as u32 {
+            let tile_centre = (Vec2::new(tiled_x as f32, tiled_y as f32) + 0.5) * units;
+            if contains(tile_centre) {
+                tiles.insert(convert_tiled_to_bevy_pos(
+                    TilePos::new(tiled_x, tiled_y),
+                    map.height,
+                ));
+            }
+        }
+    }
+
+    tiles
+}
+
+/// Returns whether a point lies within a polygon, by counting
+/// how many of its sides a ray cast out from the point crosses.
+fn polygon_contains(polygon: &[Vec2], point: Vec2) -> bool {
+    let mut inside = false;
+    for (side_idx, side_start) in polygon.iter().enumerate() {
+        let side_end = polygon[(side_idx + 1) % polygon.len()];
+        let crosses_ray = (side_start.y > point.y) != (side_end.y > point.y)
+            && point.x
+                < side_start.x
+                    + (point.y - side_start.y) * (side_end.x - side_start.x)
+                        / (side_end.y - side_start.y);
+        if crosses_ray {
+            inside = !inside;
+        }
+    }
+
+    inside
+}
+
+/// Returns some Tile along with each of its neighbours.
+fn neighbourhood_of(tile_pos: TilePos, map_size: &TilemapSize) -> HashSet<TilePos> {
+    let mut neighbourhood = HashSet::from([tile_pos]);
+    for neighbour_pos in
+        Neighbors::get_square_neighboring_positions(&tile_pos, map_size, true).iter()
+    {
+        neighbourhood.insert(*neighbour_pos);
+    }
+
+    neighbourhood
+}
+
+impl MapObjectKind {
+    /// Returns what some Tiled object becomes, or None for
+    /// objects such as text that have no use within the map.
+    pub fn from_object(map: &tiled::Map, object: &tiled::Object) -> Option<Self> {
+        let object_pos = Vec2::new(object.x, object.y);
+
+        if let Some(tile_data) = object.tile_data() {
+            let units = tile_units(map);
+            // NOTE: Tile objects sit on the bottom corner of their Tile
+            // within isometric maps, and on its bottom left otherwise.
+            let tile_centre = match map.orientation {
+                tiled::Orientation::Isometric => object_pos - units / 2.0,
+                _ => object_pos + Vec2::new(units.x, -units.y) / 2.0,
+            };
+            let tile_pos = tile_at(map, tile_centre)?;
+            let map_size = TilemapSize {
+                x: map.width,
+                y: map.height,
+            };
+
+            return Some(MapObjectKind::Prop {
+                tile_pos,
+                tileset_index: match tile_data.tileset_location() {
+                    tiled::TilesetLocation::Map(tileset_index) => Some(*tileset_index),
+                    tiled::TilesetLocation::Template(_) => None,
+                },
+                tile_id: tile_data.id(),
+                reach: neighbourhood_of(tile_pos, &map_size),
+            });
+        }
+
+        match &object.shape {
+            tiled::ObjectShape::Point(..) => {
+                let tile_pos = tile_at(map, object_pos)?;
+                if object.user_type == SPAWN_CLASS {
+                    Some(MapObjectKind::SpawnPoint(tile_pos))
+                } else {
+                    Some(MapObjectKind::PointOfInterest(tile_pos))
+                }
+            }
+            tiled::ObjectShape::Rect { width, height } => {
+                let size = Vec2::new(*width, *height);
+                Some(MapObjectKind::TriggerZone(tiles_within(
+                    map,
+                    object_pos,
+                    object_pos + size,
+                    |point| point.cmpge(object_pos).all() && point.cmple(object_pos + size).all(),
+                )))
+            }
+            tiled::ObjectShape::Ellipse { width, height } => {
+                let radii = Vec2::new(*width, *height) / 2.0;
+                let centre = object_pos + radii;
+                Some(MapObjectKind::TriggerZone(tiles_within(
+                    map,
+                    object_pos,
+                    object_pos + radii * 2.0,
+                    |point| ((point - centre) / radii).length_squared() <= 1.0,
+                )))
+            }
+            tiled::ObjectShape::Polygon { points } => {
+                let polygon = points
+                    .iter()
+                    .map(|(x, y)| object_pos + Vec2::new(*x, *y))
+                    .collect::<Vec<Vec2>>();
+                let min = polygon.iter().fold(Vec2::MAX, |min, point| min.min(*point));
+                let max = polygon.iter().fold(Vec2::MIN, |max, point| max.max(*point));
+                Some(MapObjectKind::TriggerZone(tiles_within(
+                    map,
+                    min,
+                    max,
+                    |point| polygon_contains(&polygon, point),
+                )))
+            }
+            tiled::ObjectShape::Polyline { .. } | tiled::ObjectShape::Text { .. } => None,
+        }
+    }
+}
+
+/// Every object found on the object layers of the Tiled Map,
+/// besides the Links joining its Tiles together.
+#[derive(Default, Debug, Clone, Deref)]
+pub struct MapObjects(pub Vec<MapObjectData>);
+
+impl MapObjects {
+    /// Returns every object found on the object layers of a Tiled
+    /// Map, skipping over any placed outside of the map.
+    pub fn from_map(map: &tiled::Map) -> Self {
+        let mut map_objects = Vec::new();
+        let mut tile_layer = 0;
+        for (layer_index, layer) in map.layers().enumerate() {
+            let object_layer = match layer.layer_type() {
+                tiled::LayerType::Tiles(_) => {
+                    tile_layer = layer_index;
+                    continue;
+                }
+                tiled::LayerType::Objects(object_layer) => object_layer,
+                _ => continue,
+            };
+
+            for object in object_layer.objects() {
+                if object.user_type == LINK_CLASS {
+                    continue;
+                }
+
+                let Some(kind) = MapObjectKind::from_object(map, &object) else {
+                    log::warn!(
+                        "Skipping object {} on layer {}, which has no place within the map.",
+                        object.id(),
+                        layer.name
+                    );
+                    continue;
+                };
+
+                map_objects.push(MapObjectData {
+                    id: object.id(),
+                    name: object.name.clone(),
+                    class: object.user_type.clone(),
+                    kind,
+                    layer: tile_layer,
+                    properties: object.properties.clone(),
+                });
+            }
+        }
+
+        Self(map_objects)
+    }
+
+    /// Spawns an entity for each object, carrying over its custom properties.
+    pub fn spawn(&self, commands: &mut Commands) {
+        for map_object in &self.0 {
+            let mut object_entity = commands.spawn((
+                MapObject {
+                    id: map_object.id,
+                    name: map_object.name.clone(),
+                    class: map_object.class.clone(),
+                },
+                ObjectProperties(map_object.properties.clone()),
+            ));
+
+            match &map_object.kind {
+                MapObjectKind::SpawnPoint(tile_pos) => {
+                    object_entity.insert((*tile_pos, ObjectSpawnPoint));
+                }
+                MapObjectKind::PointOfInterest(tile_pos) => {
+                    object_entity.insert((*tile_pos, PointOfInterest));
+                }
+                MapObjectKind::TriggerZone(tiles) => {
+                    object_entity.insert((TriggerZone(tiles.clone()), ZoneOccupants::default()));
+                }
+                MapObjectKind::Prop {
+                    tile_pos,
+                    tileset_index,
+                    tile_id,
+                    reach,
+                } => {
+                    object_entity.insert((
+                        *tile_pos,
+                        MapProp {
+                            tileset_index: *tileset_index,
+                            tile_id: *tile_id,
+                            layer: map_object.layer,
+                        },
+                        TriggerZone(reach.clone()),
+                        ZoneOccupants::default(),
+                    ));
+                }
+            }
+        }
+    }
+}
+
+/// Some object placed on an object layer of the Tiled Map.
+#[derive(Component, Debug, Clone, PartialEq, Eq)]
+pub struct MapObject {
+    pub id: u32,
+    pub name: String,
+    pub class: String,
+}
+
+/// The custom properties given to some object within Tiled.
+#[derive(Component, Default, Debug, Clone, PartialEq, Deref)]
+pub struct ObjectProperties(pub tiled::Properties);
+
+/// Marks where something spawns within the map.
+#[derive(Component, Debug)]
+pub struct ObjectSpawnPoint;
+
+/// Marks some place of note within the map.
+#[derive(Component, Debug)]
+pub struct PointOfInterest;
+
+/// The Tiles making up an area of the map, which tells
+/// everyone whenever someone walks in or out of it.
+#[derive(Component, Debug, Clone, PartialEq, Eq, Deref)]
+pub struct TriggerZone(pub HashSet<TilePos>);
+
+/// Everyone standing within some Trigger Zone.
+#[derive(Component, Default, Debug, Clone, Deref)]
+pub struct ZoneOccupants(pub HashSet<Entity>);
+
+/// An interactive piece of scenery drawn with a Tile, which is
+/// reached by walking into the Trigger Zone around it.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MapProp {
+    pub tileset_index: Option<usize>,
+    pub tile_id: u32,
+    /// The tile layer the Prop is placed on.
+    pub layer: usize,
+}
+
+/// Tells everyone some entity has walked into a Trigger Zone.
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub struct ZoneEntered {
+    pub zone: Entity,
+    pub zone_name: String,
+    pub entity: Entity,
+}
+
+/// Tells everyone some entity has walked out of a Trigger Zone.
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub struct ZoneLeft {
+    pub zone: Entity,
+    pub zone_name: String,
+    pub entity: Entity,
+}
+
+/// Everything needed to tell who walks in or out of a Trigger Zone.
+type Zone<'a> = (
+    Entity,
+    &'a MapObject,
+    &'a TriggerZone,
+    &'a mut ZoneOccupants,
+);
+
+/// Anyone getting around who has just stepped onto another Tile.
+type SteppedMover = (With<GameEntityType>, Changed<TilePos>);
+
+/// Tells everyone whenever someone walks in or out of a Trigger Zone.
+pub fn trigger_zones(
+    movers: Query<(Entity, &TilePos), SteppedMover>,
+    mut zones: Query<Zone>,
+    mut zone_entered_writer: EventWriter<ZoneEntered>,
+    mut zone_left_writer: EventWriter<ZoneLeft>,
+) {
+    for (mover_entity, mover_tile_pos) in &movers {
+        for (zone_entity, map_object, trigger_zone, mut zone_occupants) in &mut zones {
+            let is_within = trigger_zone.contains(mover_tile_pos);
+            let was_within = zone_occupants.contains(&mover_entity);
+            if is_within == was_within {
+                continue;
+            }
+
+            if is_within {
+                zone_occupants.0.insert(mover_entity);
+                zone_entered_writer.send(ZoneEntered {
+                    zone: zone_entity,
+                    zone_name: map_object.name.clone(),
+                    entity: mover_entity,
+                });
+            } else {
+                zone_occupants.0.remove(&mover_entity);
+                zone_left_writer.send(ZoneLeft {
+                    zone: zone_entity,
+                    zone_name: map_object.name.clone(),
+                    entity: mover_entity,
+                });
+            }
+        }
+    }
+}
+
+/// Places each Prop onto the layer beneath it, once that layer is in.
+pub fn place_map_props(
+    props: Query<(Entity, &TilePos, &MapProp), Without<Transform>>,
+    map_layers: Query<MapLayer>,
+    mut commands: Commands,
+) {
+    for (prop_entity, tile_pos, map_prop) in &props {
+        let Some(prop_map_layer) = find_layer(&map_layers, map_prop.layer) else {
+            continue;
+        };
+
+        commands
+            .entity(prop_entity)
+            .insert(tile_transform_on(tile_pos, prop_map_layer));
+    }
+}
+
+/// Draws each Prop placed within the map with its Tile.
+pub fn replace_prop_sprites(
+    props: Query<(Entity, &MapProp, &Transform), Without<Sprite>>,
+    tiled_map_handles: Query<&Handle<TiledMap>>,
+    tiled_maps: Res<Assets<TiledMap>>,
+    asset_server: Res<AssetServer>,
+    mut texture_atlases: ResMut<Assets<TextureAtlasLayout>>,
+    mut commands: Commands,
+) {
+    let Some(tiled_map) = tiled_map_handles
+        .iter()
+        .find_map(|map_handle| tiled_maps.get(map_handle))
+    else {
+        return;
+    };
+
+    for (prop_entity, map_prop, prop_transform) in &props {
+        let Some(tileset_index) = map_prop.tileset_index else {
+            continue;
+        };
+        let Some(tileset) = tiled_map.map.tilesets().get(tileset_index) else {
+            continue;
+        };
+
+        // NOTE: Props are drawn from their tilesets the same as the Tilemap
+        // draws its Tiles, or from their own image within a collection of images.
+        let (texture, texture_atlas) = match &tileset.image {
+            Some(tileset_image) => {
+                let tileset_layout = TextureAtlasLayout::from_grid(
+                    UVec2::new(tileset.tile_width, tileset.tile_height),
+                    tileset.columns,
+                    tileset.tilecount / tileset.columns.max(1),
+                    Some(UVec2::splat(tileset.spacing)),
+                    Some(UVec2::splat(tileset.margin)),
+                );
+                let texture_atlas = TextureAtlas {
+                    layout: texture_atlases.add(tileset_layout),
+                    index: map_prop.tile_id as usize,
+                };
+
+                (
+                    asset_server.load(to_bevy_path(tileset_image.source.clone())),
+                    Some(texture_atlas),
+                )
+            }
+            None => {
+                let Some(tile_image) = tileset
+                    .get_tile(map_prop.tile_id)
+                    .and_then(|tile| tile.image.clone())
+                else {
+                    continue;
+                };
+
+                (asset_server.load(to_bevy_path(tile_image.source)), None)
+            }
+        };
+
+        let prop_sprite = SpriteBundle {
+            sprite: Sprite::default(),
+            texture,
+            transform: *prop_transform,
+            ..default()
+        };
+
+        commands.entity(prop_entity).remove::<Transform>();
+        commands.entity(prop_entity).insert(prop_sprite);
+        if let Some(texture_atlas) = texture_atlas {
+            commands.entity(prop_entity).insert(texture_atlas);
+        }
+    }
+}
@@ -0,0 +1,139 @@
+use std::collections::{HashMap, HashSet};
+
+use bevy::prelude::*;
+use bevy_ecs_tilemap::prelude::*;
+
+use crate::entities::GameEntityType;
+
+use super::path_finding::{Path, StartingPoint, Target};
+
+/// How long something stuck behind someone else waits for
+/// them to move along before finding another way around.
+pub const BLOCKED_WAIT_SECS: f32 = 0.5;
+
+/// Which entity is standing on, or about to step onto, each Tile.
+/// Only entities getting around the same way get in each other's
+/// way, so anything flying passes right over anything walking.
+#[derive(Resource, Default, Debug)]
+pub struct OccupancyMap {
+    occupants: HashMap<(GameEntityType, TilePos), Entity>,
+    /// Everyone with nowhere left to go, who will not be
+    /// moving out of the way any time soon.
+    settled: HashSet<Entity>,
+}
+
+impl OccupancyMap {
+    /// Returns whoever is standing on, or about to step onto,
+    /// some Tile, if anyone.
+    pub fn occupant_of(&self, movement_type: GameEntityType, tile_pos: TilePos) -> Option<Entity> {
+        self.occupants.get(&(movement_type, tile_pos)).copied()
+    }
+
+    /// Returns whether some Tile is taken by someone, besides the
+    /// given entity, who has nowhere left to go.
+    pub fn is_settled_on(
+        &self,
+        entity: Entity,
+        movement_type: GameEntityType,
+        tile_pos: TilePos,
+    ) -> bool {
+        self.occupant_of(movement_type, tile_pos)
+            .is_some_and(|occupant| occupant != entity && self.settled.contains(&occupant))
+    }
+
+    /// Records that some entity has nowhere left to go.
+    pub fn settle(&mut self, entity: Entity) {
+        self.settled.insert(entity);
+    }
+
+    /// Returns whether some Tile is taken by anyone but the
+    /// given entity.
+    pub fn is_taken_for(
+        &self,
+        entity: Entity,
+        movement_type: GameEntityType,
+        tile_pos: TilePos,
+    ) -> bool {
+        self.occupant_of(movement_type, tile_pos)
+            .is_some_and(|occupant| occupant != entity)
+    }
+
+    /// Reserves some Tile for the given entity, returning
+    /// false when it has already been taken by someone else.
+    pub fn reserve(
+        &mut self,
+        entity: Entity,
+        movement_type: GameEntityType,
+        tile_pos: TilePos,
+    ) -> bool {
+        let occupant = *self
+            .occupants
+            .entry((movement_type, tile_pos))
+            .or_insert(entity);
+
+        occupant == entity
+    }
+
+    /// Frees up some Tile, as long as the given entity
+    /// was the one holding onto it.
+    pub fn release(&mut self, entity: Entity, movement_type: GameEntityType, tile_pos: TilePos) {
+        if self.occupant_of(movement_type, tile_pos) == Some(entity) {
+            self.occupants.remove(&(movement_type, tile_pos));
+        }
+    }
+
+    /// Returns every Tile taken by anyone getting around the
+    /// same way as the given entity, besides the entity itself.
+    pub fn taken_tiles_for(
+        &self,
+        entity: Entity,
+        movement_type: GameEntityType,
+    ) -> HashSet<TilePos> {
+        self.occupants
+            .iter()
+            .filter(|((occupant_movement_type, _tile_pos), occupant)| {
+                *occupant_movement_type == movement_type && **occupant != entity
+            })
+            .map(|((_movement_type, tile_pos), _occupant)| *tile_pos)
+            .collect()
+    }
+
+    /// Frees up every Tile.
+    pub fn clear(&mut self) {
+        self.occupants.clear();
+        self.settled.clear();
+    }
+}
+
+/// How long some entity has been stuck waiting for the
+/// next Tile along its Path to free up.
+#[derive(Component, Deref, DerefMut)]
+pub struct BlockedTimer(pub Timer);
+
+impl Default for BlockedTimer {
+    fn default() -> Self {
+        BlockedTimer(Timer::from_seconds(BLOCKED_WAIT_SECS, TimerMode::Once))
+    }
+}
+
+/// Rebuilds the Occupancy Map from where every entity is standing,
+/// followed by wherever each of them is about to step onto.
+pub fn update_occupancy_map(
+    moving_entities: Query<(Entity, &GameEntityType, &StartingPoint, &Target, &Path)>,
+    mut occupancy: ResMut<OccupancyMap>,
+) {
+    occupancy.clear();
+
+    for (entity, movement_type, starting_point, target, path) in &moving_entities {
+        occupancy.reserve(entity, *movement_type, starting_point.1);
+        if target.is_none() && path.is_empty() {
+            occupancy.settle(entity);
+        }
+    }
+
+    for (entity, movement_type, _starting_point, target, _path) in &moving_entities {
+        if let Some((_target_translation, target_tile_pos)) = target.0 {
+            occupancy.reserve(entity, *movement_type, target_tile_pos);
+        }
+    }
+}
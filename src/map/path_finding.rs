@@ -1,19 +1,39 @@
-use std::collections::{HashSet, VecDeque};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
 
 use bevy::prelude::*;
 use bevy_ecs_tilemap::prelude::*;
+use serde::Deserialize;
 
-use crate::entities::{subscriber::SUBSCRIBER_LAYER_NUM, GameEntityType};
+use crate::entities::GameEntityType;
 
-use super::tiled::{to_bevy_transform, LayerNumber, TiledMapInformation};
+use super::destinations::DestinationQueue;
+use super::layer_roles::{LayerRole, LayerRoles};
+use super::links::{CrossingLink, NavLink, NavLinks};
+use super::movement::{MovementSpeed, StepProgress};
+use super::occupancy::{BlockedTimer, OccupancyMap};
+use super::path_requests::{AwaitingPath, PathError};
+use super::terrain::{can_walk_between, Slope, Terrain, MIN_MOVEMENT_COST};
+use super::tiled::{tile_transform, tile_transform_on, LayerNumber, MapLayer};
 
-#[derive(Component, PartialEq, Debug)]
+#[derive(Component, Clone, Copy, PartialEq, Eq, Hash, Debug)]
 pub enum GraphType {
     Ground,
     Air,
     Water,
 }
 
+impl GraphType {
+    /// Returns the type of Graph some kind of entity gets around.
+    pub fn for_movement(movement_type: &GameEntityType) -> GraphType {
+        match movement_type {
+            GameEntityType::Walk => GraphType::Ground,
+            GameEntityType::Fly => GraphType::Air,
+            _ => GraphType::Water,
+        }
+    }
+}
+
 pub struct TranslationGatherer {
     map_information: Vec<TileLayerPosition>,
 }
@@ -41,13 +61,10 @@ impl TranslationGatherer {
                 .expect("translations_from: Could not find map information at given tile height.");
 
             let grid_size = tile_layer_position.get_grid_size();
-            let map_type = tile_layer_position.get_map_type();
+            let map_size = tile_layer_position.get_map_size();
             let map_position = tile_layer_position.get_position();
 
-            let tile_translation = tile
-                .center_in_world(grid_size, map_type)
-                .extend(map_position.translation.z);
-            let tile_transform = *map_position * Transform::from_translation(tile_translation);
+            let tile_transform = tile_transform(&tile, grid_size, map_size, map_position);
 
             heighted_tile_translations.push(tile_transform.translation);
         }
@@ -67,13 +84,10 @@ impl TranslationGatherer {
             );
 
             let grid_size = tile_layer_position.get_grid_size();
-            let map_type = tile_layer_position.get_map_type();
+            let map_size = tile_layer_position.get_map_size();
             let map_position = tile_layer_position.get_position();
 
-            let tile_translation = tile
-                .center_in_world(grid_size, map_type)
-                .extend(map_position.translation.z);
-            let tile_transform = *map_position * Transform::from_translation(tile_translation);
+            let tile_transform = tile_transform(&tile, grid_size, map_size, map_position);
 
             heighted_tile_translations.push(tile_transform.translation);
         }
@@ -97,13 +111,10 @@ impl TranslationGatherer {
             );
 
             let grid_size = tile_layer_position.get_grid_size();
-            let map_type = tile_layer_position.get_map_type();
+            let map_size = tile_layer_position.get_map_size();
             let map_position = tile_layer_position.get_position();
 
-            let tile_translation = tile
-                .center_in_world(grid_size, map_type)
-                .extend(map_position.translation.z);
-            let tile_transform = *map_position * Transform::from_translation(tile_translation);
+            let tile_transform = tile_transform(&tile, grid_size, map_size, map_position);
 
             heighted_tile_translations.push(tile_transform.translation);
         }
@@ -112,12 +123,39 @@ impl TranslationGatherer {
     }
 }
 
-#[derive(Component)]
+/// Everything besides their positions deciding how
+/// Tiles join up into an Undirected Graph.
+#[derive(Default, Clone)]
+pub struct GraphLayout {
+    pub terrains: HashMap<HeightedTilePos, Terrain>,
+    pub slopes: HashMap<HeightedTilePos, Slope>,
+    /// Ways across between Tiles that are not neighbours.
+    pub links: Vec<NavLink>,
+    /// Whether Tiles are joined to their diagonal neighbours as
+    /// well, as long as no corner is cut along the way.
+    pub diagonals: bool,
+    /// The layer Swimmers are drawn on, whose placement
+    /// the Nodes of the Water Graph take on.
+    pub water_layer: usize,
+}
+
+/// How the Undirected Graphs are laid out once the map loads.
+#[derive(Resource, Default)]
+pub struct PathFindingSettings {
+    /// Lets anything getting around step diagonally.
+    pub diagonal_movement: bool,
+}
+
+#[derive(Component, Clone)]
 pub struct UndirectedGraph {
     tile_type: GraphType,
     length: u32,
     nodes: NodeData,
     edges: NodeEdges,
+    costs: NodeCosts,
+    heights: Vec<usize>,
+    links: Vec<NavLink>,
+    diagonals: bool,
 }
 
 impl UndirectedGraph {
@@ -128,35 +166,232 @@ impl UndirectedGraph {
         tile_type: GraphType,
         tiles: Vec<HeightedTilePos>,
         tile_layers: Vec<TileLayerPosition>,
+    ) -> Self {
+        Self::from_tiles_with_layout(tile_type, tiles, tile_layers, &GraphLayout::default())
+    }
+
+    /// Converts a Tile map with layers into an Undirected
+    /// Graph, where the layout decides how each Tile joins up
+    /// with its neighbours and how costly it is to step onto.
+    pub fn from_tiles_with_layout(
+        tile_type: GraphType,
+        tiles: Vec<HeightedTilePos>,
+        tile_layers: Vec<TileLayerPosition>,
+        layout: &GraphLayout,
     ) -> Self {
         let (length, _width, _height) = dimensions_from(&tiles);
-        let (nodes, edges) = match tile_type {
+        let costs = NodeCosts::from_tiles(&tile_type, &tiles, &layout.terrains)
+            .with_links(&layout.links, length);
+        let heights = height_map_from(&tiles);
+        let (nodes, mut edges) = match tile_type {
             GraphType::Ground => (
                 NodeData::from_ground_tiles(&tiles, tile_layers),
-                NodeEdges::from_ground_tiles(tiles),
+                NodeEdges::from_ground_tiles(tiles, layout),
             ),
             GraphType::Air => (
                 NodeData::from_air_tiles(&tiles, tile_layers),
-                NodeEdges::from_air_tiles(tiles),
+                NodeEdges::from_air_tiles(tiles, layout),
             ),
             GraphType::Water => (
-                NodeData::from_water_tiles(&tiles, tile_layers),
-                NodeEdges::from_water_tiles(tiles),
+                NodeData::from_water_tiles(&tiles, tile_layers, layout.water_layer),
+                NodeEdges::from_water_tiles(tiles, layout),
             ),
         };
 
+        edges.add_links(&layout.links, length);
+
         Self {
             tile_type,
             length,
             nodes,
             edges,
+            costs,
+            heights,
+            links: layout.links.clone(),
+            diagonals: layout.diagonals,
         }
     }
 
-    /// Returns a Shortest Path for some start and destination
-    /// Tile Positions.
+    /// Returns the cheapest Path to walk for some start
+    /// and destination Tile Positions.
     pub fn shortest_path(&self, start: TilePos, end: TilePos) -> Option<Path> {
-        self.edges.shortest_path(start, end, self.length)
+        self.edges.shortest_path(
+            start,
+            end,
+            self.length,
+            &self.costs,
+            self.diagonals,
+            &HashSet::new(),
+        )
+    }
+
+    /// Returns the cheapest Path to walk for some start and
+    /// destination Tile Positions, or why none could be found.
+    pub fn find_path(&self, start: TilePos, end: TilePos) -> Result<Path, PathError> {
+        let start_idx = self.node_idx(start).ok_or(PathError::OutOfBounds(start))?;
+        self.node_idx(end).ok_or(PathError::OutOfBounds(end))?;
+
+        if start != end && self.edges.0[start_idx].is_empty() {
+            return Err(PathError::StartNotOnGraph(start));
+        }
+
+        self.shortest_path(start, end)
+            .ok_or(PathError::Unreachable { start, end })
+    }
+
+    /// Returns the reachable Tile closest to some target, preferring
+    /// the fewest steps between Tiles equally close to the target.
+    pub fn nearest_reachable_to(&self, start: TilePos, target: TilePos) -> Option<TilePos> {
+        self.distances_from(start)
+            .into_iter()
+            .enumerate()
+            .filter_map(|(node_idx, distance)| {
+                Some((idx_to_tilepos(node_idx, self.length), distance?))
+            })
+            .min_by_key(|(tile_pos, distance)| (tiles_between(*tile_pos, target), *distance))
+            .map(|(tile_pos, _distance)| tile_pos)
+    }
+
+    /// Returns whether some Tile can be stood on while getting
+    /// around the Graph, joining up with at least one neighbour.
+    pub fn is_on_graph(&self, tile_pos: TilePos) -> bool {
+        self.node_idx(tile_pos)
+            .is_some_and(|node_idx| !self.edges.0[node_idx].is_empty())
+    }
+
+    /// Returns every Tile that can be stood on while getting around the Graph.
+    fn tiles_on_graph(&self) -> impl Iterator<Item = TilePos> + '_ {
+        self.edges
+            .0
+            .iter()
+            .enumerate()
+            .filter(|(_node_idx, node_edges)| !node_edges.is_empty())
+            .map(|(node_idx, _node_edges)| idx_to_tilepos(node_idx, self.length))
+    }
+
+    /// Returns the Tile on the Graph closest to some Tile, which
+    /// is the Tile itself whenever it is already on the Graph.
+    pub fn nearest_node_to(&self, tile_pos: TilePos) -> Option<TilePos> {
+        self.tiles_on_graph()
+            .min_by_key(|node_tilepos| tiles_between(*node_tilepos, tile_pos))
+    }
+
+    /// Returns every Tile that can be stood on while getting
+    /// around the Graph, along with where it is drawn.
+    pub fn drawn_nodes(&self) -> impl Iterator<Item = (TilePos, Vec3)> + '_ {
+        self.tiles_on_graph().filter_map(|tile_pos| {
+            let node_idx = self.node_idx(tile_pos)?;
+
+            Some((tile_pos, *self.get_node(node_idx)?))
+        })
+    }
+
+    /// Returns the Link crossed when stepping from one Tile to another, if any.
+    pub fn link_between(&self, from: TilePos, to: TilePos) -> Option<&NavLink> {
+        self.links.iter().find(|link| link.crosses(from, to))
+    }
+
+    /// Returns how many layers high the ground is stacked on some Tile.
+    pub fn height_of(&self, tile_pos: TilePos) -> Option<usize> {
+        self.node_idx(tile_pos)
+            .and_then(|node_idx| self.heights.get(node_idx).copied())
+    }
+
+    /// Returns every Tile on the Graph at most some
+    /// number of Tiles away from another Tile.
+    pub fn nodes_within(&self, tile_pos: TilePos, radius: u32) -> Vec<TilePos> {
+        self.tiles_on_graph()
+            .filter(|node_tilepos| tiles_between(*node_tilepos, tile_pos) <= radius)
+            .collect()
+    }
+
+    /// Returns every Tile on the Graph found right next to a Tile
+    /// only found on some other Graph, such as where the land
+    /// meets the water.
+    pub fn shoreline_with(&self, other_graph: &UndirectedGraph) -> Vec<TilePos> {
+        self.tiles_on_graph()
+            .filter(|node_tilepos| {
+                [(-1, 0), (0, -1), (0, 1), (1, 0)]
+                    .iter()
+                    .filter_map(|(x_offset, y_offset)| {
+                        let neighbour_x = node_tilepos.x.checked_add_signed(*x_offset)?;
+                        let neighbour_y = node_tilepos.y.checked_add_signed(*y_offset)?;
+                        Some(TilePos::new(neighbour_x, neighbour_y))
+                    })
+                    .any(|neighbour| {
+                        !self.is_on_graph(neighbour) && other_graph.is_on_graph(neighbour)
+                    })
+            })
+            .collect()
+    }
+
+    /// Returns the index of the Node found at some Tile
+    /// Position, or None when it lies outside of the Graph.
+    pub fn node_idx(&self, tile_pos: TilePos) -> Option<usize> {
+        let node_idx = tilepos_to_idx(tile_pos.x, tile_pos.y, self.length);
+
+        (tile_pos.y < self.length && node_idx < self.edges.0.len()).then_some(node_idx)
+    }
+
+    /// Returns the cheapest Path to walk for some start and
+    /// destination Tile Positions, stepping around every
+    /// avoided Tile along the way.
+    pub fn shortest_path_avoiding(
+        &self,
+        start: TilePos,
+        end: TilePos,
+        avoided_tiles: &HashSet<TilePos>,
+    ) -> Option<Path> {
+        let avoided_nodes = avoided_tiles
+            .iter()
+            .map(|tile_pos| tilepos_to_idx(tile_pos.x, tile_pos.y, self.length))
+            .collect::<HashSet<usize>>();
+
+        self.edges.shortest_path(
+            start,
+            end,
+            self.length,
+            &self.costs,
+            self.diagonals,
+            &avoided_nodes,
+        )
+    }
+
+    /// Returns how much it costs to step onto some Node.
+    pub fn movement_cost(&self, index: usize) -> Option<u32> {
+        self.costs.tiles.get(index).copied()
+    }
+
+    /// Returns the number of steps needed to reach each Node
+    /// from some starting Tile Position, or None for Nodes that
+    /// cannot be reached at all.
+    pub fn distances_from(&self, start: TilePos) -> Vec<Option<usize>> {
+        self.edges.distances_from(start, self.length)
+    }
+
+    /// Returns how much it costs to reach some target Tile Position
+    /// from each Node, or None for Nodes with no way there at all.
+    pub fn costs_to(&self, target: TilePos) -> Vec<Option<u32>> {
+        self.edges.costs_to(target, self.length, &self.costs)
+    }
+
+    /// Returns how much it costs to walk from one Tile Position
+    /// to another, or None when there is no way there at all.
+    pub fn walk_cost(&self, start: TilePos, end: TilePos) -> Option<u32> {
+        self.node_idx(start)
+            .and_then(|start_idx| self.costs_to(end).get(start_idx).copied().flatten())
+    }
+
+    /// Returns how much it costs to take a single step
+    /// between two neighbouring Nodes.
+    pub fn step_cost(&self, from_idx: usize, to_idx: usize) -> u32 {
+        self.costs.step_cost(from_idx, to_idx, self.length)
+    }
+
+    /// Returns the length (number of Tiles along the x-axis)
+    /// used to index Nodes in the Undirected Graph.
+    pub fn length(&self) -> u32 {
+        self.length
     }
 
     /// Returns the contents of a Node found in the
@@ -184,15 +419,15 @@ pub struct NodeData(pub Vec<Vec3>);
 #[derive(Clone)]
 pub struct TileLayerPosition {
     grid_size: TilemapGridSize,
-    map_type: TilemapType,
+    map_size: TilemapSize,
     position: Transform,
 }
 
 impl TileLayerPosition {
-    pub fn new(grid_size: TilemapGridSize, map_type: TilemapType, position: Transform) -> Self {
+    pub fn new(grid_size: TilemapGridSize, map_size: TilemapSize, position: Transform) -> Self {
         Self {
             grid_size,
-            map_type,
+            map_size,
             position,
         }
     }
@@ -201,8 +436,8 @@ impl TileLayerPosition {
         &self.grid_size
     }
 
-    pub fn get_map_type(&self) -> &TilemapType {
-        &self.map_type
+    pub fn get_map_size(&self) -> &TilemapSize {
+        &self.map_size
     }
 
     pub fn get_position(&self) -> &Transform {
@@ -236,11 +471,12 @@ impl NodeData {
     pub fn from_water_tiles(
         heighted_tiles: &Vec<HeightedTilePos>,
         layer_map_information: Vec<TileLayerPosition>,
+        water_layer: usize,
     ) -> Self {
         let translation_gatherer = TranslationGatherer::new(layer_map_information);
 
         let tile_translations =
-            translation_gatherer.translations_at_height(heighted_tiles, SUBSCRIBER_LAYER_NUM);
+            translation_gatherer.translations_at_height(heighted_tiles, water_layer);
 
         NodeData(tile_translations)
     }
@@ -249,6 +485,85 @@ impl NodeData {
 #[derive(Component, Clone, Deref, PartialEq, Debug)]
 pub struct NodeEdges(pub Vec<Vec<usize>>);
 
+/// How much it costs to step onto each Node, or
+/// to cross each Link from one Node to another.
+#[derive(Component, Clone, PartialEq, Debug)]
+pub struct NodeCosts {
+    tiles: Vec<u32>,
+    links: HashMap<(usize, usize), u32>,
+}
+
+impl NodeCosts {
+    /// Returns the cost of stepping onto each Tile, decided by
+    /// the Terrain of the Tile being stood on. Tiles without any
+    /// recorded Terrain are treated as Plain.
+    pub fn from_tiles(
+        graph_type: &GraphType,
+        heighted_tiles: &Vec<HeightedTilePos>,
+        terrains: &HashMap<HeightedTilePos, Terrain>,
+    ) -> Self {
+        let height_map = height_map_from(heighted_tiles);
+        let (length, _width, _height) = dimensions_from(heighted_tiles);
+
+        let mut node_costs = vec![Terrain::default().movement_cost(graph_type); height_map.len()];
+        for tile in unique_tiles_from(heighted_tiles) {
+            let tile_idx = tilepos_to_idx(tile.x, tile.y, length);
+            let standing_tile = HeightedTilePos::new(tile, height_map[tile_idx] as u32);
+
+            if let Some(terrain) = terrains.get(&standing_tile) {
+                node_costs[tile_idx] = terrain.movement_cost(graph_type);
+            }
+        }
+
+        NodeCosts {
+            tiles: node_costs,
+            links: HashMap::new(),
+        }
+    }
+
+    /// Returns the same Node Costs, along with how much it
+    /// costs to cross each way along every Link.
+    pub fn with_links(mut self, links: &[NavLink], length: u32) -> Self {
+        for link in links {
+            for (from, to) in link.crossings() {
+                let from_idx = tilepos_to_idx(from.x, from.y, length);
+                let to_idx = tilepos_to_idx(to.x, to.y, length);
+
+                self.links.insert(
+                    (from_idx, to_idx),
+                    link.cost * STEP_COST * MIN_MOVEMENT_COST,
+                );
+            }
+        }
+
+        self
+    }
+
+    /// Returns how much it costs to step onto some Node.
+    fn cost_of(&self, node_idx: usize) -> u32 {
+        self.tiles
+            .get(node_idx)
+            .copied()
+            .unwrap_or(MIN_MOVEMENT_COST)
+    }
+
+    /// Returns how much it costs to step from one Node onto
+    /// another, crossing a Link between them if there is one.
+    fn step_cost(&self, from_idx: usize, to_idx: usize, length: u32) -> u32 {
+        if let Some(link_cost) = self.links.get(&(from_idx, to_idx)) {
+            return *link_cost;
+        }
+
+        let step_cost = if is_diagonal_step(from_idx, to_idx, length) {
+            DIAGONAL_STEP_COST
+        } else {
+            STEP_COST
+        };
+
+        self.cost_of(to_idx) * step_cost
+    }
+}
+
 /// Returns the Length, Width, and Height derived from
 /// some collection of Heighted Tile Positions.
 fn dimensions_from(heighted_tiles: &Vec<HeightedTilePos>) -> (u32, u32, u32) {
@@ -324,138 +639,216 @@ pub fn unique_tiles_from(tiles: &Vec<HeightedTilePos>) -> Vec<TilePos> {
     unique_tiles_no_dups
 }
 
+/// The offsets towards each neighbouring Tile, where
+/// the last four are only ever stepped to diagonally.
+const NEIGHBOUR_OFFSETS: [(i64, i64); 8] = [
+    (-1, 0),
+    (0, -1),
+    (0, 1),
+    (1, 0),
+    (-1, -1),
+    (-1, 1),
+    (1, -1),
+    (1, 1),
+];
+
+/// The cost of stepping straight onto a Node costing one,
+/// leaving room for diagonal steps to cost a little more.
+const STEP_COST: u32 = 10;
+
+/// The cost of stepping diagonally onto a Node costing one.
+const DIAGONAL_STEP_COST: u32 = 14;
+
+/// Returns whether stepping between two Nodes is diagonal.
+fn is_diagonal_step(from_idx: usize, to_idx: usize, length: u32) -> bool {
+    let from_tilepos = idx_to_tilepos(from_idx, length);
+    let to_tilepos = idx_to_tilepos(to_idx, length);
+
+    from_tilepos.x != to_tilepos.x && from_tilepos.y != to_tilepos.y
+}
+
 impl NodeEdges {
-    /// Returns a set of Node Edges derived from a collection of Tiles
-    /// designated for Ground traversal.
-    pub fn from_ground_tiles(ground_tiles: Vec<HeightedTilePos>) -> NodeEdges {
-        let mut directed_graph_edges: Vec<Vec<usize>> = Vec::with_capacity(ground_tiles.len());
+    /// Returns a set of Node Edges joining each Tile to every neighbour
+    /// it can step to. Diagonal steps are only taken when both Tiles
+    /// beside them could be stepped through instead, so no corner is cut.
+    fn from_steps(
+        heighted_tiles: &Vec<HeightedTilePos>,
+        diagonals: bool,
+        can_step: impl Fn(usize, usize) -> bool,
+    ) -> NodeEdges {
+        let (length, _width, _height) = dimensions_from(heighted_tiles);
 
-        let height_map: Vec<usize> = height_map_from(&ground_tiles);
-        let (length, _width, _height) = dimensions_from(&ground_tiles);
+        let tile_positions_no_layers = unique_tiles_from(heighted_tiles);
+        let num_nodes = tile_positions_no_layers.len();
+        let mut directed_graph_edges: Vec<Vec<usize>> = vec![Vec::new(); num_nodes];
+
+        let neighbour_idx_of = |tile_position: TilePos, (x_offset, y_offset): (i64, i64)| {
+            let neighbour_x = u32::try_from(tile_position.x as i64 + x_offset).ok()?;
+            let neighbour_y = u32::try_from(tile_position.y as i64 + y_offset).ok()?;
+            if neighbour_y >= length {
+                return None;
+            }
 
-        let tile_positions_no_layers = unique_tiles_from(&ground_tiles);
+            let neighbour_idx = tilepos_to_idx(neighbour_x, neighbour_y, length);
+            (neighbour_idx < num_nodes).then_some(neighbour_idx)
+        };
 
-        for tile_position in tile_positions_no_layers {
+        let num_offsets = if diagonals { 8 } else { 4 };
+        for tile_position in &tile_positions_no_layers {
             let tile_idx = tilepos_to_idx(tile_position.x, tile_position.y, length);
-            let tile_height = height_map[tile_idx];
 
             let mut current_node_edges = Vec::new();
+            for offset in &NEIGHBOUR_OFFSETS[..num_offsets] {
+                let Some(neighbour_idx) = neighbour_idx_of(*tile_position, *offset) else {
+                    continue;
+                };
 
-            if tile_position.x > 0 && tile_height > 0 {
-                let top_node_idx = tilepos_to_idx(tile_position.x - 1, tile_position.y, length);
-                let height_difference: i32 = height_map[top_node_idx] as i32 - tile_height as i32;
-                if height_difference.abs() <= 1 {
-                    current_node_edges.push(top_node_idx);
-                    directed_graph_edges[top_node_idx].push(tile_idx);
+                if !can_step(tile_idx, neighbour_idx) {
+                    continue;
                 }
-            }
 
-            if tile_position.y > 0 && tile_height > 0 {
-                let left_node_idx = tilepos_to_idx(tile_position.x, tile_position.y - 1, length);
-                let height_difference: i32 = height_map[left_node_idx] as i32 - tile_height as i32;
-                if height_difference.abs() <= 1 {
-                    current_node_edges.push(left_node_idx);
-                    directed_graph_edges[left_node_idx].push(tile_idx);
+                let is_diagonal = offset.0 != 0 && offset.1 != 0;
+                if is_diagonal {
+                    let beside_idxs = [
+                        neighbour_idx_of(*tile_position, (offset.0, 0)),
+                        neighbour_idx_of(*tile_position, (0, offset.1)),
+                    ];
+                    let cuts_corner = beside_idxs.iter().any(|beside_idx| {
+                        beside_idx.is_none_or(|beside_idx| {
+                            !can_step(tile_idx, beside_idx) || !can_step(beside_idx, neighbour_idx)
+                        })
+                    });
+                    if cuts_corner {
+                        continue;
+                    }
                 }
+
+                current_node_edges.push(neighbour_idx);
             }
 
-            directed_graph_edges.push(current_node_edges);
+            directed_graph_edges[tile_idx] = current_node_edges;
         }
 
         NodeEdges(directed_graph_edges)
     }
 
     /// Returns a set of Node Edges derived from a collection of Tiles
-    /// designated for Air traversal.
-    pub fn from_air_tiles(air_tiles: Vec<HeightedTilePos>) -> NodeEdges {
-        let mut directed_graph_edges: Vec<Vec<usize>> = Vec::with_capacity(air_tiles.len());
+    /// designated for Ground traversal. Neighbouring Tiles are joined
+    /// when at most 1 level apart, unless a Slope says otherwise.
+    pub fn from_ground_tiles(
+        ground_tiles: Vec<HeightedTilePos>,
+        layout: &GraphLayout,
+    ) -> NodeEdges {
+        let height_map: Vec<usize> = height_map_from(&ground_tiles);
+        let (length, _width, _height) = dimensions_from(&ground_tiles);
 
-        let (length, _width, _height) = dimensions_from(&air_tiles);
+        let standing_tile_of = |tile_idx: usize| {
+            let tile_height = height_map[tile_idx] as u32;
+            let standing_tile = HeightedTilePos::new(idx_to_tilepos(tile_idx, length), tile_height);
 
-        let tile_positions_no_layers = unique_tiles_from(&air_tiles);
-        for tile_position in tile_positions_no_layers {
-            let tile_idx = tilepos_to_idx(tile_position.x, tile_position.y, length);
+            (tile_height, layout.slopes.get(&standing_tile))
+        };
 
-            let mut current_node_edges = Vec::new();
-            if tile_position.x > 0 {
-                let top_node_idx = tilepos_to_idx(tile_position.x - 1, tile_position.y, length);
+        NodeEdges::from_steps(&ground_tiles, layout.diagonals, |from_idx, to_idx| {
+            let (from_height, from_slope) = standing_tile_of(from_idx);
+            let (to_height, to_slope) = standing_tile_of(to_idx);
 
-                current_node_edges.push(top_node_idx);
-                directed_graph_edges[top_node_idx].push(tile_idx);
-            }
+            can_walk_between(from_height, from_slope, to_height, to_slope)
+        })
+    }
 
-            if tile_position.y > 0 {
-                let left_node_idx = tilepos_to_idx(tile_position.x, tile_position.y - 1, length);
-                current_node_edges.push(left_node_idx);
-                directed_graph_edges[left_node_idx].push(tile_idx);
-            }
+    /// Joins up each pair of Tiles linked together, however
+    /// far apart they are, each way the Link can be crossed.
+    pub fn add_links(&mut self, links: &[NavLink], length: u32) {
+        for link in links {
+            for (from, to) in link.crossings() {
+                let from_idx = tilepos_to_idx(from.x, from.y, length);
+                let to_idx = tilepos_to_idx(to.x, to.y, length);
+                if from.y >= length || to.y >= length || to_idx >= self.0.len() {
+                    continue;
+                }
 
-            directed_graph_edges.push(current_node_edges);
+                let Some(node_edges) = self.0.get_mut(from_idx) else {
+                    continue;
+                };
+                if !node_edges.contains(&to_idx) {
+                    node_edges.push(to_idx);
+                }
+            }
         }
+    }
 
-        NodeEdges(directed_graph_edges)
+    /// Returns a set of Node Edges derived from a collection of Tiles
+    /// designated for Air traversal.
+    pub fn from_air_tiles(air_tiles: Vec<HeightedTilePos>, layout: &GraphLayout) -> NodeEdges {
+        NodeEdges::from_steps(&air_tiles, layout.diagonals, |_from_idx, _to_idx| true)
     }
 
     /// Returns a set of Node Edges derived from a collection of Tiles
     /// designated for Water traversal.
-    pub fn from_water_tiles(water_tiles: Vec<HeightedTilePos>) -> NodeEdges {
-        let mut directed_graph_edges: Vec<Vec<usize>> = Vec::with_capacity(water_tiles.len());
-
+    pub fn from_water_tiles(water_tiles: Vec<HeightedTilePos>, layout: &GraphLayout) -> NodeEdges {
         let height_map: Vec<usize> = height_map_from(&water_tiles);
-        let (length, _width, _height) = dimensions_from(&water_tiles);
-
-        let tile_positions_no_layers = unique_tiles_from(&water_tiles);
 
-        for tile_position in tile_positions_no_layers {
-            let tile_idx = tilepos_to_idx(tile_position.x, tile_position.y, length);
-            let tile_height = height_map[tile_idx];
+        NodeEdges::from_steps(&water_tiles, layout.diagonals, |from_idx, to_idx| {
+            height_map[from_idx] == 0 && height_map[to_idx] == 0
+        })
+    }
 
-            let mut current_node_edges = Vec::new();
+    /// Returns the cheapest Path between a source and target Tile
+    /// Position, or nothing if none were found. Paths are searched
+    /// for with A*, guided by how many steps are left to take.
+    pub fn shortest_path(
+        &self,
+        source: TilePos,
+        target: TilePos,
+        length: u32,
+        node_costs: &NodeCosts,
+        diagonals: bool,
+        avoided_nodes: &HashSet<usize>,
+    ) -> Option<Path> {
+        let graph_node_edges = &self.0;
 
-            if tile_position.x > 0 && tile_height == 0 {
-                let top_node_idx = tilepos_to_idx(tile_position.x - 1, tile_position.y, length);
-                let height_difference: i32 = height_map[top_node_idx] as i32 - tile_height as i32;
-                if height_difference.abs() == 0 {
-                    current_node_edges.push(top_node_idx);
-                    directed_graph_edges[top_node_idx].push(tile_idx);
-                }
-            }
+        let mapped_source_idx = tilepos_to_idx(source.x, source.y, length);
+        let mut mapped_target_idx = tilepos_to_idx(target.x, target.y, length);
+        if mapped_source_idx >= graph_node_edges.len()
+            || mapped_target_idx >= graph_node_edges.len()
+        {
+            return None;
+        }
 
-            if tile_position.y > 0 && tile_height == 0 {
-                let left_node_idx = tilepos_to_idx(tile_position.x, tile_position.y - 1, length);
-                let height_difference: i32 = height_map[left_node_idx] as i32 - tile_height as i32;
-                if height_difference.abs() == 0 {
-                    current_node_edges.push(left_node_idx);
-                    directed_graph_edges[left_node_idx].push(tile_idx);
-                }
+        // Each step costs at least the cheapest Terrain, and diagonal steps
+        // are taken wherever allowed, so the estimate never makes a Path
+        // look more expensive than it really is. Links can cut across any
+        // distance at all, so no estimate is made wherever there are any.
+        let estimate_cost_to_target = |node_idx: usize| {
+            if !node_costs.links.is_empty() {
+                return 0;
             }
 
-            directed_graph_edges.push(current_node_edges);
-        }
+            let node_tilepos = idx_to_tilepos(node_idx, length);
+            let x_dist = node_tilepos.x.abs_diff(target.x);
+            let y_dist = node_tilepos.y.abs_diff(target.y);
 
-        NodeEdges(directed_graph_edges)
-    }
+            let steps_cost = if diagonals {
+                STEP_COST * x_dist.max(y_dist)
+                    + (DIAGONAL_STEP_COST - STEP_COST) * x_dist.min(y_dist)
+            } else {
+                STEP_COST * (x_dist + y_dist)
+            };
 
-    /// Returns a Single Shortest Path between a source and target Tile
-    /// Position, or nothing if none were found.
-    pub fn shortest_path(&self, source: TilePos, target: TilePos, length: u32) -> Option<Path> {
-        let source_tilepos = source; //convert_tiled_to_bevy_pos(source, length);
-        let target_tilepos = target; //convert_tiled_to_bevy_pos(target, length);
-
-        let graph_node_edges = &self.0;
+            steps_cost * MIN_MOVEMENT_COST
+        };
 
-        let mut node_distances = vec![0; graph_node_edges.len()];
-        let mut node_parents = vec![-1; graph_node_edges.len()];
+        let mut node_costs_so_far: Vec<Option<u32>> = vec![None; graph_node_edges.len()];
+        let mut node_parents: Vec<Option<usize>> = vec![None; graph_node_edges.len()];
         let mut node_visited = vec![false; graph_node_edges.len()];
 
-        let mapped_source_idx = tilepos_to_idx(source_tilepos.x, source_tilepos.y, length);
-        let mut mapped_target_idx = tilepos_to_idx(target_tilepos.x, target_tilepos.y, length);
-
-        let mut bfs_queue = VecDeque::from([mapped_source_idx]);
-        while !bfs_queue.is_empty() {
-            let current_node_idx = bfs_queue
-                .pop_front()
-                .expect("shortest_path: BFS Queue is Empty, but it shouldn't be?");
+        node_costs_so_far[mapped_source_idx] = Some(0);
+        let mut open_nodes = BinaryHeap::from([Reverse((
+            estimate_cost_to_target(mapped_source_idx),
+            mapped_source_idx,
+        ))]);
+        while let Some(Reverse((_estimated_cost, current_node_idx))) = open_nodes.pop() {
             if current_node_idx == mapped_target_idx {
                 node_visited[current_node_idx] = true;
                 break;
@@ -467,15 +860,26 @@ impl NodeEdges {
 
             node_visited[current_node_idx] = true;
 
+            let current_cost = node_costs_so_far[current_node_idx]
+                .expect("shortest_path: Opened Nodes should always have a cost.");
             for node_edge in &graph_node_edges[current_node_idx] {
-                if node_visited[*node_edge] {
+                if node_visited[*node_edge] || avoided_nodes.contains(node_edge) {
+                    continue;
+                }
+
+                let edge_cost =
+                    current_cost + node_costs.step_cost(current_node_idx, *node_edge, length);
+                if node_costs_so_far[*node_edge].is_some_and(|known_cost| known_cost <= edge_cost) {
                     continue;
                 }
 
-                node_parents[*node_edge] = current_node_idx as i32;
-                node_distances[*node_edge] = node_distances[current_node_idx] + 1;
+                node_costs_so_far[*node_edge] = Some(edge_cost);
+                node_parents[*node_edge] = Some(current_node_idx);
 
-                bfs_queue.push_back(*node_edge);
+                open_nodes.push(Reverse((
+                    edge_cost + estimate_cost_to_target(*node_edge),
+                    *node_edge,
+                )));
             }
         }
 
@@ -485,18 +889,108 @@ impl NodeEdges {
 
         let mut path = VecDeque::from([mapped_target_idx]);
         while mapped_source_idx != mapped_target_idx {
-            let node_parent = node_parents[mapped_target_idx];
-            if node_parent == -1 {
+            let Some(node_parent) = node_parents[mapped_target_idx] else {
                 break;
-            }
+            };
 
-            mapped_target_idx = node_parent as usize;
+            mapped_target_idx = node_parent;
 
             path.push_front(mapped_target_idx);
         }
 
         Some(Path(path))
     }
+
+    /// Returns the cheapest cost of reaching a target Tile Position from
+    /// every Node, where Nodes with no way there are recorded as None.
+    /// Costs are found with Dijkstra, stepping backwards from the target.
+    pub fn costs_to(
+        &self,
+        target: TilePos,
+        length: u32,
+        node_costs: &NodeCosts,
+    ) -> Vec<Option<u32>> {
+        let graph_node_edges = &self.0;
+
+        let mut node_costs_to_target = vec![None; graph_node_edges.len()];
+
+        let target_idx = tilepos_to_idx(target.x, target.y, length);
+        if target_idx >= graph_node_edges.len() {
+            return node_costs_to_target;
+        }
+
+        // Steps are not always taken both ways, such as dropping
+        // off of a ledge, so each Node needs to know who can
+        // step onto it rather than where it can step to.
+        let mut reversed_node_edges = vec![Vec::new(); graph_node_edges.len()];
+        for (node_idx, node_edges) in graph_node_edges.iter().enumerate() {
+            for node_edge in node_edges {
+                reversed_node_edges[*node_edge].push(node_idx);
+            }
+        }
+
+        let mut node_visited = vec![false; graph_node_edges.len()];
+
+        node_costs_to_target[target_idx] = Some(0);
+        let mut open_nodes = BinaryHeap::from([Reverse((0, target_idx))]);
+        while let Some(Reverse((current_cost, current_node_idx))) = open_nodes.pop() {
+            if node_visited[current_node_idx] {
+                continue;
+            }
+
+            node_visited[current_node_idx] = true;
+
+            for node_edge in &reversed_node_edges[current_node_idx] {
+                if node_visited[*node_edge] {
+                    continue;
+                }
+
+                let edge_cost =
+                    current_cost + node_costs.step_cost(*node_edge, current_node_idx, length);
+                if node_costs_to_target[*node_edge]
+                    .is_some_and(|known_cost| known_cost <= edge_cost)
+                {
+                    continue;
+                }
+
+                node_costs_to_target[*node_edge] = Some(edge_cost);
+                open_nodes.push(Reverse((edge_cost, *node_edge)));
+            }
+        }
+
+        node_costs_to_target
+    }
+
+    /// Returns the number of steps from a source Tile Position to every
+    /// Node, where unreachable Nodes are recorded as None.
+    pub fn distances_from(&self, source: TilePos, length: u32) -> Vec<Option<usize>> {
+        let graph_node_edges = &self.0;
+
+        let mut node_distances = vec![None; graph_node_edges.len()];
+
+        let source_idx = tilepos_to_idx(source.x, source.y, length);
+        if source_idx >= graph_node_edges.len() {
+            return node_distances;
+        }
+
+        node_distances[source_idx] = Some(0);
+        let mut bfs_queue = VecDeque::from([source_idx]);
+        while let Some(current_node_idx) = bfs_queue.pop_front() {
+            let current_distance = node_distances[current_node_idx]
+                .expect("distances_from: Queued Nodes should always have a distance.");
+
+            for node_edge in &graph_node_edges[current_node_idx] {
+                if node_distances[*node_edge].is_some() {
+                    continue;
+                }
+
+                node_distances[*node_edge] = Some(current_distance + 1);
+                bfs_queue.push_back(*node_edge);
+            }
+        }
+
+        node_distances
+    }
 }
 
 #[derive(Bundle)]
@@ -506,6 +1000,12 @@ pub struct Graph {
     node_edges: NodeEdges,
 }
 
+/// Returns how many Tiles apart two Tiles are, when
+/// only ever stepping straight between neighbours.
+pub fn tiles_between(from: TilePos, to: TilePos) -> u32 {
+    from.x.abs_diff(to.x) + from.y.abs_diff(to.y)
+}
+
 /// Maps a 2-dimensional (x, y) index into a 1-dimensional array index.
 pub fn tilepos_to_idx(x: u32, y: u32, world_size: u32) -> usize {
     ((world_size * x) + y) as usize
@@ -522,12 +1022,60 @@ pub fn idx_to_tilepos(mapped_idx: usize, world_size: u32) -> TilePos {
     }
 }
 
+/// Returns the Undirected Graph holding some type of
+/// Nodes, or why it could not be found.
+pub fn graph_of<'a>(
+    graphs: impl IntoIterator<Item = &'a UndirectedGraph>,
+    graph_type: GraphType,
+) -> Result<&'a UndirectedGraph, PathError> {
+    graphs
+        .into_iter()
+        .find(|graph| *graph.get_node_type() == graph_type)
+        .ok_or(PathError::GraphMissing(graph_type))
+}
+
+/// Everything about a Tile deciding how it joins up into a Graph.
+type GraphTile<'a> = (
+    &'a TilePos,
+    &'a LayerNumber,
+    Option<&'a Terrain>,
+    Option<&'a Slope>,
+);
+
+/// Returns the layout recorded for each Heighted Tile.
+fn layout_from<'a>(
+    tiles: impl Iterator<Item = GraphTile<'a>>,
+    settings: &PathFindingSettings,
+    links: Vec<NavLink>,
+) -> GraphLayout {
+    let mut layout = GraphLayout {
+        links,
+        diagonals: settings.diagonal_movement,
+        ..default()
+    };
+
+    for (tile_pos, layer_number, terrain, slope) in tiles {
+        let heighted_tile = HeightedTilePos::new(*tile_pos, layer_number.0 as u32);
+        if let Some(terrain) = terrain {
+            layout.terrains.insert(heighted_tile.clone(), *terrain);
+        }
+
+        if let Some(slope) = slope {
+            layout.slopes.insert(heighted_tile, *slope);
+        }
+    }
+
+    layout
+}
+
 /// Spawns an Undirected Graph representing all land titles where the edges
 /// indicate an at most 1 tile offset between two tiles.
 pub fn create_ground_graph(
-    tile_positions: Query<(&TilePos, &LayerNumber)>,
-    map_information: Query<(&TilemapGridSize, &TilemapType, &Transform)>,
+    tile_positions: Query<GraphTile>,
+    settings: Res<PathFindingSettings>,
+    map_information: Query<(&TilemapGridSize, &TilemapSize, &Transform)>,
     ground_graph_query: Query<&UndirectedGraph>,
+    links: Res<NavLinks>,
     mut spawner: Commands,
 ) {
     if map_information.is_empty() {
@@ -545,24 +1093,33 @@ pub fn create_ground_graph(
         .iter()
         .map(|tile| HeightedTilePos::new(*tile.0, tile.1 .0 as u32))
         .collect::<Vec<HeightedTilePos>>();
+    let layout = layout_from(
+        tile_positions.iter(),
+        &settings,
+        links.on_graph(GraphType::Ground),
+    );
 
     let layer_map_information = map_information
         .iter()
         .map(|layer_entry| TileLayerPosition::new(*layer_entry.0, *layer_entry.1, *layer_entry.2))
         .collect::<Vec<TileLayerPosition>>();
 
-    spawner.spawn(UndirectedGraph::from_tiles(
+    spawner.spawn(UndirectedGraph::from_tiles_with_layout(
         GraphType::Ground,
         heighted_tiles,
         layer_map_information,
+        &layout,
     ));
 }
 
 /// Spawns an Undirected Graph representing all water titles
 pub fn create_water_graph(
-    tile_positions: Query<(&TilePos, &LayerNumber)>,
-    map_information: Query<(&TilemapGridSize, &TilemapType, &Transform)>,
+    tile_positions: Query<GraphTile>,
+    settings: Res<PathFindingSettings>,
+    map_information: Query<(&TilemapGridSize, &TilemapSize, &Transform)>,
     water_graph_query: Query<&UndirectedGraph>,
+    links: Res<NavLinks>,
+    layer_roles: Res<LayerRoles>,
     mut spawner: Commands,
 ) {
     if map_information.is_empty() {
@@ -576,28 +1133,49 @@ pub fn create_water_graph(
         return;
     }
 
+    let Some(water_layer) = layer_roles.index_of(LayerRole::Subscriber) else {
+        // NOTE: Only reported once per map, since the missing role
+        // is already listed out when the map is loaded.
+        if layer_roles.is_changed() {
+            error!(
+                "create_water_graph: No layer holds the {} role, so there is no Water Graph.",
+                LayerRole::Subscriber
+            );
+        }
+        return;
+    };
+
     let heighted_tiles = tile_positions
         .iter()
         .map(|tile| HeightedTilePos::new(*tile.0, tile.1 .0 as u32))
         .collect::<Vec<HeightedTilePos>>();
+    let mut layout = layout_from(
+        tile_positions.iter(),
+        &settings,
+        links.on_graph(GraphType::Water),
+    );
+    layout.water_layer = water_layer;
 
     let layer_map_information = map_information
         .iter()
         .map(|layer_entry| TileLayerPosition::new(*layer_entry.0, *layer_entry.1, *layer_entry.2))
         .collect::<Vec<TileLayerPosition>>();
 
-    spawner.spawn(UndirectedGraph::from_tiles(
+    spawner.spawn(UndirectedGraph::from_tiles_with_layout(
         GraphType::Water,
         heighted_tiles,
         layer_map_information,
+        &layout,
     ));
 }
 
 /// Spawns an Undirected Graph representing all air titles
 pub fn create_air_graph(
-    tile_positions: Query<(&TilePos, &LayerNumber)>,
-    map_information: Query<(&TilemapGridSize, &TilemapType, &Transform)>,
+    tile_positions: Query<GraphTile>,
+    settings: Res<PathFindingSettings>,
+    map_information: Query<(&TilemapGridSize, &TilemapSize, &Transform)>,
     air_graph_query: Query<&UndirectedGraph>,
+    links: Res<NavLinks>,
     mut spawner: Commands,
 ) {
     if map_information.is_empty() {
@@ -615,16 +1193,22 @@ pub fn create_air_graph(
         .iter()
         .map(|tile| HeightedTilePos::new(*tile.0, tile.1 .0 as u32))
         .collect::<Vec<HeightedTilePos>>();
+    let layout = layout_from(
+        tile_positions.iter(),
+        &settings,
+        links.on_graph(GraphType::Air),
+    );
 
     let layer_map_information = map_information
         .iter()
         .map(|layer_entry| TileLayerPosition::new(*layer_entry.0, *layer_entry.1, *layer_entry.2))
         .collect::<Vec<TileLayerPosition>>();
 
-    spawner.spawn(UndirectedGraph::from_tiles(
+    spawner.spawn(UndirectedGraph::from_tiles_with_layout(
         GraphType::Air,
         heighted_tiles,
         layer_map_information,
+        &layout,
     ));
 }
 
@@ -634,22 +1218,36 @@ pub struct Target(pub Option<(Vec3, TilePos)>);
 #[derive(Component)]
 pub struct StartingPoint(pub Vec3, pub TilePos);
 
-#[derive(Component, Deref, DerefMut)]
+#[derive(Component, Clone, Debug, Deref, DerefMut)]
 pub struct Path(pub VecDeque<usize>);
 
-#[derive(Component, PartialEq, PartialOrd, Debug)]
+/// The heading some entity faces on screen, where the
+/// diagonal headings follow the Tiles' isometric axes.
+#[derive(Component, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Deserialize)]
 pub enum Direction {
     TopLeft,
     TopRight,
     BottomLeft,
     BottomRight,
+    Top,
+    Bottom,
+    Left,
+    Right,
 }
 
-#[derive(Component, Deref, DerefMut)]
-pub struct MovementTimer(pub Timer);
-
-#[derive(Component, Deref, DerefMut)]
-pub struct DestinationQueue(VecDeque<TilePos>);
+impl Direction {
+    /// Returns the diagonal heading nearest to this one,
+    /// turning clockwise for straight headings.
+    pub fn nearest_diagonal(&self) -> Direction {
+        match self {
+            Direction::Top => Direction::TopRight,
+            Direction::Right => Direction::BottomRight,
+            Direction::Bottom => Direction::BottomLeft,
+            Direction::Left => Direction::TopLeft,
+            diagonal => *diagonal,
+        }
+    }
+}
 
 #[derive(Component)]
 pub struct SpawnPoint(pub TilePos);
@@ -662,36 +1260,123 @@ pub struct PathInfo {
     start_pos: StartingPoint,
     target_pos: Target,
     direction: Direction,
-    movement_timer: MovementTimer,
+    step_progress: StepProgress,
+    crossing_link: CrossingLink,
+    blocked_timer: BlockedTimer,
+    awaiting_path: AwaitingPath,
 }
 
+/// Everything needed to start some entity getting around the map.
+type UnpathedEntity<'a> = (
+    Entity,
+    &'a Transform,
+    &'a TilePos,
+    &'a GameEntityType,
+    Option<&'a MovementSpeed>,
+);
+
 pub fn insert_pathing_information(
-    moving_entities: Query<(Entity, &Transform, &TilePos), (With<GameEntityType>, Without<Path>)>,
+    moving_entities: Query<UnpathedEntity, Without<Path>>,
     mut spawner: Commands,
 ) {
-    for (moving_entity, entity_transform, entity_tilepos) in &moving_entities {
+    for (moving_entity, entity_transform, entity_tilepos, movement_type, movement_speed) in
+        &moving_entities
+    {
         spawner.entity(moving_entity).insert(PathInfo {
             spawn_pos: SpawnPoint(*entity_tilepos),
             path: Path(VecDeque::new()),
-            requested_targets: DestinationQueue(VecDeque::new()),
+            requested_targets: DestinationQueue::default(),
             start_pos: StartingPoint(entity_transform.translation, *entity_tilepos),
             target_pos: Target(None),
             direction: Direction::TopRight,
-            movement_timer: MovementTimer(Timer::from_seconds(0.05, TimerMode::Repeating)),
+            step_progress: StepProgress::default(),
+            crossing_link: CrossingLink::default(),
+            blocked_timer: BlockedTimer::default(),
+            awaiting_path: AwaitingPath(false),
         });
+
+        // Anyone spawned with a speed of their own keeps it.
+        if movement_speed.is_none() {
+            spawner
+                .entity(moving_entity)
+                .insert(MovementSpeed::for_movement(movement_type));
+        }
+    }
+}
+
+/// Everything about some entity needed to head to
+/// the next Node along its Path.
+type PathFollower<'a> = (
+    Entity,
+    &'a mut Target,
+    &'a mut Path,
+    &'a Transform,
+    &'a StartingPoint,
+    &'a mut Direction,
+    &'a GameEntityType,
+    &'a mut BlockedTimer,
+);
+
+/// Finds another way to wherever some entity is headed once the
+/// next Tile along its Path has been taken for too long. Entities
+/// stop short of a destination someone has settled on, and
+/// side-step out of the way when there is no other way around.
+fn route_around_taken_tiles(
+    entity: Entity,
+    movement_type: GameEntityType,
+    current_tile_pos: TilePos,
+    path: &mut Path,
+    tile_graph: &UndirectedGraph,
+    occupancy: &OccupancyMap,
+) {
+    let Some(destination_idx) = path.0.back().copied() else {
+        return;
+    };
+
+    let destination = idx_to_tilepos(destination_idx, tile_graph.length());
+    let mut taken_tiles = occupancy.taken_tiles_for(entity, movement_type);
+
+    if occupancy.is_settled_on(entity, movement_type, destination) {
+        taken_tiles.remove(&destination);
+
+        let mut new_path = tile_graph
+            .shortest_path_avoiding(current_tile_pos, destination, &taken_tiles)
+            .unwrap_or(Path(VecDeque::new()));
+        new_path.0.pop_back();
+        *path = new_path;
+        return;
+    }
+
+    if let Some(new_path) =
+        tile_graph.shortest_path_avoiding(current_tile_pos, destination, &taken_tiles)
+    {
+        *path = new_path;
+        return;
+    }
+
+    let current_idx = tilepos_to_idx(current_tile_pos.x, current_tile_pos.y, tile_graph.length());
+    let side_step = tile_graph
+        .edges()
+        .0
+        .get(current_idx)
+        .into_iter()
+        .flatten()
+        .map(|neighbour_idx| idx_to_tilepos(*neighbour_idx, tile_graph.length()))
+        .find(|neighbour| !taken_tiles.contains(neighbour));
+
+    if let Some(new_path) =
+        side_step.and_then(|side_step| tile_graph.shortest_path(side_step, destination))
+    {
+        *path = new_path;
     }
 }
 
 pub fn update_movement_target(
-    mut moving_entity: Query<(
-        &mut Target,
-        &mut Path,
-        &Transform,
-        &mut Direction,
-        &GameEntityType,
-    )>,
+    mut moving_entity: Query<PathFollower>,
     map_information: Query<&TilemapSize>,
     graph_query: Query<&UndirectedGraph>,
+    mut occupancy: ResMut<OccupancyMap>,
+    time: Res<Time>,
 ) {
     if graph_query.is_empty() {
         return;
@@ -714,30 +1399,54 @@ pub fn update_movement_target(
         })
         .expect("Could not find largest world size. Is the map loaded?");
 
-    for (mut target, mut path, current_pos, mut direction, movement_type) in
-        moving_entity.iter_mut()
+    for (
+        entity,
+        mut target,
+        mut path,
+        current_pos,
+        starting_point,
+        mut direction,
+        movement_type,
+        mut blocked_timer,
+    ) in moving_entity.iter_mut()
     {
-        let tile_graph = if *movement_type == GameEntityType::Walk {
-            graph_query
-                .iter()
-                .find(|graph| graph.get_node_type() == &GraphType::Ground)
-                .expect("update_movement_target: Could not find Ground Graph")
-        } else if movement_type == &GameEntityType::Fly {
-            graph_query
-                .iter()
-                .find(|graph| graph.get_node_type() == &GraphType::Air)
-                .expect("update_movement_target: Could not find Air Graph")
-        } else {
-            graph_query
-                .iter()
-                .find(|graph| graph.get_node_type() == &GraphType::Water)
-                .expect("update_movement_target: Could not find Water Graph")
+        if target.0.is_some() || path.0.is_empty() {
+            continue;
+        }
+
+        let tile_graph = match graph_of(&graph_query, GraphType::for_movement(movement_type)) {
+            Ok(tile_graph) => tile_graph,
+            Err(path_error) => {
+                warn!("update_movement_target: Dropping Path, {path_error}.");
+                path.0.clear();
+                continue;
+            }
         };
 
-        if target.0.is_some() || path.0.is_empty() {
+        // Someone else has taken the next Tile, so wait for them
+        // to move along for a while before finding a way around.
+        let next_tile_pos = idx_to_tilepos(path.0[0], world_size.y);
+        if next_tile_pos != starting_point.1
+            && !occupancy.reserve(entity, *movement_type, next_tile_pos)
+        {
+            blocked_timer.tick(time.delta());
+            if blocked_timer.finished() {
+                blocked_timer.reset();
+                route_around_taken_tiles(
+                    entity,
+                    *movement_type,
+                    starting_point.1,
+                    &mut path,
+                    tile_graph,
+                    &occupancy,
+                );
+            }
+
             continue;
         }
 
+        blocked_timer.reset();
+
         let new_target = path
             .0
             .pop_front()
@@ -745,9 +1454,13 @@ pub fn update_movement_target(
         let target_tile_pos = idx_to_tilepos(new_target, world_size.y);
         let target_pos = tile_graph.get_node(new_target).unwrap();
 
-        if let Some(new_direction) =
-            get_direction(*current_pos, Transform::from_translation(*target_pos))
-        {
+        let diagonal_step =
+            starting_point.1.x != target_tile_pos.x && starting_point.1.y != target_tile_pos.y;
+        if let Some(new_direction) = get_direction(
+            *current_pos,
+            Transform::from_translation(*target_pos),
+            diagonal_step,
+        ) {
             *direction = new_direction;
         }
 
@@ -755,7 +1468,14 @@ pub fn update_movement_target(
     }
 }
 
-fn get_direction(current_pos: Transform, target_pos: Transform) -> Option<Direction> {
+/// Returns the heading faced when moving between two positions.
+/// Diagonal steps across Tiles move straight along the screen,
+/// while every other step moves along the Tiles' isometric axes.
+pub fn get_direction(
+    current_pos: Transform,
+    target_pos: Transform,
+    diagonal_step: bool,
+) -> Option<Direction> {
     let current_translation = &current_pos.translation;
     let target_translation = &target_pos.translation;
 
@@ -766,6 +1486,19 @@ fn get_direction(current_pos: Transform, target_pos: Transform) -> Option<Direct
     let x_direction = target_translation.x - current_translation.x;
     let y_direction = target_translation.y - current_translation.y;
 
+    if diagonal_step {
+        return match (
+            x_direction.abs() > y_direction.abs(),
+            x_direction.is_sign_positive(),
+            y_direction.is_sign_positive(),
+        ) {
+            (true, false, _) => Some(Direction::Left),
+            (true, true, _) => Some(Direction::Right),
+            (false, _, false) => Some(Direction::Bottom),
+            (false, _, true) => Some(Direction::Top),
+        };
+    }
+
     match (
         x_direction.is_sign_positive(),
         y_direction.is_sign_positive(),
@@ -777,55 +1510,6 @@ fn get_direction(current_pos: Transform, target_pos: Transform) -> Option<Direct
     }
 }
 
-const NUM_STEPS: f32 = 8.0;
-
-pub fn move_entities(
-    mut moving_entity: Query<(
-        &mut Transform,
-        &mut Target,
-        &mut MovementTimer,
-        &mut StartingPoint,
-        &mut TilePos,
-    )>,
-    time: Res<Time>,
-) {
-    for (mut current_pos, mut target, mut movement_timer, mut starting_point, mut tile_pos) in
-        &mut moving_entity
-    {
-        movement_timer.tick(time.delta());
-        if !movement_timer.just_finished() {
-            continue;
-        }
-
-        if target.0.is_none() {
-            continue;
-        }
-
-        let (target_vec, target_tile_pos) = target.0.expect("Target should be populated by now.");
-        let target_pos = Transform::from_translation(target_vec);
-
-        if *current_pos == target_pos {
-            *starting_point = StartingPoint(target_vec, target_tile_pos);
-            *tile_pos = target_tile_pos;
-            target.0 = None;
-            continue;
-        }
-
-        let x_dist = target_pos.translation.x - starting_point.0.x;
-        let x_step = x_dist / NUM_STEPS;
-
-        let y_dist = target_pos.translation.y - starting_point.0.y;
-        let y_step = y_dist / NUM_STEPS;
-
-        let z_dist = target_pos.translation.z - starting_point.0.z;
-        let z_step = z_dist / NUM_STEPS;
-
-        current_pos.translation.x += x_step;
-        current_pos.translation.y += y_step;
-        current_pos.translation.z += z_step;
-    }
-}
-
 pub fn update_current_tilepos(
     mut moving_entity: Query<(&mut TilePos, &StartingPoint), Changed<StartingPoint>>,
 ) {
@@ -834,7 +1518,7 @@ pub fn update_current_tilepos(
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub struct HeightedTilePos {
     xy: TilePos,
     z: u32,
@@ -881,12 +1565,9 @@ impl HeightedTilePos {
     }
 
     /// Returns the position in Pixels (Using a Transform) of the Heighted
-    /// Tile Position with respect to the size of the grid in pixels holding
-    /// this tile.
-    pub fn transform(&self, map_info: TiledMapInformation) -> Transform {
-        let tile_pos = self.xy;
-
-        to_bevy_transform(&tile_pos, map_info)
+    /// Tile Position on the laid out layer holding this tile.
+    pub fn transform(&self, map_layer: MapLayer) -> Transform {
+        tile_transform_on(&self.xy, map_layer)
     }
 
     /// Returns a new instance of a HeightedTilePos with its y axis flipped.
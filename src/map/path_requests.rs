@@ -0,0 +1,135 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use bevy::prelude::*;
+use bevy::tasks::{block_on, futures_lite::future, AsyncComputeTaskPool, Task};
+use bevy_ecs_tilemap::prelude::*;
+use thiserror::Error;
+
+use super::path_finding::{GraphType, Path, UndirectedGraph};
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum PathError {
+    #[error("no {0:?} Graph has been built yet")]
+    GraphMissing(GraphType),
+    #[error("Tile {0:?} lies outside of the Graph")]
+    OutOfBounds(TilePos),
+    #[error("Tile {0:?} cannot be stood on")]
+    StartNotOnGraph(TilePos),
+    #[error("no Path leads from Tile {start:?} to Tile {end:?}")]
+    Unreachable { start: TilePos, end: TilePos },
+}
+
+/// Asks for a Path between two Tiles to be found in the
+/// background on behalf of some entity.
+#[derive(Event, Clone, Debug)]
+pub struct PathRequest {
+    pub requester: Entity,
+    pub graph_type: GraphType,
+    pub start: TilePos,
+    pub end: TilePos,
+}
+
+impl PathRequest {
+    pub fn new(requester: Entity, graph_type: GraphType, start: TilePos, end: TilePos) -> Self {
+        Self {
+            requester,
+            graph_type,
+            start,
+            end,
+        }
+    }
+}
+
+/// The Path found for some Path Request, or why none was found.
+#[derive(Event, Clone, Debug)]
+pub struct PathResult {
+    pub request: PathRequest,
+    pub result: Result<Path, PathError>,
+}
+
+/// Whether some entity is still waiting on the
+/// Path it requested to be found.
+#[derive(Component, Default, Deref, DerefMut)]
+pub struct AwaitingPath(pub bool);
+
+/// A copy of each Undirected Graph that Paths
+/// are found on in the background.
+#[derive(Resource, Default)]
+pub struct SharedGraphs(HashMap<GraphType, Arc<UndirectedGraph>>);
+
+/// Every Path Request still being worked on in the background.
+#[derive(Resource, Default)]
+pub struct PendingPaths(Vec<(PathRequest, Task<Result<Path, PathError>>)>);
+
+/// Shares a copy of every newly built, or rebuilt, Undirected
+/// Graph with the background Path finding, no longer sharing
+/// those taken down, such as by switching scenes or reloading.
+pub fn share_graphs_for_path_requests(
+    changed_graphs: Query<&UndirectedGraph, Changed<UndirectedGraph>>,
+    live_graphs: Query<&UndirectedGraph>,
+    mut removed_graphs: RemovedComponents<UndirectedGraph>,
+    mut shared_graphs: ResMut<SharedGraphs>,
+) {
+    if removed_graphs.read().count() > 0 {
+        let live_graph_types = live_graphs
+            .iter()
+            .map(|graph| *graph.get_node_type())
+            .collect::<HashSet<GraphType>>();
+        shared_graphs
+            .0
+            .retain(|graph_type, _graph| live_graph_types.contains(graph_type));
+    }
+
+    for graph in &changed_graphs {
+        shared_graphs
+            .0
+            .insert(*graph.get_node_type(), Arc::new(graph.clone()));
+    }
+}
+
+/// Starts finding a Path in the background for each Path Request,
+/// failing straight away for Graphs that have yet to be built.
+pub fn solve_path_requests(
+    mut path_requests: EventReader<PathRequest>,
+    shared_graphs: Res<SharedGraphs>,
+    mut pending_paths: ResMut<PendingPaths>,
+    mut path_results: EventWriter<PathResult>,
+) {
+    let task_pool = AsyncComputeTaskPool::get();
+    for path_request in path_requests.read() {
+        let Some(graph) = shared_graphs.0.get(&path_request.graph_type) else {
+            path_results.send(PathResult {
+                request: path_request.clone(),
+                result: Err(PathError::GraphMissing(path_request.graph_type)),
+            });
+            continue;
+        };
+
+        let graph = Arc::clone(graph);
+        let (start, end) = (path_request.start, path_request.end);
+        let path_task = task_pool.spawn(async move { graph.find_path(start, end) });
+
+        pending_paths.0.push((path_request.clone(), path_task));
+    }
+}
+
+/// Sends out the result of every Path Request
+/// that has finished in the background.
+pub fn send_finished_path_results(
+    mut pending_paths: ResMut<PendingPaths>,
+    mut path_results: EventWriter<PathResult>,
+) {
+    pending_paths.0.retain_mut(|(path_request, path_task)| {
+        let Some(result) = block_on(future::poll_once(path_task)) else {
+            return true;
+        };
+
+        path_results.send(PathResult {
+            request: path_request.clone(),
+            result,
+        });
+
+        false
+    });
+}
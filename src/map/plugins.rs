@@ -2,21 +2,57 @@ use bevy::prelude::*;
 use bevy_ecs_tilemap::tiles::TilePos;
 
 use super::{
+    destinations::*,
+    hot_reload::*,
+    inspector::*,
+    layer_roles::LayerRoles,
+    links::*,
+    movement::*,
+    nav_overlay::*,
+    objects::*,
+    occupancy::*,
     path_finding::*,
+    path_requests::*,
+    scenes::*,
     tiled::{process_loaded_maps, spawn_map, TiledLoader, TiledMap},
 };
 use crate::map::camera::*;
 use crate::GameState;
 
+/// Requests the Streamer to head to some destination.
 #[derive(Event)]
 pub struct TilePosEvent {
-    pub destination: TilePos,
+    pub request: DestinationRequest,
+    /// Whether the request takes the place of every trip
+    /// still queued from the same source.
+    pub replaces_source: bool,
 }
 
 impl TilePosEvent {
     pub fn new(destination: TilePos) -> Self {
-        TilePosEvent { destination }
+        Self::from_request(DestinationRequest::new(destination))
     }
+
+    pub fn from_request(request: DestinationRequest) -> Self {
+        TilePosEvent {
+            request,
+            replaces_source: false,
+        }
+    }
+
+    pub fn replacing(request: DestinationRequest) -> Self {
+        TilePosEvent {
+            request,
+            replaces_source: true,
+        }
+    }
+}
+
+/// Takes back trips the Streamer has yet to head to.
+#[derive(Event, Clone, Debug)]
+pub enum CancelDestinationEvent {
+    Matching(DedupeKey),
+    From(DestinationSource),
 }
 
 #[derive(Default)]
@@ -24,16 +60,35 @@ pub struct PathFindingPlugin;
 
 impl Plugin for PathFindingPlugin {
     fn build(&self, app: &mut App) {
-        app.add_event::<TilePosEvent>().add_systems(
+        app.add_event::<TilePosEvent>();
+        app.init_resource::<PathFindingSettings>();
+        app.init_resource::<OccupancyMap>();
+        app.init_resource::<NavLinks>();
+        app.init_resource::<LayerRoles>();
+        app.init_resource::<SharedGraphs>();
+        app.init_resource::<PendingPaths>();
+        app.add_event::<PathRequest>();
+        app.add_event::<PathResult>();
+        app.add_event::<MapReloaded>();
+        app.add_event::<CancelDestinationEvent>().add_systems(
             Update,
             (
+                take_down_reloaded_graphs,
                 create_ground_graph,
                 create_air_graph,
                 create_water_graph,
                 insert_pathing_information,
+                update_occupancy_map.before(update_movement_target),
                 update_movement_target,
-                move_entities,
+                mark_link_crossings.after(update_movement_target),
+                move_entities.after(update_movement_target),
                 update_current_tilepos,
+                (
+                    share_graphs_for_path_requests,
+                    solve_path_requests,
+                    send_finished_path_results,
+                )
+                    .chain(),
             )
                 .run_if(in_state(GameState::InGame)),
         );
@@ -47,8 +102,26 @@ impl Plugin for TiledMapPlugin {
     fn build(&self, app: &mut bevy::prelude::App) {
         app.init_asset::<TiledMap>()
             .register_asset_loader(TiledLoader)
+            .init_resource::<MapScenes>()
+            .init_resource::<LayerRoles>()
+            .add_event::<SwitchSceneEvent>()
+            .add_event::<SceneSwitched>()
+            .add_event::<MapReloaded>()
+            .add_event::<ZoneEntered>()
+            .add_event::<ZoneLeft>()
             .add_systems(Startup, spawn_map)
-            .add_systems(Update, process_loaded_maps);
+            .add_systems(
+                Update,
+                (
+                    process_loaded_maps,
+                    trigger_zones,
+                    place_map_props,
+                    replace_prop_sprites.after(place_map_props),
+                    request_next_scene,
+                    switch_scene.after(request_next_scene),
+                    load_switched_scene_map.after(switch_scene),
+                ),
+            );
     }
 }
 
@@ -57,7 +130,55 @@ pub struct TiledCameraPlugin;
 
 impl Plugin for TiledCameraPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Startup, spawn_camera)
-            .add_systems(Update, movement.run_if(in_state(GameState::InGame)));
+        app.add_systems(Startup, spawn_camera).add_systems(
+            Update,
+            (
+                center_camera_on_map,
+                movement.run_if(in_state(GameState::InGame)),
+            ),
+        );
+    }
+}
+
+/// Inspects whichever Tile is clicked on, shows or hides layers,
+/// and tests Paths between Tiles, for the Inspected Map.
+#[derive(Default)]
+pub struct MapInspectorPlugin;
+
+impl Plugin for MapInspectorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MapInspector>()
+            .add_systems(Startup, spawn_inspector_panel)
+            .add_systems(
+                Update,
+                (
+                    (inspect_clicked_tile, toggle_layers, test_path),
+                    (hide_layers, draw_inspection, update_inspector_panel),
+                )
+                    .chain()
+                    .run_if(in_state(GameState::InGame)),
+            );
+    }
+}
+
+/// Draws every Undirected Graph, and where everyone is headed
+/// along them, over the map whenever toggled on.
+#[derive(Default)]
+pub struct NavOverlayPlugin;
+
+impl Plugin for NavOverlayPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<NavOverlay>()
+            .add_systems(Startup, spawn_nav_tooltip)
+            .add_systems(
+                Update,
+                (
+                    toggle_nav_overlay,
+                    draw_graphs,
+                    draw_entity_paths,
+                    update_nav_tooltip,
+                )
+                    .run_if(in_state(GameState::InGame)),
+            );
     }
 }
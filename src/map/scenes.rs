@@ -0,0 +1,292 @@
+use std::collections::VecDeque;
+
+use bevy::log;
+use bevy::prelude::*;
+use bevy_ecs_tilemap::prelude::*;
+
+use crate::entities::critter::CritterLabel;
+use crate::entities::crop::CropState;
+use crate::entities::fruit::FruitState;
+use crate::entities::raider::RaiderLabel;
+use crate::visual::environment::CampfireLabel;
+
+use super::destinations::DestinationQueue;
+use super::movement::StepProgress;
+use super::objects::MapObject;
+use super::path_finding::{Path, SpawnPoint, StartingPoint, Target, UndirectedGraph};
+use super::tiled::{TiledMap, TiledMapBundle};
+
+/// The key pressed to switch over to the next Map Scene.
+pub const NEXT_SCENE_KEY: KeyCode = KeyCode::F4;
+
+/// The chat command the Streamer sends to switch to a Map Scene by name.
+pub const SCENE_COMMAND: &str = "!scene";
+
+/// The chat command the Streamer sends when the stream's activity changes.
+pub const ACTIVITY_COMMAND: &str = "!activity";
+
+/// One of the maps the stream can be set in, along with
+/// where the Streamer spends their time within it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MapScene {
+    pub name: String,
+    /// The Tiled map, relative to the assets folder.
+    pub map_path: String,
+    /// The stream activities, such as "Just Chatting",
+    /// that the stream is set in this Map Scene for.
+    pub activities: Vec<String>,
+    /// Where the Streamer appears, and heads back to when away.
+    pub streamer_spawn: TilePos,
+    /// Where the Streamer heads to once online.
+    pub online_spot: TilePos,
+}
+
+impl MapScene {
+    /// Returns whether the stream is set in this Map
+    /// Scene for some activity, whatever its casing.
+    pub fn is_for_activity(&self, activity: &str) -> bool {
+        self.activities
+            .iter()
+            .any(|scene_activity| scene_activity.eq_ignore_ascii_case(activity.trim()))
+    }
+}
+
+/// Every Map Scene the stream can be set in, and
+/// which of them the stream is set in right now.
+#[derive(Resource, Debug, Clone)]
+pub struct MapScenes {
+    scenes: Vec<MapScene>,
+    active: usize,
+}
+
+impl Default for MapScenes {
+    fn default() -> Self {
+        Self {
+            scenes: vec![
+                MapScene {
+                    name: String::from("Coding"),
+                    map_path: String::from("TM_map.tmx"),
+                    activities: vec![
+                        String::from("Coding"),
+                        String::from("Software and Game Development"),
+                    ],
+                    streamer_spawn: TilePos::new(39, 40),
+                    online_spot: TilePos::new(41, 49),
+                },
+                // A closer look at the campfire corner of the
+                // island, for when there is only chatting to do.
+                MapScene {
+                    name: String::from("Just Chatting"),
+                    map_path: String::from("campfire_cove.tmx"),
+                    activities: vec![String::from("Just Chatting")],
+                    streamer_spawn: TilePos::new(17, 8),
+                    online_spot: TilePos::new(19, 17),
+                },
+            ],
+            active: 0,
+        }
+    }
+}
+
+impl MapScenes {
+    /// Returns the Map Scene the stream is set in right now.
+    pub fn active(&self) -> &MapScene {
+        &self.scenes[self.active]
+    }
+
+    /// Returns every Map Scene, in the order they are switched between.
+    pub fn scenes(&self) -> &[MapScene] {
+        &self.scenes
+    }
+
+    /// Sets the stream in the Map Scene going by some name before its
+    /// map has been brought in, returning whether there is one.
+    pub fn start_in(&mut self, name: &str) -> bool {
+        let Some(scene_idx) = self.resolve(&SwitchSceneEvent::Named(name.to_string())) else {
+            return false;
+        };
+
+        self.active = scene_idx;
+        true
+    }
+
+    /// Adds another Map Scene to switch to, taking the place
+    /// of any Map Scene already going by the same name.
+    pub fn register(&mut self, scene: MapScene) {
+        match self
+            .scenes
+            .iter_mut()
+            .find(|known_scene| known_scene.name == scene.name)
+        {
+            Some(known_scene) => *known_scene = scene,
+            None => self.scenes.push(scene),
+        }
+    }
+
+    /// Returns which Map Scene some request switches over
+    /// to, or None when no Map Scene matches it.
+    pub fn resolve(&self, request: &SwitchSceneEvent) -> Option<usize> {
+        match request {
+            SwitchSceneEvent::Named(name) => self
+                .scenes
+                .iter()
+                .position(|scene| scene.name.eq_ignore_ascii_case(name.trim())),
+            SwitchSceneEvent::ForActivity(activity) => self
+                .scenes
+                .iter()
+                .position(|scene| scene.is_for_activity(activity)),
+            SwitchSceneEvent::Next => Some((self.active + 1) % self.scenes.len()),
+        }
+    }
+}
+
+/// Asks for the stream to be set in another Map Scene.
+#[derive(Event, Clone, Debug, PartialEq)]
+pub enum SwitchSceneEvent {
+    Named(String),
+    /// Picks whichever Map Scene is for the stream's activity.
+    ForActivity(String),
+    Next,
+}
+
+impl SwitchSceneEvent {
+    /// Returns the request some chat command asks for, such as
+    /// "!activity Just Chatting", or None for any other message.
+    pub fn from_command(msg: &str) -> Option<Self> {
+        let (command, argument) = msg.trim().split_once(char::is_whitespace)?;
+        let argument = argument.trim().to_string();
+
+        match command {
+            SCENE_COMMAND => Some(SwitchSceneEvent::Named(argument)),
+            ACTIVITY_COMMAND => Some(SwitchSceneEvent::ForActivity(argument)),
+            _ => None,
+        }
+    }
+}
+
+/// Tells everyone the old map has been taken down, and
+/// the map of some Map Scene is on its way in.
+#[derive(Event, Clone, Debug)]
+pub struct SceneSwitched {
+    pub name: String,
+}
+
+/// Everything about some entity reset once they have
+/// made it over to the map of a newly switched Map Scene.
+#[derive(Bundle)]
+pub struct SceneArrival {
+    transform: Transform,
+    tile_pos: TilePos,
+    spawn_point: SpawnPoint,
+    starting_point: StartingPoint,
+    target: Target,
+    path: Path,
+    destinations: DestinationQueue,
+    step_progress: StepProgress,
+}
+
+impl SceneArrival {
+    /// Returns everything needed to stand some entity on a
+    /// Tile of the new map, with nowhere left to head to.
+    pub fn at(transform: Transform, tile_pos: TilePos) -> Self {
+        Self {
+            transform,
+            tile_pos,
+            spawn_point: SpawnPoint(tile_pos),
+            starting_point: StartingPoint(transform.translation, tile_pos),
+            target: Target(None),
+            path: Path(VecDeque::new()),
+            destinations: DestinationQueue::default(),
+            step_progress: StepProgress::default(),
+        }
+    }
+}
+
+/// Anything making up the map itself, along with
+/// the Undirected Graphs and Map Objects built from it.
+type MapPiece = Or<(
+    With<TilemapId>,
+    With<TilemapType>,
+    With<Handle<TiledMap>>,
+    With<UndirectedGraph>,
+    With<MapObject>,
+)>;
+
+/// Anything placed by the map that only belongs to it,
+/// and so is left behind when switching to another.
+type LeftBehind = Or<(
+    With<FruitState>,
+    With<CropState>,
+    With<CampfireLabel>,
+    With<RaiderLabel>,
+    With<CritterLabel>,
+)>;
+
+/// Asks for the next Map Scene whenever its key is pressed.
+pub fn request_next_scene(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut switch_scene_writer: EventWriter<SwitchSceneEvent>,
+) {
+    if keyboard_input.just_pressed(NEXT_SCENE_KEY) {
+        switch_scene_writer.send(SwitchSceneEvent::Next);
+    }
+}
+
+/// Takes down the map, its Graphs, and everything left behind with
+/// it, whenever another Map Scene is asked for. The Streamer and
+/// Visitors stay around, moving onto the new map once it is in.
+pub fn switch_scene(
+    mut switch_scene_reader: EventReader<SwitchSceneEvent>,
+    mut scene_switched_writer: EventWriter<SceneSwitched>,
+    mut map_scenes: ResMut<MapScenes>,
+    map_pieces: Query<Entity, MapPiece>,
+    left_behind: Query<Entity, LeftBehind>,
+    mut commands: Commands,
+) {
+    // Only the latest request matters when several come in at once.
+    let Some(request) = switch_scene_reader.read().last() else {
+        return;
+    };
+
+    let Some(scene_idx) = map_scenes.resolve(request) else {
+        log::warn!("No Map Scene matches {request:?}.");
+        return;
+    };
+    if scene_idx == map_scenes.active {
+        return;
+    }
+
+    for entity in map_pieces.iter().chain(&left_behind) {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    map_scenes.active = scene_idx;
+    let scene_name = map_scenes.active().name.clone();
+    log::info!("Switching to the {scene_name} Map Scene.");
+    scene_switched_writer.send(SceneSwitched { name: scene_name });
+}
+
+/// Brings in the map of the Map Scene being switched to.
+pub fn load_switched_scene_map(
+    mut scene_switched_reader: EventReader<SceneSwitched>,
+    map_scenes: Res<MapScenes>,
+    commands: Commands,
+    asset_server: Res<AssetServer>,
+) {
+    if scene_switched_reader.read().last().is_none() {
+        return;
+    }
+
+    spawn_scene_map(map_scenes.active(), commands, &asset_server);
+}
+
+/// Spawns the map of some Map Scene, ready to be
+/// filled in with Tiles once it has loaded.
+pub fn spawn_scene_map(scene: &MapScene, mut commands: Commands, asset_server: &AssetServer) {
+    let map_handle: Handle<TiledMap> = asset_server.load(scene.map_path.clone());
+
+    commands.spawn(TiledMapBundle {
+        tiled_map: map_handle,
+        ..Default::default()
+    });
+}
@@ -0,0 +1,141 @@
+use bevy::prelude::*;
+
+use super::path_finding::GraphType;
+
+/// The name of the Tiled tile property holding a Tile's Terrain.
+pub const TERRAIN_PROPERTY: &str = "terrain";
+
+/// The name of the Tiled tile property holding a Tile's Slope.
+pub const SLOPE_PROPERTY: &str = "slope";
+
+/// The least it can ever cost to step onto a Tile, keeping
+/// the distance estimate used while path finding from ever
+/// overestimating.
+pub const MIN_MOVEMENT_COST: u32 = 1;
+
+/// What some Tile is made of, which decides how long
+/// it takes to get across it.
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Terrain {
+    Path,
+    #[default]
+    Plain,
+    TallGrass,
+    ShallowWater,
+}
+
+impl Terrain {
+    /// Returns the Terrain named by a Tiled tile property value.
+    pub fn from_name(terrain_name: &str) -> Option<Self> {
+        match terrain_name.trim().to_lowercase().as_str() {
+            "path" => Some(Terrain::Path),
+            "plain" => Some(Terrain::Plain),
+            "tall_grass" => Some(Terrain::TallGrass),
+            "shallow_water" => Some(Terrain::ShallowWater),
+            _ => None,
+        }
+    }
+
+    /// Returns the Terrain recorded in the custom properties
+    /// of a Tiled tile, or Plain when none was recorded.
+    pub fn from_properties(properties: &tiled::Properties) -> Self {
+        match properties.get(TERRAIN_PROPERTY) {
+            Some(tiled::PropertyValue::StringValue(terrain_name)) => {
+                Terrain::from_name(terrain_name).unwrap_or_default()
+            }
+            _ => Terrain::default(),
+        }
+    }
+
+    /// Returns how much it costs to step onto this Terrain
+    /// for anything getting around the given Graph.
+    pub fn movement_cost(&self, graph_type: &GraphType) -> u32 {
+        match graph_type {
+            GraphType::Ground => match self {
+                Terrain::Path => MIN_MOVEMENT_COST,
+                Terrain::Plain => 2,
+                Terrain::TallGrass => 4,
+                Terrain::ShallowWater => 8,
+            },
+            // Nothing slows down flying over or swimming through a Tile.
+            GraphType::Air | GraphType::Water => MIN_MOVEMENT_COST,
+        }
+    }
+}
+
+/// How some Tile joins up with neighbouring Tiles found at
+/// other heights, beyond stepping a single level up or down.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Slope {
+    /// Climbs up or down as many as three levels in one step.
+    Stairs,
+    /// Climbs up or down as many as two levels in one step.
+    Ramp,
+    /// Drops down any number of levels in one step, with
+    /// no way of climbing back up the same way.
+    Ledge,
+}
+
+impl Slope {
+    /// Returns the Slope named by a Tiled tile property value.
+    pub fn from_name(slope_name: &str) -> Option<Self> {
+        match slope_name.trim().to_lowercase().as_str() {
+            "stairs" => Some(Slope::Stairs),
+            "ramp" => Some(Slope::Ramp),
+            "ledge" => Some(Slope::Ledge),
+            _ => None,
+        }
+    }
+
+    /// Returns the Slope recorded in the custom properties
+    /// of a Tiled tile, if any was recorded.
+    pub fn from_properties(properties: &tiled::Properties) -> Option<Self> {
+        match properties.get(SLOPE_PROPERTY) {
+            Some(tiled::PropertyValue::StringValue(slope_name)) => Slope::from_name(slope_name),
+            _ => None,
+        }
+    }
+
+    /// Returns how many levels can be climbed, whether
+    /// up or down, in a single step along this Slope.
+    pub fn max_rise(&self) -> u32 {
+        match self {
+            Slope::Stairs => 3,
+            Slope::Ramp => 2,
+            Slope::Ledge => 1,
+        }
+    }
+}
+
+/// Returns whether a walker can step between two neighbouring
+/// Tiles standing at the given heights, where height 0 is the
+/// water underneath everything. Water is only ever waded into
+/// from the shore, never crossed from one water Tile to the next.
+pub fn can_walk_between(
+    from_height: u32,
+    from_slope: Option<&Slope>,
+    to_height: u32,
+    to_slope: Option<&Slope>,
+) -> bool {
+    if from_height == 0 && to_height == 0 {
+        return false;
+    }
+
+    let rise = from_height.abs_diff(to_height);
+    if rise <= 1 {
+        return true;
+    }
+
+    if from_height == 0 || to_height == 0 {
+        return false;
+    }
+
+    if to_height < from_height && from_slope == Some(&Slope::Ledge) {
+        return true;
+    }
+
+    [from_slope, to_slope]
+        .into_iter()
+        .flatten()
+        .any(|slope| *slope != Slope::Ledge && rise <= slope.max_rise())
+}
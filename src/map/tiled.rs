@@ -1,16 +1,11 @@
-// How to use this:
-//   You should copy/paste this into your project and use it much like examples/tiles.rs uses this
-//   file. When you do so you will need to adjust the code based on whether you're using the
-//   'atlas` feature in bevy_ecs_tilemap. The bevy_ecs_tilemap uses this as an example of how to
-//   use both single image tilesets and image collection tilesets. Since your project won't have
-//   the 'atlas' feature defined in your Cargo config, the expressions prefixed by the #[cfg(...)]
-//   macro will not compile in your project as-is. If your project depends on the bevy_ecs_tilemap
-//   'atlas' feature then move all of the expressions prefixed by #[cfg(not(feature = "atlas"))].
-//   Otherwise remove all of the expressions prefixed by #[cfg(feature = "atlas")].
+// Lays out Tiled maps loaded as assets, spawning an entity for each Tile
+// and each layer, then draws every Tile through the Tilemap, the same as
+// the map viewer does. Whatever stands on the map is placed with the same
+// coordinates the Tilemap draws its Tiles with.
 //
 // Functional limitations:
-//   * When the 'atlas' feature is enabled tilesets using a collection of images will be skipped.
-//   * Only finite tile layers are loaded. Infinite tile layers and object layers will be skipped.
+//   * Only finite tile layers are loaded. Infinite tile layers will be skipped.
+//   * Object layers are not drawn, but their objects are spawned as Map Objects instead.
 use tiled::Loader;
 
 use std::io::{Cursor, ErrorKind};
@@ -18,27 +13,32 @@ use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use bevy::{
-    asset::{io::Reader, AssetLoader, AssetPath, AsyncReadExt},
+    asset::{io::Reader, AssetLoader, AssetPath, AsyncReadExt, LoadContext},
     log,
     prelude::*,
     reflect::TypePath,
+    tasks::block_on,
     utils::HashMap,
 };
 use bevy_ecs_tilemap::prelude::*;
 
 use thiserror::Error;
 
-pub fn spawn_map(mut commands: Commands, asset_server: Res<AssetServer>) {
-    let map_handle: Handle<TiledMap> = asset_server.load("TM_map.tmx");
-
-    commands.spawn(TiledMapBundle {
-        tiled_map: map_handle,
-        render_settings: TilemapRenderSettings {
-            render_chunk_size: UVec2::new(1280, 1),
-            y_sort: true,
-        },
-        ..Default::default()
-    });
+use super::hot_reload::MapReloaded;
+use super::layer_roles::LayerRoles;
+use super::links::NavLinks;
+use super::objects::{MapObject, MapObjects};
+use super::scenes::{spawn_scene_map, MapScenes};
+use super::terrain::{Slope, Terrain};
+use super::tilemap::{
+    convert_tilemap_to_bevy_render_tiles, layer_origin, normalize_path, to_bevy_path,
+    TileDimensions, TileGridCoordinates, Tilemap,
+};
+use crate::visual::tile_animations::TileAnimation;
+
+/// Spawns the map of whichever Map Scene the stream starts out in.
+pub fn spawn_map(commands: Commands, asset_server: Res<AssetServer>, map_scenes: Res<MapScenes>) {
+    spawn_scene_map(map_scenes.active(), commands, &asset_server);
 }
 
 #[derive(Default)]
@@ -48,6 +48,7 @@ impl Plugin for TiledMapPlugin {
     fn build(&self, app: &mut bevy::prelude::App) {
         app.init_asset::<TiledMap>()
             .register_asset_loader(TiledLoader)
+            .add_event::<MapReloaded>()
             .add_systems(Update, process_loaded_maps);
     }
 }
@@ -55,11 +56,9 @@ impl Plugin for TiledMapPlugin {
 #[derive(TypePath, Asset)]
 pub struct TiledMap {
     pub map: tiled::Map,
-
-    pub tilemap_textures: HashMap<usize, TilemapTexture>,
-
-    // The offset into the tileset_images for each tile id within each tileset.
-    pub tile_image_offsets: HashMap<(usize, tiled::TileId), u32>,
+    /// Every image the map is drawn with, kept loaded along with it.
+    #[dependency]
+    pub images: Vec<Handle<Image>>,
 }
 
 // Stores a list of tiled layers.
@@ -68,7 +67,7 @@ pub struct TiledLayersStorage {
     pub storage: HashMap<u32, Entity>,
 }
 
-// Stores the Layer number for some associated Tile.
+// Stores the Layer number for some associated Tile, or for the layer itself.
 #[derive(Component, Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord)]
 pub struct LayerNumber(pub usize);
 
@@ -78,28 +77,50 @@ pub struct TiledMapBundle {
     pub storage: TiledLayersStorage,
     pub transform: Transform,
     pub global_transform: GlobalTransform,
-    pub render_settings: TilemapRenderSettings,
 }
 
-struct BytesResourceReader {
-    bytes: Arc<[u8]>,
+/// Reads the Tiled map itself from the bytes already read for it, and whatever
+/// it refers to, such as external tilesets, through Bevy's asset reader.
+struct AssetResourceReader<'a, 'b> {
+    map_path: PathBuf,
+    map_bytes: Arc<[u8]>,
+    load_context: &'a mut LoadContext<'b>,
 }
 
-impl BytesResourceReader {
-    fn new(bytes: &[u8]) -> Self {
+impl<'a, 'b> AssetResourceReader<'a, 'b> {
+    fn new(map_bytes: &[u8], load_context: &'a mut LoadContext<'b>) -> Self {
         Self {
-            bytes: Arc::from(bytes),
+            map_path: normalize_path(load_context.path()),
+            map_bytes: Arc::from(map_bytes),
+            load_context,
         }
     }
 }
 
-impl tiled::ResourceReader for BytesResourceReader {
+impl tiled::ResourceReader for AssetResourceReader<'_, '_> {
     type Resource = Cursor<Arc<[u8]>>;
     type Error = std::io::Error;
 
-    fn read_from(&mut self, _path: &Path) -> std::result::Result<Self::Resource, Self::Error> {
-        // In this case, the path is ignored because the byte data is already provided.
-        Ok(Cursor::new(self.bytes.clone()))
+    fn read_from(&mut self, path: &Path) -> std::result::Result<Self::Resource, Self::Error> {
+        // NOTE: Tiled asks for everything relative to the map,
+        // such as "maps/../tilesets/terrain.tsx".
+        let path = normalize_path(path);
+        if path == self.map_path {
+            return Ok(Cursor::new(self.map_bytes.clone()));
+        }
+
+        // NOTE: Reading through the Load Context records what was read as
+        // a dependency of the map, so editing it reloads the map as well.
+        let asset_path = AssetPath::from(path.clone())
+            .with_source(self.load_context.asset_path().source().clone_owned());
+        let bytes = block_on(self.load_context.read_asset_bytes(asset_path)).map_err(|e| {
+            std::io::Error::new(
+                ErrorKind::NotFound,
+                format!("Could not read {}: {e}", path.display()),
+            )
+        })?;
+
+        Ok(Cursor::new(Arc::from(bytes)))
     }
 }
 
@@ -123,74 +144,34 @@ impl AssetLoader for TiledLoader {
         &'a self,
         reader: &'a mut Reader<'_>,
         _settings: &'a Self::Settings,
-        load_context: &'a mut bevy::asset::LoadContext<'_>,
+        load_context: &'a mut LoadContext<'_>,
     ) -> Result<Self::Asset, Self::Error> {
         let mut bytes = Vec::new();
         reader.read_to_end(&mut bytes).await?;
 
+        let map_path = load_context.path().to_path_buf();
         let mut loader = tiled::Loader::with_cache_and_reader(
             tiled::DefaultResourceCache::new(),
-            BytesResourceReader::new(&bytes),
+            AssetResourceReader::new(&bytes, load_context),
         );
-        let map = loader.load_tmx_map(load_context.path()).map_err(|e| {
+        let map = loader.load_tmx_map(&map_path).map_err(|e| {
             std::io::Error::new(ErrorKind::Other, format!("Could not load TMX map: {e}"))
         })?;
 
-        let mut dependencies = Vec::new();
-        let mut tilemap_textures = HashMap::default();
-        let mut tile_image_offsets = HashMap::default();
-
-        for (tileset_index, tileset) in map.tilesets().iter().enumerate() {
-            let tilemap_texture = match &tileset.image {
-                None => {
-                    {
-                        let mut tile_images: Vec<Handle<Image>> = Vec::new();
-                        for (tile_id, tile) in tileset.tiles() {
-                            if let Some(img) = &tile.image {
-                                // The load context path is the TMX file itself. If the file is at the root of the
-                                // assets/ directory structure then the tmx_dir will be empty, which is fine.
-                                let tmx_dir = load_context
-                                    .path()
-                                    .parent()
-                                    .expect("The asset load context was empty.");
-                                let tile_path = tmx_dir.join(&img.source);
-                                let asset_path = AssetPath::from(tile_path);
-                                log::info!("Loading tile image from {asset_path:?} as image ({tileset_index}, {tile_id})");
-                                let texture: Handle<Image> = load_context.load(asset_path.clone());
-                                tile_image_offsets
-                                    .insert((tileset_index, tile_id), tile_images.len() as u32);
-                                tile_images.push(texture.clone());
-                                dependencies.push(asset_path);
-                            }
-                        }
-
-                        TilemapTexture::Vector(tile_images)
-                    }
-                }
-                Some(img) => {
-                    // The load context path is the TMX file itself. If the file is at the root of the
-                    // assets/ directory structure then the tmx_dir will be empty, which is fine.
-                    let tmx_dir = load_context
-                        .path()
-                        .parent()
-                        .expect("The asset load context was empty.");
-                    let tile_path = tmx_dir.join(&img.source);
-                    let asset_path = AssetPath::from(tile_path);
-                    let texture: Handle<Image> = load_context.load(asset_path.clone());
-                    dependencies.push(asset_path);
-
-                    TilemapTexture::Single(texture.clone())
-                }
-            };
-
-            tilemap_textures.insert(tileset_index, tilemap_texture);
+        // NOTE: Load every image the map is drawn with alongside it, whether
+        // a whole tileset or a single Tile from a collection of images.
+        let mut images = Vec::new();
+        for tileset in map.tilesets() {
+            let tileset_image = tileset.image.iter().map(|image| image.source.clone());
+            let tile_images = tileset
+                .tiles()
+                .filter_map(|(_, tile)| tile.image.as_ref().map(|image| image.source.clone()));
+            for image_source in tileset_image.chain(tile_images) {
+                images.push(load_context.load(to_bevy_path(image_source)));
+            }
         }
 
-        let asset_map = TiledMap {
-            map,
-            tilemap_textures,
-            tile_image_offsets,
-        };
+        let asset_map = TiledMap { map, images };
 
         log::info!("Loaded map: {}", load_context.path().display());
         Ok(asset_map)
@@ -208,52 +189,242 @@ pub fn convert_tiled_to_bevy_pos(tile_pos: TilePos, width: u32) -> TilePos {
     TilePos::new(tile_pos.x, mapped_y)
 }
 
-pub struct TiledMapInformation<'a> {
-    grid_size: &'a TilemapGridSize,
-    map_size: &'a TilemapSize,
-    map_type: &'a TilemapType,
-    map_transform: &'a Transform,
+/// Where some laid out layer of the map sits and how its Tiles are sized,
+/// found by its number within the Tiled map.
+pub type MapLayer<'a> = (
+    &'a LayerNumber,
+    &'a TilemapGridSize,
+    &'a TilemapSize,
+    &'a Transform,
+);
+
+/// Returns the laid out layer with some number within the Tiled map, if any.
+pub fn find_layer<'a>(
+    map_layers: impl IntoIterator<Item = MapLayer<'a>>,
+    layer_number: usize,
+) -> Option<MapLayer<'a>> {
+    map_layers
+        .into_iter()
+        .find(|(map_layer_number, ..)| map_layer_number.0 == layer_number)
+}
+
+/// Returns where the center of some Tile is within its layer, the
+/// same as where the Tilemap draws the Tile in that spot.
+pub fn tile_center_in_world(
+    tile_pos: &TilePos,
+    grid_size: &TilemapGridSize,
+    map_size: &TilemapSize,
+) -> Vec2 {
+    tile_grid_coordinates_of(tile_pos, map_size)
+        .to_world_center(&tile_dimensions_of(grid_size), map_size.y as usize)
+}
+
+/// Returns where whatever stands on some Tile of a layer placed at some
+/// origin is placed, the same as where the Tilemap draws the Tile.
+pub fn tile_transform(
+    tile_pos: &TilePos,
+    grid_size: &TilemapGridSize,
+    map_size: &TilemapSize,
+    layer_origin: &Transform,
+) -> Transform {
+    tile_grid_coordinates_of(tile_pos, map_size).to_world_transform(
+        &tile_dimensions_of(grid_size),
+        map_size.y as usize,
+        layer_origin,
+    )
 }
 
-impl<'a> TiledMapInformation<'a> {
-    pub fn new(
-        grid_size: &'a TilemapGridSize,
-        map_size: &'a TilemapSize,
-        map_type: &'a TilemapType,
-        map_transform: &'a Transform,
-    ) -> Self {
-        TiledMapInformation {
+/// Returns where whatever stands on some Tile of a laid out layer is placed.
+pub fn tile_transform_on(tile_pos: &TilePos, map_layer: MapLayer) -> Transform {
+    let (_, grid_size, map_size, layer_origin) = map_layer;
+
+    tile_transform(tile_pos, grid_size, map_size, layer_origin)
+}
+
+/// Returns where some Tile is within the Tilemap.
+fn tile_grid_coordinates_of(tile_pos: &TilePos, map_size: &TilemapSize) -> TileGridCoordinates {
+    // NOTE: The Tilemap follows Tiled, where the y-axis points down.
+    let tiled_pos = convert_tiled_to_bevy_pos(*tile_pos, map_size.y);
+
+    TileGridCoordinates::new(tiled_pos.x as usize, tiled_pos.y as usize)
+}
+
+/// Returns how big each Tile of some layer is within the Tilemap.
+fn tile_dimensions_of(grid_size: &TilemapGridSize) -> TileDimensions {
+    TileDimensions::new(grid_size.x as usize, grid_size.y as usize)
+}
+
+/// Every Tile and layer spawned for some Tiled map.
+#[derive(Default)]
+pub struct LaidOutMap {
+    /// Each layer, by its number within the Tiled map.
+    pub layers: HashMap<u32, Entity>,
+    /// Each Tile, by where it is within the Tiled map.
+    pub tiles: HashMap<TileGridCoordinates, Entity>,
+}
+
+/// Returns how the Tiles of some Tiled map are arranged.
+fn map_type_of(tiled_map: &tiled::Map) -> TilemapType {
+    match tiled_map.orientation {
+        tiled::Orientation::Hexagonal => TilemapType::Hexagon(HexCoordSystem::Row),
+        tiled::Orientation::Isometric => TilemapType::Isometric(IsoCoordSystem::Diamond),
+        tiled::Orientation::Staggered => TilemapType::Isometric(IsoCoordSystem::Staggered),
+        tiled::Orientation::Orthogonal => TilemapType::Square,
+    }
+}
+
+/// Spawns an entity for every tile layer of some Tiled map, holding where the
+/// layer is placed, along with an entity for each of its Tiles, holding where the Tile
+/// is, which layer it is on, and what it is made of. Nothing is drawn yet.
+pub fn lay_out_tiled_map(commands: &mut Commands, tiled_map: &tiled::Map) -> LaidOutMap {
+    let mut laid_out_map = LaidOutMap::default();
+
+    // NOTE: Capture the size of the current Tile Map,
+    // where Width and Height are measured in Number
+    // of Tiles.
+    let map_size = TilemapSize {
+        x: tiled_map.width,
+        y: tiled_map.height,
+    };
+
+    // NOTE: Capture the maximum size of each Tile from
+    // the current Tile Map in pixels.
+    let grid_size = TilemapGridSize {
+        x: tiled_map.tile_width as f32,
+        y: tiled_map.tile_height as f32,
+    };
+    let map_type = map_type_of(tiled_map);
+
+    for (layer_index, layer) in tiled_map.layers().enumerate() {
+        // NOTE: Filter for all Finite Tile Layers.
+        let Some(tiled::TileLayer::Finite(layer_data)) = layer.as_tile_layer() else {
+            log::info!(
+                "Skipping layer {} because only finite tile layers are supported.",
+                layer.id()
+            );
+            continue;
+        };
+
+        // NOTE: Hold references to each newly created Tile
+        // from this current Layer.
+        let mut tile_storage = TileStorage::empty(map_size);
+        let layer_entity = commands.spawn_empty().id();
+
+        for x in 0..map_size.x {
+            for y in 0..map_size.y {
+                // Transform TMX coords into bevy coords.
+                //
+                // NOTE: Flip the Y-Axis from what Bevy expects
+                // to coordinate with Tiled handles coordinates.
+                let mapped_y = tiled_map.height - 1 - y;
+
+                // NOTE: Pull the Tile from the Tiled Map.
+                let Some(layer_tile) = layer_data.get_tile(x as i32, mapped_y as i32) else {
+                    continue;
+                };
+
+                // NOTE: Pull what the Layer Tile is made of
+                // from its Tiled tile properties.
+                let tile = layer_tile.get_tile();
+                let terrain = tile
+                    .as_ref()
+                    .map(|tile| Terrain::from_properties(&tile.properties))
+                    .unwrap_or_default();
+                let slope = tile
+                    .as_ref()
+                    .and_then(|tile| Slope::from_properties(&tile.properties));
+                // NOTE: Play whatever animation the Tiled
+                // tileset gives the Layer Tile. Tiles from a collection
+                // of images are kept still, since each frame is an image
+                // of its own rather than part of one spritesheet.
+                let tile_animation = tile
+                    .as_ref()
+                    .filter(|_| layer_tile.get_tileset().image.is_some())
+                    .and_then(|tile| TileAnimation::from_tile(tile, Some));
+
+                // NOTE: Spawn the Layer Tile in Bevy Coordinates.
+                let tile_pos = TilePos { x, y };
+                let mut tile_commands = commands.spawn((
+                    tile_pos,
+                    TilemapId(layer_entity),
+                    TileTextureIndex(layer_tile.id()),
+                    LayerNumber(layer_index),
+                    terrain,
+                ));
+                if let Some(slope) = slope {
+                    tile_commands.insert(slope);
+                }
+                if let Some(tile_animation) = tile_animation {
+                    tile_commands.insert(tile_animation);
+                }
+
+                // NOTE: Record the recently spawned Layer Tile
+                // into Tile Storage.
+                let tile_entity = tile_commands.id();
+                tile_storage.set(&tile_pos, tile_entity);
+                laid_out_map.tiles.insert(
+                    TileGridCoordinates::new_3d(x as usize, mapped_y as usize, layer_index),
+                    tile_entity,
+                );
+            }
+        }
+
+        // NOTE: Spawn the Tiled Map Layer as a whole.
+        commands.entity(layer_entity).insert((
+            LayerNumber(layer_index),
             grid_size,
             map_size,
             map_type,
-            map_transform,
-        }
+            tile_storage,
+            SpatialBundle::from_transform(layer_origin(tiled_map, layer_index)),
+        ));
+        laid_out_map.layers.insert(layer_index as u32, layer_entity);
     }
-}
 
-pub fn tiled_to_bevy_transform(tile_pos: &TilePos, map_info: TiledMapInformation) -> Transform {
-    let tiled_to_bevy_pos = convert_tiled_to_bevy_pos(*tile_pos, map_info.map_size.y);
-    to_bevy_transform(&tiled_to_bevy_pos, map_info)
+    laid_out_map
 }
 
-pub fn to_bevy_transform(tile_pos: &TilePos, map_info: TiledMapInformation) -> Transform {
-    let streamer_translation = tile_pos
-        .center_in_world(map_info.grid_size, map_info.map_type)
-        .extend(map_info.map_transform.translation.z);
-    *map_info.map_transform * Transform::from_translation(streamer_translation)
+/// Draws every Tile of some Tiled map through the Tilemap,
+/// onto the Tile entities already laid out for it.
+fn draw_tiled_map(
+    commands: &mut Commands,
+    tiled_map: &TiledMap,
+    laid_out_map: &LaidOutMap,
+    asset_server: &AssetServer,
+    texture_atlas_assets: &mut Assets<TextureAtlasLayout>,
+) {
+    let mut tilemap = Tilemap::from_tiled_map(&tiled_map.map);
+    tilemap.to_isometric_coordinates();
+    tilemap.y_sort_tiles();
+    tilemap.flip_y_axis();
+
+    let render_tiles = convert_tilemap_to_bevy_render_tiles(
+        &tilemap,
+        &tiled_map.images,
+        asset_server,
+        texture_atlas_assets,
+    );
+    for render_tile in render_tiles {
+        let Some(tile_entity) = laid_out_map.tiles.get(render_tile.get_grid_coordinates()) else {
+            continue;
+        };
+
+        commands.entity(*tile_entity).insert(render_tile);
+    }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn process_loaded_maps(
     mut commands: Commands,
     mut map_events: EventReader<AssetEvent<TiledMap>>,
     maps: Res<Assets<TiledMap>>,
     tile_storage_query: Query<(Entity, &TileStorage)>,
-    mut map_query: Query<(
-        &Handle<TiledMap>,
-        &mut TiledLayersStorage,
-        &TilemapRenderSettings,
-    )>,
+    mut map_query: Query<(&Handle<TiledMap>, &mut TiledLayersStorage)>,
     new_maps: Query<&Handle<TiledMap>, Added<Handle<TiledMap>>>,
+    map_objects: Query<Entity, With<MapObject>>,
+    mut map_reloaded_writer: EventWriter<MapReloaded>,
+    asset_server: Res<AssetServer>,
+    mut texture_atlas_assets: ResMut<Assets<TextureAtlasLayout>>,
 ) {
     // NOTE: Collect all TiledMap references that have been changed
     let mut changed_maps = Vec::<AssetId<TiledMap>>::default();
@@ -285,7 +456,7 @@ pub fn process_loaded_maps(
     }
 
     for changed_map in changed_maps.iter() {
-        for (map_handle, mut layer_storage, render_settings) in map_query.iter_mut() {
+        for (map_handle, mut layer_storage) in map_query.iter_mut() {
             // only deal with currently changed map
             // NOTE: This effectively filters down all TiledMaps to
             // ones we have recorded into the changed_maps collection.
@@ -296,195 +467,56 @@ pub fn process_loaded_maps(
             // NOTE: Get the TiledMap instance from all known
             // Assets.
             if let Some(tiled_map) = maps.get(map_handle) {
-                // TODO: Create a RemoveMap component..
-                // NOTE: Despawn _ALL_ currently rendered Tiles.
-                for layer_entity in layer_storage.storage.values() {
-                    if let Ok((_, layer_tile_storage)) = tile_storage_query.get(*layer_entity) {
+                // NOTE: Despawn _ALL_ currently rendered Tiles, along with
+                // the Layers holding them, whenever the map was already laid
+                // out and has since been edited.
+                let reloading = !layer_storage.storage.is_empty();
+                for (_, layer_entity) in layer_storage.storage.drain() {
+                    if let Ok((_, layer_tile_storage)) = tile_storage_query.get(layer_entity) {
                         for tile in layer_tile_storage.iter().flatten() {
                             commands.entity(*tile).despawn_recursive()
                         }
                     }
-                    // commands.entity(*layer_entity).despawn_recursive();
+                    commands.entity(layer_entity).despawn_recursive();
+                }
+                if reloading {
+                    log::info!("Reloading the edited map.");
+                    map_reloaded_writer.send(MapReloaded);
                 }
 
-                // The TilemapBundle requires that all tile images come exclusively from a single
-                // tiled texture or from a Vec of independent per-tile images. Furthermore, all of
-                // the per-tile images must be the same size. Since Tiled allows tiles of mixed
-                // tilesets on each layer and allows differently-sized tile images in each tileset,
-                // this means we need to load each combination of tileset and layer separately.
-                for (tileset_index, tileset) in tiled_map.map.tilesets().iter().enumerate() {
-                    // NOTE: Load the Texture for each TileMap based on the used Tile Set (Tile
-                    // Palette?)
-                    let Some(tilemap_texture) = tiled_map.tilemap_textures.get(&tileset_index)
-                    else {
-                        log::warn!("Skipped creating layer with missing tilemap textures.");
-                        continue;
-                    };
-
-                    // NOTE: Define the Tile Size based on the Tile Set used.
-                    let tile_size = TilemapTileSize {
-                        x: tileset.tile_width as f32,
-                        y: tileset.tile_height as f32,
-                    };
-
-                    // NOTE: Record the Spacing between Tiles
-                    // based on the Tile Set used.
-                    let tile_spacing = TilemapSpacing {
-                        x: tileset.spacing as f32,
-                        y: tileset.spacing as f32,
-                    };
-
-                    // Once materials have been created/added we need to then create the layers.
-                    //
-                    // NOTE: For each Layer,
-                    for (layer_index, layer) in tiled_map.map.layers().enumerate() {
-                        let offset_x = layer.offset_x;
-                        let offset_y = layer.offset_y;
-
-                        // NOTE: Filter for all Layers with type Tiles from the Tiled Map.
-                        let tiled::LayerType::Tiles(tile_layer) = layer.layer_type() else {
-                            log::info!(
-                                "Skipping layer {} because only tile layers are supported.",
-                                layer.id()
-                            );
-                            continue;
-                        };
-
-                        // NOTE: Filter for all Finite Tile Layers.
-                        let tiled::TileLayer::Finite(layer_data) = tile_layer else {
-                            log::info!(
-                                "Skipping layer {} because only finite layers are supported.",
-                                layer.id()
-                            );
-                            continue;
-                        };
-
-                        // NOTE: Capture the size of the current Tile Map,
-                        // where Width and Height are measured in Number
-                        // of Tiles.
-                        let map_size = TilemapSize {
-                            x: tiled_map.map.width,
-                            y: tiled_map.map.height,
-                        };
-
-                        // NOTE: Capture the maximum size of each Tile from
-                        // the current Tile Map in pixels.
-                        let grid_size = TilemapGridSize {
-                            x: tiled_map.map.tile_width as f32,
-                            y: tiled_map.map.tile_height as f32,
-                        };
-
-                        // NOTE: Determine the Orientation of the Tile Map.
-                        let map_type = match tiled_map.map.orientation {
-                            tiled::Orientation::Hexagonal => {
-                                TilemapType::Hexagon(HexCoordSystem::Row)
-                            }
-                            tiled::Orientation::Isometric => {
-                                TilemapType::Isometric(IsoCoordSystem::Diamond)
-                            }
-                            tiled::Orientation::Staggered => {
-                                TilemapType::Isometric(IsoCoordSystem::Staggered)
-                            }
-                            tiled::Orientation::Orthogonal => TilemapType::Square,
-                        };
-
-                        // NOTE: Hold references to each newly created Tile
-                        // from this current Layer.
-                        let mut tile_storage = TileStorage::empty(map_size);
-                        let layer_entity = commands.spawn_empty().id();
-
-                        for x in 0..map_size.x {
-                            for y in 0..map_size.y {
-                                // Transform TMX coords into bevy coords.
-                                //
-                                // NOTE: Flip the Y-Axis from what Bevy expects
-                                // to coordinate with Tiled handles coordinates.
-                                let mapped_y = tiled_map.map.height - 1 - y;
-
-                                let mapped_x = x as i32;
-                                let mapped_y = mapped_y as i32;
-
-                                // NOTE: Pull the Tile from the Tiled Map.
-                                let layer_tile = match layer_data.get_tile(mapped_x, mapped_y) {
-                                    Some(t) => t,
-                                    None => {
-                                        continue;
-                                    }
-                                };
-                                // NOTE: Filter out all Layer Tiles that are not
-                                // dealing with the currently loaded Tile Set (Palette).
-                                if tileset_index != layer_tile.tileset_index() {
-                                    continue;
-                                }
-                                // NOTE: Pulls Properties about Layer Tile.
-                                let layer_tile_data =
-                                    match layer_data.get_tile_data(mapped_x, mapped_y) {
-                                        Some(d) => d,
-                                        None => {
-                                            continue;
-                                        }
-                                    };
-
-                                // NOTE: Get the Texture used for the Layer Tile in
-                                // question.
-                                let texture_index = match tilemap_texture {
-                                    TilemapTexture::Single(_) => layer_tile.id(),
-                                    TilemapTexture::Vector(_) =>
-                                        *tiled_map.tile_image_offsets.get(&(tileset_index, layer_tile.id()))
-                                        .expect("The offset into to image vector should have been saved during the initial load."),
-                                    _ => unreachable!()
-                                };
-
-                                // NOTE: Spawn the Layer Tile in Bevy Coordinates.
-                                let tile_pos = TilePos { x, y };
-                                let tile_entity = commands
-                                    .spawn((
-                                        TileBundle {
-                                            position: tile_pos,
-                                            tilemap_id: TilemapId(layer_entity),
-                                            texture_index: TileTextureIndex(texture_index),
-                                            flip: TileFlip {
-                                                x: layer_tile_data.flip_h,
-                                                y: layer_tile_data.flip_v,
-                                                d: layer_tile_data.flip_d,
-                                            },
-                                            ..Default::default()
-                                        },
-                                        LayerNumber(layer_index),
-                                    ))
-                                    .id();
-                                // NOTE: Record the recently spawned Layer Tile
-                                // into Tile Storage.
-                                tile_storage.set(&tile_pos, tile_entity);
-                            }
-                        }
+                // NOTE: Record every Link declared on the object layers,
+                // ready for the Undirected Graphs to be joined up with.
+                commands.insert_resource(NavLinks::from_map(&tiled_map.map));
 
-                        // NOTE: Spawn the Tiled Map Layer as a whole.
-                        commands.entity(layer_entity).insert(TilemapBundle {
-                            grid_size,
-                            size: map_size,
-                            storage: tile_storage,
-                            texture: tilemap_texture.clone(),
-                            tile_size,
-                            spacing: tile_spacing,
-                            transform: get_tilemap_center_transform(
-                                &map_size,
-                                &grid_size,
-                                &map_type,
-                                layer_index as f32,
-                            ) * Transform::from_xyz(offset_x, -offset_y, 0.0),
-                            map_type,
-                            render_settings: *render_settings,
-                            ..Default::default()
-                        });
-
-                        // NOTE: Record the recently spawned Tiled Map Layer
-                        // into Layer Storage.
-                        layer_storage
-                            .storage
-                            .insert(layer_index as u32, layer_entity);
-                    }
+                // NOTE: Work out which layer holds each role, so entities
+                // are found by what the layers hold instead of their order.
+                let layer_roles = LayerRoles::from_map(&tiled_map.map);
+                if let Err(layer_role_error) = layer_roles.ensure_all() {
+                    log::error!("{layer_role_error}");
+                }
+                commands.insert_resource(layer_roles);
+
+                // NOTE: Swap out the objects of any earlier version of the
+                // map for those found on its object layers now.
+                for map_object in &map_objects {
+                    commands.entity(map_object).despawn_recursive();
                 }
+                MapObjects::from_map(&tiled_map.map).spawn(&mut commands);
+
+                // NOTE: Lay out every Tile and Layer, then draw each Tile
+                // through the Tilemap where it was laid out.
+                let laid_out_map = lay_out_tiled_map(&mut commands, &tiled_map.map);
+                draw_tiled_map(
+                    &mut commands,
+                    tiled_map,
+                    &laid_out_map,
+                    &asset_server,
+                    &mut texture_atlas_assets,
+                );
+
+                // NOTE: Record the recently spawned Tiled Map Layers
+                // into Layer Storage.
+                layer_storage.storage = laid_out_map.layers;
             }
         }
     }
@@ -498,20 +530,28 @@ fn load_tmx_map(tiled_map_path: &Path) -> TiledMap {
         .load_tmx_map(tiled_map_path)
         .expect("load_tmx_map: Unable to load tmx map.");
 
-    let tilemap_textures = HashMap::default();
-    let tile_image_offsets = HashMap::default();
-
     TiledMap {
         map,
-        tilemap_textures,
-        tile_image_offsets,
+        images: Vec::new(),
+    }
+}
+
+/// Anything laid out straight from the Tiled Map.
+type LaidOut = Or<(With<TilemapId>, With<TilemapType>, With<MapObject>)>;
+
+/// Takes down every Tile, Layer and Map Object of a reloaded
+/// map, ready for it to be laid out again.
+/// NOTE: For Integration Testing purposes only.
+pub fn despawn_tiles_from_tiledmap(laid_out: Query<Entity, LaidOut>, mut commands: Commands) {
+    for laid_out_entity in &laid_out {
+        commands.entity(laid_out_entity).despawn_recursive();
     }
 }
 
 /// Loads all necessary information to represent
 /// each Tile from a Tiled (.tmx) file.
 /// NOTE: For Integration Testing purposes only.
-pub fn spawn_tiles_from_tiledmap(mut commands: Commands) {
+pub fn spawn_tiles_from_tiledmap(mut commands: Commands, map_scenes: Res<MapScenes>) {
     // NOTE: Get the TiledMap instance from all known
     // Assets.
     let mut tiled_map_path = PathBuf::new();
@@ -520,132 +560,18 @@ pub fn spawn_tiles_from_tiledmap(mut commands: Commands) {
     if let Ok(manifest_dir) = std::env::var("CARGO_MANIFEST_DIR") {
         tiled_map_path.push(manifest_dir);
     }
-    tiled_map_path.push("assets/TM_map.tmx");
+    tiled_map_path.push("assets");
+    tiled_map_path.push(&map_scenes.active().map_path);
 
     let tiled_map = load_tmx_map(&tiled_map_path);
-    for (tileset_index, tileset) in tiled_map.map.tilesets().iter().enumerate() {
-        // NOTE: Define the Tile Size based on the Tile Set used.
-        let tile_size = TilemapTileSize {
-            x: tileset.tile_width as f32,
-            y: tileset.tile_height as f32,
-        };
-
-        // NOTE: Record the Spacing between Tiles
-        // based on the Tile Set used.
-        let tile_spacing = TilemapSpacing {
-            x: tileset.spacing as f32,
-            y: tileset.spacing as f32,
-        };
+    commands.insert_resource(NavLinks::from_map(&tiled_map.map));
 
-        // Once materials have been created/added we need to then create the layers.
-        //
-        // NOTE: For each Layer,
-        for (layer_index, layer) in tiled_map.map.layers().enumerate() {
-            let offset_x = layer.offset_x;
-            let offset_y = layer.offset_y;
-
-            // NOTE: Filter for all Layers with type Tiles from the Tiled Map.
-            let tiled::LayerType::Tiles(tile_layer) = layer.layer_type() else {
-                log::info!(
-                    "Skipping layer {} because only tile layers are supported.",
-                    layer.id()
-                );
-                continue;
-            };
-
-            // NOTE: Filter for all Finite Tile Layers.
-            let tiled::TileLayer::Finite(layer_data) = tile_layer else {
-                log::info!(
-                    "Skipping layer {} because only finite layers are supported.",
-                    layer.id()
-                );
-                continue;
-            };
-
-            // NOTE: Capture the size of the current Tile Map,
-            // where Width and Height are measured in Number
-            // of Tiles.
-            let map_size = TilemapSize {
-                x: tiled_map.map.width,
-                y: tiled_map.map.height,
-            };
-
-            // NOTE: Capture the maximum size of each Tile from
-            // the current Tile Map in pixels.
-            let grid_size = TilemapGridSize {
-                x: tiled_map.map.tile_width as f32,
-                y: tiled_map.map.tile_height as f32,
-            };
-
-            // NOTE: Determine the Orientation of the Tile Map.
-            let map_type = match tiled_map.map.orientation {
-                tiled::Orientation::Hexagonal => TilemapType::Hexagon(HexCoordSystem::Row),
-                tiled::Orientation::Isometric => TilemapType::Isometric(IsoCoordSystem::Diamond),
-                tiled::Orientation::Staggered => TilemapType::Isometric(IsoCoordSystem::Staggered),
-                tiled::Orientation::Orthogonal => TilemapType::Square,
-            };
-
-            let layer_entity = commands.spawn_empty().id();
-
-            for x in 0..map_size.x {
-                for y in 0..map_size.y {
-                    // Transform TMX coords into bevy coords.
-                    //
-                    // NOTE: Flip the Y-Axis from what Bevy expects
-                    // to coordinate with Tiled handles coordinates.
-                    let mapped_y = tiled_map.map.height - 1 - y;
-
-                    let mapped_x = x as i32;
-                    let mapped_y = mapped_y as i32;
-
-                    // NOTE: Pull the Tile from the Tiled Map.
-                    let layer_tile = match layer_data.get_tile(mapped_x, mapped_y) {
-                        Some(t) => t,
-                        None => {
-                            continue;
-                        }
-                    };
-                    // NOTE: Filter out all Layer Tiles that are not
-                    // dealing with the currently loaded Tile Set (Palette).
-                    if tileset_index != layer_tile.tileset_index() {
-                        continue;
-                    }
-                    // NOTE: Pulls Properties about Layer Tile.
-                    let layer_tile_data = match layer_data.get_tile_data(mapped_x, mapped_y) {
-                        Some(d) => d,
-                        None => {
-                            continue;
-                        }
-                    };
-
-                    // NOTE: Spawn the Layer Tile in Bevy Coordinates.
-                    let tile_pos = TilePos { x, y };
-                    commands.spawn((
-                        TileBundle {
-                            position: tile_pos,
-                            tilemap_id: TilemapId(layer_entity),
-                            flip: TileFlip {
-                                x: layer_tile_data.flip_h,
-                                y: layer_tile_data.flip_v,
-                                d: layer_tile_data.flip_d,
-                            },
-                            ..Default::default()
-                        },
-                        LayerNumber(layer_index),
-                    ));
-                }
-            }
-
-            // NOTE: Spawn the Tiled Map Layer as a whole.
-            commands.entity(layer_entity).insert((
-                grid_size,
-                map_size,
-                tile_size,
-                tile_spacing,
-                get_tilemap_center_transform(&map_size, &grid_size, &map_type, layer_index as f32)
-                    * Transform::from_xyz(offset_x, -offset_y, 0.0),
-                map_type,
-            ));
-        }
+    let layer_roles = LayerRoles::from_map(&tiled_map.map);
+    if let Err(layer_role_error) = layer_roles.ensure_all() {
+        log::error!("{layer_role_error}");
     }
+    commands.insert_resource(layer_roles);
+    MapObjects::from_map(&tiled_map.map).spawn(&mut commands);
+
+    lay_out_tiled_map(&mut commands, &tiled_map.map);
 }
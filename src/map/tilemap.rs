@@ -1,7 +1,9 @@
 use bevy::prelude::*;
-use std::path::PathBuf;
+use std::path::{Component, Path, PathBuf};
 use tiled::{Loader, Map};
 
+use crate::visual::tile_animations::TileAnimation;
+
 #[derive(Debug, PartialEq)]
 pub struct MapGridDimensions {
     width: usize,
@@ -86,7 +88,7 @@ impl TileDrawingOffset {
     }
 }
 
-#[derive(Debug, PartialEq, Component, Clone)]
+#[derive(Debug, PartialEq, Eq, Hash, Component, Clone)]
 pub struct TileGridCoordinates {
     x: usize,
     y: usize,
@@ -116,6 +118,103 @@ impl TileGridCoordinates {
     pub fn z(&self) -> usize {
         self.z
     }
+
+    /// Returns where the Tile is placed on an isometric grid in pixels, with
+    /// the y-axis pointing down as in Tiled, before any drawing offsets.
+    pub fn to_isometric_pixels(&self, tile_dimensions: &TileDimensions) -> Vec2 {
+        let tile_width = tile_dimensions.width();
+        let tile_height = tile_dimensions.height();
+        // Isometric tiles in games are typically 2:1, like 32 width:16 height,
+        // but just in case this is different, it's captured here.
+        let tile_ratio = (tile_width / tile_height) as f32;
+
+        let tile_px_x = (self.x * tile_width) as f32;
+        // On a tilemap whose tiles are 32x16, it's assumed that 32x32 sprites are
+        // being used. Because of that, tile_height is "restored" to what the sprite
+        // size should be by multiplying it by the tile ratio, which is typically 2.
+        let tile_px_y = self.y as f32 * (tile_height as f32 * tile_ratio);
+
+        Vec2::new(
+            (tile_px_x - tile_px_y) / tile_ratio,
+            (tile_px_x + tile_px_y) / (2.0 * tile_ratio),
+        )
+    }
+
+    /// Returns where the center of the Tile is drawn on an isometric map some
+    /// number of Tiles high, with the y-axis flipped to point up as in Bevy.
+    pub fn to_world_center(&self, tile_dimensions: &TileDimensions, map_height: usize) -> Vec2 {
+        let isometric_pixels = self.to_isometric_pixels(tile_dimensions);
+
+        Vec2::new(
+            isometric_pixels.x,
+            flip_pixel_y(isometric_pixels.y, map_height, tile_dimensions),
+        )
+    }
+
+    /// Returns where whatever stands on the Tile is placed on a layer set at
+    /// some origin, centered on the Tile as it is drawn there.
+    pub fn to_world_transform(
+        &self,
+        tile_dimensions: &TileDimensions,
+        map_height: usize,
+        layer_origin: &Transform,
+    ) -> Transform {
+        let tile_center = self
+            .to_world_center(tile_dimensions, map_height)
+            .extend(layer_origin.translation.z);
+
+        *layer_origin * Transform::from_translation(tile_center)
+    }
+
+    /// Returns the Tile whose center is drawn nearest to some point on an
+    /// isometric map some number of Tiles high, undoing to_world_center,
+    /// or None when the point lies beyond the top or left edges of the map.
+    pub fn from_world_center(
+        world_pos: Vec2,
+        tile_dimensions: &TileDimensions,
+        map_height: usize,
+    ) -> Option<Self> {
+        let tile_width = tile_dimensions.width() as f32;
+        let tile_height = tile_dimensions.height() as f32;
+        let tile_ratio = (tile_dimensions.width() / tile_dimensions.height()) as f32;
+
+        // Flipping the y-axis twice leaves it as it was, pointing down as in Tiled.
+        let isometric_px_x = world_pos.x;
+        let isometric_px_y = flip_pixel_y(world_pos.y, map_height, tile_dimensions);
+
+        // Solving both isometric axes from to_isometric_pixels for each grid axis.
+        let tile_px_x = tile_ratio * (isometric_px_x + 2.0 * isometric_px_y) / 2.0;
+        let tile_px_y = tile_ratio * (2.0 * isometric_px_y - isometric_px_x) / 2.0;
+
+        let grid_x = (tile_px_x / tile_width).round();
+        let grid_y = (tile_px_y / (tile_height * tile_ratio)).round();
+        if grid_x < 0.0 || grid_y < 0.0 {
+            return None;
+        }
+
+        Some(Self::new(grid_x as usize, grid_y as usize))
+    }
+}
+
+/// Returns some y coordinate in pixels on a map some number of Tiles
+/// high, flipped over from pointing down as in Tiled to up as in Bevy.
+fn flip_pixel_y(px_y: f32, map_height: usize, tile_dimensions: &TileDimensions) -> f32 {
+    let map_height = (map_height * tile_dimensions.height()) as f32;
+    let tile_width = tile_dimensions.width() as f32;
+    // Originally, the grid coordinates used to be flipped, subtracting one since all tiles
+    // start at 0, 0. Now, since we're dealing with width and height directly, subtracting
+    // one now means we subtract the tile_width.
+    (map_height - px_y) - tile_width
+}
+
+/// Returns where some layer of a Tiled map is placed, shifted by its
+/// offsets and stacked by its number, for whatever stands on its Tiles.
+pub fn layer_origin(tiled_map: &Map, layer_index: usize) -> Transform {
+    let layer = tiled_map
+        .get_layer(layer_index)
+        .expect("layer_origin: The layer should be within the Tiled map.");
+
+    Transform::from_xyz(layer.offset_x, -layer.offset_y, layer_index as f32)
 }
 
 #[derive(Debug, PartialEq)]
@@ -254,6 +353,7 @@ impl SpriteSheetDimensions {
 pub struct TileTexture {
     sprite: TileSprite,
     spritesheet_dimensions: SpriteSheetDimensions,
+    animation: Option<TileAnimation>,
 }
 
 impl TileTexture {
@@ -261,9 +361,16 @@ impl TileTexture {
         Self {
             sprite,
             spritesheet_dimensions,
+            animation: None,
         }
     }
 
+    /// Returns the Tile Texture flicking through some animation.
+    pub fn with_animation(mut self, animation: Option<TileAnimation>) -> Self {
+        self.animation = animation;
+        self
+    }
+
     /// Returns a reference to the sprite recorded.
     pub fn get_sprite(&self) -> &TileSprite {
         &self.sprite
@@ -273,6 +380,11 @@ impl TileTexture {
     pub fn get_spritesheet_dimensions(&self) -> &SpriteSheetDimensions {
         &self.spritesheet_dimensions
     }
+
+    /// Returns the animation given by the Tiled tileset, if any.
+    pub fn get_animation(&self) -> Option<&TileAnimation> {
+        self.animation.as_ref()
+    }
 }
 
 #[derive(Debug)]
@@ -326,38 +438,59 @@ fn get_texture_from_tiled(
     grid_coordinates: &TileGridCoordinates,
 ) -> Option<TileTexture> {
     let tile_grid_z = grid_coordinates.z();
-    let tile_layer = tiled_map
-        .get_layer(tile_grid_z)
-        .unwrap()
-        .as_tile_layer()
-        .unwrap();
+    // NOTE: Object layers hold no Tiles to draw.
+    let tile_layer = tiled_map.get_layer(tile_grid_z).unwrap().as_tile_layer()?;
 
     let tile_grid_x = grid_coordinates.x() as i32;
     let tile_grid_y = grid_coordinates.y() as i32;
-    if let Some(tile) = tile_layer.get_tile(tile_grid_x, tile_grid_y) {
-        let tile_tileset = tile.get_tileset();
-        let spritesheet_image = tile_tileset.image.clone().unwrap();
-        let spritesheet_file = spritesheet_image.source;
-        // NOTE: Why not fs::canonicalize? Because on Windows only, this adds a weird
-        // prefix at the beginning of the path, making the tests fail. This is called
-        // a UNC path, and we remove these with dunce:
-        // https://docs.rs/dunce/latest/dunce/
-        let spritesheet_file_path = dunce::canonicalize(spritesheet_file).unwrap();
-        let sprite_idx = tile.id() as usize;
-        let sprite_dimensions = TileDimensions::new(
-            tile_tileset.tile_width as usize,
-            tile_tileset.tile_height as usize,
-        );
-        let tile_sprite = TileSprite::new(sprite_dimensions, spritesheet_file_path, sprite_idx);
-
-        let num_rows = (spritesheet_image.height as u32 / tile_tileset.tile_height) as usize;
-        let num_columns = (spritesheet_image.width as u32 / tile_tileset.tile_width) as usize;
-        let tile_spritesheet_dimensions = SpriteSheetDimensions::new(num_rows, num_columns);
+    let tile = tile_layer.get_tile(tile_grid_x, tile_grid_y)?;
+    let tile_tileset = tile.get_tileset();
+    let Some(spritesheet_image) = tile_tileset.image.clone() else {
+        // NOTE: Each Tile from a collection of images is drawn from its
+        // own image, as a spritesheet holding only that Tile.
+        let tile_image = tile.get_tile()?.image.clone()?;
+        let sprite_dimensions =
+            TileDimensions::new(tile_image.width as usize, tile_image.height as usize);
+        let tile_sprite = TileSprite::new(sprite_dimensions, to_sprite_path(tile_image.source), 0);
+
+        return Some(TileTexture::new(
+            tile_sprite,
+            SpriteSheetDimensions::new(1, 1),
+        ));
+    };
+
+    let sprite_idx = tile.id() as usize;
+    let sprite_dimensions = TileDimensions::new(
+        tile_tileset.tile_width as usize,
+        tile_tileset.tile_height as usize,
+    );
+    let tile_sprite = TileSprite::new(
+        sprite_dimensions,
+        to_sprite_path(spritesheet_image.source),
+        sprite_idx,
+    );
+
+    let num_rows = (spritesheet_image.height as u32 / tile_tileset.tile_height) as usize;
+    let num_columns = (spritesheet_image.width as u32 / tile_tileset.tile_width) as usize;
+    let tile_spritesheet_dimensions = SpriteSheetDimensions::new(num_rows, num_columns);
+
+    // NOTE: Each frame is a Tile within the same spritesheet.
+    let tile_animation = tile
+        .get_tile()
+        .and_then(|tiled_tile| TileAnimation::from_tile(&tiled_tile, Some));
+
+    Some(TileTexture::new(tile_sprite, tile_spritesheet_dimensions).with_animation(tile_animation))
+}
 
-        Some(TileTexture::new(tile_sprite, tile_spritesheet_dimensions))
-    } else {
-        None
-    }
+/// Returns the path of some image a Tile is drawn from.
+fn to_sprite_path(image_file: PathBuf) -> PathBuf {
+    // NOTE: Why not fs::canonicalize? Because on Windows only, this adds a weird
+    // prefix at the beginning of the path, making the tests fail. This is called
+    // a UNC path, and we remove these with dunce:
+    // https://docs.rs/dunce/latest/dunce/
+    // Maps loaded as assets already point within the assets folder, and
+    // are kept as they are.
+    dunce::canonicalize(&image_file).unwrap_or(image_file)
 }
 
 /// Returns Drawing Offsets represented in pixels if found from a Tile's
@@ -368,21 +501,18 @@ fn get_drawing_offsets_from_tiled(
 ) -> TileDrawingOffset {
     let tile_grid_z = grid_coordinates.z();
 
-    // TODO: Consider horizontal layer offsets as well, and
-    // making this into MapLayerOffset.
+    let horizontal_layer_offset = tiled_map.layers().nth(tile_grid_z).unwrap().offset_x;
     let vertical_layer_offset = tiled_map.layers().nth(tile_grid_z).unwrap().offset_y;
-    let tile_layer = tiled_map
-        .get_layer(tile_grid_z)
-        .unwrap()
-        .as_tile_layer()
-        .unwrap();
+    let Some(tile_layer) = tiled_map.get_layer(tile_grid_z).unwrap().as_tile_layer() else {
+        return TileDrawingOffset::new(0, 0);
+    };
 
     let tile_grid_x = grid_coordinates.x() as i32;
     let tile_grid_y = grid_coordinates.y() as i32;
 
     if let Some(tile) = tile_layer.get_tile(tile_grid_x, tile_grid_y) {
         let tileset = tile.get_tileset();
-        let tile_offset_x = tileset.offset_x as isize;
+        let tile_offset_x = (tileset.offset_x as f32 + horizontal_layer_offset) as isize;
         let tile_offset_y = (tileset.offset_y as f32 + vertical_layer_offset) as isize;
 
         TileDrawingOffset::new(tile_offset_x, tile_offset_y)
@@ -391,10 +521,33 @@ fn get_drawing_offsets_from_tiled(
     }
 }
 
+/// Returns some path with each step back to a parent folder
+/// taken, such as "maps/../tilesets/terrain.tsx" becoming
+/// "tilesets/terrain.tsx", without reaching for the file system.
+pub fn normalize_path(input_path: &Path) -> PathBuf {
+    let mut new_path = PathBuf::new();
+    for path_component in input_path.components() {
+        match path_component {
+            Component::CurDir => {}
+            Component::ParentDir => match new_path.components().next_back() {
+                Some(Component::Normal(_)) => {
+                    new_path.pop();
+                }
+                Some(Component::RootDir | Component::Prefix(_)) => {}
+                _ => new_path.push(Component::ParentDir),
+            },
+            _ => new_path.push(path_component),
+        }
+    }
+
+    new_path
+}
+
 /// Returns a relative path pointing to some asset in
 /// the assets folder, or returns the path as-is if the
 /// assets folder is not found.
 pub fn to_bevy_path(input_path: PathBuf) -> PathBuf {
+    let input_path = normalize_path(&input_path);
     let mut new_path = PathBuf::new();
 
     let mut path_element_stack = Vec::new();
@@ -429,13 +582,18 @@ impl Tilemap {
 
     /// Populates tiles found from some tiled map.
     pub fn load_tiles_from_tiled_map(&mut self, tiled_map_path: &PathBuf) {
-        let mut tiles = Vec::new();
-
         let mut tiled_loader = Loader::new();
         let tiled_map = tiled_loader
             .load_tmx_map(tiled_map_path)
             .expect("load_tiles_from_tiled: Could not load Tiled map");
 
+        *self = Self::from_tiled_map(&tiled_map);
+    }
+
+    /// Returns a Tilemap holding the tiles of some already loaded tiled map.
+    pub fn from_tiled_map(tiled_map: &Map) -> Self {
+        let mut tiles = Vec::new();
+
         let tile_width = tiled_map.tile_width as usize;
         let tile_height = tiled_map.tile_height as usize;
 
@@ -448,9 +606,8 @@ impl Tilemap {
             for x in 0..map_width {
                 for y in 0..map_height {
                     let tile_grid_pos = TileGridCoordinates::new_3d(x, y, z);
-                    let tile_texture = get_texture_from_tiled(&tiled_map, &tile_grid_pos);
-                    let drawing_offsets =
-                        get_drawing_offsets_from_tiled(&tiled_map, &tile_grid_pos);
+                    let tile_texture = get_texture_from_tiled(tiled_map, &tile_grid_pos);
+                    let drawing_offsets = get_drawing_offsets_from_tiled(tiled_map, &tile_grid_pos);
 
                     let tile_px_x = (tile_width * x) as isize;
                     let tile_px_y = (tile_height * y) as isize;
@@ -469,8 +626,10 @@ impl Tilemap {
             }
         }
 
-        self.tiles = tiles;
-        self.map_grid_dimensions = map_grid_dimensions;
+        Self {
+            map_grid_dimensions,
+            tiles,
+        }
     }
 
     /// Returns the dimensions of the Tile map in Tiles.
@@ -483,19 +642,33 @@ impl Tilemap {
         &self.tiles
     }
 
+    /// Returns the Tile loaded at some grid coordinates, if any.
+    pub fn get_tile(&self, grid_coordinates: &TileGridCoordinates) -> Option<&Tile> {
+        let map_width = self.map_grid_dimensions.width();
+        let map_height = self.map_grid_dimensions.height();
+        if grid_coordinates.x() >= map_width || grid_coordinates.y() >= map_height {
+            return None;
+        }
+
+        // NOTE: Tiles are loaded layer by layer, then column by column.
+        let tile_idx = grid_coordinates.z() * map_width * map_height
+            + grid_coordinates.x() * map_height
+            + grid_coordinates.y();
+        self.tiles.get(tile_idx)
+    }
+
     /// "Flips" the y-axis for all loaded tiles.
     pub fn flip_y_axis(&mut self) {
+        let map_height = self.map_grid_dimensions.height();
         for tile in &mut self.tiles {
-            let map_height =
-                (self.map_grid_dimensions.height() * tile.get_tile_dimensions().height()) as f32;
-            let tile_width = tile.get_tile_dimensions().width() as f32;
-            // Originally, the grid coordinates used to be flipped, subtracting one since all tiles
-            // start at 0, 0. Now, since we're dealing with width and height directly, subtracting
-            // one now means we subtract the tile_width.
-            let flipped_y_coordinate = (map_height - tile.get_pixel_coordinates().y()) - tile_width;
+            let flipped_y_coordinate = flip_pixel_y(
+                tile.get_pixel_coordinates().y(),
+                map_height,
+                tile.get_tile_dimensions(),
+            );
 
             let tile_pixel_coordinates = tile.get_pixel_coordinates_mut();
-            tile_pixel_coordinates.set_y(flipped_y_coordinate as f32);
+            tile_pixel_coordinates.set_y(flipped_y_coordinate);
         }
     }
 
@@ -512,29 +685,22 @@ impl Tilemap {
 
             let tile_width = tile.get_tile_dimensions().width();
             let tile_height = tile.get_tile_dimensions().height();
-            // Isometric tiles in games are typically 2:1, like 32 width:16 height,
-            // but just in case this is different, it's captured here.
             let tile_ratio = (tile_width / tile_height) as f32;
 
-            let tile_px_x = (tile.get_grid_coordinates().x() * tile_width) as f32;
-
-            // On a tilemap whose tiles are 32x16, it's assumed that 32x32 sprites are
-            // being used. Because of that, tile_height is "restored" to what the sprite
-            // size should be by multiplying it by the tile ratio, which is typically 2.
-            let tile_px_y =
-                tile.get_grid_coordinates().y() as f32 * (tile_height as f32 * tile_ratio);
+            let isometric_pixels = tile
+                .get_grid_coordinates()
+                .to_isometric_pixels(tile.get_tile_dimensions());
             let tile_px_x_offset = tile.get_drawing_offsets().x() as f32;
             let tile_px_y_offset = tile.get_drawing_offsets().y() as f32;
 
-            let mut isometric_px_x = (tile_px_x - tile_px_y) / tile_ratio + tile_px_x_offset;
+            let mut isometric_px_x = isometric_pixels.x + tile_px_x_offset;
             // A sprite whose smaller than a tile's size should be centered. This is
             // what's happening here, but on the x-axis.
             if tile_sprite_width != tile_width {
                 isometric_px_x += (tile_sprite_width as f32 - tile_width as f32) / 2.0;
             }
 
-            let mut isometric_px_y =
-                ((tile_px_x + tile_px_y) / (2.0 * tile_ratio)) + tile_px_y_offset;
+            let mut isometric_px_y = isometric_pixels.y + tile_px_y_offset;
             // A sprite whose smaller than a tile's size should be centered. This is
             // what's happening here, but on the y-axis.
             if tile_sprite_height != tile_height * tile_ratio as usize {
@@ -549,20 +715,21 @@ impl Tilemap {
     }
 
     /// Adjusts depth (via z coordinate) for all recorded tiles,
-    /// sorted by their y coordinate.
+    /// sorted by their y coordinate, with each layer stacked on
+    /// top of the last, much like whatever stands on them.
     pub fn y_sort_tiles(&mut self) {
         // Fun fact: Tiles are loaded first and foremost based on their
         // y value. See load_tiles_from_tiled_map for more details.
         let map_width = self.get_dimensions().width();
         let map_height = self.get_dimensions().height();
-        let map_depth = self.get_dimensions().depth();
-        let map_area = map_width * map_height * map_depth;
+        let layer_area = (map_width * map_height).max(1);
 
         for (depth, tile) in self.tiles.iter_mut().enumerate() {
             // The tile height would be too high without this,
             // making the rendering look funny, with seemingly "invisible"
             // tiles if out of bounds.
-            let new_depth = depth as f32 / map_area as f32;
+            let layer_depth = (depth % layer_area) as f32 / layer_area as f32;
+            let new_depth = tile.get_grid_coordinates().z() as f32 + layer_depth;
             tile.get_pixel_coordinates_mut().set_z(new_depth);
         }
     }
@@ -573,6 +740,8 @@ pub struct RenderTile {
     grid_coordinate: TileGridCoordinates,
     tile_sprite: SpriteBundle,
     tile_texture_atlas: TextureAtlas,
+    #[bundle(ignore)]
+    animation: Option<TileAnimation>,
 }
 
 impl RenderTile {
@@ -585,15 +754,35 @@ impl RenderTile {
             grid_coordinate,
             tile_sprite: sprite,
             tile_texture_atlas: texture_atlas,
+            animation: None,
         }
     }
+
+    /// Returns the Render Tile flicking through some animation.
+    pub fn with_animation(mut self, animation: Option<TileAnimation>) -> Self {
+        self.animation = animation;
+        self
+    }
+
+    /// Returns the grid coordinates of the Tile being drawn.
+    pub fn get_grid_coordinates(&self) -> &TileGridCoordinates {
+        &self.grid_coordinate
+    }
+
+    /// Returns the animation to play once the Render Tile is
+    /// spawned, which is left out of the bundle itself.
+    pub fn get_animation(&self) -> Option<&TileAnimation> {
+        self.animation.as_ref()
+    }
 }
 
 /// Returns a collection of Render Tiles converted from some Tilemap for the
-/// Bevy game engine.
+/// Bevy game engine, drawn with whichever images were loaded along with the
+/// map, or loaded now otherwise.
 /// Precondition: Tilemap was called with a load method.
 pub fn convert_tilemap_to_bevy_render_tiles(
     tilemap: &Tilemap,
+    loaded_images: &[Handle<Image>],
     asset_server: &AssetServer,
     texture_atlas_assets: &mut Assets<TextureAtlasLayout>,
 ) -> Vec<RenderTile> {
@@ -612,7 +801,16 @@ pub fn convert_tilemap_to_bevy_render_tiles(
         let tile_sprite_dimensions = tile_sprite.get_sprite_dimensions();
         let tile_spritesheet_dimensions = tile_texture.get_spritesheet_dimensions();
 
-        let bevy_tile_texture = asset_server.load(to_bevy_path(tile_sprite.get_path().clone()));
+        let tile_image_path = to_bevy_path(tile_sprite.get_path().clone());
+        let bevy_tile_texture = loaded_images
+            .iter()
+            .find(|image| {
+                image
+                    .path()
+                    .is_some_and(|image_path| image_path.path() == tile_image_path)
+            })
+            .cloned()
+            .unwrap_or_else(|| asset_server.load(tile_image_path));
 
         let tile_size = UVec2::new(
             tile_sprite_dimensions.width() as u32,
@@ -643,7 +841,8 @@ pub fn convert_tilemap_to_bevy_render_tiles(
         };
 
         let render_tile =
-            RenderTile::new(tile_grid_coordinate, bevy_tile_sprite, tile_texture_atlas);
+            RenderTile::new(tile_grid_coordinate, bevy_tile_sprite, tile_texture_atlas)
+                .with_animation(tile_texture.get_animation().cloned());
         render_tiles.push(render_tile);
     }
 
@@ -0,0 +1,241 @@
+use std::collections::HashSet;
+use std::panic::{self, AssertUnwindSafe};
+
+use bevy::ecs::system::RunSystemOnce;
+use bevy::ecs::world::CommandQueue;
+use bevy::prelude::*;
+use bevy_ecs_tilemap::prelude::*;
+use thiserror::Error;
+
+use crate::entities::definitions::{EntityDefinitions, EntityKind};
+use crate::entities::fruit::fall_target;
+
+use super::layer_roles::{LayerRoleError, LayerRoles};
+use super::links::NavLinks;
+use super::objects::{MapObjectKind, MapObjects};
+use super::path_finding::{
+    create_air_graph, create_ground_graph, create_water_graph, GraphType, PathFindingSettings,
+    UndirectedGraph,
+};
+use super::tiled::{lay_out_tiled_map, LayerNumber};
+
+/// Returns some Tile written out as x, y.
+fn tile(tile_pos: &TilePos) -> String {
+    format!("{}, {}", tile_pos.x, tile_pos.y)
+}
+
+/// Something about a Tiled map that would go wrong once it is live.
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum MapValidationError {
+    #[error(transparent)]
+    MissingLayers(#[from] LayerRoleError),
+    #[error("The Streamer spawn on {} is not on the ground graph.", tile(.0))]
+    StreamerSpawnOffGround(TilePos),
+    #[error(
+        "The Point of Interest {name:?} on {} cannot be reached from the Streamer spawn.",
+        tile(.tile_pos)
+    )]
+    UnreachablePointOfInterest { name: String, tile_pos: TilePos },
+    #[error("The Fruit on {} falls off the ground graph.", tile(.0))]
+    FruitFallsOffGround(TilePos),
+    #[error("The Chatter perch on {} is not on the air graph.", tile(.0))]
+    PerchOffAir(TilePos),
+    #[error("The water graph is split into {0} regions that cannot reach each other.")]
+    DisconnectedWater(usize),
+    #[error("The graphs could not be built from the map: {0}")]
+    GraphsNotBuilt(String),
+}
+
+/// Some Tiled map laid out the same way the game lays it out,
+/// along with the Undirected Graphs built from its Tiles.
+pub struct MapValidation {
+    layer_roles: LayerRoles,
+    map_objects: MapObjects,
+    layer_tiles: Vec<(TilePos, usize)>,
+    graphs: Vec<UndirectedGraph>,
+}
+
+impl MapValidation {
+    /// Lays out some Tiled map within a world of its own, then builds its
+    /// Undirected Graphs from what was laid out, or returns why they could not
+    /// be built, rather than panicking the same way the game would.
+    pub fn from_map(tiled_map: &tiled::Map) -> Result<Self, MapValidationError> {
+        let layer_roles = LayerRoles::from_map(tiled_map);
+
+        let mut world = World::new();
+        world.init_resource::<PathFindingSettings>();
+        world.insert_resource(NavLinks::from_map(tiled_map));
+        world.insert_resource(layer_roles.clone());
+
+        let mut command_queue = CommandQueue::default();
+        lay_out_tiled_map(&mut Commands::new(&mut command_queue, &world), tiled_map);
+        command_queue.apply(&mut world);
+
+        panic::catch_unwind(AssertUnwindSafe(|| {
+            world.run_system_once(create_ground_graph);
+            world.run_system_once(create_water_graph);
+            world.run_system_once(create_air_graph);
+        }))
+        .map_err(|panic_payload| {
+            let panic_message = panic_payload
+                .downcast_ref::<String>()
+                .cloned()
+                .or_else(|| {
+                    panic_payload
+                        .downcast_ref::<&str>()
+                        .map(|message| message.to_string())
+                })
+                .unwrap_or_default();
+            MapValidationError::GraphsNotBuilt(panic_message)
+        })?;
+
+        let layer_tiles = world
+            .query::<(&TilePos, &LayerNumber)>()
+            .iter(&world)
+            .map(|(tile_pos, layer_number)| (*tile_pos, layer_number.0))
+            .collect();
+        let graphs = world
+            .query::<&UndirectedGraph>()
+            .iter(&world)
+            .cloned()
+            .collect();
+
+        Ok(Self {
+            layer_roles,
+            map_objects: MapObjects::from_map(tiled_map),
+            layer_tiles,
+            graphs,
+        })
+    }
+
+    /// Returns the Undirected Graph holding some type of Nodes.
+    pub fn graph(&self, graph_type: GraphType) -> Option<&UndirectedGraph> {
+        self.graphs
+            .iter()
+            .find(|graph| *graph.get_node_type() == graph_type)
+    }
+
+    /// Returns each Tile laid out on some layer.
+    fn tiles_on_layer(&self, layer: Option<usize>) -> impl Iterator<Item = TilePos> + '_ {
+        self.layer_tiles
+            .iter()
+            .filter(move |(_, tile_layer)| Some(*tile_layer) == layer)
+            .map(|(tile_pos, _)| *tile_pos)
+    }
+
+    /// Returns everything that would go wrong with the map once
+    /// it is live, with the Streamer spawned on some Tile, if known, and
+    /// everything else placed on the layers given by their definitions.
+    pub fn errors(
+        &self,
+        streamer_spawn: Option<TilePos>,
+        entity_definitions: &EntityDefinitions,
+    ) -> Vec<MapValidationError> {
+        let mut errors = Vec::new();
+
+        if let Err(layer_role_error) = self.layer_roles.ensure_all() {
+            errors.push(layer_role_error.into());
+        }
+
+        if let (Some(ground_graph), Some(streamer_spawn)) =
+            (self.graph(GraphType::Ground), streamer_spawn)
+        {
+            errors.extend(self.unreachable_from_spawn(ground_graph, streamer_spawn));
+        }
+
+        if let Some(ground_graph) = self.graph(GraphType::Ground) {
+            let fruit_layer = entity_definitions
+                .get(EntityKind::Fruit)
+                .drawn_layer(&self.layer_roles);
+            errors.extend(
+                self.tiles_on_layer(fruit_layer)
+                    .filter(|fruit_tile_pos| {
+                        !fall_target(*fruit_tile_pos)
+                            .is_some_and(|fall_target| ground_graph.is_on_graph(fall_target))
+                    })
+                    .map(MapValidationError::FruitFallsOffGround),
+            );
+        }
+
+        if let Some(air_graph) = self.graph(GraphType::Air) {
+            let chatter_layer = entity_definitions
+                .get(EntityKind::Chatter)
+                .drawn_layer(&self.layer_roles);
+            errors.extend(
+                self.tiles_on_layer(chatter_layer)
+                    .filter(|perch_tile_pos| !air_graph.is_on_graph(*perch_tile_pos))
+                    .map(MapValidationError::PerchOffAir),
+            );
+        }
+
+        if let Some(water_graph) = self.graph(GraphType::Water) {
+            let water_regions = regions_of(water_graph);
+            if water_regions > 1 {
+                errors.push(MapValidationError::DisconnectedWater(water_regions));
+            }
+        }
+
+        errors
+    }
+
+    /// Returns why the Streamer spawn, or any Point
+    /// of Interest, cannot be reached along the ground.
+    fn unreachable_from_spawn(
+        &self,
+        ground_graph: &UndirectedGraph,
+        streamer_spawn: TilePos,
+    ) -> Vec<MapValidationError> {
+        if !ground_graph.is_on_graph(streamer_spawn) {
+            return vec![MapValidationError::StreamerSpawnOffGround(streamer_spawn)];
+        }
+
+        let distances = ground_graph.distances_from(streamer_spawn);
+        self.map_objects
+            .iter()
+            .filter_map(|map_object| match map_object.kind {
+                MapObjectKind::PointOfInterest(tile_pos) => Some((&map_object.name, tile_pos)),
+                _ => None,
+            })
+            .filter(|(_, tile_pos)| {
+                ground_graph
+                    .node_idx(*tile_pos)
+                    .and_then(|node_idx| distances.get(node_idx).copied().flatten())
+                    .is_none()
+            })
+            .map(
+                |(name, tile_pos)| MapValidationError::UnreachablePointOfInterest {
+                    name: name.clone(),
+                    tile_pos,
+                },
+            )
+            .collect()
+    }
+}
+
+/// Returns how many regions the Nodes of some Undirected
+/// Graph are split into, where none can reach another.
+fn regions_of(graph: &UndirectedGraph) -> usize {
+    let mut reached = HashSet::new();
+    let mut regions = 0;
+    for (tile_pos, _) in graph.drawn_nodes() {
+        if reached.contains(&tile_pos) {
+            continue;
+        }
+
+        regions += 1;
+        let distances = graph.distances_from(tile_pos);
+        reached.extend(
+            graph
+                .drawn_nodes()
+                .map(|(node_tile_pos, _)| node_tile_pos)
+                .filter(|node_tile_pos| {
+                    graph
+                        .node_idx(*node_tile_pos)
+                        .and_then(|node_idx| distances.get(node_idx).copied().flatten())
+                        .is_some()
+                }),
+        );
+    }
+
+    regions
+}
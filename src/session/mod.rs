@@ -0,0 +1,3 @@
+pub mod plugins;
+pub mod records;
+pub mod saving;
@@ -0,0 +1,46 @@
+use bevy::prelude::*;
+use bevy::time::common_conditions::on_timer;
+use bevy::utils::Duration;
+
+use super::records::SessionRecords;
+use super::saving::*;
+use crate::entities::crop::replace_crop_sprites;
+use crate::entities::fruit::replace_fruit_sprites;
+use crate::GameState;
+
+/// Autosaves the world every so often, restoring the last
+/// saved session on launch when asked to.
+#[derive(Default)]
+pub struct SessionPlugin {
+    pub restore_on_launch: bool,
+}
+
+impl Plugin for SessionPlugin {
+    fn build(&self, app: &mut App) {
+        let session_records = SessionRecords::new(String::from("task_masker_session.db"));
+        let pending_session = if self.restore_on_launch {
+            PendingSession(session_records.load())
+        } else {
+            PendingSession::default()
+        };
+
+        app.insert_resource(session_records);
+        app.insert_resource(pending_session);
+        // NOTE: The Map Scene is restored before the map is spawned at Startup.
+        app.add_systems(PreStartup, restore_scene_session);
+        app.add_systems(
+            Update,
+            (
+                restore_streamer_session,
+                restore_crop_session.after(replace_crop_sprites),
+                restore_fruit_session.after(replace_fruit_sprites),
+                restore_chat_session,
+                restore_health_session,
+                abandon_unrestored_session
+                    .run_if(on_timer(Duration::from_secs_f32(RESTORE_TIMEOUT_SECS))),
+                save_session.run_if(on_timer(Duration::from_secs_f32(AUTOSAVE_INTERVAL_SECS))),
+            )
+                .run_if(in_state(GameState::InGame)),
+        );
+    }
+}
@@ -0,0 +1,388 @@
+use bevy::prelude::*;
+use bevy_ecs_tilemap::prelude::*;
+use sqlite::{Connection, ConnectionThreadSafe, State, Value};
+
+use crate::entities::chatter::ChatMsg;
+use crate::entities::crop::CropState;
+use crate::entities::fruit::FruitState;
+use crate::entities::GameEntityType;
+use crate::ui::chatting::Msg;
+
+/// Where the Streamer was, and where they still had to go.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StreamerSnapshot {
+    pub tile_pos: TilePos,
+    pub destinations: Vec<TilePos>,
+}
+
+/// How far along some Crop was in growing.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CropSnapshot {
+    pub tile_pos: TilePos,
+    pub state: CropState,
+    pub texture_idx: u32,
+}
+
+/// Whether some Fruit was still hanging, or where it had dropped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FruitSnapshot {
+    pub respawn_tile_pos: TilePos,
+    pub tile_pos: TilePos,
+    pub state: FruitState,
+}
+
+/// Everything needed to pick a stream back up as if it
+/// never stopped, where each part is None when there
+/// was nothing to save for it.
+#[derive(Default, Clone)]
+pub struct SessionSnapshot {
+    pub streamer: Option<StreamerSnapshot>,
+    pub crops: Option<Vec<CropSnapshot>>,
+    pub fruits: Option<Vec<FruitSnapshot>>,
+    pub chat_msgs: Option<Vec<ChatMsg>>,
+    pub msgs: Option<Vec<Msg>>,
+    pub health: Option<u32>,
+    /// The name of the Map Scene the stream was set in.
+    pub scene: Option<String>,
+}
+
+fn crop_state_to_id(crop_state: &CropState) -> i64 {
+    match crop_state {
+        CropState::Spawned => 0,
+        CropState::Planted => 1,
+        CropState::Growing => 2,
+        CropState::Grown => 3,
+    }
+}
+
+fn crop_state_from_id(id: i64) -> CropState {
+    match id {
+        1 => CropState::Planted,
+        2 => CropState::Growing,
+        3 => CropState::Grown,
+        _ => CropState::Spawned,
+    }
+}
+
+fn fruit_state_to_id(fruit_state: &FruitState) -> i64 {
+    match fruit_state {
+        FruitState::Spawned => 0,
+        FruitState::Hanging => 1,
+        FruitState::Falling => 2,
+        FruitState::Dropped => 3,
+    }
+}
+
+fn fruit_state_from_id(id: i64) -> FruitState {
+    match id {
+        1 => FruitState::Hanging,
+        2 => FruitState::Falling,
+        3 => FruitState::Dropped,
+        _ => FruitState::Spawned,
+    }
+}
+
+fn entity_type_to_id(entity_type: &GameEntityType) -> i64 {
+    match entity_type {
+        GameEntityType::Walk => 0,
+        GameEntityType::Fly => 1,
+        GameEntityType::Swim => 2,
+        GameEntityType::Environment => 3,
+    }
+}
+
+fn entity_type_from_id(id: i64) -> GameEntityType {
+    match id {
+        0 => GameEntityType::Walk,
+        2 => GameEntityType::Swim,
+        3 => GameEntityType::Environment,
+        _ => GameEntityType::Fly,
+    }
+}
+
+fn tilepos_from_row(row: &sqlite::Row, x_column: &str, y_column: &str) -> TilePos {
+    TilePos::new(
+        row.read::<i64, _>(x_column) as u32,
+        row.read::<i64, _>(y_column) as u32,
+    )
+}
+
+#[derive(Resource)]
+pub struct SessionRecords {
+    db_connection: ConnectionThreadSafe,
+}
+
+impl SessionRecords {
+    pub fn new(db_name: String) -> Self {
+        let sqlite_connection = Connection::open_thread_safe(db_name).unwrap();
+
+        let create_tables_query = "
+            CREATE TABLE IF NOT EXISTS session_streamer (x INTEGER, y INTEGER);
+            CREATE TABLE IF NOT EXISTS session_destinations (position INTEGER, x INTEGER, y INTEGER);
+            CREATE TABLE IF NOT EXISTS session_crops (x INTEGER, y INTEGER, state INTEGER, texture_idx INTEGER);
+            CREATE TABLE IF NOT EXISTS session_fruits (respawn_x INTEGER, respawn_y INTEGER, x INTEGER, y INTEGER, state INTEGER);
+            CREATE TABLE IF NOT EXISTS session_chat_msgs (position INTEGER, name TEXT, msg TEXT);
+            CREATE TABLE IF NOT EXISTS session_msgs (position INTEGER, speaker_name TEXT, msg TEXT, speaker_role INTEGER);
+            CREATE TABLE IF NOT EXISTS session_health (current INTEGER);
+            CREATE TABLE IF NOT EXISTS session_scene (name TEXT);
+        ";
+
+        sqlite_connection
+            .execute(create_tables_query)
+            .expect("SessionRecords::new: Could not create Session tables.");
+
+        Self {
+            db_connection: sqlite_connection,
+        }
+    }
+
+    /// Runs some statement for each set of values bound to it.
+    fn insert_rows(&self, insert_query: &str, rows: &[Vec<Value>]) {
+        let mut insert_statement = self
+            .db_connection
+            .prepare(insert_query)
+            .expect("SessionRecords save: Could not make Insert Statement from query.");
+
+        for row in rows {
+            insert_statement
+                .reset()
+                .expect("SessionRecords save: Could not reset insert_statement.");
+            insert_statement
+                .bind(&row[..])
+                .expect("SessionRecords save: Could not bind row values.");
+
+            while insert_statement
+                .next()
+                .expect("SessionRecords save: Could not process insert_statement.")
+                != State::Done
+            {}
+        }
+    }
+
+    /// Replaces whatever session was saved before with
+    /// the one provided.
+    pub fn save(&mut self, session: &SessionSnapshot) {
+        self.db_connection
+            .execute(
+                "
+                BEGIN;
+                DELETE FROM session_streamer;
+                DELETE FROM session_destinations;
+                DELETE FROM session_crops;
+                DELETE FROM session_fruits;
+                DELETE FROM session_chat_msgs;
+                DELETE FROM session_msgs;
+                DELETE FROM session_health;
+                DELETE FROM session_scene;
+                ",
+            )
+            .expect("SessionRecords save: Could not clear the previous session.");
+
+        if let Some(streamer) = &session.streamer {
+            self.insert_rows(
+                "INSERT INTO session_streamer(x, y) VALUES (?, ?);",
+                &[vec![
+                    Value::from(streamer.tile_pos.x as i64),
+                    Value::from(streamer.tile_pos.y as i64),
+                ]],
+            );
+
+            let destination_rows = streamer
+                .destinations
+                .iter()
+                .enumerate()
+                .map(|(position, destination)| {
+                    vec![
+                        Value::from(position as i64),
+                        Value::from(destination.x as i64),
+                        Value::from(destination.y as i64),
+                    ]
+                })
+                .collect::<Vec<Vec<Value>>>();
+            self.insert_rows(
+                "INSERT INTO session_destinations(position, x, y) VALUES (?, ?, ?);",
+                &destination_rows,
+            );
+        }
+
+        if let Some(crops) = &session.crops {
+            let crop_rows = crops
+                .iter()
+                .map(|crop| {
+                    vec![
+                        Value::from(crop.tile_pos.x as i64),
+                        Value::from(crop.tile_pos.y as i64),
+                        Value::from(crop_state_to_id(&crop.state)),
+                        Value::from(crop.texture_idx as i64),
+                    ]
+                })
+                .collect::<Vec<Vec<Value>>>();
+            self.insert_rows(
+                "INSERT INTO session_crops(x, y, state, texture_idx) VALUES (?, ?, ?, ?);",
+                &crop_rows,
+            );
+        }
+
+        if let Some(fruits) = &session.fruits {
+            let fruit_rows = fruits
+                .iter()
+                .map(|fruit| {
+                    vec![
+                        Value::from(fruit.respawn_tile_pos.x as i64),
+                        Value::from(fruit.respawn_tile_pos.y as i64),
+                        Value::from(fruit.tile_pos.x as i64),
+                        Value::from(fruit.tile_pos.y as i64),
+                        Value::from(fruit_state_to_id(&fruit.state)),
+                    ]
+                })
+                .collect::<Vec<Vec<Value>>>();
+            self.insert_rows(
+                "INSERT INTO session_fruits(respawn_x, respawn_y, x, y, state) VALUES (?, ?, ?, ?, ?);",
+                &fruit_rows,
+            );
+        }
+
+        if let Some(chat_msgs) = &session.chat_msgs {
+            let chat_msg_rows = chat_msgs
+                .iter()
+                .enumerate()
+                .map(|(position, chat_msg)| {
+                    vec![
+                        Value::from(position as i64),
+                        Value::from(chat_msg.name.clone()),
+                        Value::from(chat_msg.msg.clone()),
+                    ]
+                })
+                .collect::<Vec<Vec<Value>>>();
+            self.insert_rows(
+                "INSERT INTO session_chat_msgs(position, name, msg) VALUES (?, ?, ?);",
+                &chat_msg_rows,
+            );
+        }
+
+        if let Some(msgs) = &session.msgs {
+            let msg_rows = msgs
+                .iter()
+                .enumerate()
+                .map(|(position, msg)| {
+                    vec![
+                        Value::from(position as i64),
+                        Value::from(msg.speaker_name.clone()),
+                        Value::from(msg.msg.clone()),
+                        Value::from(entity_type_to_id(&msg.speaker_role)),
+                    ]
+                })
+                .collect::<Vec<Vec<Value>>>();
+            self.insert_rows(
+                "INSERT INTO session_msgs(position, speaker_name, msg, speaker_role) VALUES (?, ?, ?, ?);",
+                &msg_rows,
+            );
+        }
+
+        if let Some(health) = session.health {
+            self.insert_rows(
+                "INSERT INTO session_health(current) VALUES (?);",
+                &[vec![Value::from(health as i64)]],
+            );
+        }
+
+        if let Some(scene) = &session.scene {
+            self.insert_rows(
+                "INSERT INTO session_scene(name) VALUES (?);",
+                &[vec![Value::from(scene.clone())]],
+            );
+        }
+
+        self.db_connection
+            .execute("COMMIT;")
+            .expect("SessionRecords save: Could not commit the session.");
+    }
+
+    /// Returns the rows found for some query.
+    fn select_rows(&self, select_query: &str) -> Vec<sqlite::Row> {
+        self.db_connection
+            .prepare(select_query)
+            .expect("SessionRecords load: Could not make Select statement from query.")
+            .into_iter()
+            .map(|row| row.unwrap())
+            .collect()
+    }
+
+    /// Returns the most recently saved session.
+    pub fn load(&self) -> SessionSnapshot {
+        let streamer = self
+            .select_rows("SELECT x, y FROM session_streamer;")
+            .first()
+            .map(|row| StreamerSnapshot {
+                tile_pos: tilepos_from_row(row, "x", "y"),
+                destinations: self
+                    .select_rows("SELECT x, y FROM session_destinations ORDER BY position;")
+                    .iter()
+                    .map(|row| tilepos_from_row(row, "x", "y"))
+                    .collect(),
+            });
+
+        let crops = self
+            .select_rows("SELECT x, y, state, texture_idx FROM session_crops;")
+            .iter()
+            .map(|row| CropSnapshot {
+                tile_pos: tilepos_from_row(row, "x", "y"),
+                state: crop_state_from_id(row.read::<i64, _>("state")),
+                texture_idx: row.read::<i64, _>("texture_idx") as u32,
+            })
+            .collect::<Vec<CropSnapshot>>();
+
+        let fruits = self
+            .select_rows("SELECT respawn_x, respawn_y, x, y, state FROM session_fruits;")
+            .iter()
+            .map(|row| FruitSnapshot {
+                respawn_tile_pos: tilepos_from_row(row, "respawn_x", "respawn_y"),
+                tile_pos: tilepos_from_row(row, "x", "y"),
+                state: fruit_state_from_id(row.read::<i64, _>("state")),
+            })
+            .collect::<Vec<FruitSnapshot>>();
+
+        let chat_msgs = self
+            .select_rows("SELECT name, msg FROM session_chat_msgs ORDER BY position;")
+            .iter()
+            .map(|row| ChatMsg {
+                name: row.read::<&str, _>("name").to_string(),
+                msg: row.read::<&str, _>("msg").to_string(),
+            })
+            .collect::<Vec<ChatMsg>>();
+
+        let msgs = self
+            .select_rows(
+                "SELECT speaker_name, msg, speaker_role FROM session_msgs ORDER BY position;",
+            )
+            .iter()
+            .map(|row| {
+                Msg::new(
+                    row.read::<&str, _>("speaker_name").to_string(),
+                    row.read::<&str, _>("msg").to_string(),
+                    entity_type_from_id(row.read::<i64, _>("speaker_role")),
+                )
+            })
+            .collect::<Vec<Msg>>();
+
+        let health = self
+            .select_rows("SELECT current FROM session_health;")
+            .first()
+            .map(|row| row.read::<i64, _>("current") as u32);
+
+        let scene = self
+            .select_rows("SELECT name FROM session_scene;")
+            .first()
+            .map(|row| row.read::<&str, _>("name").to_string());
+
+        SessionSnapshot {
+            streamer,
+            crops: (!crops.is_empty()).then_some(crops),
+            fruits: (!fruits.is_empty()).then_some(fruits),
+            chat_msgs: (!chat_msgs.is_empty()).then_some(chat_msgs),
+            msgs: (!msgs.is_empty()).then_some(msgs),
+            health,
+            scene,
+        }
+    }
+}
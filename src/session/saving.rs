@@ -0,0 +1,412 @@
+use bevy::prelude::*;
+use bevy_ecs_tilemap::prelude::*;
+
+use super::records::*;
+use crate::entities::chatter::ChatMessageQueue;
+use crate::entities::crop::CropState;
+use crate::entities::fruit::{FruitState, RespawnPoint};
+use crate::entities::streamer::StreamerLabel;
+use crate::entities::TriggerQueue;
+use crate::map::destinations::*;
+use crate::map::path_finding::*;
+use crate::map::scenes::MapScenes;
+use crate::ui::chatting::MessageQueue;
+use crate::ui::screens::HealthProgress;
+
+pub const AUTOSAVE_INTERVAL_SECS: f32 = 30.0;
+
+/// How long the saved session waits on the entities it belongs
+/// to before giving up on whatever has yet to be restored.
+pub const RESTORE_TIMEOUT_SECS: f32 = 60.0;
+
+/// The parts of a saved session still waiting for the
+/// entities they belong to before being restored.
+#[derive(Resource, Default, Deref, DerefMut)]
+pub struct PendingSession(pub SessionSnapshot);
+
+impl PendingSession {
+    /// Returns whether every part of the saved session
+    /// has been restored.
+    pub fn is_restored(&self) -> bool {
+        self.streamer.is_none()
+            && self.crops.is_none()
+            && self.fruits.is_none()
+            && self.chat_msgs.is_none()
+            && self.msgs.is_none()
+            && self.health.is_none()
+            && self.scene.is_none()
+    }
+
+    /// Returns the name of each part of the saved session
+    /// that has yet to be restored.
+    pub fn unrestored_parts(&self) -> Vec<&'static str> {
+        [
+            (self.streamer.is_some(), "Streamer"),
+            (self.crops.is_some(), "Crops"),
+            (self.fruits.is_some(), "Fruit"),
+            (self.chat_msgs.is_some(), "chat messages"),
+            (self.msgs.is_some(), "messages"),
+            (self.health.is_some(), "stream countdown"),
+            (self.scene.is_some(), "Map Scene"),
+        ]
+        .into_iter()
+        .filter(|(is_unrestored, _part_name)| *is_unrestored)
+        .map(|(_is_unrestored, part_name)| part_name)
+        .collect()
+    }
+}
+
+/// Returns the Ground Graph, if it has been made yet.
+fn ground_graph_from<'a>(
+    graphs: impl Iterator<Item = &'a UndirectedGraph>,
+) -> Option<&'a UndirectedGraph> {
+    graphs
+        .into_iter()
+        .find(|graph| *graph.get_node_type() == GraphType::Ground)
+}
+
+/// Saves everything a restart would otherwise lose.
+#[allow(clippy::too_many_arguments)]
+pub fn save_session(
+    mut session_records: ResMut<SessionRecords>,
+    pending_session: Res<PendingSession>,
+    streamer: Query<(&TilePos, &Path, &DestinationQueue), With<StreamerLabel>>,
+    crops: Query<(
+        &TilePos,
+        &CropState,
+        &TileTextureIndex,
+        Option<&TextureAtlas>,
+    )>,
+    fruits: Query<(&TilePos, &FruitState, &RespawnPoint, &Target)>,
+    chat_msg_queues: Query<&ChatMessageQueue>,
+    msg_queues: Query<&MessageQueue>,
+    health_progress: Query<&HealthProgress>,
+    map_info: Query<&TilemapSize>,
+    map_scenes: Res<MapScenes>,
+) {
+    // Saving before the previous session has been restored
+    // would throw away whatever has yet to be restored.
+    if !pending_session.is_restored() {
+        return;
+    }
+
+    let streamer_snapshot = streamer.get_single().ok().and_then(
+        |(streamer_tilepos, streamer_path, streamer_destinations)| {
+            let map_size = map_info.iter().last()?;
+
+            // The Streamer's current destination has already been taken
+            // off of the queue, so it is put back in front of it.
+            let destinations = streamer_path
+                .0
+                .back()
+                .map(|destination| idx_to_tilepos(*destination, map_size.y))
+                .into_iter()
+                .chain(streamer_destinations.destinations())
+                .collect::<Vec<TilePos>>();
+
+            Some(StreamerSnapshot {
+                tile_pos: *streamer_tilepos,
+                destinations,
+            })
+        },
+    );
+
+    let crop_snapshots = crops
+        .iter()
+        .map(
+            |(crop_tilepos, crop_state, tile_texture_index, crop_texture_atlas)| CropSnapshot {
+                tile_pos: *crop_tilepos,
+                state: crop_state.clone(),
+                texture_idx: crop_texture_atlas
+                    .map(|texture_atlas| texture_atlas.index as u32)
+                    .unwrap_or(tile_texture_index.0),
+            },
+        )
+        .collect::<Vec<CropSnapshot>>();
+
+    let fruit_snapshots = fruits
+        .iter()
+        .map(
+            |(fruit_tilepos, fruit_state, fruit_respawn_point, fruit_target)| {
+                // Falling Fruit would have landed by the time the game is back,
+                // so it is saved as if it already has.
+                match (fruit_state, fruit_target.0) {
+                    (FruitState::Falling, Some((_target_translation, target_tilepos))) => {
+                        FruitSnapshot {
+                            respawn_tile_pos: fruit_respawn_point.0 .1,
+                            tile_pos: target_tilepos,
+                            state: FruitState::Dropped,
+                        }
+                    }
+                    _ => FruitSnapshot {
+                        respawn_tile_pos: fruit_respawn_point.0 .1,
+                        tile_pos: *fruit_tilepos,
+                        state: fruit_state.clone(),
+                    },
+                }
+            },
+        )
+        .collect::<Vec<FruitSnapshot>>();
+
+    let chat_msgs = chat_msg_queues
+        .iter()
+        .flat_map(|chat_msg_queue| chat_msg_queue.iter().cloned())
+        .collect::<Vec<_>>();
+
+    let msgs = msg_queues
+        .iter()
+        .flat_map(|msg_queue| (**msg_queue).clone().into_sorted_vec().into_iter().rev())
+        .collect::<Vec<_>>();
+
+    let session = SessionSnapshot {
+        streamer: streamer_snapshot,
+        crops: (!crop_snapshots.is_empty()).then_some(crop_snapshots),
+        fruits: (!fruit_snapshots.is_empty()).then_some(fruit_snapshots),
+        chat_msgs: (!chat_msgs.is_empty()).then_some(chat_msgs),
+        msgs: (!msgs.is_empty()).then_some(msgs),
+        health: health_progress
+            .get_single()
+            .ok()
+            .map(|health_progress| health_progress.current),
+        scene: Some(map_scenes.active().name.clone()),
+    };
+
+    session_records.save(&session);
+}
+
+/// Sets the stream back in the Map Scene it was in, before
+/// its map is brought in and everything else is restored onto it.
+pub fn restore_scene_session(
+    mut pending_session: ResMut<PendingSession>,
+    mut map_scenes: ResMut<MapScenes>,
+) {
+    let Some(saved_scene) = pending_session.scene.take() else {
+        return;
+    };
+
+    if !map_scenes.start_in(&saved_scene) {
+        warn!("restore_scene_session: No Map Scene is named {saved_scene}, so the stream starts in the {} Map Scene.", map_scenes.active().name);
+    }
+}
+
+/// Everything about the Streamer that gets restored.
+type RestoredStreamer<'a> = (
+    &'a mut Transform,
+    &'a mut TilePos,
+    &'a mut StartingPoint,
+    &'a mut Path,
+    &'a mut Target,
+    &'a mut DestinationQueue,
+);
+
+/// Puts the Streamer back where they were, along with
+/// everywhere they still had to go.
+pub fn restore_streamer_session(
+    mut pending_session: ResMut<PendingSession>,
+    mut streamer: Query<RestoredStreamer, With<StreamerLabel>>,
+    graphs: Query<&UndirectedGraph>,
+) {
+    if pending_session.streamer.is_none() || streamer.is_empty() {
+        return;
+    }
+
+    let Some(ground_graph) = ground_graph_from(graphs.iter()) else {
+        return;
+    };
+
+    let streamer_snapshot = pending_session
+        .streamer
+        .take()
+        .expect("restore_streamer_session: Streamer session should exist by now.");
+    let (
+        mut streamer_transform,
+        mut streamer_tilepos,
+        mut streamer_starting_point,
+        mut streamer_path,
+        mut streamer_target,
+        mut streamer_destinations,
+    ) = streamer
+        .get_single_mut()
+        .expect("restore_streamer_session: There should only be one streamer.");
+
+    let saved_idx = tilepos_to_idx(
+        streamer_snapshot.tile_pos.x,
+        streamer_snapshot.tile_pos.y,
+        ground_graph.length(),
+    );
+    if let Some(saved_translation) = ground_graph.get_node(saved_idx) {
+        streamer_transform.translation = *saved_translation;
+        *streamer_tilepos = streamer_snapshot.tile_pos;
+        *streamer_starting_point = StartingPoint(*saved_translation, streamer_snapshot.tile_pos);
+        streamer_path.clear();
+        streamer_target.0 = None;
+    }
+
+    streamer_destinations.clear();
+    for destination in streamer_snapshot.destinations {
+        streamer_destinations
+            .push(DestinationRequest::new(destination).from_source(DestinationSource::Session));
+    }
+}
+
+/// Grows each Crop back to where it was before.
+pub fn restore_crop_session(
+    mut pending_session: ResMut<PendingSession>,
+    mut crops: Query<(
+        &TilePos,
+        &mut CropState,
+        &mut TileTextureIndex,
+        Option<&mut TextureAtlas>,
+    )>,
+) {
+    if pending_session.crops.is_none() || crops.is_empty() {
+        return;
+    }
+
+    let crop_snapshots = pending_session
+        .crops
+        .take()
+        .expect("restore_crop_session: Crop session should exist by now.");
+
+    for (crop_tilepos, mut crop_state, mut tile_texture_index, crop_texture_atlas) in &mut crops {
+        let Some(crop_snapshot) = crop_snapshots
+            .iter()
+            .find(|crop_snapshot| crop_snapshot.tile_pos == *crop_tilepos)
+        else {
+            continue;
+        };
+
+        // Changing these normally grows the Crop by another stage,
+        // so the change is kept hidden from those systems.
+        *crop_state.bypass_change_detection() = crop_snapshot.state.clone();
+        tile_texture_index.0 = crop_snapshot.texture_idx;
+        if let Some(mut crop_texture_atlas) = crop_texture_atlas {
+            crop_texture_atlas.bypass_change_detection().index = crop_snapshot.texture_idx as usize;
+        }
+    }
+}
+
+/// Puts each Fruit back on its tree, or back where it
+/// had dropped for the Streamer to pick up.
+pub fn restore_fruit_session(
+    mut pending_session: ResMut<PendingSession>,
+    mut fruits: Query<(
+        &mut Transform,
+        &mut TilePos,
+        &mut StartingPoint,
+        &RespawnPoint,
+        &mut FruitState,
+        &mut TriggerQueue,
+    )>,
+    graphs: Query<&UndirectedGraph>,
+) {
+    if pending_session.fruits.is_none() || fruits.is_empty() {
+        return;
+    }
+
+    let Some(ground_graph) = ground_graph_from(graphs.iter()) else {
+        return;
+    };
+
+    let fruit_snapshots = pending_session
+        .fruits
+        .take()
+        .expect("restore_fruit_session: Fruit session should exist by now.");
+
+    for (
+        mut fruit_transform,
+        mut fruit_tilepos,
+        mut fruit_starting_point,
+        fruit_respawn_point,
+        mut fruit_state,
+        mut fruit_trigger_queue,
+    ) in &mut fruits
+    {
+        let Some(fruit_snapshot) = fruit_snapshots
+            .iter()
+            .find(|fruit_snapshot| fruit_snapshot.respawn_tile_pos == fruit_respawn_point.0 .1)
+        else {
+            continue;
+        };
+
+        if fruit_snapshot.state == FruitState::Dropped {
+            let dropped_idx = tilepos_to_idx(
+                fruit_snapshot.tile_pos.x,
+                fruit_snapshot.tile_pos.y,
+                ground_graph.length(),
+            );
+            let Some(dropped_translation) = ground_graph.get_node(dropped_idx) else {
+                continue;
+            };
+
+            fruit_transform.translation = *dropped_translation;
+            *fruit_tilepos = fruit_snapshot.tile_pos;
+            *fruit_starting_point = StartingPoint(*dropped_translation, fruit_snapshot.tile_pos);
+            fruit_trigger_queue.push_back(());
+        }
+
+        // The Streamer's saved destinations already lead to any
+        // dropped Fruit, so the change is kept hidden.
+        *fruit_state.bypass_change_detection() = fruit_snapshot.state.clone();
+    }
+}
+
+/// Gives back the chat messages that had yet to be read.
+pub fn restore_chat_session(
+    mut pending_session: ResMut<PendingSession>,
+    mut chat_msg_queues: Query<&mut ChatMessageQueue>,
+    mut msg_queues: Query<&mut MessageQueue>,
+) {
+    if pending_session.chat_msgs.is_some() && !chat_msg_queues.is_empty() {
+        let chat_msgs = pending_session
+            .chat_msgs
+            .take()
+            .expect("restore_chat_session: Chat Message session should exist by now.");
+
+        let mut chat_msg_queue = chat_msg_queues.single_mut();
+        chat_msg_queue.extend(chat_msgs);
+    }
+
+    if pending_session.msgs.is_some() && !msg_queues.is_empty() {
+        let msgs = pending_session
+            .msgs
+            .take()
+            .expect("restore_chat_session: Message session should exist by now.");
+
+        let mut msg_queue = msg_queues.single_mut();
+        msg_queue.extend(msgs);
+    }
+}
+
+/// Picks the stream's countdown back up from where it was.
+pub fn restore_health_session(
+    mut pending_session: ResMut<PendingSession>,
+    mut health_progress: Query<&mut HealthProgress>,
+) {
+    if pending_session.health.is_none() || health_progress.is_empty() {
+        return;
+    }
+
+    let saved_health = pending_session
+        .health
+        .take()
+        .expect("restore_health_session: Health session should exist by now.");
+
+    let mut health_progress = health_progress.single_mut();
+    health_progress.current = saved_health.min(health_progress.total);
+}
+
+/// Gives up on each part of the saved session whose entities never
+/// appeared, such as those of a map or scene that is gone, so that
+/// autosaving carries on instead of waiting on them forever.
+pub fn abandon_unrestored_session(mut pending_session: ResMut<PendingSession>) {
+    if pending_session.is_restored() {
+        return;
+    }
+
+    warn!(
+        "abandon_unrestored_session: Gave up on restoring the saved {} after {RESTORE_TIMEOUT_SECS} seconds.",
+        pending_session.unrestored_parts().join(", ")
+    );
+    *pending_session = PendingSession::default();
+}
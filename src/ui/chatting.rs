@@ -4,6 +4,8 @@ use std::collections::BinaryHeap;
 use bevy::prelude::*;
 
 use super::screens::{SpeakerChatBox, SpeakerPortrait, SpeakerUI};
+use crate::entities::critter::CritterLabel;
+use crate::entities::raider::RaiderLabel;
 use crate::entities::GameEntityType;
 use crate::ui::portrait_preferences::PortraitPreferences;
 
@@ -231,6 +233,14 @@ pub fn load_queued_msg_into_textfield(
         .insert((TypingMsg::new(recent_msg.clone()), typing_speed_timer));
 }
 
+/// Leaves out entities sharing a movement type with a speaker,
+/// so their textures are never picked for the portrait.
+type PortraitSourceFilter = (
+    Without<SpeakerPortrait>,
+    Without<CritterLabel>,
+    Without<RaiderLabel>,
+);
+
 /// Loads the Speaker Portrait based on the currently
 /// loaded message.
 pub fn load_portrait_from_msg(
@@ -238,7 +248,7 @@ pub fn load_portrait_from_msg(
     mut speaker_portrait: Query<(&mut UiImage, &mut TextureAtlas), With<SpeakerPortrait>>,
     chatting_portraits: Query<
         (&TextureAtlas, &Handle<Image>, &GameEntityType),
-        Without<SpeakerPortrait>,
+        PortraitSourceFilter,
     >,
     portrait_preferences: Res<PortraitPreferences>,
 ) {
@@ -2,8 +2,6 @@ use bevy::prelude::*;
 
 use super::chatting::*;
 use super::screens::*;
-use crate::entities::chatter::speak_to_streamer_from_chatter;
-use crate::entities::subscriber::speak_to_streamer_from_subscriber;
 use crate::ui::portrait_preferences::{PortraitPreferences, DEFAULT_SUBSCRIBER_SPRITE_IDX};
 use crate::GameState;
 
@@ -55,8 +53,6 @@ impl Plugin for ChattingPlugin {
                 insert_chatting_information,
                 load_msg_into_queue,
                 load_portrait_from_msg,
-                speak_to_streamer_from_chatter,
-                speak_to_streamer_from_subscriber,
                 load_queued_msg_into_textfield,
                 teletype_current_message,
                 play_typing_noise,
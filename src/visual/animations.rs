@@ -1,8 +1,17 @@
 use bevy::prelude::*;
 
 use crate::{
-    entities::{streamer::StreamerLabel, GameEntityType},
-    map::path_finding::{tilepos_to_idx, Direction, Path},
+    entities::{
+        critter::CritterKind,
+        definitions::{EntityDefinitions, EntityKind},
+        streamer::StreamerLabel,
+        GameEntityType,
+    },
+    map::{
+        links::CrossingLink,
+        path_finding::{tilepos_to_idx, Direction, Path},
+    },
+    visual::tile_animations::TileAnimation,
 };
 
 #[derive(Component, Deref, DerefMut)]
@@ -14,20 +23,48 @@ pub struct AnimationIndices {
     pub end_idx: usize,
 }
 
+/// Anything that isn't animated by its Tiled tileset.
+type SpriteAnimated<'a> = (Entity, Ref<'a, GameEntityType>, Option<&'a CritterKind>);
+
+/// Sets up the sprite sheet animation of anything not already
+/// animated by its Tiled tileset, once it is spawned and again
+/// whenever the definitions of how it animates change.
 pub fn insert_animation_information(
-    moving_entities: Query<(Entity, &GameEntityType), Added<GameEntityType>>,
+    moving_entities: Query<SpriteAnimated, Without<TileAnimation>>,
+    entity_definitions: Res<EntityDefinitions>,
     mut commands: Commands,
 ) {
-    for (moving_entity, entity_type) in &moving_entities {
-        let start_idx = match entity_type {
-            GameEntityType::Walk => ground_directional_index_from(&Direction::BottomRight),
-            GameEntityType::Fly => fly_directional_index_from(&Direction::BottomRight),
-            GameEntityType::Swim => swim_directional_index_from(&Direction::BottomRight),
+    for (moving_entity, entity_type, critter_kind) in &moving_entities {
+        if !entity_type.is_added() && !entity_definitions.is_changed() {
+            continue;
+        }
+
+        // NOTE: Critters share a spritesheet of their own, animating
+        // along the row for their kind whichever way they head.
+        if let Some(critter_kind) = critter_kind {
+            let critter_definition = entity_definitions.get(EntityKind::Critter);
+            let start_idx = critter_kind.sprite_row() * critter_definition.columns as usize;
+            let end_idx = start_idx + critter_definition.animation_len();
+
+            commands.entity(moving_entity).insert((
+                AnimationTimer(Timer::from_seconds(0.1, TimerMode::Repeating)),
+                AnimationIndices { start_idx, end_idx },
+            ));
+            continue;
+        }
+
+        let row_len = movement_type_len(&entity_type, &entity_definitions);
+        let start_idx = match *entity_type {
+            GameEntityType::Walk => {
+                ground_directional_index_from(&Direction::BottomRight, row_len, &entity_definitions)
+            }
+            GameEntityType::Fly => fly_directional_index_from(&Direction::BottomRight, row_len),
+            GameEntityType::Swim => swim_directional_index_from(&Direction::BottomRight, row_len),
             // This represents the Campfire for now, which consists of a single row.
             GameEntityType::Environment => 0,
         };
 
-        let end_idx = start_idx + movement_type_len(entity_type);
+        let end_idx = start_idx + row_len;
 
         commands.entity(moving_entity).insert((
             AnimationTimer(Timer::from_seconds(0.1, TimerMode::Repeating)),
@@ -36,44 +73,52 @@ pub fn insert_animation_information(
     }
 }
 
-pub fn movement_type_len(entity_type: &GameEntityType) -> usize {
-    match entity_type {
-        GameEntityType::Walk => 4,
-        GameEntityType::Fly => 8,
-        GameEntityType::Swim => 16,
-        // "Environment" is Campfire for the time being.
-        GameEntityType::Environment => 23,
-    }
+/// Returns how many frames make up each animation for
+/// anything getting around in some way.
+pub fn movement_type_len(
+    entity_type: &GameEntityType,
+    entity_definitions: &EntityDefinitions,
+) -> usize {
+    entity_definitions.animation_len_for(entity_type)
 }
 
-fn ground_directional_index_from(direction: &Direction) -> usize {
-    let num_ground_sprites_in_row = movement_type_len(&GameEntityType::Walk) as u32;
+fn ground_directional_index_from(
+    direction: &Direction,
+    row_len: usize,
+    entity_definitions: &EntityDefinitions,
+) -> usize {
+    let num_ground_sprites_in_row = row_len as u32;
+    let heading_row = entity_definitions
+        .heading_row_for(&GameEntityType::Walk, direction)
+        .unwrap_or(0);
 
-    match direction {
-        Direction::BottomLeft => tilepos_to_idx(7, 0, num_ground_sprites_in_row),
-        Direction::BottomRight => tilepos_to_idx(8, 0, num_ground_sprites_in_row),
-        Direction::TopLeft => tilepos_to_idx(5, 0, num_ground_sprites_in_row),
-        Direction::TopRight => tilepos_to_idx(6, 0, num_ground_sprites_in_row),
-    }
+    tilepos_to_idx(heading_row as u32, 0, num_ground_sprites_in_row)
 }
 
-fn fly_directional_index_from(_direction: &Direction) -> usize {
-    let num_flying_sprites_in_row = movement_type_len(&GameEntityType::Fly) as u32;
+fn fly_directional_index_from(_direction: &Direction, row_len: usize) -> usize {
+    let num_flying_sprites_in_row = row_len as u32;
 
     tilepos_to_idx(1, 0, num_flying_sprites_in_row)
 }
 
-fn swim_directional_index_from(_direction: &Direction) -> usize {
-    let num_swim_sprites_in_row = movement_type_len(&GameEntityType::Swim) as u32;
+fn swim_directional_index_from(_direction: &Direction, row_len: usize) -> usize {
+    let num_swim_sprites_in_row = row_len as u32;
 
     tilepos_to_idx(1, 0, num_swim_sprites_in_row)
 }
 
-fn direction_to_row_index(direction: &Direction, entity_type: &GameEntityType) -> usize {
+fn direction_to_row_index(
+    direction: &Direction,
+    entity_type: &GameEntityType,
+    row_len: usize,
+    entity_definitions: &EntityDefinitions,
+) -> usize {
     match entity_type {
-        GameEntityType::Walk => ground_directional_index_from(direction),
-        GameEntityType::Fly => fly_directional_index_from(direction),
-        GameEntityType::Swim => swim_directional_index_from(direction),
+        GameEntityType::Walk => {
+            ground_directional_index_from(direction, row_len, entity_definitions)
+        }
+        GameEntityType::Fly => fly_directional_index_from(direction, row_len),
+        GameEntityType::Swim => swim_directional_index_from(direction, row_len),
         _ => 0,
     }
 }
@@ -88,8 +133,14 @@ pub fn change_fish_or_chatter_direction(
         // the fish is facing something or someone.
         if *entity_type == GameEntityType::Swim || *entity_type == GameEntityType::Fly {
             match entity_direction {
-                Direction::BottomLeft | Direction::TopLeft => entity_spritesheet.flip_x = false,
-                Direction::BottomRight | Direction::TopRight => entity_spritesheet.flip_x = true,
+                Direction::BottomLeft | Direction::TopLeft | Direction::Left => {
+                    entity_spritesheet.flip_x = false
+                }
+                Direction::BottomRight | Direction::TopRight | Direction::Right => {
+                    entity_spritesheet.flip_x = true
+                }
+                // Heading straight up or down keeps facing the same side.
+                Direction::Top | Direction::Bottom => {}
             }
 
             continue;
@@ -97,26 +148,52 @@ pub fn change_fish_or_chatter_direction(
     }
 }
 
+/// Everything needed to pick which row of its spritesheet some entity animates.
+type AnimatedMover<'a> = (
+    &'a mut AnimationIndices,
+    &'a mut TextureAtlas,
+    &'a GameEntityType,
+    &'a Direction,
+    Option<&'a CrossingLink>,
+);
+
+/// Whatever changes which row of its spritesheet some entity animates,
+/// leaving out Critters, whose row is set by their kind instead.
+type SpriteRowChanged = (
+    Or<(Changed<Direction>, Changed<CrossingLink>)>,
+    Without<CritterKind>,
+);
+
+/// Animates each entity along the row for its heading, or for
+/// however it is crossing a Link while it crosses one.
 pub fn change_sprite_direction(
-    mut moving_entities: Query<
-        (
-            &mut AnimationIndices,
-            &mut TextureAtlas,
-            &GameEntityType,
-            &Direction,
-        ),
-        Changed<Direction>,
-    >,
+    mut moving_entities: Query<AnimatedMover, SpriteRowChanged>,
+    entity_definitions: Res<EntityDefinitions>,
 ) {
-    for (mut animation_indices, mut entity_spritesheet, entity_type, entity_direction) in
-        &mut moving_entities
+    for (
+        mut animation_indices,
+        mut entity_spritesheet,
+        entity_type,
+        entity_direction,
+        crossing_link,
+    ) in &mut moving_entities
     {
         if *entity_type == GameEntityType::Swim || *entity_type == GameEntityType::Fly {
             continue;
         }
 
-        animation_indices.start_idx = direction_to_row_index(entity_direction, entity_type);
-        animation_indices.end_idx = animation_indices.start_idx + movement_type_len(entity_type);
+        let row_len = movement_type_len(entity_type, &entity_definitions);
+        let transition_row = crossing_link
+            .and_then(|crossing_link| crossing_link.0)
+            .and_then(|transition| entity_definitions.transition_row_for(entity_type, &transition));
+
+        animation_indices.start_idx = match transition_row {
+            Some(transition_row) => tilepos_to_idx(transition_row as u32, 0, row_len as u32),
+            None => {
+                direction_to_row_index(entity_direction, entity_type, row_len, &entity_definitions)
+            }
+        };
+        animation_indices.end_idx = animation_indices.start_idx + row_len;
         entity_spritesheet.index = animation_indices.start_idx;
     }
 }
@@ -1,12 +1,14 @@
 use bevy::prelude::*;
 use bevy_ecs_tilemap::prelude::*;
 
+use crate::entities::definitions::{EntityDefinitions, EntityKind, SpriteDefinition};
 use crate::entities::streamer::StreamerLabel;
 use crate::entities::GameEntityType;
+use crate::map::hot_reload::MapPlaced;
+use crate::map::layer_roles::LayerRoles;
 use crate::map::path_finding::Direction;
-use crate::map::tiled::{to_bevy_transform, LayerNumber, TiledMapInformation};
-
-pub const CAMPFIRE_LAYER_NUM: usize = 20;
+use crate::map::tiled::{find_layer, tile_transform_on, LayerNumber, MapLayer};
+use crate::visual::tile_animations::TileAnimation;
 
 #[derive(Component)]
 pub struct CampfireLabel;
@@ -17,12 +19,12 @@ pub fn replace_campfire_sprite(
     mut texture_atlases: ResMut<Assets<TextureAtlasLayout>>,
     mut commands: Commands,
     asset_server: Res<AssetServer>,
+    entity_definitions: Res<EntityDefinitions>,
 ) {
+    let campfire_definition = entity_definitions.get(EntityKind::Campfire);
     for (campfire_entity, campfire_transform, tile_texture_index) in &campfire {
-        let texture_handle =
-            asset_server.load("environment/Sprite-sheet-campfire-trimmed-64x64.png");
-        let campfire_texture_atlas =
-            TextureAtlasLayout::from_grid(UVec2::new(64, 64), 23, 1, None, None);
+        let texture_handle = asset_server.load(campfire_definition.sprite.clone());
+        let campfire_texture_atlas = campfire_definition.texture_atlas_layout();
         let campfire_texture_atlas_handle = texture_atlases.add(campfire_texture_atlas);
 
         let campfire_texture_atlas = TextureAtlas {
@@ -38,42 +40,61 @@ pub fn replace_campfire_sprite(
         };
 
         commands.entity(campfire_entity).remove::<Transform>();
-        commands
-            .entity(campfire_entity)
-            .insert((campfire_sprite, campfire_texture_atlas));
+        commands.entity(campfire_entity).insert((
+            campfire_sprite,
+            campfire_texture_atlas,
+            SpriteDefinition(EntityKind::Campfire),
+        ));
     }
 }
 
 /// Respawns Campfire without rendering components
 pub fn replace_campfire_tile(
-    tiles_query: Query<(Entity, &LayerNumber, &TilePos, &TileTextureIndex)>,
-    map_info_query: Query<
-        (&Transform, &TilemapGridSize, &TilemapSize, &TilemapType),
-        Added<TilemapGridSize>,
-    >,
+    tiles_query: Query<(
+        Entity,
+        &LayerNumber,
+        &TilePos,
+        &TileTextureIndex,
+        Option<&TileAnimation>,
+    )>,
+    map_layers: Query<MapLayer, Added<TilemapGridSize>>,
+    placed_campfires: Query<(Entity, &TilePos), With<CampfireLabel>>,
     mut commands: Commands,
+    entity_definitions: Res<EntityDefinitions>,
+    layer_roles: Res<LayerRoles>,
 ) {
-    let map_information = map_info_query
-        .iter()
-        .find(|map_info| map_info.0.translation.z == CAMPFIRE_LAYER_NUM as f32);
-
-    if map_information.is_none() {
+    let campfire_definition = entity_definitions.get(EntityKind::Campfire);
+    let (Some(campfire_layer), Some(campfire_spawn_layer)) = (
+        campfire_definition.drawn_layer(&layer_roles),
+        campfire_definition.spawn_layer(&layer_roles),
+    ) else {
         return;
-    }
-
-    let (map_transform, grid_size, map_size, map_type) =
-        map_information.expect("replace_campfire_tile: Map information should exist by now.");
+    };
 
-    for (campfire_entity, layer_number, tile_pos, tile_texture_index) in &tiles_query {
-        if layer_number.0 != CAMPFIRE_LAYER_NUM {
+    let Some(spawn_map_layer) = find_layer(&map_layers, campfire_spawn_layer) else {
+        return;
+    };
+
+    let mut placed_campfires = MapPlaced::new(
+        placed_campfires
+            .iter()
+            .map(|(campfire_entity, tile_pos)| (campfire_entity, *tile_pos)),
+    );
+    for (campfire_entity, layer_number, tile_pos, tile_texture_index, tile_animation) in
+        &tiles_query
+    {
+        if layer_number.0 != campfire_layer {
             continue;
         }
 
-        let map_info = TiledMapInformation::new(grid_size, map_size, map_type, map_transform);
-        let tile_transform = to_bevy_transform(tile_pos, map_info);
+        let tile_transform = tile_transform_on(tile_pos, spawn_map_layer);
 
         commands.entity(campfire_entity).despawn_recursive();
-        commands.spawn((
+        if placed_campfires.keep(tile_pos).is_some() {
+            continue;
+        }
+
+        let mut campfire_commands = commands.spawn((
             (
                 CampfireLabel,
                 tile_transform,
@@ -82,7 +103,13 @@ pub fn replace_campfire_tile(
             ),
             *tile_pos,
         ));
+        // NOTE: Keep flickering through the frames the Tiled tileset
+        // animates the Campfire with, once drawn as a sprite.
+        if let Some(tile_animation) = tile_animation {
+            campfire_commands.insert(tile_animation.clone());
+        }
     }
+    placed_campfires.despawn_rest(&mut commands);
 }
 
 /// Makes the Streamer face towards the Campfire when
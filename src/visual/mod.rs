@@ -1,3 +1,4 @@
 pub mod animations;
 pub mod environment;
 pub mod plugins;
+pub mod tile_animations;
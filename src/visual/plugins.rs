@@ -2,6 +2,7 @@ use bevy::prelude::*;
 
 use super::animations::*;
 use super::environment::*;
+use super::tile_animations::*;
 
 #[derive(Default)]
 pub struct AnimationPlugin;
@@ -16,6 +17,7 @@ impl Plugin for AnimationPlugin {
                 change_fish_or_chatter_direction,
                 animate,
                 check_if_idle,
+                animate_tile_sprites,
             ),
         );
     }
@@ -0,0 +1,107 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+/// One frame of some Tile's animation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TileFrame {
+    /// The texture index drawn during the frame.
+    pub index: u32,
+    pub duration: Duration,
+}
+
+/// Flicks some Tile through the frames its Tiled tileset animates it with.
+#[derive(Component, Debug, Clone, PartialEq, Eq)]
+pub struct TileAnimation {
+    frames: Vec<TileFrame>,
+    frame_idx: usize,
+    frame_elapsed: Duration,
+}
+
+impl TileAnimation {
+    /// Returns an animation going through some frames in order,
+    /// or None when there is nothing to animate.
+    pub fn new(frames: Vec<TileFrame>) -> Option<Self> {
+        let total_duration = frames.iter().map(|frame| frame.duration).sum::<Duration>();
+        if frames.len() < 2 || total_duration.is_zero() {
+            return None;
+        }
+
+        Some(Self {
+            frames,
+            frame_idx: 0,
+            frame_elapsed: Duration::ZERO,
+        })
+    }
+
+    /// Returns the animation a Tiled tileset gives one of its Tiles, with
+    /// each frame's Tile turned into the texture index drawing it.
+    pub fn from_tile(
+        tile: &tiled::Tile,
+        texture_index_of: impl Fn(u32) -> Option<u32>,
+    ) -> Option<Self> {
+        let frames = tile
+            .animation
+            .as_ref()?
+            .iter()
+            .map(|frame| {
+                Some(TileFrame {
+                    index: texture_index_of(frame.tile_id)?,
+                    duration: Duration::from_millis(frame.duration as u64),
+                })
+            })
+            .collect::<Option<Vec<TileFrame>>>()?;
+
+        Self::new(frames)
+    }
+
+    /// Returns every frame of the animation.
+    pub fn frames(&self) -> &[TileFrame] {
+        &self.frames
+    }
+
+    /// Returns the texture index of the frame being drawn.
+    pub fn current_index(&self) -> u32 {
+        self.frames[self.frame_idx].index
+    }
+
+    /// Moves the animation along by some time, returning
+    /// the texture index of the frame now being drawn.
+    pub fn advance(&mut self, delta: Duration) -> u32 {
+        self.frame_elapsed += delta;
+
+        // NOTE: Skip over whole loops of the animation at once,
+        // such as after the game has been paused for a while.
+        let total_duration = self
+            .frames
+            .iter()
+            .map(|frame| frame.duration)
+            .sum::<Duration>();
+        if self.frame_elapsed >= total_duration {
+            self.frame_elapsed = Duration::from_nanos(
+                (self.frame_elapsed.as_nanos() % total_duration.as_nanos()) as u64,
+            );
+        }
+
+        while self.frame_elapsed >= self.frames[self.frame_idx].duration {
+            self.frame_elapsed -= self.frames[self.frame_idx].duration;
+            self.frame_idx = (self.frame_idx + 1) % self.frames.len();
+        }
+
+        self.current_index()
+    }
+}
+
+/// Plays the animation of each Tile drawn as a sprite, whether
+/// drawn by the Tilemap or spawned as some entity of its own.
+pub fn animate_tile_sprites(
+    mut animated_sprites: Query<(&mut TileAnimation, &mut TextureAtlas)>,
+    time: Res<Time>,
+) {
+    for (mut tile_animation, mut texture_atlas) in &mut animated_sprites {
+        let frame_index = tile_animation.advance(time.delta()) as usize;
+        if texture_atlas.index != frame_index {
+            texture_atlas.index = frame_index;
+        }
+    }
+}
@@ -26,3 +26,15 @@ fn path_without_assets_not_trimmed() {
     let expected_path = input_path.clone();
     assert_eq!(expected_path, actual_path);
 }
+
+#[test]
+fn path_with_parent_folders_normalized() {
+    // Given
+    let input_path = PathBuf::from("maps/nested/../../environment/./terrain_0.png");
+    // When
+    let actual_path = to_bevy_path(input_path);
+
+    // Then
+    let expected_path = PathBuf::from("environment/terrain_0.png");
+    assert_eq!(expected_path, actual_path);
+}
@@ -15,6 +15,7 @@ use cucumber::{given, then, when, World};
 
 use task_masker::entities::chatter::*;
 use task_masker::entities::streamer::*;
+use task_masker::entities::visitor::{StreamerFlowFields, VisitorStatus};
 use task_masker::map::path_finding::*;
 use task_masker::map::plugins::PathFindingPlugin;
 
@@ -95,10 +96,10 @@ fn wait_for_chatter_to_approach_to_speak(world: &mut GameWorld) {
         world.update(1);
 
         let chatter_status = world
-            .find::<ChatterStatus>()
+            .find::<VisitorStatus>()
             .expect("wait_for_chatter_to_approach_to_speak: Chatter does not have a Status.");
 
-        if *chatter_status == ChatterStatus::Speaking {
+        if *chatter_status == VisitorStatus::Speaking {
             break;
         }
     }
@@ -145,10 +146,10 @@ fn wait_for_chatter_to_finish_speaking(world: &mut GameWorld) {
         world.update(1);
 
         let chatter_status = world
-            .find::<ChatterStatus>()
+            .find::<VisitorStatus>()
             .expect("wait_for_chatter_to_finish_speaking: Chatter does not have a Status.");
 
-        if *chatter_status != ChatterStatus::Speaking {
+        if *chatter_status != VisitorStatus::Speaking {
             break;
         }
     }
@@ -195,9 +196,9 @@ fn chatter_should_still_be_speaking(world: &mut GameWorld) {
         .at_end();
     assert!(msg_is_still_being_typed);
 
-    let expected_chatter_status = ChatterStatus::Speaking;
+    let expected_chatter_status = VisitorStatus::Speaking;
     let actual_chatter_status = world
-        .find::<ChatterStatus>()
+        .find::<VisitorStatus>()
         .expect("chatter_should_still_be_speaking: Chatter status could not be found.");
 
     assert_eq!(expected_chatter_status, *actual_chatter_status);
@@ -211,27 +212,60 @@ fn chatter_should_approach_to_streamer(world: &mut GameWorld) {
     world.update(1);
 
     let chatter_status = world
-        .find::<ChatterStatus>()
+        .find::<VisitorStatus>()
         .expect("chatter_should_approach_to_streamer: Chatter does not have a Status.");
 
-    assert_eq!(*chatter_status, ChatterStatus::Approaching);
+    assert_eq!(*chatter_status, VisitorStatus::Approaching);
 
     let chatter_path = world
-        .find_with::<Path, ChatterStatus>()
+        .find_with::<Path, VisitorStatus>()
         .expect("chatter_should_approach_to_streamer: Chatter does not have a Path.");
 
     assert_ne!(chatter_path.len(), 0);
 }
 
+#[then("the Chatter will follow the Flow Field towards the Streamer")]
+fn chatter_should_follow_flow_field(world: &mut GameWorld) {
+    world.update(1);
+
+    let streamer_tilepos = *world
+        .find_with::<TilePos, StreamerLabel>()
+        .expect("chatter_should_follow_flow_field: Streamer does not have a TilePos.");
+    let chatter_path = world
+        .find_with::<Path, ChatterLabel>()
+        .expect("chatter_should_follow_flow_field: Chatter does not have a Path.")
+        .clone();
+
+    let mut graphs = world.app.world_mut().query::<&UndirectedGraph>();
+    let air_graph = graph_of(graphs.iter(world.app.world()), GraphType::Air)
+        .expect("chatter_should_follow_flow_field: The Air Graph should exist.");
+    let flow_field = world
+        .app
+        .world()
+        .resource::<StreamerFlowFields>()
+        .towards(GraphType::Air, streamer_tilepos)
+        .expect("chatter_should_follow_flow_field: A Flow Field should lead to the Streamer.");
+
+    let chatter_start = idx_to_tilepos(chatter_path.0[0], air_graph.length());
+    let flow_path = flow_field
+        .path_from(air_graph, chatter_start)
+        .expect("chatter_should_follow_flow_field: The Flow Field should reach the Chatter.");
+
+    // The Chatter stops short of the Streamer, so only
+    // follows the start of the Flow Field's Path.
+    assert!(flow_path.0.iter().zip(&chatter_path.0).all(|(a, b)| a == b));
+    assert!(chatter_path.len() <= flow_path.len());
+}
+
 #[then("the Chatter will be two tiles away from the Streamer")]
 fn chatter_should_be_two_tiles_away_from_streamer(world: &mut GameWorld) {
     world.update(1);
 
     let chatter_status = world
-        .find::<ChatterStatus>()
+        .find::<VisitorStatus>()
         .expect("chatter_should_approach_to_streamer: Chatter does not have a Status.");
 
-    assert_eq!(*chatter_status, ChatterStatus::Speaking);
+    assert_eq!(*chatter_status, VisitorStatus::Speaking);
 
     let chatter_tilepos = *world
         .find_with::<TilePos, ChatterLabel>()
@@ -250,10 +284,10 @@ fn chatter_should_start_speaking(world: &mut GameWorld) {
     world.update(1);
 
     let chatter_status = world
-        .find::<ChatterStatus>()
+        .find::<VisitorStatus>()
         .expect("chatter_should_start_speaking: Chatter does not have a Status.");
 
-    assert_eq!(*chatter_status, ChatterStatus::Speaking);
+    assert_eq!(*chatter_status, VisitorStatus::Speaking);
 }
 
 #[then("the Chatter leaves back to its resting point")]
@@ -262,10 +296,10 @@ fn chatter_should_be_leaving_back_to_spawn(world: &mut GameWorld) {
         world.update(1);
 
         let chatter_status = world
-            .find::<ChatterStatus>()
+            .find::<VisitorStatus>()
             .expect("chatter_should_be_leaving_back_to_spawn: Chatter does not have a Status.");
 
-        if *chatter_status == ChatterStatus::Idle {
+        if *chatter_status == VisitorStatus::Idle {
             break;
         }
     }
@@ -273,7 +307,7 @@ fn chatter_should_be_leaving_back_to_spawn(world: &mut GameWorld) {
     let (chatter_tilepos, chatter_spawn) = world
         .app
         .world_mut()
-        .query_filtered::<(&TilePos, &SpawnPoint), With<ChatterStatus>>()
+        .query_filtered::<(&TilePos, &SpawnPoint), With<ChatterLabel>>()
         .get_single(&world.app.world())
         .expect("chatter_should_be_leaving_back_to_spawn: Chatter is missing pathfinding-based information and/or Status.");
 
@@ -0,0 +1,195 @@
+mod mock_plugins;
+
+use bevy::prelude::*;
+use bevy_ecs_tilemap::prelude::*;
+use cucumber::{given, then, when, World};
+
+use crate::mock_plugins::{GameWorld, MockCritterPlugin, MockTiledMapPlugin};
+use task_masker::entities::critter::*;
+use task_masker::entities::definitions::{EntityDefinitions, EntityKind};
+use task_masker::entities::GameEntityType;
+use task_masker::map::plugins::PathFindingPlugin;
+use task_masker::map::scenes::{MapScene, MapScenes, SwitchSceneEvent};
+use task_masker::visual::animations::{insert_animation_information, AnimationIndices};
+
+#[given("a Tiled Map,")]
+fn spawn_tiled_map(world: &mut GameWorld) {
+    world.app.add_plugins(MockTiledMapPlugin);
+    world.update(1);
+
+    world.app.add_plugins(PathFindingPlugin);
+    world.update(1);
+}
+
+#[given("a record of previous followers,")]
+fn insert_follower_records(world: &mut GameWorld) {
+    world
+        .app
+        .insert_resource(FollowerRecords::new(String::from(":memory:")));
+}
+
+#[given(regex = r"^(\d+) followers were recorded on a previous stream,")]
+fn record_previous_followers(world: &mut GameWorld, num_followers: String) {
+    let mut follower_records = world.app.world_mut().resource_mut::<FollowerRecords>();
+
+    for follower_num in 0..num_followers.parse::<u32>().unwrap() {
+        follower_records.add(
+            &format!("Followu{}", follower_num),
+            CritterKind::Rabbit,
+            TilePos::new(follower_num, 0),
+        );
+    }
+}
+
+#[given("a Frog follower was recorded on a previous stream,")]
+fn record_previous_frog_follower(world: &mut GameWorld) {
+    world.app.world_mut().resource_mut::<FollowerRecords>().add(
+        "Froggu",
+        CritterKind::Frog,
+        TilePos::new(0, 0),
+    );
+}
+
+#[given("Critters can live on the Tiled Map,")]
+fn allow_critters_on_tiled_map(world: &mut GameWorld) {
+    world.app.add_plugins(MockCritterPlugin);
+    world.update(1);
+}
+
+#[given("Critters are animated,")]
+fn animate_critters(world: &mut GameWorld) {
+    world.app.init_resource::<EntityDefinitions>();
+    world.app.add_systems(Update, insert_animation_information);
+}
+
+#[when(regex = r"^(\w+) follows the channel,")]
+fn follower_follows(world: &mut GameWorld, follower_name: String) {
+    world.broadcast_event(FollowMsg {
+        name: follower_name,
+    });
+    world.update(2);
+}
+
+#[when("as many followers as the Critter cap follow the channel,")]
+fn many_followers_follow(world: &mut GameWorld) {
+    for follower_num in 0..MAX_CRITTERS_ON_SCREEN {
+        world.broadcast_event(FollowMsg {
+            name: format!("Earlyu{}", follower_num),
+        });
+        world.update(1);
+    }
+    world.update(1);
+}
+
+#[when(regex = r"^the stream switches to the (.+) scene,")]
+fn switch_to_scene(world: &mut GameWorld, scene_name: String) {
+    world.broadcast_event(SwitchSceneEvent::Named(scene_name));
+
+    // The new map is laid out, then has its Graphs built
+    // from it, before the Critters are restored onto it.
+    world.update(3);
+}
+
+#[when("the stream switches to a map without water,")]
+fn switch_to_map_without_water(world: &mut GameWorld) {
+    world
+        .app
+        .world_mut()
+        .resource_mut::<MapScenes>()
+        .register(MapScene {
+            name: String::from("Reedy Pond"),
+            map_path: String::from("../tests/test-assets/maps/no_subscribers.tmx"),
+            activities: Vec::new(),
+            streamer_spawn: TilePos::new(3, 3),
+            online_spot: TilePos::new(3, 4),
+        });
+    switch_to_scene(world, String::from("Reedy Pond"));
+}
+
+#[then(regex = r"^there should be (\d+) Critters on the Tiled Map")]
+fn critters_on_map(world: &mut GameWorld, expected_critters: String) {
+    world.update(1);
+
+    let expected_num_critters = expected_critters.parse::<usize>().unwrap();
+    let actual_num_critters = world.find_all::<CritterLabel>().len();
+
+    assert_eq!(expected_num_critters, actual_num_critters);
+}
+
+#[then("there should be as many Critters as the Critter cap on the Tiled Map,")]
+fn critters_capped(world: &mut GameWorld) {
+    let actual_num_critters = world.find_all::<CritterLabel>().len();
+
+    assert_eq!(MAX_CRITTERS_ON_SCREEN, actual_num_critters);
+}
+
+#[then("the Critter should be on a Tile no one else is on.")]
+fn critter_on_free_tile(world: &mut GameWorld) {
+    let critter_tilepos = *world
+        .find_with::<TilePos, CritterLabel>()
+        .expect("critter_on_free_tile: Critter does not have a TilePos.");
+
+    let others_on_tile = world
+        .app
+        .world_mut()
+        .query_filtered::<&TilePos, (With<GameEntityType>, Without<CritterLabel>)>()
+        .iter(world.app.world())
+        .filter(|tile_pos| **tile_pos == critter_tilepos)
+        .count();
+
+    assert_eq!(others_on_tile, 0);
+}
+
+#[then(regex = r"^there should be a Critter named (\w+)\.")]
+fn critter_named(world: &mut GameWorld, follower_name: String) {
+    let critter_names = world.find_all::<CritterName>();
+
+    assert!(critter_names
+        .iter()
+        .any(|critter_name| critter_name.0 == follower_name));
+}
+
+#[then(regex = r"^(\w+) should be recorded where their Critter settled\.")]
+fn follower_recorded(world: &mut GameWorld, follower_name: String) {
+    let critter_tilepos = *world
+        .find_with::<TilePos, CritterLabel>()
+        .expect("follower_recorded: Critter does not have a TilePos.");
+
+    let follower_records = world.app.world().resource::<FollowerRecords>();
+    assert!(follower_records.contains(&follower_name));
+
+    let recorded_follower = follower_records
+        .most_recent(1)
+        .pop()
+        .expect("follower_recorded: Follower was not recorded.");
+
+    assert_eq!(recorded_follower.name, follower_name);
+    assert_eq!(recorded_follower.tile_pos, critter_tilepos);
+}
+
+#[then("the Critter should animate along the row for its kind.")]
+fn critter_animates_along_kind_row(world: &mut GameWorld) {
+    world.update(1);
+
+    let (critter_kind, animation_indices) = world
+        .app
+        .world_mut()
+        .query::<(&CritterKind, &AnimationIndices)>()
+        .get_single(world.app.world())
+        .expect("critter_animates_along_kind_row: The Critter should be animated.");
+
+    let row_len = world
+        .app
+        .world()
+        .resource::<EntityDefinitions>()
+        .get(EntityKind::Critter)
+        .columns as usize;
+    let kind_row = critter_kind.sprite_row() * row_len;
+
+    assert_eq!(kind_row, animation_indices.start_idx);
+    assert!(animation_indices.end_idx <= kind_row + row_len);
+}
+
+fn main() {
+    futures::executor::block_on(GameWorld::run("tests/feature-files/critter.feature"));
+}
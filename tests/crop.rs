@@ -7,6 +7,7 @@ use bevy_ecs_tilemap::prelude::*;
 use task_masker::entities::crop::*;
 use task_masker::entities::streamer::*;
 use task_masker::map::path_finding::*;
+use task_masker::map::path_requests::AwaitingPath;
 use task_masker::map::plugins::PathFindingPlugin;
 
 use crate::mock_plugins::{GameWorld, MockCropPlugin, MockStreamerPlugin, MockTiledMapPlugin};
@@ -77,19 +78,19 @@ fn wait_for_streamer_to_be_over_crop(world: &mut GameWorld) {
 
 #[then("the Streamer should be heading towards the grown Crop's position.")]
 fn streamer_should_be_heading_towards_crop(world: &mut GameWorld) {
-    // We need to wait for the Streamer to actually be moving
-    // in order for their Path to be populated with something.
+    // We need to wait for the Streamer to actually be moving, and for
+    // their Path to be found, in order for it to be populated with something.
     loop {
         world.app.update();
 
-        let streamer_status = world
+        let (streamer_status, streamer_awaiting_path) = world
             .app
             .world_mut()
-            .query::<&StreamerState>()
+            .query::<(&StreamerState, &AwaitingPath)>()
             .get_single(&world.app.world())
             .expect("streamer_should_be_heading_towards_crop: Streamer does not have a State.");
 
-        if *streamer_status == StreamerState::Moving {
+        if *streamer_status == StreamerState::Moving && !**streamer_awaiting_path {
             break;
         }
     }
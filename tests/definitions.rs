@@ -0,0 +1,216 @@
+use std::fs;
+
+use bevy::prelude::*;
+use cucumber::{given, then, when, World};
+
+use task_masker::entities::definitions::*;
+use task_masker::entities::streamer::{spawn_player_sprite, StreamerLabel};
+use task_masker::entities::GameEntityType;
+use task_masker::map::path_finding::Direction;
+
+#[derive(Debug, World)]
+#[world(init = Self::new)]
+pub struct DefinitionContext {
+    definition_files: Vec<(EntityKind, String)>,
+}
+
+impl DefinitionContext {
+    pub fn new() -> Self {
+        Self {
+            definition_files: Vec::new(),
+        }
+    }
+
+    /// Returns what loading each definition file resulted in.
+    fn load_all(&self) -> Vec<(EntityKind, Result<EntityDefinition, EntityDefinitionError>)> {
+        self.definition_files
+            .iter()
+            .map(|(entity_kind, contents)| {
+                (
+                    *entity_kind,
+                    EntityDefinition::from_bytes(contents.as_bytes()),
+                )
+            })
+            .collect()
+    }
+
+    /// Applies some change to the contents of every
+    /// definition file.
+    fn edit_all(&mut self, edit: impl Fn(&str) -> String) {
+        for (_entity_kind, contents) in &mut self.definition_files {
+            *contents = edit(contents);
+        }
+    }
+}
+
+/// Returns the contents of the definition file shipped
+/// for some kind of entity.
+fn read_definition_file(entity_kind: EntityKind) -> String {
+    fs::read_to_string(format!("assets/{}", entity_kind.asset_path()))
+        .expect("read_definition_file: Definition file should be shipped in the assets folder.")
+}
+
+#[given("the definition files shipped with the game,")]
+fn read_shipped_definitions(context: &mut DefinitionContext) {
+    context.definition_files = EntityKind::ALL
+        .iter()
+        .map(|entity_kind| (*entity_kind, read_definition_file(*entity_kind)))
+        .collect();
+}
+
+#[given("the Chatter definition file,")]
+fn read_chatter_definition(context: &mut DefinitionContext) {
+    context.definition_files = vec![(
+        EntityKind::Chatter,
+        read_definition_file(EntityKind::Chatter),
+    )];
+}
+
+#[given("the Streamer definition file,")]
+fn read_streamer_definition(context: &mut DefinitionContext) {
+    context.definition_files = vec![(
+        EntityKind::Streamer,
+        read_definition_file(EntityKind::Streamer),
+    )];
+}
+
+#[when(regex = r"^the (\w+) field is set to (.+),$")]
+fn set_field(context: &mut DefinitionContext, field: String, value: String) {
+    context.edit_all(|contents| {
+        let field_prefix = format!("{}:", field);
+        let new_line = format!("    {}: {},", field, value);
+
+        if contents
+            .lines()
+            .any(|line| line.trim_start().starts_with(&field_prefix))
+        {
+            contents
+                .lines()
+                .map(|line| {
+                    if line.trim_start().starts_with(&field_prefix) {
+                        new_line.clone()
+                    } else {
+                        line.to_string()
+                    }
+                })
+                .collect::<Vec<String>>()
+                .join("\n")
+        } else {
+            let closing_idx = contents
+                .rfind(')')
+                .expect("set_field: Definition should end with a closing parenthesis.");
+            format!("{}{}\n)", &contents[..closing_idx], new_line)
+        }
+    });
+}
+
+#[when(regex = r"^the (\w+) field is misspelled as (\w+),$")]
+fn misspell_field(context: &mut DefinitionContext, field: String, misspelling: String) {
+    context.edit_all(|contents| {
+        contents.replace(&format!("{}:", field), &format!("{}:", misspelling))
+    });
+}
+
+#[then("each definition should load without errors,")]
+fn definitions_load(context: &mut DefinitionContext) {
+    for (entity_kind, loaded_definition) in context.load_all() {
+        assert!(
+            loaded_definition.is_ok(),
+            "{:?} definition failed to load: {}",
+            entity_kind,
+            loaded_definition.unwrap_err()
+        );
+    }
+}
+
+#[then("each definition should match its built-in definition.")]
+fn definitions_match_builtin(context: &mut DefinitionContext) {
+    for (entity_kind, loaded_definition) in context.load_all() {
+        assert_eq!(loaded_definition.unwrap(), entity_kind.builtin_definition());
+    }
+}
+
+#[then(regex = r"^loading the definition should fail naming the (\w+) field.$")]
+fn loading_fails_naming_field(context: &mut DefinitionContext, field: String) {
+    for (_entity_kind, loaded_definition) in context.load_all() {
+        let load_error = loaded_definition
+            .expect_err("loading_fails_naming_field: Definition should not have loaded.");
+
+        assert!(load_error.to_string().contains(&field));
+    }
+}
+
+#[then(regex = r"^the Streamer should be animated (\d+) frames at a time.$")]
+fn streamer_animation_len(context: &mut DefinitionContext, animation_len: usize) {
+    let mut entity_definitions = EntityDefinitions::default();
+    for (entity_kind, loaded_definition) in context.load_all() {
+        entity_definitions.insert(entity_kind, loaded_definition.unwrap());
+    }
+
+    assert_eq!(
+        entity_definitions.animation_len_for(&GameEntityType::Walk),
+        animation_len
+    );
+}
+
+#[then(regex = r"^the Streamer should walk (Top|Bottom|Left|Right|Bottom Right) along row (\d+).$")]
+fn streamer_heading_row(context: &mut DefinitionContext, heading: String, heading_row: usize) {
+    let direction = match heading.as_str() {
+        "Top" => Direction::Top,
+        "Bottom" => Direction::Bottom,
+        "Left" => Direction::Left,
+        "Right" => Direction::Right,
+        "Bottom Right" => Direction::BottomRight,
+        _ => unreachable!(),
+    };
+
+    let mut entity_definitions = EntityDefinitions::default();
+    for (entity_kind, loaded_definition) in context.load_all() {
+        entity_definitions.insert(entity_kind, loaded_definition.unwrap());
+    }
+
+    assert_eq!(
+        entity_definitions.heading_row_for(&GameEntityType::Walk, &direction),
+        Some(heading_row)
+    );
+}
+
+#[then(regex = r"^a Streamer drawn beforehand should be redrawn from (.+)\.$")]
+fn streamer_redrawn(context: &mut DefinitionContext, sprite: String) {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, AssetPlugin::default()));
+    app.init_asset::<Image>();
+    app.init_asset::<TextureAtlasLayout>();
+    app.init_resource::<EntityDefinitions>();
+    app.add_systems(
+        Update,
+        (spawn_player_sprite, redraw_sprites_from_definitions).chain(),
+    );
+
+    app.world_mut().spawn((StreamerLabel, Transform::default()));
+    app.update();
+
+    for (entity_kind, loaded_definition) in context.load_all() {
+        app.world_mut()
+            .resource_mut::<EntityDefinitions>()
+            .insert(entity_kind, loaded_definition.unwrap());
+    }
+    app.update();
+
+    let streamer_texture = app
+        .world_mut()
+        .query_filtered::<&Handle<Image>, With<StreamerLabel>>()
+        .get_single(app.world())
+        .expect("streamer_redrawn: Streamer should have been drawn.");
+
+    assert_eq!(
+        streamer_texture.path().map(|path| path.to_string()),
+        Some(sprite)
+    );
+}
+
+fn main() {
+    futures::executor::block_on(DefinitionContext::run(
+        "tests/feature-files/definitions.feature",
+    ));
+}
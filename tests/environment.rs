@@ -2,10 +2,13 @@ mod mock_plugins;
 
 use crate::mock_plugins::{GameWorld, MockEnvironmentAnimationsPlugin, MockTiledMapPlugin};
 
+use std::time::Duration;
+
 use cucumber::{given, then, when, World};
 use task_masker::map::plugins::PathFindingPlugin;
-use task_masker::visual::animations::{AnimationIndices, AnimationTimer};
+use task_masker::visual::animations::AnimationTimer;
 use task_masker::visual::environment::*;
+use task_masker::visual::tile_animations::TileAnimation;
 
 #[given("a Tiled Map,")]
 fn spawn_tiled_map(world: &mut GameWorld) {
@@ -23,22 +26,35 @@ fn spawn_campfire_on_tiled_map(world: &mut GameWorld) {
     world.update(1);
 }
 
-#[then("the Campfire should have an animation speed set,")]
-fn campfire_has_animation_speed(world: &mut GameWorld) {
+#[then("the Campfire should have 23 frames to flicker through while animating,")]
+fn campfire_has_23_frames(world: &mut GameWorld) {
     world.update(1);
 
-    let campfire_animation_speed = world.find_with::<AnimationTimer, CampfireLabel>();
+    let campfire_animation = world
+        .find_with::<TileAnimation, CampfireLabel>()
+        .expect("campfire_has_23_frames: Unable to find Campfire's Tile Animation.");
 
-    assert!(campfire_animation_speed.is_some());
+    assert_eq!(23, campfire_animation.frames().len());
 }
 
-#[then("the Campfire should have 23 frames to flicker through while animating.")]
-fn campfire_has_23_frames(world: &mut GameWorld) {
-    let campfire_animation_indices = world
-        .find_with::<AnimationIndices, CampfireLabel>()
-        .expect("campfire_has_23_frames: Unable to find Campfire's Animation Indices information.");
+#[then(regex = r"^each of the Campfire's frames should last (\d+) milliseconds.")]
+fn campfire_frames_last(world: &mut GameWorld, milliseconds: u64) {
+    let campfire_animation = world
+        .find_with::<TileAnimation, CampfireLabel>()
+        .expect("campfire_frames_last: Unable to find Campfire's Tile Animation.");
+
+    assert!(campfire_animation
+        .frames()
+        .iter()
+        .all(|frame| frame.duration == Duration::from_millis(milliseconds)));
+}
+
+#[then("the Campfire should not have an animation speed set.")]
+fn campfire_has_no_animation_speed(world: &mut GameWorld) {
+    world.update(1);
 
-    assert_eq!(23, campfire_animation_indices.end_idx);
+    assert!(world.find_with::<TileAnimation, CampfireLabel>().is_some());
+    assert!(world.find_with::<AnimationTimer, CampfireLabel>().is_none());
 }
 
 fn main() {
@@ -7,6 +7,13 @@ Feature: A Chatter, represented as a Bird, can approach the Streamer to speak, a
         When the Chatter wants to speak
         Then the Chatter will approach the Streamer
 
+    Scenario: The Chatter approaches the Streamer along a shared Flow Field.
+        Given a Tiled Map
+        And a Streamer spawned on the Tiled Map
+        And a Chatter spawned on the Tiled Map
+        When the Chatter wants to speak
+        Then the Chatter will follow the Flow Field towards the Streamer
+
     Scenario: The Chatter arrives to the Streamer two tiles away.
         Given a Tiled Map
         And a Streamer spawned on the Tiled Map
@@ -0,0 +1,65 @@
+Feature: Followers live on the map as Critters.
+    Scenario: A new follower settles a Critter on a free Tile.
+        Given a Tiled Map,
+        And a record of previous followers,
+        And Critters can live on the Tiled Map,
+        When Followu follows the channel,
+        Then there should be 1 Critters on the Tiled Map,
+        And the Critter should be on a Tile no one else is on.
+
+    Scenario: A Critter is named after its follower.
+        Given a Tiled Map,
+        And a record of previous followers,
+        And Critters can live on the Tiled Map,
+        When Followu follows the channel,
+        Then there should be a Critter named Followu.
+
+    Scenario: A new follower is recorded with where their Critter settled.
+        Given a Tiled Map,
+        And a record of previous followers,
+        And Critters can live on the Tiled Map,
+        When Followu follows the channel,
+        Then Followu should be recorded where their Critter settled.
+
+    Scenario: Critters from previous streams come back at launch.
+        Given a Tiled Map,
+        And a record of previous followers,
+        And 3 followers were recorded on a previous stream,
+        And Critters can live on the Tiled Map,
+        Then there should be 3 Critters on the Tiled Map.
+
+    Scenario: The oldest Critters make way once there are too many.
+        Given a Tiled Map,
+        And a record of previous followers,
+        And Critters can live on the Tiled Map,
+        When as many followers as the Critter cap follow the channel,
+        And Followu follows the channel,
+        Then there should be as many Critters as the Critter cap on the Tiled Map,
+        And there should be a Critter named Followu.
+
+    Scenario: Critters come back on the new map after a Map Scene switch.
+        Given a Tiled Map,
+        And a record of previous followers,
+        And 3 followers were recorded on a previous stream,
+        And Critters can live on the Tiled Map,
+        When the stream switches to the Just Chatting scene,
+        Then there should be 3 Critters on the Tiled Map,
+        And there should be a Critter named Followu0.
+
+    Scenario: Only Critters that can get around come back on a map without water.
+        Given a Tiled Map,
+        And a record of previous followers,
+        And 3 followers were recorded on a previous stream,
+        And a Frog follower was recorded on a previous stream,
+        And Critters can live on the Tiled Map,
+        When the stream switches to a map without water,
+        Then there should be 3 Critters on the Tiled Map,
+        And there should be a Critter named Followu0.
+
+    Scenario: Critters animate along the row of their spritesheet for their kind.
+        Given a Tiled Map,
+        And a record of previous followers,
+        And Critters can live on the Tiled Map,
+        And Critters are animated,
+        When Followu follows the channel,
+        Then the Critter should animate along the row for its kind.
@@ -0,0 +1,54 @@
+Feature: Entities are described by definition files instead of code.
+
+    Scenario: The shipped definition files describe the entities as they always were.
+        Given the definition files shipped with the game,
+        Then each definition should load without errors,
+        And each definition should match its built-in definition.
+
+    Scenario: A definition with no columns is rejected.
+        Given the Chatter definition file,
+        When the columns field is set to 0,
+        Then loading the definition should fail naming the columns field.
+
+    Scenario: A definition with an empty spritesheet is rejected.
+        Given the Chatter definition file,
+        When the sprite field is set to "",
+        Then loading the definition should fail naming the sprite field.
+
+    Scenario: A definition animating past the end of a row is rejected.
+        Given the Chatter definition file,
+        When the animation_len field is set to Some(9),
+        Then loading the definition should fail naming the animation_len field.
+
+    Scenario: A misspelled field is named when a definition is rejected.
+        Given the Chatter definition file,
+        When the rows field is misspelled as rowz,
+        Then loading the definition should fail naming the rowz field.
+
+    Scenario: A custom mascot only needs a different definition.
+        Given the Streamer definition file,
+        When the sprite field is set to "mascot/mascot-sheet.png",
+        And the columns field is set to 6,
+        Then each definition should load without errors,
+        And the Streamer should be animated 6 frames at a time.
+
+    Scenario: A definition animating a heading past the last row is rejected.
+        Given the Chatter definition file,
+        When the heading_rows field is set to { Top: 3 },
+        Then loading the definition should fail naming the heading_rows field.
+
+    Scenario: A definition animating a Link crossing past the last row is rejected.
+        Given the Streamer definition file,
+        When the transition_rows field is set to { Ladder: 9 },
+        Then loading the definition should fail naming the transition_rows field.
+
+    Scenario: Headings without a row of their own borrow the row of their nearest diagonal heading.
+        Given the Streamer definition file,
+        Then the Streamer should walk Bottom Right along row 8.
+        And the Streamer should walk Right along row 8.
+        And the Streamer should walk Top along row 6.
+
+    Scenario: Sprites drawn before a definition changes are redrawn from the new definition.
+        Given the Streamer definition file,
+        When the sprite field is set to "mascot/mascot-sheet.png",
+        Then a Streamer drawn beforehand should be redrawn from mascot/mascot-sheet.png.
@@ -1,6 +1,11 @@
 Feature: Props from the Environment should animate.
-    Scenario: The Campfire is set up to flicker in the wind.
+    Scenario: The Campfire flickers through the frames its Tiled tileset animates it with.
         Given a Tiled Map,
         When the Campfire is spawned on the Tiled Map,
-        Then the Campfire should have an animation speed set,
-        And the Campfire should have 23 frames to flicker through while animating.
+        Then the Campfire should have 23 frames to flicker through while animating,
+        And each of the Campfire's frames should last 100 milliseconds.
+
+    Scenario: The Campfire is left to its Tiled animation rather than a sprite sheet row.
+        Given a Tiled Map,
+        When the Campfire is spawned on the Tiled Map,
+        Then the Campfire should not have an animation speed set.
@@ -0,0 +1,35 @@
+Feature: Editing the map while the stream is live lays it out again, keeping what is still around.
+
+    Scenario: Crops still placed by the edited map keep growing where they left off.
+        Given a Tiled Map,
+        And Crops spawned on the Tiled Map,
+        And the Crop on 53, 59 is Growing,
+        When the map is reloaded,
+        Then the same Crop should be on 53, 59.
+        And the Crop on 53, 59 should still be Growing.
+        And there should be 16 Crops on the Tiled Map.
+
+    Scenario: Crops the edited map no longer places are taken away.
+        Given a Tiled Map,
+        And Crops spawned on the Tiled Map,
+        And a Crop the map does not place on 0, 0,
+        When the map is reloaded,
+        Then there should be no Crop on 0, 0.
+        And there should be 16 Crops on the Tiled Map.
+
+    Scenario: The Graphs are built again from the edited map.
+        Given a Tiled Map,
+        When the map is reloaded,
+        Then the Ground Graph should have been built again.
+
+    Scenario: Chatters keep the messages they have yet to say.
+        Given a Tiled Map,
+        And a Chatter spawned on the Tiled Map,
+        And the Chatter has 2 messages to say,
+        When the map is reloaded,
+        Then the same Chatter should still have 2 messages to say.
+
+    Scenario: Map Objects are swapped for those of the edited map.
+        Given a Tiled Map,
+        When the map is reloaded,
+        Then there should be 1 Map Object named Garden.
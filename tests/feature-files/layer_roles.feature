@@ -0,0 +1,27 @@
+Feature: Entities find their Tiled layers by role instead of by number.
+
+    Scenario: The shipped maps give every role a layer.
+        Given the shipped map called TM_map.tmx,
+        Then every role should have a layer,
+        And the streamer role should be on layer 6,
+        And the fruit role should be on layer 17,
+        And the subscriber role should be on layer 18.
+
+    Scenario: The cropped map keeps every role on the same layers.
+        Given the shipped map called campfire_cove.tmx,
+        Then every role should have a layer,
+        And the chatter role should be on layer 19,
+        And the campfire role should be on layer 20.
+
+    Scenario: Layers take their role from a property or else from their name.
+        Given a Tiled map called layer_roles.tmx,
+        Then the fruit role should be on layer 1,
+        And the chatter role should be on layer 2.
+
+    Scenario: A map missing some roles names every one of them.
+        Given a Tiled map called layer_roles.tmx,
+        Then the map should be missing layers for the roles: streamer, subscriber, crop, campfire, water, farmland.
+
+    Scenario: Entities placed on another layer still find the layer they are drawn on by role.
+        Given the shipped map called TM_map.tmx,
+        Then the Crop should be placed on layer 3 and drawn on layer 13.
@@ -0,0 +1,35 @@
+Feature: The map viewer inspects Tiles, shows or hides layers, and tests Paths between Tiles.
+
+    Scenario: Clicking where a Tile is drawn inspects the highest Tile drawn there.
+        Given the inspected Tiled map called validation.tmx,
+        When the point where Tile 4, 0, 3 is drawn is clicked,
+        Then Tile 4, 0, 3 should be inspected.
+        And the inspected Tile should be described as on layer 3 (Hilltop),
+        And the inspected Tile should be described with the texture index 1.
+
+    Scenario: Clicking through a hidden layer inspects the Tile beneath it.
+        Given the inspected Tiled map called validation.tmx,
+        When layer 3 is hidden,
+        And the point where Tile 4, 0, 2 is drawn is clicked,
+        Then Tile 4, 0, 2 should be inspected.
+
+    Scenario: Clicking beside the map inspects nothing.
+        Given the inspected Tiled map called validation.tmx,
+        When the point where Tile 0, 0, 0 is drawn is clicked from 64 pixels to its left,
+        Then no Tile should be inspected.
+
+    Scenario: An inspected Tile lists the Graphs it can be stood on in.
+        Given the inspected Tiled map called validation.tmx,
+        When the point where Tile 3, 5, 1 is drawn is clicked,
+        Then the inspected Tile should be on the Ground Graph,
+        And the inspected Tile should not be on the Water Graph.
+
+    Scenario: A Path is found between two Tiles along the ground.
+        Given the inspected Tiled map called validation.tmx,
+        When the Path is tested along the Ground Graph from Tile 3, 7, 1 to Tile 4, 1, 1,
+        Then a Path should be found.
+
+    Scenario: No Path is found across the ground between both sides of the water.
+        Given the inspected Tiled map called validation.tmx,
+        When the Path is tested along the Water Graph from Tile 0, 5, 0 to Tile 7, 5, 0,
+        Then no Path should be found.
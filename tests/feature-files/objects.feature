@@ -0,0 +1,40 @@
+Feature: Objects placed on Tiled object layers become part of the game.
+
+    Scenario: Every object but the Links becomes a Map Object.
+        Given the objects of a Tiled map called objects.tmx,
+        Then there should be 5 Map Objects.
+
+    Scenario: Point objects become spawn points and Points of Interest.
+        Given the objects of a Tiled map called objects.tmx,
+        Then the spawn point named Streamer Start should be on 1, 1.
+        And the Point of Interest named Well should be on 3, 3.
+
+    Scenario: Custom properties are carried over onto the Map Objects.
+        Given the objects of a Tiled map called objects.tmx,
+        Then the Well should have its description set to A deep well.
+        And the Garden should have its crop set to carrot.
+
+    Scenario: Rectangles and polygons become Trigger Zones covering the Tiles within them.
+        Given the objects of a Tiled map called objects.tmx,
+        Then the Garden zone should cover 0, 2 and 0, 3 and 1, 2 and 1, 3.
+        And the Pond zone should cover 2, 1.
+
+    Scenario: Walking into the garden tells everyone about it.
+        Given the objects of a Tiled map called objects.tmx,
+        And a walker standing on 3, 0,
+        When the walker steps onto 1, 2,
+        Then the walker should have entered the Garden.
+
+    Scenario: Walking out of the garden tells everyone about it too.
+        Given the objects of a Tiled map called objects.tmx,
+        And a walker standing on 3, 0,
+        When the walker steps onto 1, 2,
+        And the walker steps onto 3, 0,
+        Then the walker should have left the Garden.
+
+    Scenario: Tile objects become Props reached by walking up to them.
+        Given the objects of a Tiled map called objects.tmx,
+        And a walker standing on 0, 0,
+        Then the Signpost should be a Prop drawn with Tile 1 on 3, 2.
+        When the walker steps onto 2, 2,
+        Then the walker should have entered the Signpost.
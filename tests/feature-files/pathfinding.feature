@@ -10,3 +10,296 @@ Feature: Any Game Entity should obey the rules of path finding when traveling an
         And the Path Finding module is loaded,
         When the Tiled map is loaded,
         Then there should be a Path from the Undirected Graph starting from one tile, going to a neighboring tile.
+
+    Scenario: Walkers go around shallow water rather than wade through it.
+        Given a patch of ground with Shallow Water in the middle,
+        When a Path is found across the patch for walkers,
+        Then the Path should go around the middle of the patch.
+
+    Scenario: Walkers cut straight across plain ground.
+        Given a patch of ground with Plain in the middle,
+        When a Path is found across the patch for walkers,
+        Then the Path should go through the middle of the patch.
+
+    Scenario: Walkers stay on the dirt path rather than cut through tall grass.
+        Given a patch of ground with Tall Grass in the middle,
+        And a dirt path along the edge of the patch,
+        When a Path is found across the patch for walkers,
+        Then the Path should go around the middle of the patch.
+
+    Scenario: Flyers are not slowed down by shallow water.
+        Given a patch of ground with Shallow Water in the middle,
+        When a Path is found across the patch for flyers,
+        Then the Path should go through the middle of the patch.
+
+    Scenario: Flow Fields lead walkers around shallow water just like searching does.
+        Given a patch of ground with Shallow Water in the middle,
+        When a Path is followed along a Flow Field from 0, 1 to 2, 1 for walkers,
+        Then the Path should go around the middle of the patch.
+
+    Scenario: Flow Fields lead flyers straight over shallow water.
+        Given a patch of ground with Shallow Water in the middle,
+        When a Path is followed along a Flow Field from 0, 1 to 2, 1 for flyers,
+        Then the Path should go through the middle of the patch.
+
+    Scenario: Flow Fields lead diagonally when diagonal movement is allowed.
+        Given a patch of ground with Plain in the middle,
+        And diagonal movement is allowed,
+        When a Path is followed along a Flow Field from 0, 0 to 2, 2 for walkers,
+        Then the Path should take 2 steps.
+
+    Scenario: Tiles are made of the terrain recorded in their Tiled tile properties.
+        Given a Tiled map called terrain_properties.tmx,
+        Then Tile 0, 0 should be made of Shallow Water.
+        And Tile 0, 1 should be made of Plain.
+        And Tile 0, 0 should have no Slope.
+        And Tile 0, 1 should be shaped like a Ledge.
+
+    Scenario: Walkers only step along the grid unless diagonal movement is allowed.
+        Given a patch of ground with Plain in the middle,
+        When a Path is found from 0, 0 to 2, 2 for walkers,
+        Then the Path should take 4 steps.
+
+    Scenario: Walkers step diagonally when diagonal movement is allowed.
+        Given a patch of ground with Plain in the middle,
+        And diagonal movement is allowed,
+        When a Path is found from 0, 0 to 2, 2 for walkers,
+        Then the Path should take 2 steps.
+
+    Scenario: Walkers do not cut the corner of a cliff when stepping diagonally.
+        Given a patch of ground with a cliff in the middle,
+        And diagonal movement is allowed,
+        When a Path is found from 0, 1 to 1, 0 for walkers,
+        Then the Path should take 2 steps.
+
+    Scenario: Walkers cannot climb a cliff.
+        Given a patch of ground with a cliff in the middle,
+        When a Path is found from 0, 1 to 1, 1 for walkers,
+        Then there should be no Path.
+
+    Scenario: Walkers climb a cliff by its Stairs.
+        Given a patch of ground with a cliff in the middle,
+        And the cliff is climbed by Stairs,
+        When a Path is found from 0, 1 to 1, 1 for walkers,
+        Then the Path should take 1 step.
+
+    Scenario: A Ramp is too shallow to climb a cliff.
+        Given a patch of ground with a cliff in the middle,
+        And the cliff is climbed by a Ramp,
+        When a Path is found from 0, 1 to 1, 1 for walkers,
+        Then there should be no Path.
+
+    Scenario: Walkers drop down off of a Ledge.
+        Given a patch of ground with a cliff in the middle,
+        And the cliff is climbed by a Ledge,
+        When a Path is found from 1, 1 to 0, 1 for walkers,
+        Then the Path should take 1 step.
+
+    Scenario: Walkers cannot climb back up a Ledge.
+        Given a patch of ground with a cliff in the middle,
+        And the cliff is climbed by a Ledge,
+        When a Path is found from 0, 1 to 1, 1 for walkers,
+        Then there should be no Path.
+
+    Scenario: Walkers climb a cliff by a Ladder linking its foot to its top.
+        Given a patch of ground with a cliff in the middle,
+        And a two way Ladder links 0, 1 to 1, 1 costing 1 step,
+        When a Path is found from 0, 1 to 1, 1 for walkers,
+        Then the Path should take 1 step.
+
+    Scenario: Walkers take a Portal between far off Tiles.
+        Given a patch of ground with Plain in the middle,
+        And a two way Portal links 0, 0 to 2, 2 costing 1 step,
+        When a Path is found from 0, 0 to 2, 2 for walkers,
+        Then the Path should take 1 step.
+
+    Scenario: Walkers cannot take a one way Portal back.
+        Given a patch of ground with Plain in the middle,
+        And a one way Portal links 0, 0 to 2, 2 costing 1 step,
+        When a Path is found from 2, 2 to 0, 0 for walkers,
+        Then the Path should take 4 steps.
+
+    Scenario: Walkers walk around rather than take a costly Portal.
+        Given a patch of ground with Plain in the middle,
+        And a two way Portal links 0, 0 to 2, 2 costing 9 steps,
+        When a Path is found from 0, 0 to 2, 2 for walkers,
+        Then the Path should take 4 steps.
+
+    Scenario: Destinations are headed to cheapest walk first rather than fewest steps first.
+        Given a patch of ground with Shallow Water in the middle,
+        When walkers on 0, 1 pick the nearest of 1, 1 and 1, 0,
+        Then 1, 0 should be headed to first.
+
+    Scenario: Walkers are drawn climbing while crossing a Ladder.
+        Given a patch of ground with a cliff in the middle,
+        And a two way Ladder links 0, 1 to 1, 1 costing 1 step,
+        When a walker steps from 0, 1 to 1, 1,
+        Then the walker should be crossing by a Ladder.
+
+    Scenario: Walkers are not drawn crossing anything between neighbouring Tiles.
+        Given a patch of ground with a cliff in the middle,
+        And a two way Ladder links 0, 1 to 1, 1 costing 1 step,
+        When a walker steps from 0, 1 to 0, 0,
+        Then the walker should be crossing by no Link.
+
+    Scenario: Links are read from objects of the link class within a Tiled map.
+        Given a Tiled map called links.tmx,
+        Then the map should declare 1 Link.
+        And the map should declare a one way Ladder from 0, 2 to 2, 0 costing 3 steps.
+
+    Scenario: Headings follow the isometric axes, or the screen when stepping diagonally.
+        Given a patch of ground with Plain in the middle,
+        Then heading 16, 8 away straight should face Top Right.
+        And heading -16, -8 away straight should face Bottom Left.
+        And heading 0, 16 away diagonally should face Top.
+        And heading 0, -16 away diagonally should face Bottom.
+        And heading -32, 6 away diagonally should face Left.
+        And heading 32, -6 away diagonally should face Right.
+
+    Scenario: Walkers step around anyone standing in their way.
+        Given a patch of ground with Plain in the middle,
+        And a walker standing on 1, 1,
+        And a walker heading from 0, 1 to 2, 1,
+        When the walkers have had time to move,
+        Then the walker from 0, 1 should be on 2, 1.
+        And no two walkers should have shared a Tile.
+
+    Scenario: Walkers wait a while for anyone in their way to move along.
+        Given a patch of ground with Plain in the middle,
+        And a walker standing on 1, 1,
+        And a walker heading from 0, 1 to 2, 1,
+        And walkers wait a long while before finding a way around,
+        When the walkers have had time to move,
+        Then the walker from 0, 1 should be on 0, 1.
+
+    Scenario: Walkers stop short of a destination someone else is standing on.
+        Given a patch of ground with Plain in the middle,
+        And a walker standing on 2, 1,
+        And a walker heading from 0, 1 to 2, 1,
+        When the walkers have had time to move,
+        Then the walker from 0, 1 should be on 1, 1.
+        And no two walkers should have shared a Tile.
+
+    Scenario: Walkers side-step into a nook to let each other pass.
+        Given a narrow patch of ground with a nook along the way,
+        And a walker heading from 0, 1 to 2, 1,
+        And a walker heading from 1, 1 to 0, 1,
+        When the walkers have had time to move,
+        Then the walker from 0, 1 should be on 2, 1.
+        And the walker from 1, 1 should be on 0, 1.
+        And no two walkers should have shared a Tile.
+
+    Scenario: Paths asked for are found in the background.
+        Given a patch of ground with Plain in the middle,
+        And Paths are found across the patch in the background,
+        When walkers ask for a Path from 0, 1 to 2, 1,
+        Then the Path found should take 2 steps.
+
+    Scenario: Paths cannot be found on a Graph that was never built.
+        Given a patch of ground with Plain in the middle,
+        And Paths are found across the patch in the background,
+        When flyers ask for a Path from 0, 1 to 2, 1,
+        Then no Path should be found since the Graph is missing.
+
+    Scenario: Paths cannot be found on a Graph that has been taken down.
+        Given a patch of ground with Plain in the middle,
+        And Paths are found across the patch in the background,
+        When the ground Graph is taken down,
+        And walkers ask for a Path from 0, 1 to 2, 1,
+        Then no Path should be found since the Graph is missing.
+
+    Scenario: Paths cannot be found to Tiles off the patch.
+        Given a patch of ground with Plain in the middle,
+        And Paths are found across the patch in the background,
+        When walkers ask for a Path from 0, 1 to 7, 1,
+        Then no Path should be found since the Tile is off the patch.
+
+    Scenario: Paths cannot be found from Tiles that cannot be stood on.
+        Given a patch of ground with a cliff in the middle,
+        And Paths are found across the patch in the background,
+        When walkers ask for a Path from 1, 1 to 0, 1,
+        Then no Path should be found since the start cannot be stood on.
+
+    Scenario: Paths cannot be found to Tiles out of reach.
+        Given a patch of ground with a cliff in the middle,
+        And Paths are found across the patch in the background,
+        When walkers ask for a Path from 0, 1 to 1, 1,
+        Then no Path should be found since the end is out of reach.
+
+    Scenario: The Tile nearest to one off the Graph is found right beside it.
+        Given a patch of ground with a cliff in the middle,
+        Then the Tile nearest to 1, 1 for walkers should be 1 Tile away.
+        And the Tile nearest to 1, 1 for flyers should be 0 Tiles away.
+
+    Scenario: Tiles within some distance of another are found.
+        Given a patch of ground with Plain in the middle,
+        Then 5 Tiles for walkers should be within 1 Tile of 1, 1.
+        And 9 Tiles for walkers should be within 2 Tiles of 1, 1.
+        And 3 Tiles for walkers should be within 1 Tile of 0, 0.
+
+    Scenario: Shorelines are found where the water meets the land.
+        Given a patch of ground with a pond along the middle,
+        Then the pond's shoreline should be 2 Tiles long.
+
+    Scenario: Movers arrive exactly on their Target however the frames are split.
+        Given a mover stepping from 0, 0 to 1, 0 at 2.5 Tiles a second,
+        When 0.1 seconds pass over 1 frame,
+        And 0.13 seconds pass over 1 frame,
+        And 0.29 seconds pass over 1 frame,
+        Then the mover should be exactly on 1, 0.
+
+    Scenario: Movers cover the same ground however many frames are drawn.
+        Given a mover stepping from 0, 0 to 1, 0 at 2.5 Tiles a second,
+        When 0.2 seconds pass over 10 frames,
+        Then the mover should be 50% of the way there.
+
+    Scenario: Slower movers are still on their way when quicker ones have arrived.
+        Given a mover stepping from 0, 0 to 1, 0 at 1.5 Tiles a second,
+        When 0.3 seconds pass over 3 frames,
+        Then the mover should be 45% of the way there.
+
+    Scenario: Diagonal steps take longer than straight ones.
+        Given a mover stepping from 0, 0 to 1, 1 at 2.5 Tiles a second,
+        When 0.4 seconds pass over 4 frames,
+        Then the mover should be 70% of the way there.
+
+    Scenario: Smoothly eased movers set off slowly along their Path.
+        Given a mover stepping from 0, 0 to 1, 0 at 2.5 Tiles a second,
+        And the mover eases smoothly, setting off along its Path,
+        When 0.2 seconds pass over 2 frames,
+        Then the mover should be 37% of the way there.
+
+    Scenario: Smoothly eased movers keep their pace between steps along their Path.
+        Given a mover stepping from 0, 0 to 1, 0 at 2.5 Tiles a second,
+        And the mover eases smoothly, carrying on along its Path,
+        When 0.2 seconds pass over 2 frames,
+        Then the mover should be 50% of the way there.
+
+    Scenario: Smoothly eased movers slow down before stopping at the end of their Path.
+        Given a mover stepping from 0, 0 to 1, 0 at 2.5 Tiles a second,
+        And the mover eases smoothly, carrying on to the end of its Path,
+        When 0.2 seconds pass over 2 frames,
+        Then the mover should be 62% of the way there.
+
+    Scenario: Movers hop when stepping up onto higher Tiles.
+        Given a mover stepping from 0, 0 to 1, 0 up a level at 2.5 Tiles a second,
+        When 0.2 seconds pass over 2 frames,
+        Then the mover should be hopping above the way there.
+
+    Scenario: Movers land exactly on higher Tiles.
+        Given a mover stepping from 0, 0 to 1, 0 up a level at 2.5 Tiles a second,
+        When 0.5 seconds pass over 5 frames,
+        Then the mover should be exactly on 1, 0 up a level.
+
+    Scenario: The navigation overlay describes the Tile being pointed at.
+        Given a patch of ground with a cliff in the middle,
+        When the cursor points just off the middle of Tile 1, 1,
+        Then the tooltip should say Tile 1, 1 (Node 4).
+        And the tooltip should say Height: 4.
+        And the tooltip should say Graphs: Air.
+
+    Scenario: The navigation overlay lists every Graph a Tile can be stood on in, including wading.
+        Given a patch of ground with a pond along the middle,
+        When the cursor points just off the middle of Tile 1, 2,
+        Then the tooltip should say Height: 0.
+        And the tooltip should say Graphs: Ground, Air, Water.
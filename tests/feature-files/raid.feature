@@ -0,0 +1,38 @@
+Feature: A Raid from another channel parades visitors around the Streamer.
+    Scenario: A Raid spawns a visitor for each raiding viewer.
+        Given a Tiled Map,
+        And a Streamer spawned on the Tiled Map,
+        And Raiders can visit the Tiled Map,
+        When a Raid of 3 viewers arrives,
+        Then there should be 3 Raiders on the Tiled Map.
+
+    Scenario: A large Raid is capped in how many visitors it spawns.
+        Given a Tiled Map,
+        And a Streamer spawned on the Tiled Map,
+        And Raiders can visit the Tiled Map,
+        When a Raid of 500 viewers arrives,
+        Then there should be as many Raiders as the visitor cap on the Tiled Map.
+
+    Scenario: The Raid shows the name of the raiding channel.
+        Given a Tiled Map,
+        And a Streamer spawned on the Tiled Map,
+        And Raiders can visit the Tiled Map,
+        When a Raid of 3 viewers arrives,
+        Then a Raid Banner should show the raiding channel's name.
+
+    Scenario: Raiders gather around the Streamer.
+        Given a Tiled Map,
+        And a Streamer spawned on the Tiled Map,
+        And Raiders can visit the Tiled Map,
+        When a Raid of 3 viewers arrives,
+        And the Raiders have gathered around the Streamer,
+        Then each Raider should be near the Streamer.
+
+    Scenario: Raiders disperse after gathering for a while.
+        Given a Tiled Map,
+        And a Streamer spawned on the Tiled Map,
+        And Raiders can visit the Tiled Map,
+        When a Raid of 3 viewers arrives,
+        And the Raiders have gathered around the Streamer,
+        And the Raiders are done gathering,
+        Then there should be no Raiders or Raid Banner left on the Tiled Map.
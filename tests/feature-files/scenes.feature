@@ -0,0 +1,37 @@
+Feature: The stream can be set in any of several Map Scenes, switched between while live.
+
+    Scenario: The Map Scene set for the stream's activity is picked.
+        Given the built-in Map Scenes,
+        Then switching for the Just Chatting activity should pick the Just Chatting scene.
+        And switching for the coding activity should pick the Coding scene.
+        And switching for the Art activity should pick no scene.
+
+    Scenario: Switching to the next Map Scene picks whichever comes after.
+        Given the built-in Map Scenes,
+        Then switching to the next scene should pick the Just Chatting scene.
+
+    Scenario: Map Scenes registered later can be switched to by name.
+        Given the built-in Map Scenes,
+        And a Cozy Cabin scene is registered,
+        Then switching to the scene named cozy cabin should pick the Cozy Cabin scene.
+
+    Scenario: The Streamer switches Map Scenes with chat commands.
+        Given the built-in Map Scenes,
+        Then the chat command !activity Just Chatting should pick the Just Chatting scene.
+        And the chat command !scene coding should pick the Coding scene.
+        And the chat command !scene should pick no scene.
+        And the chat command hello there should pick no scene.
+
+    Scenario: The Streamer moves over to the spawn point of the new Map Scene.
+        Given a Tiled Map,
+        And a Streamer spawned on the Tiled Map,
+        When the stream switches to the Just Chatting scene,
+        Then the Streamer should be on 17, 8.
+        And the Ground Graph should be rebuilt for a map 56 Tiles long.
+
+    Scenario: Chatters move over to where Chatters spawn on the new Map Scene.
+        Given a Tiled Map,
+        And a Streamer spawned on the Tiled Map,
+        And a Chatter spawned on the Tiled Map,
+        When the stream switches to the Just Chatting scene,
+        Then the same Chatter should be on 13, 52.
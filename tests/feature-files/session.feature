@@ -0,0 +1,52 @@
+Feature: A restarted stream picks back up from its last saved session.
+    Scenario: The Streamer carries on to where they were heading.
+        Given a game that saves its session,
+        When the Streamer is heading somewhere,
+        And the session is saved,
+        And the game restarts from the saved session,
+        Then the Streamer should be where they were when saved,
+        And the Streamer should carry on to where they were heading.
+
+    Scenario: Crops keep growing from where they were.
+        Given a game that saves its session,
+        When a Crop has grown a few stages,
+        And the session is saved,
+        And the game restarts from the saved session,
+        Then the Crop should be as grown as it was when saved.
+
+    Scenario: Unread chat messages are not lost.
+        Given a game that saves its session,
+        When the Chatter has messages waiting to be read,
+        And the session is saved,
+        And the game restarts from the saved session,
+        Then the Chatter should still have the same messages waiting.
+
+    Scenario: The stream's countdown picks back up from where it was.
+        Given a game that saves its session,
+        When some of the stream's time has passed,
+        And the session is saved,
+        And the game restarts from the saved session,
+        Then the same amount of the stream's time should have passed.
+
+    Scenario: A restart without restoring starts a fresh session.
+        Given a game that saves its session,
+        When some of the stream's time has passed,
+        And the session is saved,
+        And the game restarts without restoring the saved session,
+        Then none of the stream's time should have passed.
+
+    Scenario: Whatever cannot be restored is given up on, so autosaving carries on.
+        Given a game that saves its session,
+        When some of the stream's time has passed,
+        And the session is saved,
+        And the game restarts from the saved session after the Streamer has gone,
+        And the saved session has waited too long to be restored,
+        Then autosaving should carry on.
+        And the same amount of the stream's time should have passed.
+
+    Scenario: The stream comes back in the Map Scene it was set in.
+        Given a game that saves its session,
+        When the stream switches to the Just Chatting scene,
+        And the session is saved,
+        And the game restarts from the saved session,
+        Then the stream should be set in the Just Chatting scene.
@@ -32,3 +32,61 @@ Feature: A Streamer can walk to any point on the map.
         When the Status changes to Away,
         And the Streamer is done traveling,
         Then the Streamer should be in the cave.
+
+    Scenario: The same destination is only queued once.
+        Given a Tiled Map,
+        And a Streamer spawned on the Tiled Map,
+        When the Streamer is requested to travel to a lower location,
+        And the Streamer is requested to head to the equal in height location twice,
+        Then the Streamer should have 1 destination queued.
+
+    Scenario: More urgent destinations are headed to first.
+        Given a Tiled Map,
+        And a Streamer spawned on the Tiled Map,
+        When the Streamer is requested to travel to a lower location,
+        And the Streamer is requested to head to the equal in height location with Low priority,
+        And the Streamer is requested to head to the higher location with High priority,
+        Then the Streamer should head to the higher location next.
+
+    Scenario: A queued destination can be cancelled.
+        Given a Tiled Map,
+        And a Streamer spawned on the Tiled Map,
+        When the Streamer is requested to travel to a lower location,
+        And the Streamer is requested to head to the equal in height location with Normal priority,
+        And the request for the equal in height location is cancelled,
+        Then the Streamer should have 0 destinations queued.
+
+    Scenario: A newer Online Status replaces where the previous one was headed.
+        Given a Tiled Map,
+        And a Streamer spawned on the Tiled Map,
+        When the Streamer is requested to travel to a lower location,
+        And the Status changes to Online,
+        And the Status changes to Away,
+        Then the Streamer should have 1 destination queued.
+        And the Streamer should head to the cave next.
+
+    Scenario: Equally urgent destinations are headed to in the order requested.
+        Given a Tiled Map,
+        And a Streamer spawned on the Tiled Map,
+        When the Streamer is requested to head to the lower and then the equal in height location,
+        Then the Streamer should be heading to the lower location.
+
+    Scenario: Shortening the walk heads to the nearest destination first.
+        Given a Tiled Map,
+        And a Streamer spawned on the Tiled Map,
+        And the Streamer shortens its walk between destinations,
+        When the Streamer is requested to head to the lower and then the equal in height location,
+        Then the Streamer should be heading to the equal in height location.
+
+    Scenario: A destination whose Path no longer leads from where the Streamer stands is headed to again.
+        Given a Tiled Map,
+        And a Streamer spawned on the Tiled Map,
+        When a Path to the higher location comes back after the Streamer was moved elsewhere,
+        Then the Streamer should still be headed to the higher location.
+
+    Scenario: A Streamer asked to head somewhere out of reach walks as close as it can.
+        Given a Tiled Map,
+        And a Streamer spawned on the Tiled Map,
+        When the Streamer is requested to head out to sea,
+        And the Streamer is done traveling,
+        Then the Streamer should be as close to the sea as it can get.
@@ -32,3 +32,11 @@ Feature: A Subscriber, represented as a Fish, can approach the Streamer to speak
         When the Subscriber sends a long chat message
         And the Subscriber is almost done speaking to the Streamer
         Then the Subscriber should still be speaking
+
+    Scenario: A map without a layer for Subscribers carries on without a Water Graph.
+        Given a Tiled Map
+        And a Streamer spawned on the Tiled Map
+        And a Subscriber spawned on the Tiled Map
+        When the stream switches to a map without a layer for Subscribers
+        And the Subscriber wants to speak
+        Then there should be a Ground Graph but no Water Graph
@@ -0,0 +1,21 @@
+Feature: Tiled maps loaded as assets find whatever they refer to relative to themselves.
+
+    Scenario: A map in a nested folder reads its external tilesets relative to itself.
+        Given the Tiled map called maps/nested/external_tilesets.tmx is loaded as an asset,
+        Then the map should have 2 tilesets,
+        And the map should have a tileset called terrain_0,
+        And the map should have a tileset called collection.
+
+    Scenario: Every image the map is drawn with is loaded along with it.
+        Given the Tiled map called maps/nested/external_tilesets.tmx is loaded as an asset,
+        Then the map should be drawn with environment/terrain_0.png,
+        And the map should be drawn with environment/terrain_0_32x.png,
+        And the map should finish loading along with everything it is drawn with.
+
+    Scenario: Tiles from an external tileset are drawn from its image.
+        Given the Tiled map called maps/nested/external_tilesets.tmx is loaded as an asset,
+        Then the Tile at 0, 0 should be drawn from environment/terrain_0.png.
+
+    Scenario: Tiles from a collection of images are drawn from their own image.
+        Given the Tiled map called maps/nested/external_tilesets.tmx is loaded as an asset,
+        Then the Tile at 1, 1 should be drawn from environment/terrain_0_32x.png.
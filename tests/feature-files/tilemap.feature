@@ -115,3 +115,22 @@ Feature: An isometric tilemap should be loaded from some map file.
         When the tiles are loaded from the Tiled map,
         And the tile coordinates have been converted to isometric,
         Then Tile 0, 0, 1 should be at pixel coordinates 0, -32, 1.
+
+    Scenario: A Tile animated in its Tiled tileset flicks through its frames over time.
+        Given a Tiled map called animated_tile.tmx,
+        When the tiles are loaded from the Tiled map,
+        Then Tile 0, 0, 0 should have an animation with the sprite entries 1, 2, 3.
+        And Tile 0, 0, 0 should show sprite entry 2 after 250 milliseconds.
+        And Tile 0, 0, 0 should show sprite entry 1 after 700 milliseconds.
+
+    Scenario: A Tile without an animation in its Tiled tileset stays still.
+        Given a Tiled map called one_tile_isometric.tmx,
+        When the tiles are loaded from the Tiled map,
+        Then Tile 0, 0, 0 should not have an animation.
+
+    Scenario: Each Tile is picked back from where its center is drawn on an isometric map.
+        Given a Tiled map called single_layer_isometric.tmx,
+        When the tiles are loaded from the Tiled map,
+        Then each Tile should be picked from where its center is drawn.
+        And Tile 1, 1, 0 should be picked from 12 pixels beside where its center is drawn.
+        And nothing should be picked beyond the left edge of the map.
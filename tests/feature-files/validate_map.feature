@@ -0,0 +1,35 @@
+Feature: Maps are validated before they go live, instead of panicking on stream.
+
+    Scenario: The shipped maps pass validation.
+        Given the shipped map called TM_map.tmx,
+        When the map is validated from the Streamer spawn of its Map Scene,
+        Then the map should have no errors.
+
+    Scenario: The cropped map passes validation as well.
+        Given the shipped map called campfire_cove.tmx,
+        When the map is validated from the Streamer spawn of its Map Scene,
+        Then the map should have no errors.
+
+    Scenario: Whatever cannot be reached or landed on is reported.
+        Given a Tiled map called validation.tmx,
+        When the map is validated from the Streamer spawning on 3, 3,
+        Then the map should have 3 errors,
+        And the map should have the error: The Point of Interest "Lookout" on 4, 7 cannot be reached from the Streamer spawn.,
+        And the map should have the error: The Fruit on 3, 1 falls off the ground graph.,
+        And the map should have the error: The water graph is split into 2 regions that cannot reach each other.
+
+    Scenario: A Streamer spawn off the ground graph is reported.
+        Given a Tiled map called validation.tmx,
+        When the map is validated from the Streamer spawning on 4, 7,
+        Then the map should have the error: The Streamer spawn on 4, 7 is not on the ground graph.
+
+    Scenario: A map missing some roles is reported.
+        Given a Tiled map called layer_roles.tmx,
+        When the map is validated without a Streamer spawn,
+        Then the map should have the error: Map has no layers for the roles: streamer, subscriber, crop, campfire, water, farmland
+
+    Scenario: A map the graphs cannot be built from is reported rather than panicking.
+        Given a Tiled map called terrain_properties.tmx,
+        When the map is validated without a Streamer spawn,
+        Then the map should have 1 error,
+        And the map should have an error about the graphs not being built.
@@ -0,0 +1,33 @@
+Feature: Idle NPCs wander around their area between conversations.
+    Scenario: An idle Chatter hops around the sky near where it rests.
+        Given a Tiled Map,
+        And a Chatter spawned on the Tiled Map,
+        And idle NPCs can wander,
+        When the NPCs have idled for a while,
+        Then the Chatter should have left its resting point,
+        And the Chatter should have stayed within its Wander Area.
+
+    Scenario: An idle Subscriber swims around the water near where it rests.
+        Given a Tiled Map,
+        And a Subscriber spawned on the Tiled Map,
+        And idle NPCs can wander,
+        When the NPCs have idled for a while,
+        Then the Subscriber should have left its resting point,
+        And the Subscriber should have stayed within its Wander Area.
+
+    Scenario: A Chatter keeps to a Wander Area it was given.
+        Given a Tiled Map,
+        And a Chatter spawned on the Tiled Map,
+        And the Chatter may only wander 1 Tile away,
+        And idle NPCs can wander,
+        When the NPCs have idled for a while,
+        Then the Chatter should have stayed within its Wander Area.
+
+    Scenario: A wandering Chatter stops to speak to the Streamer.
+        Given a Tiled Map,
+        And a Streamer spawned on the Tiled Map,
+        And a Chatter spawned on the Tiled Map,
+        And idle NPCs can wander,
+        When the Chatter is wandering,
+        And the Chatter wants to speak,
+        Then the Chatter should be approaching the Streamer.
@@ -8,6 +8,7 @@ use task_masker::entities::fruit::*;
 use task_masker::entities::streamer::*;
 use task_masker::entities::TriggerQueue;
 use task_masker::map::path_finding::*;
+use task_masker::map::path_requests::AwaitingPath;
 use task_masker::map::plugins::PathFindingPlugin;
 
 use crate::mock_plugins::{GameWorld, MockFruitPlugin, MockStreamerPlugin, MockTiledMapPlugin};
@@ -123,19 +124,19 @@ fn fruit_should_be_falling(world: &mut GameWorld) {
 
 #[then("the Streamer should be heading towards the fallen Fruit's position.")]
 fn streamer_should_be_heading_towards_fruit(world: &mut GameWorld) {
-    // We need to wait for the Streamer to actually be moving
-    // in order for their Path to be populated with something.
+    // We need to wait for the Streamer to actually be moving, and for
+    // their Path to be found, in order for it to be populated with something.
     loop {
         world.app.update();
 
-        let streamer_status = world
+        let (streamer_status, streamer_awaiting_path) = world
             .app
             .world_mut()
-            .query::<&StreamerState>()
+            .query::<(&StreamerState, &AwaitingPath)>()
             .get_single(&world.app.world())
             .expect("streamer_should_be_heading_towards_fruit: Streamer does not have a State.");
 
-        if *streamer_status == StreamerState::Moving {
+        if *streamer_status == StreamerState::Moving && !**streamer_awaiting_path {
             break;
         }
     }
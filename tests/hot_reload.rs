@@ -0,0 +1,200 @@
+mod mock_plugins;
+
+use bevy::prelude::*;
+use bevy_ecs_tilemap::prelude::*;
+use cucumber::{given, then, when, World};
+
+use crate::mock_plugins::{GameWorld, MockChatterPlugin, MockCropPlugin, MockTiledMapPlugin};
+use task_masker::entities::chatter::{ChatMessageQueue, ChatMsg, ChatterLabel};
+use task_masker::entities::crop::CropState;
+use task_masker::map::hot_reload::MapReloaded;
+use task_masker::map::objects::MapObject;
+use task_masker::map::path_finding::{GraphType, UndirectedGraph};
+use task_masker::map::plugins::PathFindingPlugin;
+
+/// Whoever was around before the map was reloaded.
+#[derive(Resource)]
+struct BeforeReload {
+    entity: Entity,
+}
+
+/// Returns the Tile some step is about.
+fn tile_from(tile: &str) -> TilePos {
+    let (x, y) = tile
+        .split_once(", ")
+        .expect("tile_from: Tiles should be written as x, y.");
+
+    TilePos::new(
+        x.parse().expect("tile_from: x should be a number."),
+        y.parse().expect("tile_from: y should be a number."),
+    )
+}
+
+/// Returns the Crop on some Tile, if any.
+fn crop_on(world: &mut GameWorld, tile_pos: TilePos) -> Option<(Entity, CropState)> {
+    world
+        .app
+        .world_mut()
+        .query::<(Entity, &TilePos, &CropState)>()
+        .iter(world.app.world())
+        .find(|(_, crop_tilepos, _)| **crop_tilepos == tile_pos)
+        .map(|(crop_entity, _, crop_state)| (crop_entity, crop_state.clone()))
+}
+
+/// Returns the Ground Graph of the map.
+fn ground_graph(world: &mut GameWorld) -> Entity {
+    let ground_graphs = world
+        .app
+        .world_mut()
+        .query::<(Entity, &UndirectedGraph)>()
+        .iter(world.app.world())
+        .filter(|(_, graph)| *graph.get_node_type() == GraphType::Ground)
+        .map(|(graph_entity, _)| graph_entity)
+        .collect::<Vec<Entity>>();
+    assert_eq!(1, ground_graphs.len());
+
+    ground_graphs[0]
+}
+
+#[given("a Tiled Map,")]
+fn spawn_tiled_map(world: &mut GameWorld) {
+    world.app.add_plugins(MockTiledMapPlugin);
+    world.update(1);
+
+    world.app.add_plugins(PathFindingPlugin);
+    world.update(1);
+
+    let ground_graph = ground_graph(world);
+    world.app.insert_resource(BeforeReload {
+        entity: ground_graph,
+    });
+}
+
+#[given("Crops spawned on the Tiled Map,")]
+fn spawn_crops_on_tiled_map(world: &mut GameWorld) {
+    world.app.add_plugins(MockCropPlugin);
+    world.update(1);
+}
+
+#[given(regex = r"^the Crop on (\d+, \d+) is Growing,")]
+fn crop_is_growing(world: &mut GameWorld, tile: String) {
+    let (crop_entity, _) = crop_on(world, tile_from(&tile))
+        .expect("crop_is_growing: There should be a Crop on the Tile.");
+
+    *world
+        .app
+        .world_mut()
+        .get_mut::<CropState>(crop_entity)
+        .expect("crop_is_growing: The Crop should have a state.") = CropState::Growing;
+    world.app.insert_resource(BeforeReload {
+        entity: crop_entity,
+    });
+}
+
+#[given(regex = r"^a Crop the map does not place on (\d+, \d+),")]
+fn spawn_unplaced_crop(world: &mut GameWorld, tile: String) {
+    world
+        .app
+        .world_mut()
+        .spawn((tile_from(&tile), CropState::Grown));
+}
+
+#[given("a Chatter spawned on the Tiled Map,")]
+fn spawn_chatter_on_tiled_map(world: &mut GameWorld) {
+    world.app.add_plugins(MockChatterPlugin);
+    world.update(1);
+}
+
+#[given(regex = r"^the Chatter has (\d+) messages to say,")]
+fn chatter_has_messages(world: &mut GameWorld, num_msgs: usize) {
+    let chatter_entity = world
+        .app
+        .world_mut()
+        .query_filtered::<Entity, With<ChatterLabel>>()
+        .single(world.app.world());
+
+    let mut chat_msgs = world
+        .app
+        .world_mut()
+        .get_mut::<ChatMessageQueue>(chatter_entity)
+        .expect("chatter_has_messages: The Chatter should have a message queue.");
+    for msg_idx in 0..num_msgs {
+        chat_msgs.push_back(ChatMsg {
+            name: String::from("Chatter"),
+            msg: format!("Message {msg_idx}"),
+        });
+    }
+    world.app.insert_resource(BeforeReload {
+        entity: chatter_entity,
+    });
+}
+
+#[when("the map is reloaded,")]
+fn reload_map(world: &mut GameWorld) {
+    world.broadcast_event(MapReloaded);
+
+    // The map is laid out again, then has its Graphs
+    // and entities built from it once more.
+    world.update(3);
+}
+
+#[then(regex = r"^the same Crop should be on (\d+, \d+).")]
+fn same_crop_should_be_on(world: &mut GameWorld, tile: String) {
+    let crop_before_reload = world.app.world().resource::<BeforeReload>().entity;
+    let (crop_entity, _) = crop_on(world, tile_from(&tile))
+        .expect("same_crop_should_be_on: There should be a Crop on the Tile.");
+
+    assert_eq!(crop_before_reload, crop_entity);
+}
+
+#[then(regex = r"^the Crop on (\d+, \d+) should still be Growing.")]
+fn crop_should_still_be_growing(world: &mut GameWorld, tile: String) {
+    let (_, crop_state) = crop_on(world, tile_from(&tile))
+        .expect("crop_should_still_be_growing: There should be a Crop on the Tile.");
+
+    assert_eq!(CropState::Growing, crop_state);
+}
+
+#[then(regex = r"^there should be (\d+) Crops on the Tiled Map.")]
+fn crops_on_tiled_map(world: &mut GameWorld, num_crops: usize) {
+    assert_eq!(num_crops, world.find_all::<CropState>().len());
+}
+
+#[then(regex = r"^there should be no Crop on (\d+, \d+).")]
+fn no_crop_on(world: &mut GameWorld, tile: String) {
+    assert!(crop_on(world, tile_from(&tile)).is_none());
+}
+
+#[then("the Ground Graph should have been built again.")]
+fn ground_graph_built_again(world: &mut GameWorld) {
+    let graph_before_reload = world.app.world().resource::<BeforeReload>().entity;
+
+    assert_ne!(graph_before_reload, ground_graph(world));
+}
+
+#[then(regex = r"^the same Chatter should still have (\d+) messages to say.")]
+fn same_chatter_has_messages(world: &mut GameWorld, num_msgs: usize) {
+    let chatter_before_reload = world.app.world().resource::<BeforeReload>().entity;
+    let chat_msgs = world
+        .app
+        .world()
+        .get::<ChatMessageQueue>(chatter_before_reload)
+        .expect("same_chatter_has_messages: The same Chatter should still be around.");
+
+    assert_eq!(num_msgs, chat_msgs.len());
+}
+
+#[then(regex = r"^there should be (\d+) Map Objects? named (.+).")]
+fn map_objects_named(world: &mut GameWorld, num_objects: usize, object_name: String) {
+    let named_objects = world
+        .find_all::<MapObject>()
+        .into_iter()
+        .filter(|map_object| map_object.name == object_name)
+        .count();
+
+    assert_eq!(num_objects, named_objects);
+}
+
+fn main() {
+    futures::executor::block_on(GameWorld::run("tests/feature-files/hot_reload.feature"));
+}
@@ -0,0 +1,98 @@
+use std::path::PathBuf;
+
+use cucumber::{given, then, World};
+
+use task_masker::entities::definitions::EntityKind;
+use task_masker::map::layer_roles::{LayerRole, LayerRoleError, LayerRoles};
+
+#[derive(Debug, Default, World)]
+pub struct LayerRoleContext {
+    layer_roles: LayerRoles,
+}
+
+/// Returns the role named within a step.
+fn role_from(role_name: &str) -> LayerRole {
+    LayerRole::from_name(role_name).expect("role_from: Step should name a known role.")
+}
+
+/// Reads the layer roles of the Tiled map found in some folder.
+fn read_layer_roles(context: &mut LayerRoleContext, map_folder: &str, map_name: String) {
+    let mut map_path = PathBuf::new();
+    if let Ok(project_root_directory) = std::env::var("CARGO_MANIFEST_DIR") {
+        map_path.push(project_root_directory);
+    }
+    map_path.push(map_folder);
+    map_path.push(map_name);
+
+    let tiled_map = tiled::Loader::new()
+        .load_tmx_map(map_path)
+        .expect("read_layer_roles: Map should load.");
+
+    context.layer_roles = LayerRoles::from_map(&tiled_map);
+}
+
+#[given(regex = r"^the shipped map called (.+\.tmx),$")]
+fn read_shipped_map(context: &mut LayerRoleContext, map_name: String) {
+    read_layer_roles(context, "assets", map_name);
+}
+
+#[given(regex = r"^a Tiled map called (.+\.tmx),$")]
+fn read_test_map(context: &mut LayerRoleContext, map_name: String) {
+    read_layer_roles(context, "tests/test-assets/maps", map_name);
+}
+
+#[then("every role should have a layer,")]
+fn every_role_has_a_layer(context: &mut LayerRoleContext) {
+    assert_eq!(context.layer_roles.ensure_all(), Ok(()));
+}
+
+#[then(regex = r"^the (\w+) role should be on layer (\d+)(,|\.)$")]
+fn role_on_layer(
+    context: &mut LayerRoleContext,
+    role_name: String,
+    layer_index: usize,
+    _end: String,
+) {
+    assert_eq!(
+        context.layer_roles.index_of(role_from(&role_name)),
+        Some(layer_index)
+    );
+}
+
+#[then(regex = r"^the map should be missing layers for the roles: (.+)\.$")]
+fn roles_missing(context: &mut LayerRoleContext, role_names: String) {
+    let missing_roles = role_names.split(", ").map(role_from).collect();
+    let layer_role_error = context
+        .layer_roles
+        .ensure_all()
+        .expect_err("roles_missing: Some roles should be missing.");
+
+    assert_eq!(
+        layer_role_error,
+        LayerRoleError::MissingRoles(missing_roles)
+    );
+    assert_eq!(
+        layer_role_error.to_string(),
+        format!("Map has no layers for the roles: {role_names}")
+    );
+}
+
+#[then(regex = r"^the Crop should be placed on layer (\d+) and drawn on layer (\d+).$")]
+fn crop_layers(context: &mut LayerRoleContext, spawn_layer: usize, drawn_layer: usize) {
+    let crop_definition = EntityKind::Crop.builtin_definition();
+
+    assert_eq!(
+        crop_definition.spawn_layer(&context.layer_roles),
+        Some(spawn_layer)
+    );
+    assert_eq!(
+        crop_definition.drawn_layer(&context.layer_roles),
+        Some(drawn_layer)
+    );
+}
+
+fn main() {
+    futures::executor::block_on(LayerRoleContext::run(
+        "tests/feature-files/layer_roles.feature",
+    ));
+}
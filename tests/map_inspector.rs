@@ -0,0 +1,210 @@
+use std::path::PathBuf;
+
+use bevy::prelude::*;
+use cucumber::{given, then, when, World};
+
+use task_masker::map::inspector::{InspectedMap, MapInspector};
+use task_masker::map::path_finding::GraphType;
+use task_masker::map::tilemap::TileGridCoordinates;
+
+#[derive(Default, World)]
+pub struct MapInspectorContext {
+    inspected_map: Option<InspectedMap>,
+    map_inspector: MapInspector,
+}
+
+impl std::fmt::Debug for MapInspectorContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MapInspectorContext")
+            .field("map_inspector", &self.map_inspector)
+            .finish()
+    }
+}
+
+impl MapInspectorContext {
+    /// Returns the map being inspected.
+    fn inspected_map(&self) -> &InspectedMap {
+        self.inspected_map
+            .as_ref()
+            .expect("inspected_map: The map should be loaded by now.")
+    }
+
+    /// Returns the description of whichever Tile is inspected.
+    fn inspected_tile_description(&self) -> String {
+        let inspected_tile = self
+            .map_inspector
+            .inspected_tile
+            .as_ref()
+            .expect("inspected_tile_description: Some Tile should be inspected.");
+        let tile = self
+            .inspected_map()
+            .tilemap()
+            .get_tile(inspected_tile)
+            .expect("inspected_tile_description: The inspected Tile should be on the map.");
+
+        self.inspected_map().describe_tile(tile)
+    }
+
+    /// Inspects whichever Tile is drawn at some point in the world.
+    fn click(&mut self, world_pos: Vec2) {
+        self.map_inspector.inspected_tile = self
+            .inspected_map()
+            .tile_at(world_pos, &self.map_inspector.hidden_layers)
+            .map(|tile| tile.get_grid_coordinates().clone());
+    }
+}
+
+/// Returns the type of Graph going by some name.
+fn graph_type_called(graph_name: &str) -> GraphType {
+    match graph_name {
+        "Ground" => GraphType::Ground,
+        "Water" => GraphType::Water,
+        "Air" => GraphType::Air,
+        _ => panic!("graph_type_called: There is no {graph_name} Graph."),
+    }
+}
+
+#[given(regex = r"^the inspected Tiled map called (.+\.tmx),$")]
+fn load_inspected_map(context: &mut MapInspectorContext, map_name: String) {
+    let mut map_path = PathBuf::new();
+    if let Ok(project_root_directory) = std::env::var("CARGO_MANIFEST_DIR") {
+        map_path.push(project_root_directory);
+    }
+    map_path.push("tests/test-assets/maps");
+    map_path.push(map_name);
+
+    let tiled_map = tiled::Loader::new()
+        .load_tmx_map(map_path)
+        .expect("load_inspected_map: Map should load.");
+    context.inspected_map = Some(InspectedMap::new(tiled_map));
+}
+
+#[when(regex = r"^layer (\d+) is hidden,$")]
+fn hide_layer(context: &mut MapInspectorContext, layer: usize) {
+    context.map_inspector.hidden_layers.insert(layer);
+}
+
+#[when(regex = r"^the point where Tile (\d+), (\d+), (\d+) is drawn is clicked,$")]
+fn click_tile(context: &mut MapInspectorContext, x: usize, y: usize, z: usize) {
+    let world_pos = context
+        .inspected_map()
+        .drawn_center(&TileGridCoordinates::new_3d(x, y, z));
+    context.click(world_pos);
+}
+
+#[when(
+    regex = r"^the point where Tile (\d+), (\d+), (\d+) is drawn is clicked from (\d+) pixels to its left,$"
+)]
+fn click_left_of_tile(
+    context: &mut MapInspectorContext,
+    x: usize,
+    y: usize,
+    z: usize,
+    pixels_left: f32,
+) {
+    let world_pos = context
+        .inspected_map()
+        .drawn_center(&TileGridCoordinates::new_3d(x, y, z))
+        - Vec2::new(pixels_left, 0.0);
+    context.click(world_pos);
+}
+
+#[when(
+    regex = r"^the Path is tested along the (\w+) Graph from Tile (\d+), (\d+), (\d+) to Tile (\d+), (\d+), (\d+),$"
+)]
+#[allow(clippy::too_many_arguments)]
+fn test_path_between(
+    context: &mut MapInspectorContext,
+    graph_name: String,
+    start_x: usize,
+    start_y: usize,
+    start_z: usize,
+    end_x: usize,
+    end_y: usize,
+    end_z: usize,
+) {
+    context.map_inspector.graph_type = graph_type_called(&graph_name);
+    context.map_inspector.path_start = Some(TileGridCoordinates::new_3d(start_x, start_y, start_z));
+    context.map_inspector.path_end = Some(TileGridCoordinates::new_3d(end_x, end_y, end_z));
+
+    let inspected_map = context
+        .inspected_map
+        .as_ref()
+        .expect("test_path_between: The map should be loaded by now.");
+    context.map_inspector.retest_path(inspected_map);
+}
+
+#[then(regex = r"^Tile (\d+), (\d+), (\d+) should be inspected.$")]
+fn tile_is_inspected(context: &mut MapInspectorContext, x: usize, y: usize, z: usize) {
+    assert_eq!(
+        Some(TileGridCoordinates::new_3d(x, y, z)),
+        context.map_inspector.inspected_tile
+    );
+}
+
+#[then("no Tile should be inspected.")]
+fn no_tile_is_inspected(context: &mut MapInspectorContext) {
+    assert_eq!(None, context.map_inspector.inspected_tile);
+}
+
+#[then(regex = r"^the inspected Tile should be described as on layer (\d+) \((.+)\),$")]
+fn tile_described_on_layer(context: &mut MapInspectorContext, layer: usize, layer_name: String) {
+    let description = context.inspected_tile_description();
+    assert!(
+        description.contains(&format!("on layer {layer} ({layer_name})")),
+        "{description}"
+    );
+}
+
+#[then(regex = r"^the inspected Tile should be described with the texture index (\d+).$")]
+fn tile_described_with_texture(context: &mut MapInspectorContext, texture_index: usize) {
+    let description = context.inspected_tile_description();
+    assert!(
+        description.contains(&format!("Texture index: {texture_index}")),
+        "{description}"
+    );
+}
+
+#[then(regex = r"^the inspected Tile should (not )?be on the (\w+) Graph(,|\.)$")]
+fn tile_on_graph(
+    context: &mut MapInspectorContext,
+    negation: String,
+    graph_name: String,
+    _end: String,
+) {
+    let inspected_tile = context
+        .map_inspector
+        .inspected_tile
+        .as_ref()
+        .expect("tile_on_graph: Some Tile should be inspected.");
+    let on_graph = context
+        .inspected_map()
+        .graphs_containing(inspected_tile)
+        .contains(&graph_type_called(&graph_name));
+
+    assert_eq!(negation.is_empty(), on_graph);
+}
+
+#[then("a Path should be found.")]
+fn path_is_found(context: &mut MapInspectorContext) {
+    assert!(
+        matches!(context.map_inspector.path, Some(Ok(_))),
+        "{:?}",
+        context.map_inspector.path
+    );
+}
+
+#[then("no Path should be found.")]
+fn no_path_is_found(context: &mut MapInspectorContext) {
+    assert!(
+        matches!(context.map_inspector.path, Some(Err(_))),
+        "{:?}",
+        context.map_inspector.path
+    );
+}
+
+fn main() {
+    futures::executor::block_on(MapInspectorContext::run(
+        "tests/feature-files/map_inspector.feature",
+    ));
+}
@@ -1,3 +1,7 @@
+// NOTE: Every test suite includes this module while only using the mocks and
+// helpers its own steps need, so the rest would be flagged as dead code.
+#![allow(dead_code)]
+
 use bevy::prelude::*;
 
 use bevy::ecs::query::QueryFilter;
@@ -6,18 +10,30 @@ use bevy::state::app::StatesPlugin;
 use bevy::utils::Duration;
 
 use task_masker::entities::chatter::*;
+use task_masker::entities::critter::*;
 use task_masker::entities::crop::*;
+use task_masker::entities::definitions::EntityDefinitions;
 use task_masker::entities::fruit::*;
 use task_masker::entities::streamer::*;
 use task_masker::entities::subscriber::*;
-use task_masker::entities::WaitToLeaveTimer;
-use task_masker::map::path_finding::*;
+use task_masker::entities::visitor::*;
+use task_masker::entities::wander::*;
+use task_masker::entities::{GameEntityType, WaitToLeaveTimer};
+use task_masker::map::hot_reload::MapReloaded;
+use task_masker::map::layer_roles::LayerRoles;
+use task_masker::map::movement::MovementSpeed;
+use task_masker::map::objects::{place_map_props, trigger_zones, ZoneEntered, ZoneLeft};
+use task_masker::map::occupancy::BlockedTimer;
+use task_masker::map::plugins::CancelDestinationEvent;
+use task_masker::map::scenes::*;
 use task_masker::map::tiled::*;
+use task_masker::session::saving::*;
 use task_masker::ui::chatting::*;
 use task_masker::ui::portrait_preferences::*;
 use task_masker::ui::screens::spawn_ingame_screen;
 use task_masker::visual::animations::*;
 use task_masker::visual::environment::*;
+use task_masker::visual::tile_animations::animate_tile_sprites;
 
 use task_masker::GameState;
 
@@ -49,20 +65,28 @@ pub struct MockChatterPlugin;
 
 impl Plugin for MockChatterPlugin {
     fn build(&self, app: &mut App) {
+        app.init_resource::<EntityDefinitions>();
+        app.init_resource::<LayerRoles>();
+        app.init_resource::<StreamerFlowFields>();
         app.add_event::<ChatMsg>();
         app.add_event::<Msg>();
         app.add_systems(
             Update,
             (
                 replace_chatter_tile,
-                add_chat_msg_to_queue.after(replace_chatter_tile),
-                fly_to_streamer_to_speak.after(add_chat_msg_to_queue),
-                speak_to_streamer_from_chatter.after(fly_to_streamer_to_speak),
-                chatter_waits_to_leave_from_streamer.after(speak_to_streamer_from_chatter),
-                leave_from_streamer_from_chatter.after(chatter_waits_to_leave_from_streamer),
-                return_chatter_to_idle,
+                add_msg_to_queue::<ChatterLabel>.after(replace_chatter_tile),
+                update_flow_field_towards_streamer::<ChatterLabel>
+                    .before(approach_streamer_to_speak::<ChatterLabel>)
+                    .before(follow_streamer_while_approaching::<ChatterLabel>),
+                approach_streamer_to_speak::<ChatterLabel>.after(add_msg_to_queue::<ChatterLabel>),
+                speak_to_streamer::<ChatterLabel>.after(approach_streamer_to_speak::<ChatterLabel>),
+                wait_to_leave_from_streamer::<ChatterLabel>
+                    .after(speak_to_streamer::<ChatterLabel>),
+                leave_from_streamer::<ChatterLabel>
+                    .after(wait_to_leave_from_streamer::<ChatterLabel>),
+                return_visitor_to_idle::<ChatterLabel>,
                 follow_streamer_while_speaking,
-                follow_streamer_while_approaching_for_chatter,
+                follow_streamer_while_approaching::<ChatterLabel>,
             ),
         );
     }
@@ -73,12 +97,18 @@ pub struct MockStreamerPlugin;
 
 impl Plugin for MockStreamerPlugin {
     fn build(&self, app: &mut App) {
+        app.init_resource::<EntityDefinitions>();
+        app.init_resource::<LayerRoles>();
+        app.init_resource::<RouteSettings>();
+        app.init_resource::<MapScenes>();
         app.add_event::<OnlineStatus>();
+        app.add_event::<CancelDestinationEvent>();
         app.add_systems(
             Update,
             (
                 spawn_player_tile,
                 move_streamer,
+                follow_path_found_for_streamer,
                 move_streamer_on_status_change,
                 queue_destination_for_streamer.after(spawn_player_tile),
                 make_streamer_idle_when_not_moving,
@@ -93,7 +123,28 @@ pub struct MockTiledMapPlugin;
 
 impl Plugin for MockTiledMapPlugin {
     fn build(&self, app: &mut App) {
+        app.init_resource::<MapScenes>();
+        app.init_resource::<LayerRoles>();
+        app.add_event::<SwitchSceneEvent>();
+        app.add_event::<SceneSwitched>();
+        app.add_event::<ZoneEntered>();
+        app.add_event::<ZoneLeft>();
+        app.add_event::<MapReloaded>();
         app.add_systems(Startup, spawn_tiles_from_tiledmap);
+        app.add_systems(
+            Update,
+            (
+                switch_scene,
+                trigger_zones,
+                place_map_props,
+                spawn_tiles_from_tiledmap
+                    .run_if(on_event::<SceneSwitched>())
+                    .after(switch_scene),
+                (despawn_tiles_from_tiledmap, spawn_tiles_from_tiledmap)
+                    .chain()
+                    .run_if(on_event::<MapReloaded>()),
+            ),
+        );
     }
 }
 
@@ -102,18 +153,84 @@ pub struct MockSubscriberPlugin;
 
 impl Plugin for MockSubscriberPlugin {
     fn build(&self, app: &mut App) {
+        app.init_resource::<EntityDefinitions>();
+        app.init_resource::<LayerRoles>();
+        app.init_resource::<StreamerFlowFields>();
         app.add_event::<SubscriberMsg>();
         app.add_event::<Msg>();
         app.add_systems(
             Update,
             (
                 replace_subscriber_tile,
-                swim_to_streamer_to_speak.after(replace_subscriber_tile),
-                speak_to_streamer_from_subscriber.after(swim_to_streamer_to_speak),
-                subscriber_waits_to_leave_from_streamer.after(speak_to_streamer_from_subscriber),
-                leave_from_streamer_from_subscriber.after(subscriber_waits_to_leave_from_streamer),
-                return_subscriber_to_idle,
-                follow_streamer_while_approaching_for_subscriber,
+                add_msg_to_queue::<SubscriberLabel>.after(replace_subscriber_tile),
+                update_flow_field_towards_streamer::<SubscriberLabel>
+                    .before(approach_streamer_to_speak::<SubscriberLabel>)
+                    .before(follow_streamer_while_approaching::<SubscriberLabel>),
+                approach_streamer_to_speak::<SubscriberLabel>
+                    .after(add_msg_to_queue::<SubscriberLabel>),
+                speak_to_streamer::<SubscriberLabel>
+                    .after(approach_streamer_to_speak::<SubscriberLabel>),
+                wait_to_leave_from_streamer::<SubscriberLabel>
+                    .after(speak_to_streamer::<SubscriberLabel>),
+                leave_from_streamer::<SubscriberLabel>
+                    .after(wait_to_leave_from_streamer::<SubscriberLabel>),
+                return_visitor_to_idle::<SubscriberLabel>,
+                follow_streamer_while_approaching::<SubscriberLabel>,
+            ),
+        );
+    }
+}
+
+#[derive(Default)]
+pub struct MockCritterPlugin;
+
+impl Plugin for MockCritterPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<FollowMsg>();
+        app.add_systems(
+            Update,
+            (
+                restore_critters_from_records,
+                settle_critter_on_follow.after(restore_critters_from_records),
+                rotate_critters_over_cap.after(settle_critter_on_follow),
+            ),
+        );
+    }
+}
+
+#[derive(Default)]
+pub struct MockWanderPlugin;
+
+impl Plugin for MockWanderPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(WanderSettings {
+            pause_secs: 0.0,
+            ..default()
+        });
+        app.add_systems(
+            Update,
+            (
+                insert_wander_area::<VisitorStatus>,
+                wander_while_idle::<VisitorStatus>.after(insert_wander_area::<VisitorStatus>),
+            ),
+        );
+    }
+}
+
+#[derive(Default)]
+pub struct MockSessionPlugin;
+
+impl Plugin for MockSessionPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(PreStartup, restore_scene_session);
+        app.add_systems(
+            Update,
+            (
+                restore_streamer_session,
+                restore_crop_session,
+                restore_fruit_session,
+                restore_chat_session,
+                restore_health_session,
             ),
         );
     }
@@ -124,6 +241,8 @@ pub struct MockFruitPlugin;
 
 impl Plugin for MockFruitPlugin {
     fn build(&self, app: &mut App) {
+        app.init_resource::<EntityDefinitions>();
+        app.init_resource::<LayerRoles>();
         app.add_systems(
             Update,
             (
@@ -142,6 +261,8 @@ pub struct MockCropPlugin;
 
 impl Plugin for MockCropPlugin {
     fn build(&self, app: &mut App) {
+        app.init_resource::<EntityDefinitions>();
+        app.init_resource::<LayerRoles>();
         app.add_event::<NewSubscriber>();
         app.add_systems(
             Update,
@@ -172,17 +293,31 @@ pub struct MockEnvironmentAnimationsPlugin;
 
 impl Plugin for MockEnvironmentAnimationsPlugin {
     fn build(&self, app: &mut App) {
+        app.init_resource::<EntityDefinitions>();
+        app.init_resource::<LayerRoles>();
         app.add_systems(Update, replace_campfire_tile);
         app.add_systems(Update, insert_animation_information);
+        app.add_systems(Update, animate_tile_sprites);
         app.add_systems(Update, make_streamer_face_campfire);
     }
 }
 
-/// Sets the time to move for any Entity to be instant
-/// for testing purposes.
-fn reduce_movement_times_to_zero(mut timer_query: Query<&mut MovementTimer, Added<MovementTimer>>) {
-    for mut movement_timer in &mut timer_query {
-        movement_timer.0 = Timer::new(Duration::from_secs(0), TimerMode::Repeating);
+/// Sets the time to move for anything getting around the map
+/// to be instant for testing purposes. Fruit keeps falling at
+/// its own pace, so it can still be caught falling.
+fn make_movement_instant(
+    mut speed_query: Query<&mut MovementSpeed, (Added<MovementSpeed>, With<GameEntityType>)>,
+) {
+    for mut movement_speed in &mut speed_query {
+        movement_speed.0 = f32::MAX;
+    }
+}
+
+/// Sets the time spent waiting on anyone in the way to be
+/// instant for testing purposes.
+fn reduce_blocked_times_to_zero(mut timer_query: Query<&mut BlockedTimer, Added<BlockedTimer>>) {
+    for mut blocked_timer in &mut timer_query {
+        blocked_timer.0 = Timer::new(Duration::from_secs(0), TimerMode::Once);
     }
 }
 
@@ -230,7 +365,8 @@ impl GameWorld {
         app.insert_state(GameState::InGame);
         app.add_plugins(MinimalPlugins);
 
-        app.add_systems(Update, reduce_movement_times_to_zero);
+        app.add_systems(Update, make_movement_instant);
+        app.add_systems(Update, reduce_blocked_times_to_zero);
 
         Self { app }
     }
@@ -0,0 +1,197 @@
+mod mock_plugins;
+
+use std::path::PathBuf;
+
+use bevy::prelude::*;
+use bevy_ecs_tilemap::prelude::*;
+use cucumber::{given, then, when, World};
+
+use crate::mock_plugins::GameWorld;
+use task_masker::entities::GameEntityType;
+use task_masker::map::objects::{
+    trigger_zones, MapObject, MapObjects, MapProp, ObjectProperties, ObjectSpawnPoint,
+    PointOfInterest, TriggerZone, ZoneEntered, ZoneLeft,
+};
+
+/// The names of each Trigger Zone walked in and out of.
+#[derive(Resource, Default)]
+struct ZoneCrossings {
+    entered: Vec<String>,
+    left: Vec<String>,
+}
+
+/// The walker moving in and out of Trigger Zones.
+#[derive(Resource)]
+struct Walker(Entity);
+
+fn record_zone_crossings(
+    mut zone_entered_reader: EventReader<ZoneEntered>,
+    mut zone_left_reader: EventReader<ZoneLeft>,
+    mut zone_crossings: ResMut<ZoneCrossings>,
+) {
+    for zone_entered in zone_entered_reader.read() {
+        zone_crossings.entered.push(zone_entered.zone_name.clone());
+    }
+
+    for zone_left in zone_left_reader.read() {
+        zone_crossings.left.push(zone_left.zone_name.clone());
+    }
+}
+
+/// Returns the Tile written out within a step as "x, y".
+fn tile_from(tile_text: &str) -> TilePos {
+    let (x, y) = tile_text
+        .split_once(", ")
+        .expect("tile_from: Tile should be written as x, y.");
+
+    TilePos::new(
+        x.trim().parse().expect("tile_from: x should be a number."),
+        y.trim().parse().expect("tile_from: y should be a number."),
+    )
+}
+
+/// Returns the entity of the Map Object going by some name.
+fn map_object_named(world: &mut GameWorld, object_name: &str) -> Entity {
+    world
+        .app
+        .world_mut()
+        .query::<(Entity, &MapObject)>()
+        .iter(world.app.world())
+        .find(|(_object_entity, map_object)| map_object.name == object_name)
+        .map(|(object_entity, _map_object)| object_entity)
+        .expect("map_object_named: Map Object should be spawned.")
+}
+
+#[given(regex = r"^the objects of a Tiled map called (.+\.tmx),$")]
+fn spawn_map_objects(world: &mut GameWorld, map_name: String) {
+    let mut map_path = PathBuf::new();
+    if let Ok(project_root_directory) = std::env::var("CARGO_MANIFEST_DIR") {
+        map_path.push(project_root_directory);
+    }
+    map_path.push("tests/test-assets/maps");
+    map_path.push(map_name);
+
+    let tiled_map = tiled::Loader::new()
+        .load_tmx_map(map_path)
+        .expect("spawn_map_objects: Test map should load.");
+
+    MapObjects::from_map(&tiled_map).spawn(&mut world.app.world_mut().commands());
+    world.app.world_mut().flush();
+
+    world.app.init_resource::<ZoneCrossings>();
+    world.app.add_event::<ZoneEntered>();
+    world.app.add_event::<ZoneLeft>();
+    world.app.add_systems(
+        Update,
+        (trigger_zones, record_zone_crossings.after(trigger_zones)),
+    );
+}
+
+#[given(regex = r"^a walker standing on (\d+, \d+),$")]
+fn spawn_walker(world: &mut GameWorld, tile_text: String) {
+    let walker = world
+        .app
+        .world_mut()
+        .spawn((GameEntityType::Walk, tile_from(&tile_text)))
+        .id();
+    world.app.insert_resource(Walker(walker));
+    world.update(1);
+}
+
+#[when(regex = r"^the walker steps onto (\d+, \d+),$")]
+fn step_walker(world: &mut GameWorld, tile_text: String) {
+    let walker = world.app.world().resource::<Walker>().0;
+    world
+        .app
+        .world_mut()
+        .entity_mut(walker)
+        .insert(tile_from(&tile_text));
+    world.update(1);
+}
+
+#[then(regex = r"^there should be (\d+) Map Objects.$")]
+fn map_objects_counted(world: &mut GameWorld, object_count: usize) {
+    assert_eq!(object_count, world.find_all::<MapObject>().len());
+}
+
+#[then(regex = r"^the spawn point named (.+) should be on (\d+, \d+).$")]
+fn spawn_point_on(world: &mut GameWorld, object_name: String, tile_text: String) {
+    let object_entity = map_object_named(world, &object_name);
+    let object_entity = world.app.world().entity(object_entity);
+
+    assert!(object_entity.contains::<ObjectSpawnPoint>());
+    assert_eq!(object_entity.get::<TilePos>(), Some(&tile_from(&tile_text)));
+}
+
+#[then(regex = r"^the Point of Interest named (.+) should be on (\d+, \d+).$")]
+fn point_of_interest_on(world: &mut GameWorld, object_name: String, tile_text: String) {
+    let object_entity = map_object_named(world, &object_name);
+    let object_entity = world.app.world().entity(object_entity);
+
+    assert!(object_entity.contains::<PointOfInterest>());
+    assert_eq!(object_entity.get::<TilePos>(), Some(&tile_from(&tile_text)));
+}
+
+#[then(regex = r"^the (\w+) should have its (\w+) set to (.+).$")]
+fn object_property_set(
+    world: &mut GameWorld,
+    object_name: String,
+    property: String,
+    value: String,
+) {
+    let object_entity = map_object_named(world, &object_name);
+    let object_properties = world
+        .app
+        .world()
+        .get::<ObjectProperties>(object_entity)
+        .expect("object_property_set: Map Object should carry its properties.");
+
+    assert_eq!(
+        object_properties.get(&property),
+        Some(&tiled::PropertyValue::StringValue(value))
+    );
+}
+
+#[then(regex = r"^the (\w+) zone should cover (.+).$")]
+fn zone_covers(world: &mut GameWorld, object_name: String, tiles_text: String) {
+    let object_entity = map_object_named(world, &object_name);
+    let trigger_zone = world
+        .app
+        .world()
+        .get::<TriggerZone>(object_entity)
+        .expect("zone_covers: Map Object should be a Trigger Zone.");
+    let expected_tiles = tiles_text.split(" and ").map(tile_from).collect();
+
+    assert_eq!(trigger_zone.0, expected_tiles);
+}
+
+#[then(regex = r"^the walker should have entered the (\w+).$")]
+fn walker_entered(world: &mut GameWorld, zone_name: String) {
+    let zone_crossings = world.app.world().resource::<ZoneCrossings>();
+
+    assert!(zone_crossings.entered.contains(&zone_name));
+}
+
+#[then(regex = r"^the walker should have left the (\w+).$")]
+fn walker_left(world: &mut GameWorld, zone_name: String) {
+    let zone_crossings = world.app.world().resource::<ZoneCrossings>();
+
+    assert!(zone_crossings.left.contains(&zone_name));
+}
+
+#[then(regex = r"^the (\w+) should be a Prop drawn with Tile (\d+) on (\d+, \d+).$")]
+fn prop_drawn_on(world: &mut GameWorld, object_name: String, tile_id: u32, tile_text: String) {
+    let object_entity = map_object_named(world, &object_name);
+    let object_entity = world.app.world().entity(object_entity);
+    let map_prop = object_entity
+        .get::<MapProp>()
+        .expect("prop_drawn_on: Map Object should be a Prop.");
+
+    assert_eq!(map_prop.tileset_index, Some(0));
+    assert_eq!(map_prop.tile_id, tile_id);
+    assert_eq!(object_entity.get::<TilePos>(), Some(&tile_from(&tile_text)));
+}
+
+fn main() {
+    futures::executor::block_on(GameWorld::run("tests/feature-files/objects.feature"));
+}
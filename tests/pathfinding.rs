@@ -1,13 +1,84 @@
 mod mock_plugins;
 
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::PathBuf;
+use std::time::Duration;
+
+use bevy::ecs::system::RunSystemOnce;
 use bevy::prelude::*;
 use bevy_ecs_tilemap::prelude::*;
 
 use cucumber::{given, then, when, World};
 
 use crate::mock_plugins::{GameWorld, MockTiledMapPlugin};
-use task_masker::map::path_finding::{GraphType, UndirectedGraph};
+use task_masker::entities::GameEntityType;
+use task_masker::map::destinations::{DestinationQueue, DestinationRequest};
+use task_masker::map::flow_fields::FlowField;
+use task_masker::map::links::{
+    mark_link_crossings, CrossingLink, LinkTransition, NavLink, NavLinks,
+};
+use task_masker::map::movement::{
+    move_entities, HopHeight, MovementEasing, MovementSpeed, StepProgress,
+};
+use task_masker::map::nav_overlay::{describe_tile, tile_at};
+use task_masker::map::occupancy::{update_occupancy_map, BlockedTimer, OccupancyMap};
+use task_masker::map::path_finding::{
+    get_direction, idx_to_tilepos, insert_pathing_information, tilepos_to_idx, tiles_between,
+    update_movement_target, Direction, GraphLayout, GraphType, HeightedTilePos, Path,
+    StartingPoint, Target, TileLayerPosition, UndirectedGraph,
+};
+use task_masker::map::path_requests::{
+    send_finished_path_results, share_graphs_for_path_requests, solve_path_requests, PathError,
+    PathRequest, PathResult, PendingPaths, SharedGraphs,
+};
 use task_masker::map::plugins::PathFindingPlugin;
+use task_masker::map::terrain::{Slope, Terrain};
+
+/// How many Tiles wide and long the patch of ground is.
+const PATCH_SIZE: u32 = 3;
+
+/// The layer Swimmers are placed on, high above the rest of the patch.
+const SWIMMER_LAYER: usize = 18;
+
+/// How many levels above the rest of the patch a cliff stands.
+const CLIFF_HEIGHT: u32 = 4;
+
+/// The layout of the patch of ground, where each Tile stands
+/// 1 level above the water unless raised any higher.
+#[derive(Resource, Default)]
+struct Patch {
+    layout: GraphLayout,
+    heights: HashMap<TilePos, u32>,
+}
+
+impl Patch {
+    /// Returns the Tile standing on top of the patch at some position.
+    fn standing_tile(&self, x: u32, y: u32) -> HeightedTilePos {
+        let tile_pos = TilePos::new(x, y);
+        let tile_height = self.heights.get(&tile_pos).copied().unwrap_or(1);
+
+        HeightedTilePos::new(tile_pos, tile_height)
+    }
+}
+
+/// The destination picked to be headed to first on the patch of ground.
+#[derive(Resource)]
+struct FirstDestination(Option<TilePos>);
+
+/// The Path found across the patch of ground.
+#[derive(Resource)]
+struct PatchPath(Option<Path>);
+
+/// Returns the Terrain named by some step.
+fn terrain_from(terrain_option: &str) -> Terrain {
+    match terrain_option {
+        "Path" => Terrain::Path,
+        "Plain" => Terrain::Plain,
+        "Tall Grass" => Terrain::TallGrass,
+        "Shallow Water" => Terrain::ShallowWater,
+        _ => unreachable!(),
+    }
+}
 
 #[given("the Tiled Loading module is loaded,")]
 fn load_tiled_module(world: &mut GameWorld) {
@@ -61,6 +132,926 @@ fn simple_tile_path_exists(world: &mut GameWorld) {
     assert!(tile_path.is_some());
 }
 
+#[given(regex = r"^a patch of ground with (Plain|Tall Grass|Shallow Water) in the middle,")]
+fn spawn_patch_of_ground(world: &mut GameWorld, terrain_option: String) {
+    let mut patch = Patch::default();
+    let middle_tile = patch.standing_tile(1, 1);
+    patch
+        .layout
+        .terrains
+        .insert(middle_tile, terrain_from(&terrain_option));
+
+    world.app.insert_resource(patch);
+}
+
+#[given(regex = r"^a patch of ground with a cliff in the middle,")]
+fn spawn_patch_of_ground_with_cliff(world: &mut GameWorld) {
+    let mut patch = Patch::default();
+    patch.heights.insert(TilePos::new(1, 1), CLIFF_HEIGHT);
+
+    world.app.insert_resource(patch);
+}
+
+#[given("a patch of ground with a pond along the middle,")]
+fn spawn_patch_of_ground_with_pond(world: &mut GameWorld) {
+    let mut patch = Patch::default();
+    patch.heights.insert(TilePos::new(1, 1), 0);
+    patch.heights.insert(TilePos::new(1, 2), 0);
+
+    world.app.insert_resource(patch);
+}
+
+#[given(regex = r"^the cliff is climbed by (Stairs|a Ramp|a Ledge),")]
+fn shape_cliff(world: &mut GameWorld, slope_option: String) {
+    let slope = match slope_option.as_str() {
+        "Stairs" => Slope::Stairs,
+        "a Ramp" => Slope::Ramp,
+        "a Ledge" => Slope::Ledge,
+        _ => unreachable!(),
+    };
+
+    let mut patch = world.app.world_mut().resource_mut::<Patch>();
+    let cliff_tile = patch.standing_tile(1, 1);
+    patch.layout.slopes.insert(cliff_tile, slope);
+}
+
+#[given("a dirt path along the edge of the patch,")]
+fn lay_dirt_path_along_patch(world: &mut GameWorld) {
+    let mut patch = world.app.world_mut().resource_mut::<Patch>();
+    for x in 0..PATCH_SIZE {
+        let path_tile = patch.standing_tile(x, 0);
+        patch.layout.terrains.insert(path_tile, Terrain::Path);
+    }
+}
+
+#[given("diagonal movement is allowed,")]
+fn allow_diagonal_movement(world: &mut GameWorld) {
+    world
+        .app
+        .world_mut()
+        .resource_mut::<Patch>()
+        .layout
+        .diagonals = true;
+}
+
+/// Returns the Tile written as "x, y" by some step.
+fn tile_from(tile_option: &str) -> TilePos {
+    let (x, y) = tile_option
+        .split_once(", ")
+        .expect("tile_from: Tiles should be written as x, y.");
+
+    TilePos::new(
+        x.parse().expect("tile_from: x should be a number."),
+        y.parse().expect("tile_from: y should be a number."),
+    )
+}
+
+/// Returns the Link Transition named by some step.
+fn transition_from(transition_option: &str) -> LinkTransition {
+    match transition_option {
+        "Ladder" => LinkTransition::Ladder,
+        "Portal" => LinkTransition::Portal,
+        _ => unreachable!(),
+    }
+}
+
+#[given(
+    regex = r"^a (two way|one way) (Ladder|Portal) links (\d+, \d+) to (\d+, \d+) costing (\d+) steps?,"
+)]
+fn link_patch_tiles(
+    world: &mut GameWorld,
+    way_option: String,
+    transition_option: String,
+    from_option: String,
+    to_option: String,
+    cost: u32,
+) {
+    let mut patch = world.app.world_mut().resource_mut::<Patch>();
+    patch.layout.links.push(NavLink {
+        from: tile_from(&from_option),
+        to: tile_from(&to_option),
+        graph_type: GraphType::Ground,
+        cost,
+        transition: Some(transition_from(&transition_option)),
+        two_way: way_option == "two way",
+    });
+}
+
+/// Returns the Undirected Graph laid out across the patch of ground.
+fn patch_graph(patch: &Patch, graph_type: GraphType) -> UndirectedGraph {
+    // Each Tile stands on top of every level below it,
+    // down to the water underneath.
+    let patch_tiles = (0..PATCH_SIZE)
+        .flat_map(|x| (0..PATCH_SIZE).map(move |y| (x, y)))
+        .flat_map(|(x, y)| {
+            let standing_tile = patch.standing_tile(x, y);
+            (0..=standing_tile.z()).map(move |z| HeightedTilePos::new(standing_tile.truncate(), z))
+        })
+        .collect::<Vec<HeightedTilePos>>();
+    let patch_layers = (0..=SWIMMER_LAYER)
+        .map(|layer_idx| {
+            TileLayerPosition::new(
+                TilemapGridSize { x: 32.0, y: 16.0 },
+                TilemapSize {
+                    x: PATCH_SIZE,
+                    y: PATCH_SIZE,
+                },
+                Transform::from_xyz(0.0, 0.0, layer_idx as f32),
+            )
+        })
+        .collect::<Vec<TileLayerPosition>>();
+
+    let patch_layout = GraphLayout {
+        water_layer: SWIMMER_LAYER,
+        ..patch.layout.clone()
+    };
+
+    UndirectedGraph::from_tiles_with_layout(graph_type, patch_tiles, patch_layers, &patch_layout)
+}
+
+/// Finds a Path between two Tiles of the patch of ground.
+fn find_path_on_patch(world: &mut GameWorld, graph_type: GraphType, start: TilePos, end: TilePos) {
+    let patch = world.app.world().resource::<Patch>();
+    let patch_path = patch_graph(patch, graph_type).shortest_path(start, end);
+
+    world.app.insert_resource(PatchPath(patch_path));
+}
+
+/// Returns the kind of Graph getting around for some step.
+fn graph_type_from(mover_option: &str) -> GraphType {
+    match mover_option {
+        "walkers" => GraphType::Ground,
+        "flyers" => GraphType::Air,
+        _ => unreachable!(),
+    }
+}
+
+#[when(regex = r"^a Path is found across the patch for (walkers|flyers),")]
+fn find_path_across_patch(world: &mut GameWorld, mover_option: String) {
+    find_path_on_patch(
+        world,
+        graph_type_from(&mover_option),
+        TilePos::new(0, 1),
+        TilePos::new(2, 1),
+    );
+}
+
+#[when(regex = r"^a Path is found from (\d+), (\d+) to (\d+), (\d+) for (walkers|flyers),")]
+fn find_path_between_patch_tiles(
+    world: &mut GameWorld,
+    start_x: u32,
+    start_y: u32,
+    end_x: u32,
+    end_y: u32,
+    mover_option: String,
+) {
+    find_path_on_patch(
+        world,
+        graph_type_from(&mover_option),
+        TilePos::new(start_x, start_y),
+        TilePos::new(end_x, end_y),
+    );
+}
+
+#[when(
+    regex = r"^a Path is followed along a Flow Field from (\d+), (\d+) to (\d+), (\d+) for (walkers|flyers),"
+)]
+fn follow_flow_field_between_patch_tiles(
+    world: &mut GameWorld,
+    start_x: u32,
+    start_y: u32,
+    end_x: u32,
+    end_y: u32,
+    mover_option: String,
+) {
+    let patch = world.app.world().resource::<Patch>();
+    let graph = patch_graph(patch, graph_type_from(&mover_option));
+
+    let flow_field = FlowField::towards(&graph, TilePos::new(end_x, end_y))
+        .expect("follow_flow_field_between_patch_tiles: The patch should have Tiles to stand on.");
+    let patch_path = flow_field.path_from(&graph, TilePos::new(start_x, start_y));
+
+    world.app.insert_resource(PatchPath(patch_path));
+}
+
+#[when(regex = r"^walkers on (\d+, \d+) pick the nearest of (\d+, \d+) and (\d+, \d+),")]
+fn pick_nearest_destination(
+    world: &mut GameWorld,
+    start_option: String,
+    first_option: String,
+    second_option: String,
+) {
+    let patch = world.app.world().resource::<Patch>();
+    let graph = patch_graph(patch, GraphType::Ground);
+    let start = tile_from(&start_option);
+
+    let mut destination_queue = DestinationQueue::default();
+    destination_queue.push(DestinationRequest::new(tile_from(&first_option)));
+    destination_queue.push(DestinationRequest::new(tile_from(&second_option)));
+    let first_destination = destination_queue
+        .pop_nearest(|destination| graph.walk_cost(start, destination))
+        .map(|request| request.destination);
+
+    world
+        .app
+        .insert_resource(FirstDestination(first_destination));
+}
+
+#[then(regex = r"^(\d+, \d+) should be headed to first.")]
+fn headed_to_first(world: &mut GameWorld, destination_option: String) {
+    let first_destination = world.app.world().resource::<FirstDestination>();
+
+    assert_eq!(Some(tile_from(&destination_option)), first_destination.0);
+}
+
+#[then(regex = r"^the Path should take (\d+) steps?.")]
+fn path_should_take_steps(world: &mut GameWorld, num_steps: usize) {
+    let patch_path = world
+        .app
+        .world()
+        .resource::<PatchPath>()
+        .0
+        .as_ref()
+        .expect("path_should_take_steps: A Path should have been found on the patch.");
+
+    // The Path starts from where it was found from.
+    assert_eq!(num_steps, patch_path.len() - 1);
+}
+
+#[then("there should be no Path.")]
+fn path_should_not_exist(world: &mut GameWorld) {
+    assert!(world.app.world().resource::<PatchPath>().0.is_none());
+}
+
+#[then(regex = r"^heading (-?\d+), (-?\d+) away (straight|diagonally) should face (.+)\.")]
+fn heading_should_face(
+    _world: &mut GameWorld,
+    x_offset: f32,
+    y_offset: f32,
+    step_option: String,
+    direction_option: String,
+) {
+    let expected_direction = match direction_option.as_str() {
+        "Top Left" => Direction::TopLeft,
+        "Top Right" => Direction::TopRight,
+        "Bottom Left" => Direction::BottomLeft,
+        "Bottom Right" => Direction::BottomRight,
+        "Top" => Direction::Top,
+        "Bottom" => Direction::Bottom,
+        "Left" => Direction::Left,
+        "Right" => Direction::Right,
+        _ => unreachable!(),
+    };
+
+    let actual_direction = get_direction(
+        Transform::default(),
+        Transform::from_xyz(x_offset, y_offset, 0.0),
+        step_option == "diagonally",
+    );
+
+    assert_eq!(Some(expected_direction), actual_direction);
+}
+
+#[then(regex = r"^the Path should go (around|through) the middle of the patch.")]
+fn path_should_cross_patch(world: &mut GameWorld, route_option: String) {
+    let patch_path = world
+        .app
+        .world()
+        .resource::<PatchPath>()
+        .0
+        .as_ref()
+        .expect("path_should_cross_patch: A Path should have been found across the patch.");
+    let path_tiles = patch_path
+        .iter()
+        .map(|node_idx| idx_to_tilepos(*node_idx, PATCH_SIZE))
+        .collect::<Vec<TilePos>>();
+
+    assert_eq!(Some(&TilePos::new(0, 1)), path_tiles.first());
+    assert_eq!(Some(&TilePos::new(2, 1)), path_tiles.last());
+
+    let crosses_middle = path_tiles.contains(&TilePos::new(1, 1));
+    match route_option.as_str() {
+        "around" => assert!(!crosses_middle),
+        "through" => assert_eq!(3, path_tiles.len()),
+        _ => unreachable!(),
+    }
+}
+
+#[given("a narrow patch of ground with a nook along the way,")]
+fn spawn_narrow_patch_of_ground(world: &mut GameWorld) {
+    let mut patch = Patch::default();
+    for cliff_tile in [(0, 0), (1, 0), (2, 0), (0, 2), (2, 2)] {
+        patch
+            .heights
+            .insert(TilePos::new(cliff_tile.0, cliff_tile.1), CLIFF_HEIGHT);
+    }
+
+    world.app.insert_resource(patch);
+}
+
+/// Every walker getting around the patch of ground, along
+/// with where each of them started from.
+#[derive(Resource, Default)]
+struct Walkers(Vec<(Entity, TilePos)>);
+
+/// Whether two walkers have ever stood on the same Tile.
+#[derive(Resource, Default)]
+struct TileShared(bool);
+
+/// Spawns a walker on the patch of ground, where walkers
+/// get in each other's way.
+fn spawn_walker(world: &mut GameWorld, start: TilePos) -> Entity {
+    if !world.app.world().contains_resource::<Walkers>() {
+        let patch = world.app.world().resource::<Patch>();
+        let ground_graph = patch_graph(patch, GraphType::Ground);
+
+        world.app.world_mut().spawn(ground_graph);
+        world.app.world_mut().spawn(TilemapSize {
+            x: PATCH_SIZE,
+            y: PATCH_SIZE,
+        });
+        world.app.init_resource::<Walkers>();
+        world.app.init_resource::<OccupancyMap>();
+        world.app.add_systems(
+            Update,
+            (
+                insert_pathing_information,
+                update_occupancy_map.before(update_movement_target),
+                update_movement_target,
+                move_entities.after(update_movement_target),
+            ),
+        );
+    }
+
+    let start_translation = *world
+        .app
+        .world_mut()
+        .query::<&UndirectedGraph>()
+        .single(world.app.world())
+        .get_node(tilepos_to_idx(start.x, start.y, PATCH_SIZE))
+        .expect("spawn_walker: Walkers should start on the patch.");
+    let walker = world
+        .app
+        .world_mut()
+        .spawn((
+            GameEntityType::Walk,
+            Transform::from_translation(start_translation),
+            start,
+        ))
+        .id();
+
+    world
+        .app
+        .world_mut()
+        .resource_mut::<Walkers>()
+        .0
+        .push((walker, start));
+
+    // Once to start following Paths, then once more
+    // for its timers to settle in.
+    world.update(2);
+
+    walker
+}
+
+#[given(regex = r"^a walker standing on (\d+), (\d+),")]
+fn spawn_standing_walker(world: &mut GameWorld, x: u32, y: u32) {
+    spawn_walker(world, TilePos::new(x, y));
+}
+
+#[given(regex = r"^a walker heading from (\d+), (\d+) to (\d+), (\d+),")]
+fn spawn_heading_walker(world: &mut GameWorld, start_x: u32, start_y: u32, end_x: u32, end_y: u32) {
+    let walker = spawn_walker(world, TilePos::new(start_x, start_y));
+
+    let walker_path = world
+        .app
+        .world_mut()
+        .query::<&UndirectedGraph>()
+        .single(world.app.world())
+        .shortest_path(TilePos::new(start_x, start_y), TilePos::new(end_x, end_y))
+        .expect("spawn_heading_walker: Walkers should be able to reach their destination.");
+    *world
+        .app
+        .world_mut()
+        .get_mut::<Path>(walker)
+        .expect("spawn_heading_walker: Walkers should be following a Path.") = walker_path;
+}
+
+#[given("walkers wait a long while before finding a way around,")]
+fn make_walkers_wait_long(world: &mut GameWorld) {
+    let mut blocked_timers = world.app.world_mut().query::<&mut BlockedTimer>();
+    for mut blocked_timer in blocked_timers.iter_mut(world.app.world_mut()) {
+        blocked_timer.0 = Timer::from_seconds(3600.0, TimerMode::Once);
+    }
+}
+
+#[when("the walkers have had time to move,")]
+fn wait_for_walkers_to_move(world: &mut GameWorld) {
+    world.app.init_resource::<TileShared>();
+
+    for _tick in 0..200 {
+        world.update(1);
+
+        let mut walker_tiles = world
+            .app
+            .world_mut()
+            .query_filtered::<&TilePos, With<GameEntityType>>();
+        let mut stood_on = HashSet::new();
+        let tile_shared = walker_tiles
+            .iter(world.app.world())
+            .any(|tile_pos| !stood_on.insert(*tile_pos));
+
+        world.app.world_mut().resource_mut::<TileShared>().0 |= tile_shared;
+    }
+}
+
+#[then(regex = r"^the walker from (\d+), (\d+) should be on (\d+), (\d+).")]
+fn walker_should_be_on(world: &mut GameWorld, start_x: u32, start_y: u32, x: u32, y: u32) {
+    let (walker, _start) = *world
+        .app
+        .world()
+        .resource::<Walkers>()
+        .0
+        .iter()
+        .find(|(_walker, start)| *start == TilePos::new(start_x, start_y))
+        .expect("walker_should_be_on: A walker should have started from there.");
+
+    let walker_tilepos = *world
+        .app
+        .world()
+        .get::<TilePos>(walker)
+        .expect("walker_should_be_on: Walkers should stand on some Tile.");
+
+    assert_eq!(TilePos::new(x, y), walker_tilepos);
+}
+
+#[then("no two walkers should have shared a Tile.")]
+fn walkers_should_not_share_tiles(world: &mut GameWorld) {
+    assert!(!world.app.world().resource::<TileShared>().0);
+}
+
+#[then(
+    regex = r"^the Tile nearest to (\d+), (\d+) for (walkers|flyers) should be (\d+) Tiles? away."
+)]
+fn nearest_tile_should_be_away(
+    world: &mut GameWorld,
+    x: u32,
+    y: u32,
+    mover_option: String,
+    num_tiles: u32,
+) {
+    let patch = world.app.world().resource::<Patch>();
+    let graph = patch_graph(patch, graph_type_from(&mover_option));
+    let tile_pos = TilePos::new(x, y);
+
+    let nearest_tilepos = graph
+        .nearest_node_to(tile_pos)
+        .expect("nearest_tile_should_be_away: Some Tile should be on the Graph.");
+
+    assert_eq!(num_tiles, tiles_between(tile_pos, nearest_tilepos));
+}
+
+#[then(regex = r"^(\d+) Tiles for (walkers|flyers) should be within (\d+) Tiles? of (\d+), (\d+).")]
+fn tiles_should_be_within_radius(
+    world: &mut GameWorld,
+    num_tiles: usize,
+    mover_option: String,
+    radius: u32,
+    x: u32,
+    y: u32,
+) {
+    let patch = world.app.world().resource::<Patch>();
+    let graph = patch_graph(patch, graph_type_from(&mover_option));
+
+    assert_eq!(
+        num_tiles,
+        graph.nodes_within(TilePos::new(x, y), radius).len()
+    );
+}
+
+#[then(regex = r"^the pond's shoreline should be (\d+) Tiles long.")]
+fn pond_shoreline_should_be_long(world: &mut GameWorld, num_tiles: usize) {
+    let patch = world.app.world().resource::<Patch>();
+    let pond_graph = patch_graph(patch, GraphType::Water);
+    let ground_graph = patch_graph(patch, GraphType::Ground);
+
+    let shoreline = pond_graph.shoreline_with(&ground_graph);
+
+    assert_eq!(num_tiles, shoreline.len());
+    assert!(shoreline
+        .iter()
+        .all(|tile_pos| tile_pos.x == 1 && tile_pos.y > 0));
+}
+
+/// Every Path Result sent back from the background.
+#[derive(Resource, Default)]
+struct PathResults(Vec<PathResult>);
+
+/// Records every Path Result sent back from the background.
+fn record_path_results(
+    mut path_results: EventReader<PathResult>,
+    mut recorded_results: ResMut<PathResults>,
+) {
+    recorded_results.0.extend(path_results.read().cloned());
+}
+
+#[given("Paths are found across the patch in the background,")]
+fn find_paths_in_background(world: &mut GameWorld) {
+    let patch = world.app.world().resource::<Patch>();
+    let ground_graph = patch_graph(patch, GraphType::Ground);
+
+    world.app.world_mut().spawn(ground_graph);
+    world.app.init_resource::<SharedGraphs>();
+    world.app.init_resource::<PendingPaths>();
+    world.app.init_resource::<PathResults>();
+    world.app.add_event::<PathRequest>();
+    world.app.add_event::<PathResult>();
+    world.app.add_systems(
+        Update,
+        (
+            share_graphs_for_path_requests,
+            solve_path_requests,
+            send_finished_path_results,
+            record_path_results,
+        )
+            .chain(),
+    );
+    world.update(1);
+}
+
+#[when("the ground Graph is taken down,")]
+fn take_down_ground_graph(world: &mut GameWorld) {
+    let graph_entities = world
+        .app
+        .world_mut()
+        .query_filtered::<Entity, With<UndirectedGraph>>()
+        .iter(world.app.world())
+        .collect::<Vec<Entity>>();
+    for graph_entity in graph_entities {
+        world.app.world_mut().despawn(graph_entity);
+    }
+
+    world.update(1);
+}
+
+#[when(regex = r"^(walkers|flyers) ask for a Path from (\d+), (\d+) to (\d+), (\d+),")]
+fn ask_for_path_in_background(
+    world: &mut GameWorld,
+    mover_option: String,
+    start_x: u32,
+    start_y: u32,
+    end_x: u32,
+    end_y: u32,
+) {
+    let requester = world.app.world_mut().spawn_empty().id();
+    world.broadcast_event(PathRequest::new(
+        requester,
+        graph_type_from(&mover_option),
+        TilePos::new(start_x, start_y),
+        TilePos::new(end_x, end_y),
+    ));
+
+    while world.app.world().resource::<PathResults>().0.is_empty() {
+        world.update(1);
+    }
+}
+
+/// Returns the result of the only Path Request asked for.
+fn path_result_of(world: &GameWorld) -> &PathResult {
+    let path_results = &world.app.world().resource::<PathResults>().0;
+    assert_eq!(1, path_results.len());
+
+    &path_results[0]
+}
+
+#[then(regex = r"^the Path found should take (\d+) steps?.")]
+fn path_found_should_take_steps(world: &mut GameWorld, num_steps: usize) {
+    let found_path = path_result_of(world)
+        .result
+        .as_ref()
+        .expect("path_found_should_take_steps: A Path should have been found.");
+
+    // The Path starts from where it was found from.
+    assert_eq!(num_steps, found_path.len() - 1);
+}
+
+#[then(
+    regex = r"^no Path should be found since the (Graph is missing|Tile is off the patch|start cannot be stood on|end is out of reach)."
+)]
+fn path_should_not_be_found(world: &mut GameWorld, reason_option: String) {
+    let path_result = path_result_of(world);
+    let (start, end) = (path_result.request.start, path_result.request.end);
+    let expected_error = match reason_option.as_str() {
+        "Graph is missing" => PathError::GraphMissing(path_result.request.graph_type),
+        "Tile is off the patch" => PathError::OutOfBounds(end),
+        "start cannot be stood on" => PathError::StartNotOnGraph(start),
+        "end is out of reach" => PathError::Unreachable { start, end },
+        _ => unreachable!(),
+    };
+
+    assert_eq!(Some(&expected_error), path_result.result.as_ref().err());
+}
+
+#[given(regex = r"^a Tiled map called (.+\.tmx),")]
+fn load_test_tiled_map(world: &mut GameWorld, map_name: String) {
+    let mut map_path = PathBuf::new();
+    if let Ok(project_root_directory) = std::env::var("CARGO_MANIFEST_DIR") {
+        map_path.push(project_root_directory);
+    }
+    map_path.push("tests/test-assets/maps");
+    map_path.push(map_name);
+
+    let tiled_map = tiled::Loader::new()
+        .load_tmx_map(map_path)
+        .expect("load_test_tiled_map: Test map should load.");
+
+    world.app.insert_non_send_resource(tiled_map);
+}
+
+#[then(regex = r"^Tile (\d+), (\d+) should be made of (Path|Plain|Tall Grass|Shallow Water).")]
+fn tile_should_be_made_of_terrain(world: &mut GameWorld, x: i32, y: i32, terrain_option: String) {
+    let tiled_map = world.app.world().non_send_resource::<tiled::Map>();
+    let tiled::LayerType::Tiles(tile_layer) = tiled_map
+        .get_layer(0)
+        .expect("tile_should_be_made_of_terrain: Test map should have a layer.")
+        .layer_type()
+    else {
+        panic!("tile_should_be_made_of_terrain: Test map layer should hold tiles.");
+    };
+
+    let actual_terrain = tile_layer
+        .get_tile(x, y)
+        .and_then(|layer_tile| layer_tile.get_tile())
+        .map(|tile| Terrain::from_properties(&tile.properties))
+        .expect("tile_should_be_made_of_terrain: Test map should have a tile there.");
+
+    assert_eq!(terrain_from(&terrain_option), actual_terrain);
+}
+
+#[then(regex = r"^Tile (\d+), (\d+) should (have no Slope|be shaped like a Ledge).")]
+fn tile_should_be_shaped_like_slope(world: &mut GameWorld, x: i32, y: i32, slope_option: String) {
+    let tiled_map = world.app.world().non_send_resource::<tiled::Map>();
+    let tiled::LayerType::Tiles(tile_layer) = tiled_map
+        .get_layer(0)
+        .expect("tile_should_be_shaped_like_slope: Test map should have a layer.")
+        .layer_type()
+    else {
+        panic!("tile_should_be_shaped_like_slope: Test map layer should hold tiles.");
+    };
+
+    let expected_slope = match slope_option.as_str() {
+        "have no Slope" => None,
+        "be shaped like a Ledge" => Some(Slope::Ledge),
+        _ => unreachable!(),
+    };
+    let actual_slope = tile_layer
+        .get_tile(x, y)
+        .and_then(|layer_tile| layer_tile.get_tile())
+        .and_then(|tile| Slope::from_properties(&tile.properties));
+
+    assert_eq!(expected_slope, actual_slope);
+}
+
+/// Someone walking across the patch of ground, whose
+/// crossings of Links are marked by hand.
+#[derive(Resource)]
+struct Walker(Entity);
+
+#[when(regex = r"^a walker steps from (\d+, \d+) to (\d+, \d+),")]
+fn step_walker_across_patch(world: &mut GameWorld, from_option: String, to_option: String) {
+    let patch = world.app.world().resource::<Patch>();
+    let graph = patch_graph(patch, GraphType::Ground);
+    let (from, to) = (tile_from(&from_option), tile_from(&to_option));
+
+    world.app.world_mut().spawn(graph);
+    let walker_entity = world
+        .app
+        .world_mut()
+        .spawn((
+            GameEntityType::Walk,
+            StartingPoint(Vec3::ZERO, from),
+            Target(Some((Vec3::ZERO, to))),
+            CrossingLink::default(),
+        ))
+        .id();
+    world.app.world_mut().run_system_once(mark_link_crossings);
+
+    world.app.insert_resource(Walker(walker_entity));
+}
+
+#[then(regex = r"^the walker should be crossing by (a Ladder|a Portal|no Link).")]
+fn walker_should_be_crossing_by(world: &mut GameWorld, transition_option: String) {
+    let expected_transition = transition_option.strip_prefix("a ").map(transition_from);
+
+    let walker_entity = world.app.world().resource::<Walker>().0;
+    let crossing_link = world
+        .app
+        .world()
+        .entity(walker_entity)
+        .get::<CrossingLink>()
+        .expect("walker_should_be_crossing_by: The walker should be able to cross Links.");
+
+    assert_eq!(expected_transition, crossing_link.0);
+}
+
+#[then(regex = r"^the map should declare (\d+) Links?.")]
+fn map_should_declare_links(world: &mut GameWorld, num_links: usize) {
+    let tiled_map = world.app.world().non_send_resource::<tiled::Map>();
+
+    assert_eq!(num_links, NavLinks::from_map(tiled_map).len());
+}
+
+#[then(
+    regex = r"^the map should declare a (two way|one way) (Ladder|Portal) from (\d+, \d+) to (\d+, \d+) costing (\d+) steps?."
+)]
+fn map_should_declare_link(
+    world: &mut GameWorld,
+    way_option: String,
+    transition_option: String,
+    from_option: String,
+    to_option: String,
+    cost: u32,
+) {
+    let tiled_map = world.app.world().non_send_resource::<tiled::Map>();
+    let expected_link = NavLink {
+        from: tile_from(&from_option),
+        to: tile_from(&to_option),
+        graph_type: GraphType::Ground,
+        cost,
+        transition: Some(transition_from(&transition_option)),
+        two_way: way_option == "two way",
+    };
+
+    assert!(NavLinks::from_map(tiled_map).contains(&expected_link));
+}
+
+/// What the navigation overlay says about the Tile being pointed at.
+#[derive(Resource)]
+struct Tooltip(Option<String>);
+
+#[when(regex = r"^the cursor points just off the middle of Tile (\d+), (\d+),")]
+fn point_cursor_at_tile(world: &mut GameWorld, x: u32, y: u32) {
+    let patch = world.app.world().resource::<Patch>();
+    let graphs = [GraphType::Ground, GraphType::Air, GraphType::Water]
+        .map(|graph_type| patch_graph(patch, graph_type));
+
+    let tile_translation = graphs[1]
+        .drawn_nodes()
+        .find(|(tile_pos, _translation)| *tile_pos == TilePos::new(x, y))
+        .map(|(_tile_pos, translation)| translation.truncate())
+        .expect("point_cursor_at_tile: Every Tile should be on the Air Graph.");
+    let cursor_pos = tile_translation + Vec2::new(2.0, -2.0);
+
+    let tooltip = tile_at(&graphs, cursor_pos).map(|tile_pos| describe_tile(&graphs, tile_pos));
+    world.app.insert_resource(Tooltip(tooltip));
+}
+
+#[then(regex = r"^the tooltip should say (.+)\.$")]
+fn tooltip_should_say(world: &mut GameWorld, tooltip_line: String) {
+    let tooltip = world
+        .app
+        .world()
+        .resource::<Tooltip>()
+        .0
+        .as_ref()
+        .expect("tooltip_should_say: Some Tile should be pointed at.");
+
+    assert!(
+        tooltip.lines().any(|line| line == tooltip_line),
+        "{tooltip_line:?} is not in {tooltip:?}"
+    );
+}
+
+/// How many pixels apart neighbouring Tiles are drawn
+/// for anything stepping between them.
+const MOVER_TILE_SIZE: f32 = 16.0;
+
+/// Someone stepping between two Tiles, moved only as
+/// time is passed along to them by hand.
+#[derive(Resource)]
+struct Mover(Entity);
+
+/// Returns where some Tile is drawn for anything stepping
+/// onto it, where each level up is drawn further up too.
+fn mover_translation(tile_pos: TilePos, level: u32) -> Vec3 {
+    Vec3::new(
+        tile_pos.x as f32 * MOVER_TILE_SIZE,
+        (tile_pos.y + level) as f32 * MOVER_TILE_SIZE,
+        level as f32,
+    )
+}
+
+#[given(
+    regex = r"^a mover stepping from (\d+), (\d+) to (\d+), (\d+)( up a level)? at ([\d.]+) Tiles a second,"
+)]
+fn spawn_mover(
+    world: &mut GameWorld,
+    start_x: u32,
+    start_y: u32,
+    end_x: u32,
+    end_y: u32,
+    level_option: String,
+    tiles_per_sec: f32,
+) {
+    let start = TilePos::new(start_x, start_y);
+    let end = TilePos::new(end_x, end_y);
+    let end_level = if level_option.is_empty() { 0 } else { 1 };
+
+    // Movers only ever hop when stepping up or down a level.
+    let mover_entity = world
+        .app
+        .world_mut()
+        .spawn((
+            Transform::from_translation(mover_translation(start, 0)),
+            StartingPoint(mover_translation(start, 0), start),
+            Target(Some((mover_translation(end, end_level), end))),
+            start,
+            StepProgress::default(),
+            MovementSpeed(tiles_per_sec),
+            HopHeight(MOVER_TILE_SIZE),
+        ))
+        .id();
+    world.app.insert_resource(Mover(mover_entity));
+}
+
+#[given(
+    regex = r"^the mover eases smoothly, (setting off|carrying on) (along|to the end of) its Path,"
+)]
+fn ease_mover_smoothly(world: &mut GameWorld, setting_off: String, path_left: String) {
+    let mover_entity = world.app.world().resource::<Mover>().0;
+
+    // The rest of the Path only needs to be there, not to lead anywhere.
+    let rest_of_path = if path_left == "along" {
+        VecDeque::from([0])
+    } else {
+        VecDeque::new()
+    };
+    world.app.world_mut().entity_mut(mover_entity).insert((
+        MovementEasing::Smooth,
+        StepProgress {
+            progress: 0.0,
+            carried_on: setting_off == "carrying on",
+        },
+        Path(rest_of_path),
+    ));
+}
+
+#[when(regex = r"^([\d.]+) seconds pass over (\d+) frames?,")]
+fn pass_time_to_mover(world: &mut GameWorld, num_secs: f32, num_frames: u32) {
+    let frame_time = Duration::from_secs_f32(num_secs / num_frames as f32);
+    for _ in 0..num_frames {
+        world
+            .app
+            .world_mut()
+            .resource_mut::<Time>()
+            .advance_by(frame_time);
+        world.app.world_mut().run_system_once(move_entities);
+    }
+}
+
+/// Returns where the mover is, and where they are headed if anywhere.
+fn mover_state(world: &mut GameWorld) -> (Vec3, TilePos, Option<(Vec3, TilePos)>, Vec3) {
+    let mover_entity = world.app.world().resource::<Mover>().0;
+    let mover = world.app.world().entity(mover_entity);
+
+    (
+        mover.get::<Transform>().unwrap().translation,
+        *mover.get::<TilePos>().unwrap(),
+        mover.get::<Target>().unwrap().0,
+        mover.get::<StartingPoint>().unwrap().0,
+    )
+}
+
+#[then(regex = r"^the mover should be exactly on (\d+), (\d+)( up a level)?.")]
+fn mover_should_be_exactly_on(world: &mut GameWorld, x: u32, y: u32, level_option: String) {
+    let level = if level_option.is_empty() { 0 } else { 1 };
+    let (translation, tile_pos, target, _start) = mover_state(world);
+
+    assert!(target.is_none());
+    assert_eq!(TilePos::new(x, y), tile_pos);
+    assert_eq!(mover_translation(TilePos::new(x, y), level), translation);
+}
+
+#[then(regex = r"^the mover should be (\d+)% of the way there.")]
+fn mover_should_be_part_way(world: &mut GameWorld, percent: f32) {
+    let (translation, _tile_pos, target, start) = mover_state(world);
+    let (target_translation, _target_tile_pos) =
+        target.expect("mover_should_be_part_way: The mover should still be on its way.");
+
+    let part_way = start.distance(translation) / start.distance(target_translation);
+    assert!((part_way - percent / 100.0).abs() < 0.01);
+}
+
+#[then("the mover should be hopping above the way there.")]
+fn mover_should_be_hopping(world: &mut GameWorld) {
+    let (translation, _tile_pos, target, start) = mover_state(world);
+    let (target_translation, _target_tile_pos) =
+        target.expect("mover_should_be_hopping: The mover should still be on its way.");
+
+    let straight_translation = start.lerp(target_translation, 0.5);
+    assert!(translation.y > straight_translation.y);
+}
+
 fn main() {
     futures::executor::block_on(GameWorld::run("tests/feature-files/pathfinding.feature"));
 }
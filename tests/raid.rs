@@ -0,0 +1,173 @@
+mod mock_plugins;
+
+use bevy::prelude::*;
+use bevy::utils::Duration;
+use bevy_ecs_tilemap::prelude::*;
+use cucumber::{given, then, when, World};
+
+use crate::mock_plugins::{GameWorld, MockStreamerPlugin, MockTiledMapPlugin};
+use task_masker::entities::raider::*;
+use task_masker::entities::streamer::*;
+use task_masker::map::plugins::PathFindingPlugin;
+
+const RAIDING_CHANNEL_NAME: &str = "Raidu";
+
+#[derive(Default)]
+pub struct MockRaiderPlugin;
+
+impl Plugin for MockRaiderPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<RaidMsg>();
+        app.add_systems(
+            Update,
+            (
+                spawn_raiders_on_raid,
+                replace_raid_banner_text.after(spawn_raiders_on_raid),
+                walk_raiders_to_streamer.after(spawn_raiders_on_raid),
+                disperse_raiders.after(walk_raiders_to_streamer),
+                despawn_dispersed_raiders.after(disperse_raiders),
+                move_raid_banner_with_leader,
+            ),
+        );
+    }
+}
+
+/// Intercepts and sets the Disperse Timer interval to 0 seconds for testing purposes.
+pub fn reduce_disperse_times_to_zero(
+    mut disperse_timers: Query<&mut DisperseTimer, Added<DisperseTimer>>,
+) {
+    for mut disperse_timer in &mut disperse_timers {
+        disperse_timer.0 = Timer::new(Duration::from_secs(0), TimerMode::Once);
+    }
+}
+
+/// Returns the approximate number of Tiles away the target_pos
+/// is from source_pos
+fn distance_of(source_pos: TilePos, target_pos: TilePos) -> usize {
+    let x1 = source_pos.x as f32;
+    let x2 = target_pos.x as f32;
+
+    let y1 = source_pos.y as f32;
+    let y2 = target_pos.y as f32;
+
+    ((x2 - x1).powi(2) + (y2 - y1).powi(2)).sqrt().floor() as usize
+}
+
+#[given("a Tiled Map,")]
+fn spawn_tiled_map(world: &mut GameWorld) {
+    world.app.add_plugins(MockTiledMapPlugin);
+    world.update(1);
+
+    world.app.add_plugins(PathFindingPlugin);
+    world.update(1);
+}
+
+#[given("a Streamer spawned on the Tiled Map,")]
+fn spawn_streamer_on_tiled_map(world: &mut GameWorld) {
+    world.app.add_plugins(MockStreamerPlugin);
+    world.update(1);
+}
+
+#[given("Raiders can visit the Tiled Map,")]
+fn allow_raiders_on_tiled_map(world: &mut GameWorld) {
+    world.app.add_plugins(MockRaiderPlugin);
+    world.update(1);
+}
+
+#[when(regex = r"^a Raid of (\d+) viewers arrives,")]
+fn raid_arrives(world: &mut GameWorld, viewer_count: String) {
+    world.broadcast_event(RaidMsg {
+        channel_name: String::from(RAIDING_CHANNEL_NAME),
+        viewer_count: viewer_count.parse::<u64>().unwrap(),
+    });
+
+    world.update(2);
+}
+
+#[when("the Raiders have gathered around the Streamer,")]
+fn wait_for_raiders_to_gather(world: &mut GameWorld) {
+    loop {
+        world.update(1);
+
+        let all_gathered = world
+            .find_all::<RaiderStatus>()
+            .iter()
+            .all(|raider_status| **raider_status == RaiderStatus::Gathering);
+
+        if all_gathered {
+            break;
+        }
+    }
+}
+
+#[when("the Raiders are done gathering,")]
+fn wait_for_raiders_to_disperse(world: &mut GameWorld) {
+    world.app.add_systems(Update, reduce_disperse_times_to_zero);
+
+    loop {
+        world.update(1);
+
+        if world.find_all::<RaiderLabel>().is_empty() {
+            break;
+        }
+    }
+}
+
+#[then(regex = r"^there should be (\d+) Raiders on the Tiled Map.")]
+fn raiders_spawned(world: &mut GameWorld, expected_raiders: String) {
+    let expected_num_raiders = expected_raiders.parse::<usize>().unwrap();
+    let actual_num_raiders = world.find_all::<RaiderLabel>().len();
+
+    assert_eq!(expected_num_raiders, actual_num_raiders);
+}
+
+#[then("there should be as many Raiders as the visitor cap on the Tiled Map.")]
+fn raiders_capped(world: &mut GameWorld) {
+    let actual_num_raiders = world.find_all::<RaiderLabel>().len();
+
+    assert_eq!(MAX_RAID_VISITORS, actual_num_raiders);
+}
+
+#[then("a Raid Banner should show the raiding channel's name.")]
+fn raid_banner_shows_channel_name(world: &mut GameWorld) {
+    world.update(1);
+
+    let banner_text = world
+        .find_with::<Text, RaidBanner>()
+        .expect("raid_banner_shows_channel_name: Raid Banner does not have any Text.");
+
+    assert_eq!(banner_text.sections[0].value, RAIDING_CHANNEL_NAME);
+}
+
+#[then("each Raider should be near the Streamer.")]
+fn raiders_near_streamer(world: &mut GameWorld) {
+    let streamer_tilepos = *world
+        .find_with::<TilePos, StreamerLabel>()
+        .expect("raiders_near_streamer: Streamer does not have a TilePos.");
+
+    let raider_tileposes = world
+        .app
+        .world_mut()
+        .query_filtered::<&TilePos, With<RaiderLabel>>()
+        .iter(world.app.world())
+        .copied()
+        .collect::<Vec<TilePos>>();
+
+    assert!(!raider_tileposes.is_empty());
+    for raider_tilepos in raider_tileposes {
+        assert_ne!(raider_tilepos, streamer_tilepos);
+        assert!(distance_of(raider_tilepos, streamer_tilepos) <= MAX_DIST_AWAY_FROM_STREAMER);
+    }
+}
+
+#[then("there should be no Raiders or Raid Banner left on the Tiled Map.")]
+fn raiders_dispersed(world: &mut GameWorld) {
+    world.update(1);
+
+    assert!(world.find_all::<RaiderLabel>().is_empty());
+    assert!(world.find_all::<RaidBanner>().is_empty());
+}
+
+fn main() {
+    futures::executor::block_on(GameWorld::run("tests/feature-files/raid.feature"));
+}
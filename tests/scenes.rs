@@ -0,0 +1,172 @@
+mod mock_plugins;
+
+use bevy::prelude::*;
+use bevy_ecs_tilemap::prelude::*;
+use cucumber::{given, then, when, World};
+
+use crate::mock_plugins::{GameWorld, MockChatterPlugin, MockStreamerPlugin, MockTiledMapPlugin};
+use task_masker::entities::chatter::ChatterLabel;
+use task_masker::entities::streamer::StreamerLabel;
+use task_masker::map::path_finding::{GraphType, UndirectedGraph};
+use task_masker::map::plugins::PathFindingPlugin;
+use task_masker::map::scenes::{MapScene, MapScenes, SwitchSceneEvent};
+
+/// The Chatter around before the Map Scene was switched.
+#[derive(Resource)]
+struct ChatterBeforeSwitch(Entity);
+
+#[given("the built-in Map Scenes,")]
+fn init_map_scenes(world: &mut GameWorld) {
+    world.app.init_resource::<MapScenes>();
+}
+
+#[given("a Cozy Cabin scene is registered,")]
+fn register_cozy_cabin_scene(world: &mut GameWorld) {
+    world
+        .app
+        .world_mut()
+        .resource_mut::<MapScenes>()
+        .register(MapScene {
+            name: String::from("Cozy Cabin"),
+            map_path: String::from("cozy_cabin.tmx"),
+            activities: vec![String::from("Art")],
+            streamer_spawn: TilePos::new(4, 4),
+            online_spot: TilePos::new(5, 5),
+        });
+}
+
+/// Returns the name of the Map Scene some request switches to, if any.
+fn resolved_scene_name(world: &GameWorld, request: &SwitchSceneEvent) -> Option<String> {
+    let map_scenes = world.app.world().resource::<MapScenes>();
+
+    map_scenes
+        .resolve(request)
+        .map(|scene_idx| map_scenes.scenes()[scene_idx].name.clone())
+}
+
+/// Returns the Map Scene a step expects to be picked, if any.
+fn expected_scene_name(scene_option: &str) -> Option<String> {
+    match scene_option {
+        "no scene" => None,
+        scene_name => Some(
+            scene_name
+                .trim_start_matches("the ")
+                .trim_end_matches(" scene")
+                .to_string(),
+        ),
+    }
+}
+
+#[then(regex = r"^switching for the (.+) activity should pick (no scene|the .+ scene).")]
+fn activity_should_pick_scene(world: &mut GameWorld, activity: String, scene_option: String) {
+    let request = SwitchSceneEvent::ForActivity(activity);
+
+    assert_eq!(
+        expected_scene_name(&scene_option),
+        resolved_scene_name(world, &request)
+    );
+}
+
+#[then(regex = r"^switching to the next scene should pick (no scene|the .+ scene).")]
+fn next_should_pick_scene(world: &mut GameWorld, scene_option: String) {
+    assert_eq!(
+        expected_scene_name(&scene_option),
+        resolved_scene_name(world, &SwitchSceneEvent::Next)
+    );
+}
+
+#[then(regex = r"^switching to the scene named (.+) should pick (no scene|the .+ scene).")]
+fn name_should_pick_scene(world: &mut GameWorld, scene_name: String, scene_option: String) {
+    let request = SwitchSceneEvent::Named(scene_name);
+
+    assert_eq!(
+        expected_scene_name(&scene_option),
+        resolved_scene_name(world, &request)
+    );
+}
+
+#[then(regex = r"^the chat command (.+) should pick (no scene|the .+ scene).")]
+fn chat_command_should_pick_scene(world: &mut GameWorld, chat_msg: String, scene_option: String) {
+    let picked_scene_name = SwitchSceneEvent::from_command(&chat_msg)
+        .and_then(|request| resolved_scene_name(world, &request));
+
+    assert_eq!(expected_scene_name(&scene_option), picked_scene_name);
+}
+
+#[given("a Tiled Map,")]
+fn spawn_tiled_map(world: &mut GameWorld) {
+    world.app.add_plugins(MockTiledMapPlugin);
+    world.update(1);
+
+    world.app.add_plugins(PathFindingPlugin);
+    world.update(1);
+}
+
+#[given("a Streamer spawned on the Tiled Map,")]
+fn spawn_streamer_on_tiled_map(world: &mut GameWorld) {
+    world.app.add_plugins(MockStreamerPlugin);
+    world.update(1);
+}
+
+#[given("a Chatter spawned on the Tiled Map,")]
+fn spawn_chatter_on_tiled_map(world: &mut GameWorld) {
+    world.app.add_plugins(MockChatterPlugin);
+    world.update(1);
+
+    let chatter_entity = world
+        .app
+        .world_mut()
+        .query_filtered::<Entity, With<ChatterLabel>>()
+        .single(world.app.world());
+    world
+        .app
+        .insert_resource(ChatterBeforeSwitch(chatter_entity));
+}
+
+#[when(regex = r"^the stream switches to the (.+) scene,")]
+fn switch_to_scene(world: &mut GameWorld, scene_name: String) {
+    world.broadcast_event(SwitchSceneEvent::Named(scene_name));
+
+    // The new map is laid out, then has its Graphs built
+    // from it, before everyone is moved over onto it.
+    world.update(3);
+}
+
+#[then(regex = r"^the Streamer should be on (\d+), (\d+).")]
+fn streamer_should_be_on(world: &mut GameWorld, x: u32, y: u32) {
+    let streamer_tilepos = world
+        .find_with::<TilePos, StreamerLabel>()
+        .expect("streamer_should_be_on: The Streamer should still be around.");
+
+    assert_eq!(TilePos::new(x, y), *streamer_tilepos);
+}
+
+#[then(regex = r"^the Ground Graph should be rebuilt for a map (\d+) Tiles long.")]
+fn ground_graph_should_be_rebuilt(world: &mut GameWorld, map_length: u32) {
+    let ground_graphs = world
+        .find_all::<UndirectedGraph>()
+        .into_iter()
+        .filter(|graph| *graph.get_node_type() == GraphType::Ground)
+        .map(|graph| graph.length())
+        .collect::<Vec<u32>>();
+
+    assert_eq!(vec![map_length], ground_graphs);
+}
+
+#[then(regex = r"^the same Chatter should be on (\d+), (\d+).")]
+fn same_chatter_should_be_on(world: &mut GameWorld, x: u32, y: u32) {
+    let chatter_before_switch = world.app.world().resource::<ChatterBeforeSwitch>().0;
+    let chatters = world
+        .app
+        .world_mut()
+        .query_filtered::<(Entity, &TilePos), With<ChatterLabel>>()
+        .iter(world.app.world())
+        .map(|(chatter_entity, chatter_tilepos)| (chatter_entity, *chatter_tilepos))
+        .collect::<Vec<(Entity, TilePos)>>();
+
+    assert_eq!(vec![(chatter_before_switch, TilePos::new(x, y))], chatters);
+}
+
+fn main() {
+    futures::executor::block_on(GameWorld::run("tests/feature-files/scenes.feature"));
+}
@@ -0,0 +1,318 @@
+mod mock_plugins;
+
+use bevy::ecs::system::RunSystemOnce;
+use bevy::prelude::*;
+use bevy_ecs_tilemap::prelude::*;
+use cucumber::{given, then, when, World};
+
+use crate::mock_plugins::{
+    GameWorld, MockChatterPlugin, MockCropPlugin, MockSessionPlugin, MockStreamerPlugin,
+    MockTiledMapPlugin,
+};
+use task_masker::entities::chatter::*;
+use task_masker::entities::crop::*;
+use task_masker::entities::streamer::*;
+use task_masker::map::path_finding::*;
+use task_masker::map::plugins::{PathFindingPlugin, TilePosEvent};
+use task_masker::map::scenes::{MapScenes, SwitchSceneEvent};
+use task_masker::session::records::SessionRecords;
+use task_masker::session::saving::*;
+use task_masker::ui::screens::HealthProgress;
+
+const STREAM_LENGTH_SECS: u32 = 8 * 60 * 60;
+const PASSED_STREAM_SECS: u32 = 1200;
+const STREAMER_DESTINATION: TilePos = TilePos { x: 41, y: 49 };
+
+/// What the world looked like when the session was saved.
+#[derive(Resource, Default)]
+struct SavedWorld {
+    streamer_tilepos: Option<TilePos>,
+    crop: Option<(TilePos, u32)>,
+    chat_msgs: Vec<String>,
+}
+
+/// Starts up the game with some Session Records, restoring
+/// from them if asked to.
+fn launch_game(world: &mut GameWorld, session_records: SessionRecords, restore_session: bool) {
+    let pending_session = if restore_session {
+        PendingSession(session_records.load())
+    } else {
+        PendingSession::default()
+    };
+
+    // The saved Map Scene is restored before the map is laid out.
+    world.app.insert_resource(session_records);
+    world.app.insert_resource(pending_session);
+    world.app.init_resource::<SavedWorld>();
+    world.app.add_plugins(MockSessionPlugin);
+
+    world.app.add_plugins(MockTiledMapPlugin);
+    world.update(1);
+
+    world.app.add_plugins(PathFindingPlugin);
+    world.update(1);
+
+    world.app.add_plugins(MockStreamerPlugin);
+    world.app.add_plugins(MockCropPlugin);
+    world.app.add_plugins(MockChatterPlugin);
+    world.app.world_mut().spawn(HealthProgress {
+        current: 0,
+        total: STREAM_LENGTH_SECS,
+    });
+    world.update(4);
+}
+
+/// Tears the game down and starts it back up again
+/// with the same Session Records.
+fn restart_game(world: &mut GameWorld, restore_session: bool) {
+    let session_records = world
+        .app
+        .world_mut()
+        .remove_resource::<SessionRecords>()
+        .expect("restart_game: Session Records should exist by now.");
+    let saved_world = world
+        .app
+        .world_mut()
+        .remove_resource::<SavedWorld>()
+        .unwrap_or_default();
+
+    *world = GameWorld::new();
+    launch_game(world, session_records, restore_session);
+    world.app.insert_resource(saved_world);
+}
+
+#[given("a game that saves its session,")]
+fn launch_saving_game(world: &mut GameWorld) {
+    launch_game(world, SessionRecords::new(String::from(":memory:")), false);
+}
+
+#[when("the Streamer is heading somewhere,")]
+fn streamer_heads_somewhere(world: &mut GameWorld) {
+    let streamer_spawn = *world
+        .find_with::<TilePos, StreamerLabel>()
+        .expect("streamer_heads_somewhere: Streamer does not have a TilePos.");
+
+    world.broadcast_event(TilePosEvent::new(STREAMER_DESTINATION));
+    loop {
+        world.update(1);
+
+        let streamer_tilepos = *world
+            .find_with::<TilePos, StreamerLabel>()
+            .expect("streamer_heads_somewhere: Streamer does not have a TilePos.");
+
+        if streamer_tilepos != streamer_spawn {
+            break;
+        }
+    }
+}
+
+#[when("a Crop has grown a few stages,")]
+fn grow_crop(world: &mut GameWorld) {
+    let (crop_tilepos, crop_texture_idx) = world
+        .app
+        .world_mut()
+        .query_filtered::<(&TilePos, &mut TileTextureIndex), With<CropState>>()
+        .iter_mut(world.app.world_mut())
+        .map(|(crop_tilepos, mut tile_texture_index)| {
+            tile_texture_index.0 += 3;
+            (*crop_tilepos, tile_texture_index.0)
+        })
+        .next()
+        .expect("grow_crop: Crop should exist by now.");
+
+    world.app.world_mut().resource_mut::<SavedWorld>().crop =
+        Some((crop_tilepos, crop_texture_idx));
+}
+
+#[when("the Chatter has messages waiting to be read,")]
+fn chatter_has_messages(world: &mut GameWorld) {
+    let chat_msg_names = vec![String::from("Bob"), String::from("Alice")];
+    for chat_msg_name in &chat_msg_names {
+        world.broadcast_event(ChatMsg {
+            name: chat_msg_name.clone(),
+            msg: String::from("Hello Caveman!"),
+        });
+    }
+    world.update(1);
+
+    world.app.world_mut().resource_mut::<SavedWorld>().chat_msgs = chat_msg_names;
+}
+
+#[when("some of the stream's time has passed,")]
+fn stream_time_passes(world: &mut GameWorld) {
+    world
+        .app
+        .world_mut()
+        .query::<&mut HealthProgress>()
+        .single_mut(world.app.world_mut())
+        .current = PASSED_STREAM_SECS;
+}
+
+#[when(regex = r"^the stream switches to the (.+) scene,")]
+fn switch_to_scene(world: &mut GameWorld, scene_name: String) {
+    world.broadcast_event(SwitchSceneEvent::Named(scene_name));
+
+    // The new map is laid out, then has its Graphs built
+    // from it, before everyone is moved over onto it.
+    world.update(3);
+}
+
+#[when("the session is saved,")]
+fn save_current_session(world: &mut GameWorld) {
+    let streamer_tilepos = *world
+        .find_with::<TilePos, StreamerLabel>()
+        .expect("save_current_session: Streamer does not have a TilePos.");
+    world
+        .app
+        .world_mut()
+        .resource_mut::<SavedWorld>()
+        .streamer_tilepos = Some(streamer_tilepos);
+
+    world.app.world_mut().run_system_once(save_session);
+}
+
+#[when("the game restarts from the saved session,")]
+fn restart_from_saved_session(world: &mut GameWorld) {
+    restart_game(world, true);
+}
+
+#[when("the game restarts without restoring the saved session,")]
+fn restart_without_saved_session(world: &mut GameWorld) {
+    restart_game(world, false);
+}
+
+#[when("the game restarts from the saved session after the Streamer has gone,")]
+fn restart_without_streamer(world: &mut GameWorld) {
+    restart_game(world, true);
+
+    // The Streamer is taken away before their part of the
+    // saved session could be restored.
+    let streamer_entity = world
+        .app
+        .world_mut()
+        .query_filtered::<Entity, With<StreamerLabel>>()
+        .single(world.app.world());
+    world.app.world_mut().despawn(streamer_entity);
+
+    // The Map Scene was already restored at launch.
+    let mut pending_session = PendingSession(world.app.world().resource::<SessionRecords>().load());
+    pending_session.scene = None;
+    world.app.insert_resource(pending_session);
+    world.update(2);
+}
+
+#[when("the saved session has waited too long to be restored,")]
+fn wait_too_long_to_restore(world: &mut GameWorld) {
+    assert_eq!(
+        vec!["Streamer"],
+        world
+            .app
+            .world()
+            .resource::<PendingSession>()
+            .unrestored_parts()
+    );
+
+    world
+        .app
+        .world_mut()
+        .run_system_once(abandon_unrestored_session);
+}
+
+#[then(regex = r"^the stream should be set in the (.+) scene\.")]
+fn stream_set_in_scene(world: &mut GameWorld, scene_name: String) {
+    assert_eq!(
+        scene_name,
+        world.app.world().resource::<MapScenes>().active().name
+    );
+}
+
+#[then("autosaving should carry on.")]
+fn autosaving_carries_on(world: &mut GameWorld) {
+    assert!(world.app.world().resource::<PendingSession>().is_restored());
+}
+
+#[then("the Streamer should be where they were when saved,")]
+fn streamer_where_saved(world: &mut GameWorld) {
+    let saved_tilepos = world
+        .app
+        .world()
+        .resource::<SavedWorld>()
+        .streamer_tilepos
+        .expect("streamer_where_saved: Streamer position was not saved.");
+    let streamer_starting_point = world
+        .find_with::<StartingPoint, StreamerLabel>()
+        .expect("streamer_where_saved: Streamer does not have a Starting Point.");
+
+    assert_eq!(streamer_starting_point.1, saved_tilepos);
+}
+
+#[then("the Streamer should carry on to where they were heading.")]
+fn streamer_carries_on(world: &mut GameWorld) {
+    loop {
+        world.update(1);
+
+        let streamer_tilepos = *world
+            .find_with::<TilePos, StreamerLabel>()
+            .expect("streamer_carries_on: Streamer does not have a TilePos.");
+
+        if streamer_tilepos == STREAMER_DESTINATION {
+            break;
+        }
+    }
+}
+
+#[then("the Crop should be as grown as it was when saved.")]
+fn crop_as_grown(world: &mut GameWorld) {
+    let (saved_tilepos, saved_texture_idx) = world
+        .app
+        .world()
+        .resource::<SavedWorld>()
+        .crop
+        .expect("crop_as_grown: Crop was not saved.");
+
+    let crop_texture_idx = world
+        .app
+        .world_mut()
+        .query_filtered::<(&TilePos, &TileTextureIndex), With<CropState>>()
+        .iter(world.app.world())
+        .find(|(crop_tilepos, _tile_texture_index)| **crop_tilepos == saved_tilepos)
+        .map(|(_crop_tilepos, tile_texture_index)| tile_texture_index.0)
+        .expect("crop_as_grown: Crop was not found after restarting.");
+
+    assert_eq!(crop_texture_idx, saved_texture_idx);
+}
+
+#[then("the Chatter should still have the same messages waiting.")]
+fn chatter_has_same_messages(world: &mut GameWorld) {
+    let saved_chat_msgs = world.app.world().resource::<SavedWorld>().chat_msgs.clone();
+    let chat_msgs = world
+        .find::<ChatMessageQueue>()
+        .expect("chatter_has_same_messages: Chatter does not have a Chat Message Queue.")
+        .iter()
+        .map(|chat_msg| chat_msg.name.clone())
+        .collect::<Vec<String>>();
+
+    assert_eq!(chat_msgs, saved_chat_msgs);
+}
+
+#[then("the same amount of the stream's time should have passed.")]
+fn same_stream_time_passed(world: &mut GameWorld) {
+    let health_progress = world
+        .find::<HealthProgress>()
+        .expect("same_stream_time_passed: Health Progress should exist by now.");
+
+    assert_eq!(health_progress.current, PASSED_STREAM_SECS);
+}
+
+#[then("none of the stream's time should have passed.")]
+fn no_stream_time_passed(world: &mut GameWorld) {
+    let health_progress = world
+        .find::<HealthProgress>()
+        .expect("no_stream_time_passed: Health Progress should exist by now.");
+
+    assert_eq!(health_progress.current, 0);
+}
+
+fn main() {
+    futures::executor::block_on(GameWorld::run("tests/feature-files/session.feature"));
+}
@@ -8,8 +8,23 @@ use bevy::prelude::*;
 use bevy_ecs_tilemap::prelude::*;
 use cucumber::{given, then, when, World};
 use task_masker::entities::streamer::*;
-use task_masker::map::path_finding::Direction;
-use task_masker::map::plugins::{PathFindingPlugin, TilePosEvent};
+use task_masker::map::destinations::*;
+use task_masker::map::path_finding::{
+    idx_to_tilepos, Direction, GraphType, Path, SpawnPoint, UndirectedGraph,
+};
+use task_masker::map::path_requests::{AwaitingPath, PathError, PathRequest, PathResult};
+use task_masker::map::plugins::{CancelDestinationEvent, PathFindingPlugin, TilePosEvent};
+
+/// Returns the location some step refers to.
+fn location_from(option: &str) -> TilePos {
+    match option {
+        "lower" => TilePos::new(64, 47),
+        "equal in height" => TilePos::new(45, 40),
+        "higher" => TilePos::new(44, 35),
+        "cave" => TilePos::new(39, 100 - 59 - 1),
+        _ => unreachable!(),
+    }
+}
 
 #[given("a Tiled Map,")]
 fn spawn_tiled_map(world: &mut GameWorld) {
@@ -157,6 +172,206 @@ fn streamer_should_be_facing_towards_campfire(world: &mut GameWorld) {
     assert_eq!(expected_direction, *actual_direction);
 }
 
+#[given("the Streamer shortens its walk between destinations,")]
+fn shorten_streamer_walk(world: &mut GameWorld) {
+    world
+        .app
+        .world_mut()
+        .resource_mut::<RouteSettings>()
+        .shorten_walk = true;
+}
+
+#[when(
+    regex = r"^the Streamer is requested to head to the (equal in height|higher) location twice,"
+)]
+fn request_streamer_destination_twice(world: &mut GameWorld, option: String) {
+    for _ in 0..2 {
+        world.broadcast_event(TilePosEvent::new(location_from(&option)));
+        world.update(1);
+    }
+}
+
+#[when(
+    regex = r"^the Streamer is requested to head to the (equal in height|higher) location with (Low|Normal|High) priority,"
+)]
+fn request_streamer_destination_with_priority(
+    world: &mut GameWorld,
+    option: String,
+    priority_option: String,
+) {
+    let priority = match priority_option.as_str() {
+        "Low" => DestinationPriority::Low,
+        "Normal" => DestinationPriority::Normal,
+        "High" => DestinationPriority::High,
+        _ => unreachable!(),
+    };
+
+    world.broadcast_event(TilePosEvent::from_request(
+        DestinationRequest::new(location_from(&option)).with_priority(priority),
+    ));
+    world.update(1);
+}
+
+#[when(regex = r"^the request for the (equal in height|higher) location is cancelled,")]
+fn cancel_streamer_destination(world: &mut GameWorld, option: String) {
+    world.broadcast_event(CancelDestinationEvent::Matching(DedupeKey::Tile(
+        location_from(&option),
+    )));
+    world.update(1);
+}
+
+#[when("the Streamer is requested to head to the lower and then the equal in height location,")]
+fn request_streamer_destinations_at_once(world: &mut GameWorld) {
+    world.broadcast_event(TilePosEvent::new(location_from("lower")));
+    world.broadcast_event(TilePosEvent::new(location_from("equal in height")));
+
+    // Paths are found in the background, so wait on the
+    // Path to be found as well as for the Streamer to set off.
+    loop {
+        world.update(1);
+
+        let streamer_status = *world
+            .find::<StreamerState>()
+            .expect("request_streamer_destinations_at_once: Streamer does not have a Status.");
+        let streamer_awaiting_path = **world
+            .find_with::<AwaitingPath, StreamerLabel>()
+            .expect("request_streamer_destinations_at_once: Streamer is not awaiting any Path.");
+
+        if streamer_status == StreamerState::Moving && !streamer_awaiting_path {
+            break;
+        }
+    }
+}
+
+#[then(regex = r"^the Streamer should have (\d+) destinations? queued.")]
+fn streamer_should_have_destinations_queued(world: &mut GameWorld, num_queued: usize) {
+    let streamer_destinations = world.find_with::<DestinationQueue, StreamerLabel>().expect(
+        "streamer_should_have_destinations_queued: Streamer does not have a DestinationQueue.",
+    );
+
+    assert_eq!(num_queued, streamer_destinations.len());
+}
+
+#[then(regex = r"^the Streamer should head to the (higher location|cave) next.")]
+fn streamer_should_head_to_destination_next(world: &mut GameWorld, option: String) {
+    let expected_destination = location_from(option.trim_end_matches(" location"));
+    let next_destination = world
+        .find_with::<DestinationQueue, StreamerLabel>()
+        .expect(
+            "streamer_should_head_to_destination_next: Streamer does not have a DestinationQueue.",
+        )
+        .destinations()
+        .first()
+        .copied();
+
+    assert_eq!(Some(expected_destination), next_destination);
+}
+
+#[then(regex = r"^the Streamer should be heading to the (lower|equal in height) location.")]
+fn streamer_should_be_heading_to_location(world: &mut GameWorld, option: String) {
+    let map_size = **world
+        .find_all::<TilemapSize>()
+        .first()
+        .expect("streamer_should_be_heading_to_location: Map does not have a TilemapSize.");
+    let streamer_path = world
+        .find_with::<Path, StreamerLabel>()
+        .expect("streamer_should_be_heading_to_location: Streamer does not have a Path.");
+    let path_end = streamer_path
+        .back()
+        .map(|destination_idx| idx_to_tilepos(*destination_idx, map_size.y));
+
+    assert_eq!(Some(location_from(&option)), path_end);
+}
+
+#[when(
+    regex = r"^a Path to the (equal in height|higher) location comes back after the Streamer was moved elsewhere,"
+)]
+fn stale_path_comes_back(world: &mut GameWorld, option: String) {
+    // The Streamer is set up for finding Paths once spawned.
+    world.update(1);
+
+    let destination = location_from(&option);
+    let (streamer, mut streamer_awaiting_path, mut streamer_requested_destination) = world
+        .app
+        .world_mut()
+        .query_filtered::<(Entity, &mut AwaitingPath, &mut RequestedDestination), With<StreamerLabel>>()
+        .single_mut(world.app.world_mut());
+    **streamer_awaiting_path = true;
+    **streamer_requested_destination = Some(DestinationRequest::new(destination));
+
+    // The Path was asked for from where the Streamer stood before being moved.
+    world.broadcast_event(PathResult {
+        request: PathRequest::new(streamer, GraphType::Ground, OUT_AT_SEA, destination),
+        result: Err(PathError::Unreachable {
+            start: OUT_AT_SEA,
+            end: destination,
+        }),
+    });
+    world.update(1);
+}
+
+#[then(regex = r"^the Streamer should still be headed to the (equal in height|higher) location.")]
+fn streamer_still_headed_to_location(world: &mut GameWorld, option: String) {
+    let destination = location_from(&option);
+    let is_queued = world
+        .find_with::<DestinationQueue, StreamerLabel>()
+        .expect("streamer_still_headed_to_location: Streamer does not have a DestinationQueue.")
+        .destinations()
+        .contains(&destination);
+    let is_requested = world
+        .find_with::<RequestedDestination, StreamerLabel>()
+        .expect("streamer_still_headed_to_location: Streamer does not have a RequestedDestination.")
+        .as_ref()
+        .is_some_and(|requested_destination| requested_destination.destination == destination);
+
+    assert!(is_queued || is_requested);
+}
+
+/// A Tile far out at sea, which no Path on land leads to.
+const OUT_AT_SEA: TilePos = TilePos { x: 0, y: 0 };
+
+#[when("the Streamer is requested to head out to sea,")]
+fn request_streamer_to_head_out_to_sea(world: &mut GameWorld) {
+    world.broadcast_event(TilePosEvent::new(OUT_AT_SEA));
+
+    loop {
+        world.update(1);
+
+        let streamer_status = world
+            .find::<StreamerState>()
+            .expect("request_streamer_to_head_out_to_sea: Streamer does not have a Status.");
+
+        if *streamer_status == StreamerState::Moving {
+            break;
+        }
+    }
+}
+
+#[then("the Streamer should be as close to the sea as it can get.")]
+fn streamer_should_be_close_to_sea(world: &mut GameWorld) {
+    let spawn_tilepos = world
+        .find_with::<SpawnPoint, StreamerLabel>()
+        .expect("streamer_should_be_close_to_sea: Streamer does not have a SpawnPoint.")
+        .0;
+    let ground_graph = world
+        .find_all::<UndirectedGraph>()
+        .into_iter()
+        .find(|graph| *graph.get_node_type() == GraphType::Ground)
+        .expect("streamer_should_be_close_to_sea: Map does not have a Ground Graph.");
+
+    assert!(ground_graph
+        .find_path(spawn_tilepos, OUT_AT_SEA)
+        .is_err_and(|path_error| matches!(path_error, PathError::Unreachable { .. })));
+
+    let expected_tilepos = ground_graph.nearest_reachable_to(spawn_tilepos, OUT_AT_SEA);
+    let streamer_tilepos = *world
+        .find_with::<TilePos, StreamerLabel>()
+        .expect("streamer_should_be_close_to_sea: Streamer does not have a TilePos.");
+
+    assert_ne!(spawn_tilepos, streamer_tilepos);
+    assert_eq!(expected_tilepos, Some(streamer_tilepos));
+}
+
 fn main() {
     futures::executor::block_on(GameWorld::run("tests/feature-files/streamer.feature"));
 }
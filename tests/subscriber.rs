@@ -10,9 +10,11 @@ use bevy_ecs_tilemap::prelude::*;
 use cucumber::{given, then, when, World};
 
 use task_masker::entities::subscriber::*;
+use task_masker::entities::visitor::VisitorStatus;
 use task_masker::entities::WaitToLeaveTimer;
 use task_masker::map::path_finding::*;
 use task_masker::map::plugins::PathFindingPlugin;
+use task_masker::map::scenes::{MapScene, MapScenes, SwitchSceneEvent};
 use task_masker::ui::chatting::TypingMsg;
 
 #[given("a Tiled Map")]
@@ -53,6 +55,26 @@ fn make_subscriber_approach_to_speak(world: &mut GameWorld) {
     world.app.update();
 }
 
+#[when("the stream switches to a map without a layer for Subscribers")]
+fn switch_to_map_without_subscribers(world: &mut GameWorld) {
+    world
+        .app
+        .world_mut()
+        .resource_mut::<MapScenes>()
+        .register(MapScene {
+            name: String::from("Reedy Pond"),
+            map_path: String::from("../tests/test-assets/maps/no_subscribers.tmx"),
+            activities: Vec::new(),
+            streamer_spawn: TilePos::new(3, 3),
+            online_spot: TilePos::new(3, 4),
+        });
+    world.broadcast_event(SwitchSceneEvent::Named(String::from("Reedy Pond")));
+
+    // The new map is laid out, then has its Graphs built
+    // from it, before everyone is moved over onto it.
+    world.update(3);
+}
+
 #[when("the Subscriber has approached the Streamer")]
 fn wait_for_subscriber_to_approach_to_speak(world: &mut GameWorld) {
     make_subscriber_approach_to_speak(world);
@@ -63,11 +85,11 @@ fn wait_for_subscriber_to_approach_to_speak(world: &mut GameWorld) {
         let subscriber_status = world
             .app
             .world_mut()
-            .query::<&SubscriberStatus>()
+            .query::<&VisitorStatus>()
             .get_single(&world.app.world())
             .expect("wait_for_subscriber_to_approach_to_speak: Subscriber does not have a Status.");
 
-        if *subscriber_status == SubscriberStatus::Speaking {
+        if *subscriber_status == VisitorStatus::Speaking {
             break;
         }
     }
@@ -96,11 +118,11 @@ fn wait_for_subscriber_to_finish_speaking(world: &mut GameWorld) {
         let subscriber_status = world
             .app
             .world_mut()
-            .query::<&SubscriberStatus>()
+            .query::<&VisitorStatus>()
             .get_single(&world.app.world())
             .expect("wait_for_subscriber_to_finish_speaking: Subscriber does not have a Status.");
 
-        if *subscriber_status != SubscriberStatus::Speaking {
+        if *subscriber_status != VisitorStatus::Speaking {
             break;
         }
     }
@@ -172,16 +194,16 @@ fn subscriber_should_approach_to_streamer(world: &mut GameWorld) {
     let subscriber_status = world
         .app
         .world_mut()
-        .query::<&SubscriberStatus>()
+        .query::<&VisitorStatus>()
         .get_single(&world.app.world())
         .expect("subscriber_should_approach_to_streamer: Subscriber does not have a Status.");
 
-    assert_eq!(*subscriber_status, SubscriberStatus::Approaching);
+    assert_eq!(*subscriber_status, VisitorStatus::Approaching);
 
     let subscriber_path = world
         .app
         .world_mut()
-        .query_filtered::<&Path, With<SubscriberStatus>>()
+        .query_filtered::<&Path, With<SubscriberLabel>>()
         .get_single(&world.app.world())
         .expect("subscriber_should_approach_to_streamer: Subscriber does not have a Path.");
 
@@ -195,11 +217,11 @@ fn subscriber_should_start_speaking(world: &mut GameWorld) {
     let subscriber_status = world
         .app
         .world_mut()
-        .query::<&SubscriberStatus>()
+        .query::<&VisitorStatus>()
         .get_single(&world.app.world())
         .expect("subscriber_should_start_speaking: Subscriber does not have a Status.");
 
-    assert_eq!(*subscriber_status, SubscriberStatus::Speaking);
+    assert_eq!(*subscriber_status, VisitorStatus::Speaking);
 }
 
 #[then("the Subscriber should still be speaking")]
@@ -212,9 +234,9 @@ fn subscriber_should_still_be_speaking(world: &mut GameWorld) {
         .at_end();
     assert!(msg_is_still_being_typed);
 
-    let expected_subscriber_status = SubscriberStatus::Speaking;
+    let expected_subscriber_status = VisitorStatus::Speaking;
     let actual_subscriber_status = world
-        .find::<SubscriberStatus>()
+        .find::<VisitorStatus>()
         .expect("subscriber_should_still_be_speaking: Subscriber status could not be found.");
 
     assert_eq!(expected_subscriber_status, *actual_subscriber_status);
@@ -231,13 +253,13 @@ fn subscriber_should_be_leaving_back_to_spawn(world: &mut GameWorld) {
         let subscriber_status = world
             .app
             .world_mut()
-            .query::<&SubscriberStatus>()
+            .query::<&VisitorStatus>()
             .get_single(&world.app.world())
             .expect(
                 "subscriber_should_be_leaving_back_to_spawn: Subscriber does not have a Status.",
             );
 
-        if *subscriber_status == SubscriberStatus::Idle {
+        if *subscriber_status == VisitorStatus::Idle {
             break;
         }
     }
@@ -245,13 +267,27 @@ fn subscriber_should_be_leaving_back_to_spawn(world: &mut GameWorld) {
     let (subscriber_tilepos, subscriber_spawn) = world
         .app
         .world_mut()
-        .query_filtered::<(&TilePos, &SpawnPoint), With<SubscriberStatus>>()
+        .query_filtered::<(&TilePos, &SpawnPoint), With<SubscriberLabel>>()
         .get_single(&world.app.world())
         .expect("subscriber_should_be_leaving_back_to_spawn: Subscriber is missing pathfinding-based information and/or Status.");
 
     assert_eq!(subscriber_spawn.0, *subscriber_tilepos);
 }
 
+#[then("there should be a Ground Graph but no Water Graph")]
+fn ground_graph_without_water_graph(world: &mut GameWorld) {
+    world.update(2);
+
+    let graph_types = world
+        .find_all::<UndirectedGraph>()
+        .iter()
+        .map(|graph| *graph.get_node_type())
+        .collect::<Vec<GraphType>>();
+
+    assert!(graph_types.contains(&GraphType::Ground));
+    assert!(!graph_types.contains(&GraphType::Water));
+}
+
 fn main() {
     futures::executor::block_on(GameWorld::run("tests/feature-files/subscriber.feature"));
 }
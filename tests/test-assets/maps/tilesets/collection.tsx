@@ -0,0 +1,7 @@
+<?xml version="1.0" encoding="UTF-8"?>
+<tileset version="1.10" tiledversion="1.11.0" name="collection" tilewidth="320" tileheight="512" tilecount="1" columns="0">
+ <grid orientation="orthogonal" width="1" height="1"/>
+ <tile id="0">
+  <image source="../../environment/terrain_0_32x.png" width="320" height="512"/>
+ </tile>
+</tileset>
@@ -0,0 +1,160 @@
+use bevy::asset::{LoadState, RecursiveDependencyLoadState};
+use bevy::prelude::*;
+
+use cucumber::{given, then, World};
+use futures::executor::block_on;
+
+use std::path::PathBuf;
+use std::time::Duration;
+use task_masker::map::tiled::{TiledLoader, TiledMap};
+use task_masker::map::tilemap::{to_bevy_path, TileGridCoordinates, Tilemap};
+
+/// How many times the App is updated while waiting on some map to load.
+const MAX_LOADING_UPDATES: usize = 500;
+
+/// Returns a Bevy App(lication) loading assets from the test-assets
+/// folder, even without the presence of a display.
+fn create_testable_bevy_app() -> App {
+    let mut app = App::new();
+
+    app.add_plugins(MinimalPlugins);
+    app.add_plugins(AssetPlugin {
+        file_path: String::from("tests/test-assets"),
+        ..default()
+    });
+    app.add_plugins(ImagePlugin::default());
+    app.init_asset::<TiledMap>()
+        .register_asset_loader(TiledLoader);
+
+    // NOTE: Images can only be loaded once every plugin is finished.
+    app.finish();
+    app.update();
+
+    app
+}
+
+#[derive(World)]
+#[world(init = Self::new)]
+pub struct TiledLoaderContext {
+    testing_app: App,
+    map_handle: Handle<TiledMap>,
+}
+
+impl std::fmt::Debug for TiledLoaderContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TiledLoaderContext")
+            .field("map_handle", &self.map_handle)
+            .finish()
+    }
+}
+
+impl TiledLoaderContext {
+    fn new() -> Self {
+        Self {
+            testing_app: create_testable_bevy_app(),
+            map_handle: Handle::default(),
+        }
+    }
+
+    /// Updates the App until the map is done loading, or failed to.
+    fn wait_until(&mut self, is_done: impl Fn(&AssetServer, &Handle<TiledMap>) -> bool) {
+        for _ in 0..MAX_LOADING_UPDATES {
+            let asset_server = self.testing_app.world().resource::<AssetServer>();
+            if is_done(asset_server, &self.map_handle) {
+                return;
+            }
+            if let LoadState::Failed(load_error) = asset_server.load_state(&self.map_handle) {
+                panic!("wait_until: The map failed to load: {load_error}");
+            }
+
+            self.testing_app.update();
+            std::thread::sleep(Duration::from_millis(10));
+        }
+
+        panic!("wait_until: The map took too long to load.");
+    }
+
+    /// Returns the loaded Tiled map.
+    fn tiled_map(&self) -> &tiled::Map {
+        &self
+            .testing_app
+            .world()
+            .resource::<Assets<TiledMap>>()
+            .get(&self.map_handle)
+            .expect("tiled_map: The map should be loaded by now.")
+            .map
+    }
+}
+
+#[given(regex = r"^the Tiled map called (.+\.tmx) is loaded as an asset,$")]
+fn load_map_as_asset(context: &mut TiledLoaderContext, map_path: String) {
+    context.map_handle = context
+        .testing_app
+        .world()
+        .resource::<AssetServer>()
+        .load(map_path);
+
+    context.wait_until(|asset_server, map_handle| {
+        asset_server.load_state(map_handle) == LoadState::Loaded
+    });
+}
+
+#[then(regex = r"^the map should have (\d+) tilesets,$")]
+fn map_has_tilesets(context: &mut TiledLoaderContext, num_tilesets: usize) {
+    assert_eq!(num_tilesets, context.tiled_map().tilesets().len());
+}
+
+#[then(regex = r"^the map should have a tileset called (\w+)(,|\.)$")]
+fn map_has_tileset_called(context: &mut TiledLoaderContext, tileset_name: String, _end: String) {
+    assert!(context
+        .tiled_map()
+        .tilesets()
+        .iter()
+        .any(|tileset| tileset.name == tileset_name));
+}
+
+#[then(regex = r"^the map should be drawn with (.+\.png),$")]
+fn map_drawn_with(context: &mut TiledLoaderContext, image_path: String) {
+    let world = context.testing_app.world();
+    let asset_server = world.resource::<AssetServer>();
+    let tiled_map = world
+        .resource::<Assets<TiledMap>>()
+        .get(&context.map_handle)
+        .expect("map_drawn_with: The map should be loaded by now.");
+
+    assert!(tiled_map.images.iter().any(|image| {
+        asset_server
+            .get_path(image)
+            .is_some_and(|path| path.path() == PathBuf::from(&image_path))
+    }));
+}
+
+#[then("the map should finish loading along with everything it is drawn with.")]
+fn map_finishes_loading(context: &mut TiledLoaderContext) {
+    context.wait_until(|asset_server, map_handle| {
+        asset_server.recursive_dependency_load_state(map_handle)
+            == RecursiveDependencyLoadState::Loaded
+    });
+}
+
+#[then(regex = r"^the Tile at (\d+), (\d+) should be drawn from (.+\.png).$")]
+fn tile_drawn_from(context: &mut TiledLoaderContext, x: usize, y: usize, image_path: String) {
+    let tilemap = Tilemap::from_tiled_map(context.tiled_map());
+    let tile_texture = tilemap
+        .get_tiles()
+        .iter()
+        .find(|tile| *tile.get_grid_coordinates() == TileGridCoordinates::new_3d(x, y, 0))
+        .and_then(|tile| tile.get_tile_texture())
+        .expect("tile_drawn_from: The Tile should have a texture.");
+
+    assert_eq!(
+        PathBuf::from(image_path),
+        to_bevy_path(tile_texture.get_sprite().get_path().clone())
+    );
+}
+
+fn main() {
+    block_on(TiledLoaderContext::run(
+        "tests/feature-files/tiled_loader.feature",
+    ));
+}
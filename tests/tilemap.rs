@@ -7,7 +7,9 @@ use cucumber::{given, then, when, World};
 use futures::executor::block_on;
 
 use std::path::{PathBuf, MAIN_SEPARATOR};
+use std::time::Duration;
 use task_masker::map::tilemap::*;
+use task_masker::visual::tile_animations::TileAnimation;
 
 /// Returns a path to some asset requested from the test-assets
 /// folder.
@@ -123,7 +125,7 @@ impl TiledContext {
     }
 
     /// Returns the collection of Texture Atlas Assets recorded from the loaded Bevy App.
-    pub fn get_texture_atlas_assets_mut(&mut self) -> Mut<Assets<TextureAtlasLayout>> {
+    pub fn get_texture_atlas_assets_mut(&mut self) -> Mut<'_, Assets<TextureAtlasLayout>> {
         self.testing_app
             .world_mut()
             .resource_mut::<Assets<TextureAtlasLayout>>()
@@ -376,8 +378,12 @@ fn check_render_tiles_equal_tiles_with_texture(tiled_context: &mut TiledContext)
     let tilemap = tiled_context.take_tilemap();
     let asset_server = tiled_context.get_asset_server();
     let mut texture_atlas_assets = tiled_context.get_texture_atlas_assets_mut();
-    let render_tiles =
-        convert_tilemap_to_bevy_render_tiles(&tilemap, &asset_server, &mut texture_atlas_assets);
+    let render_tiles = convert_tilemap_to_bevy_render_tiles(
+        &tilemap,
+        &[],
+        &asset_server,
+        &mut texture_atlas_assets,
+    );
 
     let tiles = tilemap.get_tiles();
     let expected_num_render_tiles = tiles
@@ -455,6 +461,146 @@ fn check_tile_heights(
     assert!(first_tile_height > second_tile_height);
 }
 
+/// Returns the animation of the Tile at some grid coordinates, if any.
+fn tile_animation_at(
+    tiled_context: &TiledContext,
+    tile_x: &str,
+    tile_y: &str,
+    tile_z: &str,
+) -> Option<TileAnimation> {
+    let tile_grid_x = tile_x.parse::<usize>().unwrap();
+    let tile_grid_y = tile_y.parse::<usize>().unwrap();
+    let tile_grid_z = tile_z.parse::<usize>().unwrap();
+    let tile_grid_coordinate = TileGridCoordinates::new_3d(tile_grid_x, tile_grid_y, tile_grid_z);
+
+    tiled_context
+        .get_tile(&tile_grid_coordinate)
+        .expect("tile_animation_at: Could not find the tile.")
+        .get_tile_texture()
+        .expect("tile_animation_at: The tile should have a texture.")
+        .get_animation()
+        .cloned()
+}
+
+#[then(regex = r"Tile (\d+), (\d+), (\d+) should have an animation with the sprite entries (.+).")]
+fn check_tile_animation_frames(
+    tiled_context: &mut TiledContext,
+    tile_x: String,
+    tile_y: String,
+    tile_z: String,
+    sprite_entries: String,
+) {
+    let expected_sprite_entries = sprite_entries
+        .split(", ")
+        .map(|sprite_entry| sprite_entry.parse::<u32>().unwrap())
+        .collect::<Vec<u32>>();
+
+    let tile_animation = tile_animation_at(tiled_context, &tile_x, &tile_y, &tile_z)
+        .expect("check_tile_animation_frames: The tile should be animated.");
+    let actual_sprite_entries = tile_animation
+        .frames()
+        .iter()
+        .map(|frame| frame.index)
+        .collect::<Vec<u32>>();
+
+    assert_eq!(expected_sprite_entries, actual_sprite_entries);
+}
+
+#[then(
+    regex = r"Tile (\d+), (\d+), (\d+) should show sprite entry (\d+) after (\d+) milliseconds."
+)]
+fn check_tile_animation_after(
+    tiled_context: &mut TiledContext,
+    tile_x: String,
+    tile_y: String,
+    tile_z: String,
+    sprite_entry: u32,
+    milliseconds: u64,
+) {
+    let mut tile_animation = tile_animation_at(tiled_context, &tile_x, &tile_y, &tile_z)
+        .expect("check_tile_animation_after: The tile should be animated.");
+
+    assert_eq!(
+        sprite_entry,
+        tile_animation.advance(Duration::from_millis(milliseconds))
+    );
+}
+
+#[then(regex = r"Tile (\d+), (\d+), (\d+) should not have an animation.")]
+fn check_tile_has_no_animation(
+    tiled_context: &mut TiledContext,
+    tile_x: String,
+    tile_y: String,
+    tile_z: String,
+) {
+    assert!(tile_animation_at(tiled_context, &tile_x, &tile_y, &tile_z).is_none());
+}
+
+/// Returns the Tile picked from some point in the world on the loaded Tilemap.
+fn tile_picked_from(tiled_context: &TiledContext, world_pos: Vec2) -> Option<TileGridCoordinates> {
+    let tile_dimensions = tiled_context.get_tiles()[0].get_tile_dimensions();
+    let map_height = tiled_context.tilemap().get_dimensions().height();
+
+    TileGridCoordinates::from_world_center(world_pos, tile_dimensions, map_height)
+}
+
+/// Returns where the center of some Tile on the loaded Tilemap is drawn.
+fn world_center_of(tiled_context: &TiledContext, grid_coordinates: &TileGridCoordinates) -> Vec2 {
+    let tile_dimensions = tiled_context.get_tiles()[0].get_tile_dimensions();
+    let map_height = tiled_context.tilemap().get_dimensions().height();
+
+    grid_coordinates.to_world_center(tile_dimensions, map_height)
+}
+
+#[then("each Tile should be picked from where its center is drawn.")]
+fn check_each_tile_picked_from_center(tiled_context: &mut TiledContext) {
+    for tile in tiled_context.get_tiles() {
+        let grid_coordinates = tile.get_grid_coordinates();
+        let world_center = world_center_of(tiled_context, grid_coordinates);
+
+        assert_eq!(
+            Some(TileGridCoordinates::new(
+                grid_coordinates.x(),
+                grid_coordinates.y()
+            )),
+            tile_picked_from(tiled_context, world_center)
+        );
+    }
+}
+
+#[then(
+    regex = r"Tile (\d+), (\d+), (\d+) should be picked from (\d+) pixels beside where its center is drawn."
+)]
+fn check_tile_picked_beside_center(
+    tiled_context: &mut TiledContext,
+    tile_x: usize,
+    tile_y: usize,
+    tile_z: usize,
+    pixels_beside: f32,
+) {
+    let grid_coordinates = TileGridCoordinates::new_3d(tile_x, tile_y, tile_z);
+    let world_center = world_center_of(tiled_context, &grid_coordinates);
+
+    for beside in [Vec2::X, Vec2::NEG_X, Vec2::Y, Vec2::NEG_Y] {
+        assert_eq!(
+            Some(TileGridCoordinates::new(tile_x, tile_y)),
+            tile_picked_from(tiled_context, world_center + beside * pixels_beside)
+        );
+    }
+}
+
+#[then("nothing should be picked beyond the left edge of the map.")]
+fn check_nothing_picked_beyond_left_edge(tiled_context: &mut TiledContext) {
+    let tile_dimensions = tiled_context.get_tiles()[0].get_tile_dimensions();
+    let tile_width = tile_dimensions.width() as f32;
+    let tile_height = tile_dimensions.height() as f32;
+    // NOTE: One Tile before the first, along the x-axis of the grid.
+    let beyond_left_edge = world_center_of(tiled_context, &TileGridCoordinates::new(0, 0))
+        + Vec2::new(-tile_width / 2.0, tile_height / 2.0);
+
+    assert_eq!(None, tile_picked_from(tiled_context, beyond_left_edge));
+}
+
 fn main() {
     block_on(TiledContext::run("tests/feature-files/tilemap.feature"));
 }
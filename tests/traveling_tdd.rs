@@ -4,20 +4,20 @@ use crate::mock_plugins::{
     MockChatterPlugin, MockStreamerPlugin, MockSubscriberPlugin, MockTiledMapPlugin,
 };
 
-use std::time::Duration;
-
 use bevy::prelude::*;
 
 use bevy::state::app::StatesPlugin;
 use bevy_ecs_tilemap::prelude::*;
 use task_masker::entities::{chatter::*, streamer::*, subscriber::*};
+use task_masker::map::movement::MovementSpeed;
 use task_masker::map::path_finding::{
-    tilepos_to_idx, unique_tiles_from, GraphType, HeightedTilePos, MovementTimer, NodeData, Path,
-    Target, TileLayerPosition, UndirectedGraph,
+    tilepos_to_idx, unique_tiles_from, GraphType, HeightedTilePos, NodeData, Path, Target,
+    TileLayerPosition, UndirectedGraph,
 };
+use task_masker::map::path_requests::AwaitingPath;
 use task_masker::map::plugins::{PathFindingPlugin, TilePosEvent};
 use task_masker::map::tiled::{
-    convert_tiled_to_bevy_pos, to_bevy_transform, LayerNumber, TiledMapInformation,
+    convert_tiled_to_bevy_pos, find_layer, tile_transform_on, LayerNumber, MapLayer,
 };
 use task_masker::GameState;
 
@@ -29,9 +29,9 @@ enum EntityType {
     Tile,
 }
 
-fn intercept_movement_timer(mut timer_query: Query<&mut MovementTimer, Added<MovementTimer>>) {
-    for mut movement_timer in &mut timer_query {
-        movement_timer.0 = Timer::new(Duration::from_secs(0), TimerMode::Repeating);
+fn intercept_movement_speed(mut speed_query: Query<&mut MovementSpeed, Added<MovementSpeed>>) {
+    for mut movement_speed in &mut speed_query {
+        movement_speed.0 = f32::MAX;
     }
 }
 
@@ -63,7 +63,7 @@ impl GameWorld {
         //app.add_plugins(MockCropPlugin);
         app.add_plugins(PathFindingPlugin);
 
-        app.add_systems(Update, intercept_movement_timer);
+        app.add_systems(Update, intercept_movement_speed);
 
         app.update();
 
@@ -177,6 +177,17 @@ impl GameWorld {
 
                 self.app.update();
                 self.app.update();
+
+                // The Streamer's Path is found in the background.
+                while self
+                    .app
+                    .world_mut()
+                    .query::<&AwaitingPath>()
+                    .get(self.app.world(), source_entity)
+                    .is_ok_and(|awaiting_path| **awaiting_path)
+                {
+                    self.app.update();
+                }
                 self.app
                     .world_mut()
                     .query::<&Path>()
@@ -388,18 +399,18 @@ impl GameWorld {
         !graph_edges[node_idx].is_empty()
     }
 
-    /// Returns Map Measurement Information based on the specified
+    /// Returns the laid out layer of the map at the specified
     /// Tile Layer Height.
-    pub fn map_info(&mut self, height: usize) -> TiledMapInformation {
-        let (grid_size, map_size, map_type, map_transform) = self
+    pub fn map_layer(&mut self, height: usize) -> MapLayer<'_> {
+        let map_layers = self
             .app
             .world_mut()
-            .query::<(&TilemapGridSize, &TilemapSize, &TilemapType, &Transform)>()
-            .iter(&self.app.world())
-            .nth(height)
-            .expect("map_info: Could not generate TiledMapInformation since all required components are missing.");
+            .query::<MapLayer>()
+            .iter(self.app.world())
+            .collect::<Vec<MapLayer>>();
 
-        TiledMapInformation::new(grid_size, map_size, map_type, map_transform)
+        find_layer(map_layers, height)
+            .expect("map_layer: Could not find the laid out layer at the given height.")
     }
 }
 
@@ -530,8 +541,8 @@ fn heighted_tile_transform_matches_tile_transform() {
 
     let heighted_tile_pos = HeightedTilePos::new(tile_pos, tile_height as u32);
 
-    let expected_transform = to_bevy_transform(&tile_pos, game.map_info(tile_height));
-    let actual_transform = heighted_tile_pos.transform(game.map_info(tile_height));
+    let expected_transform = tile_transform_on(&tile_pos, game.map_layer(tile_height));
+    let actual_transform = heighted_tile_pos.transform(game.map_layer(tile_height));
 
     assert_eq!(expected_transform, actual_transform);
 }
@@ -599,7 +610,7 @@ fn node_data_from_ground_tiles_works() {
     let map_layer_information = world
         .app
         .world_mut()
-        .query::<(&TilemapGridSize, &TilemapType, &Transform)>()
+        .query::<(&TilemapGridSize, &TilemapSize, &Transform)>()
         .iter(&world.app.world_mut())
         .map(|layer_info| TileLayerPosition::new(*layer_info.0, *layer_info.1, *layer_info.2))
         .collect::<Vec<TileLayerPosition>>();
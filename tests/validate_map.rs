@@ -0,0 +1,126 @@
+use std::path::PathBuf;
+
+use bevy_ecs_tilemap::prelude::*;
+use cucumber::{given, then, when, World};
+
+use task_masker::entities::definitions::EntityDefinitions;
+use task_masker::map::scenes::MapScenes;
+use task_masker::map::validation::{MapValidation, MapValidationError};
+
+#[derive(Debug, Default, World)]
+pub struct MapValidationContext {
+    map_name: String,
+    tiled_map: Option<tiled::Map>,
+    errors: Vec<MapValidationError>,
+}
+
+/// Loads the Tiled map found in some folder.
+fn load_map(context: &mut MapValidationContext, map_folder: &str, map_name: String) {
+    let mut map_path = PathBuf::new();
+    if let Ok(project_root_directory) = std::env::var("CARGO_MANIFEST_DIR") {
+        map_path.push(project_root_directory);
+    }
+    map_path.push(map_folder);
+    map_path.push(&map_name);
+
+    context.tiled_map = Some(
+        tiled::Loader::new()
+            .load_tmx_map(map_path)
+            .expect("load_map: Map should load."),
+    );
+    context.map_name = map_name;
+}
+
+/// Validates the loaded map from some Streamer spawn, if any.
+fn validate_map(context: &mut MapValidationContext, streamer_spawn: Option<TilePos>) {
+    let tiled_map = context
+        .tiled_map
+        .as_ref()
+        .expect("validate_map: Map should be loaded by now.");
+
+    let mut assets_folder = PathBuf::new();
+    if let Ok(project_root_directory) = std::env::var("CARGO_MANIFEST_DIR") {
+        assets_folder.push(project_root_directory);
+    }
+    assets_folder.push("assets");
+    let entity_definitions = EntityDefinitions::from_assets_folder(&assets_folder)
+        .expect("validate_map: Shipped definitions should load.");
+
+    context.errors = match MapValidation::from_map(tiled_map) {
+        Ok(map_validation) => map_validation.errors(streamer_spawn, &entity_definitions),
+        Err(map_error) => vec![map_error],
+    };
+}
+
+#[given(regex = r"^the shipped map called (.+\.tmx),$")]
+fn load_shipped_map(context: &mut MapValidationContext, map_name: String) {
+    load_map(context, "assets", map_name);
+}
+
+#[given(regex = r"^a Tiled map called (.+\.tmx),$")]
+fn load_test_map(context: &mut MapValidationContext, map_name: String) {
+    load_map(context, "tests/test-assets/maps", map_name);
+}
+
+#[when("the map is validated from the Streamer spawn of its Map Scene,")]
+fn validate_from_scene_spawn(context: &mut MapValidationContext) {
+    let streamer_spawn = MapScenes::default()
+        .scenes()
+        .iter()
+        .find(|scene| scene.map_path == context.map_name)
+        .map(|scene| scene.streamer_spawn)
+        .expect("validate_from_scene_spawn: Some Map Scene should use the map.");
+
+    validate_map(context, Some(streamer_spawn));
+}
+
+#[when(regex = r"^the map is validated from the Streamer spawning on (\d+), (\d+),$")]
+fn validate_from_spawn(context: &mut MapValidationContext, x: u32, y: u32) {
+    validate_map(context, Some(TilePos::new(x, y)));
+}
+
+#[when("the map is validated without a Streamer spawn,")]
+fn validate_without_spawn(context: &mut MapValidationContext) {
+    validate_map(context, None);
+}
+
+#[then("the map should have no errors.")]
+fn has_no_errors(context: &mut MapValidationContext) {
+    assert_eq!(context.errors, Vec::new());
+}
+
+#[then(regex = r"^the map should have (\d+) errors?,$")]
+fn has_number_of_errors(context: &mut MapValidationContext, number_of_errors: usize) {
+    assert_eq!(
+        context.errors.len(),
+        number_of_errors,
+        "{:?}",
+        context.errors
+    );
+}
+
+#[then(regex = r"^the map should have the error: (.+?)(,)?$")]
+fn has_error(context: &mut MapValidationContext, error_message: String, _end: String) {
+    assert!(
+        context
+            .errors
+            .iter()
+            .any(|map_error| map_error.to_string() == error_message),
+        "{:?}",
+        context.errors
+    );
+}
+
+#[then("the map should have an error about the graphs not being built.")]
+fn has_graphs_not_built_error(context: &mut MapValidationContext) {
+    assert!(context
+        .errors
+        .iter()
+        .any(|map_error| matches!(map_error, MapValidationError::GraphsNotBuilt(_))));
+}
+
+fn main() {
+    futures::executor::block_on(MapValidationContext::run(
+        "tests/feature-files/validate_map.feature",
+    ));
+}
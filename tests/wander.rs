@@ -0,0 +1,209 @@
+mod mock_plugins;
+
+use bevy::prelude::*;
+use bevy_ecs_tilemap::prelude::*;
+use cucumber::{given, then, when, World};
+
+use crate::mock_plugins::{
+    GameWorld, MockChatterPlugin, MockStreamerPlugin, MockSubscriberPlugin, MockTiledMapPlugin,
+    MockWanderPlugin,
+};
+use task_masker::entities::chatter::*;
+use task_masker::entities::subscriber::*;
+use task_masker::entities::visitor::VisitorStatus;
+use task_masker::entities::wander::*;
+use task_masker::map::path_finding::*;
+use task_masker::map::plugins::PathFindingPlugin;
+
+const IDLE_TICKS: usize = 200;
+
+/// Every Tile some NPC was headed towards while wandering.
+#[derive(Resource, Default)]
+struct WanderDestinations(Vec<TilePos>);
+
+/// Returns the Tile each NPC of some kind is currently headed towards.
+fn destinations_for<T: Component>(world: &mut GameWorld) -> Vec<TilePos> {
+    let map_size = **world
+        .find_all::<TilemapSize>()
+        .last()
+        .expect("destinations_for: Map should be spawned by now.");
+
+    world
+        .app
+        .world_mut()
+        .query_filtered::<&Path, With<T>>()
+        .iter(world.app.world())
+        .filter_map(|path| path.0.back())
+        .map(|destination| idx_to_tilepos(*destination, map_size.y))
+        .collect()
+}
+
+/// Returns whether every Tile some NPC has wandered to was
+/// inside of its Wander Area.
+fn rested_within_area<T: Component>(world: &mut GameWorld, graph_type: GraphType) -> bool {
+    let wander_area = *world
+        .find_with::<WanderArea, T>()
+        .expect("rested_within_area: NPC was not given a Wander Area.");
+
+    let distances_from_home = world
+        .find_all::<UndirectedGraph>()
+        .into_iter()
+        .find(|graph| *graph.get_node_type() == graph_type)
+        .map(|graph| (graph.distances_from(wander_area.home), graph.length()))
+        .expect("rested_within_area: Graph should exist by now.");
+
+    let wander_destinations = &world.app.world().resource::<WanderDestinations>().0;
+    wander_destinations.iter().all(|tile_pos| {
+        let idx = tilepos_to_idx(tile_pos.x, tile_pos.y, distances_from_home.1);
+        distances_from_home.0[idx].is_some_and(|distance| distance <= wander_area.radius)
+    })
+}
+
+/// Returns whether any Tile some NPC has wandered to was
+/// somewhere other than where it spawned.
+fn left_spawn_point<T: Component>(world: &mut GameWorld) -> bool {
+    let spawn_tilepos = world
+        .find_with::<SpawnPoint, T>()
+        .expect("left_spawn_point: NPC does not have a Spawn Point.")
+        .0;
+
+    world
+        .app
+        .world()
+        .resource::<WanderDestinations>()
+        .0
+        .iter()
+        .any(|tile_pos| *tile_pos != spawn_tilepos)
+}
+
+#[given("a Tiled Map,")]
+fn spawn_tiled_map(world: &mut GameWorld) {
+    world.app.add_plugins(MockTiledMapPlugin);
+    world.update(1);
+
+    world.app.add_plugins(PathFindingPlugin);
+    world.update(1);
+}
+
+#[given("a Streamer spawned on the Tiled Map,")]
+fn spawn_streamer_on_tiled_map(world: &mut GameWorld) {
+    world.app.add_plugins(MockStreamerPlugin);
+    world.update(1);
+}
+
+#[given("a Chatter spawned on the Tiled Map,")]
+fn spawn_chatter_on_tiled_map(world: &mut GameWorld) {
+    world.app.add_plugins(MockChatterPlugin);
+    world.update(2);
+}
+
+#[given("a Subscriber spawned on the Tiled Map,")]
+fn spawn_subscriber_on_tiled_map(world: &mut GameWorld) {
+    world.app.add_plugins(MockSubscriberPlugin);
+    world.update(2);
+}
+
+#[given(regex = r"^the Chatter may only wander (\d+) Tiles? away,")]
+fn limit_chatter_wander_area(world: &mut GameWorld, radius: String) {
+    let (chatter_entity, chatter_spawn) = world
+        .app
+        .world_mut()
+        .query_filtered::<(Entity, &SpawnPoint), With<ChatterLabel>>()
+        .iter(world.app.world())
+        .map(|(chatter_entity, chatter_spawn)| (chatter_entity, chatter_spawn.0))
+        .next()
+        .expect("limit_chatter_wander_area: Chatter should exist by now.");
+
+    world
+        .app
+        .world_mut()
+        .entity_mut(chatter_entity)
+        .insert(WanderArea {
+            home: chatter_spawn,
+            radius: radius.parse::<usize>().unwrap(),
+        });
+}
+
+#[given("idle NPCs can wander,")]
+fn allow_npcs_to_wander(world: &mut GameWorld) {
+    world.app.add_plugins(MockWanderPlugin);
+    world.app.init_resource::<WanderDestinations>();
+    world.update(1);
+}
+
+#[when("the NPCs have idled for a while,")]
+fn wait_while_idle(world: &mut GameWorld) {
+    for _tick in 0..IDLE_TICKS {
+        world.update(1);
+
+        let mut wander_destinations = destinations_for::<ChatterLabel>(world);
+        wander_destinations.extend(destinations_for::<SubscriberLabel>(world));
+
+        world
+            .app
+            .world_mut()
+            .resource_mut::<WanderDestinations>()
+            .0
+            .extend(wander_destinations);
+    }
+}
+
+#[when("the Chatter is wandering,")]
+fn wait_for_chatter_to_wander(world: &mut GameWorld) {
+    loop {
+        world.update(1);
+
+        let chatter_path = world
+            .find_with::<Path, ChatterLabel>()
+            .expect("wait_for_chatter_to_wander: Chatter does not have a Path.");
+
+        if !chatter_path.is_empty() {
+            break;
+        }
+    }
+}
+
+#[when("the Chatter wants to speak,")]
+fn chatter_wants_to_speak(world: &mut GameWorld) {
+    world.broadcast_event(ChatMsg {
+        name: String::from("Chatter"),
+        msg: String::from("Hello Caveman!"),
+    });
+    world.update(1);
+}
+
+#[then("the Chatter should have left its resting point,")]
+fn chatter_left_spawn(world: &mut GameWorld) {
+    assert!(left_spawn_point::<ChatterLabel>(world));
+}
+
+#[then("the Subscriber should have left its resting point,")]
+fn subscriber_left_spawn(world: &mut GameWorld) {
+    assert!(left_spawn_point::<SubscriberLabel>(world));
+}
+
+#[then("the Chatter should have stayed within its Wander Area.")]
+fn chatter_stayed_in_area(world: &mut GameWorld) {
+    assert!(rested_within_area::<ChatterLabel>(world, GraphType::Air));
+}
+
+#[then("the Subscriber should have stayed within its Wander Area.")]
+fn subscriber_stayed_in_area(world: &mut GameWorld) {
+    assert!(rested_within_area::<SubscriberLabel>(
+        world,
+        GraphType::Water
+    ));
+}
+
+#[then("the Chatter should be approaching the Streamer.")]
+fn chatter_approaching_streamer(world: &mut GameWorld) {
+    let chatter_status = world
+        .find_with::<VisitorStatus, ChatterLabel>()
+        .expect("chatter_approaching_streamer: Chatter does not have a Status.");
+
+    assert_eq!(*chatter_status, VisitorStatus::Approaching);
+}
+
+fn main() {
+    futures::executor::block_on(GameWorld::run("tests/feature-files/wander.feature"));
+}